@@ -0,0 +1,222 @@
+use crate::constants::AI_THREAT_RADIUS;
+use crate::ecs::{Faction, GameWorld, HEX_POSITION, TILE, UNIT, faction_name, map_distance};
+use crate::hex::{HexCoord, hex_to_world_position};
+use crate::hud_text::{HudText, hud_text_despawn, hud_text_set, hud_text_spawn};
+use crate::map_generation::spawn_hidden_lines_entity;
+use crate::rendering::generate_hex_outline_with_color;
+use crate::teams::factions_hostile;
+use nightshade::ecs::world::components::Line;
+use nightshade::prelude::*;
+use std::collections::HashMap;
+
+const OVERLAY_TEXT_LINES: usize = 7;
+const OVERLAY_LINE_HEIGHT: f32 = 20.0;
+const MAX_LISTED_ODDS: usize = 4;
+const THREAT_HEIGHT: f32 = 8.0;
+const MOVE_HEIGHT: f32 = 10.0;
+const MARKER_HEIGHT: f32 = 12.0;
+const THREAT_COLOR: [f32; 3] = [1.0, 0.15, 0.1];
+const MOVE_COLOR: [f32; 4] = [0.2, 0.9, 1.0, 1.0];
+const ODDS_COLOR: [f32; 4] = [1.0, 0.55, 0.1, 1.0];
+const TARGET_COLOR: [f32; 4] = [1.0, 1.0, 0.2, 1.0];
+const UNIT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+#[derive(Default)]
+pub struct AiOverlay {
+    pub enabled: bool,
+    pub texts: Vec<HudText>,
+    pub lines_entity: Option<Entity>,
+}
+
+pub fn ai_overlay_toggle(overlay: &mut AiOverlay) {
+    overlay.enabled = !overlay.enabled;
+}
+
+fn spawn_ai_overlay_ui(overlay: &mut AiOverlay, world: &mut World, ui_scale: f32) {
+    let props = TextProperties {
+        font_size: 16.0,
+        color: nalgebra_glm::vec4(1.0, 0.8, 0.4, 1.0),
+        alignment: TextAlignment::Right,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    for index in 0..OVERLAY_TEXT_LINES {
+        overlay.texts.push(hud_text_spawn(
+            world,
+            "",
+            HudAnchor::BottomRight,
+            nalgebra_glm::vec2(
+                -10.0,
+                -(20.0 + (OVERLAY_TEXT_LINES - index) as f32 * OVERLAY_LINE_HEIGHT),
+            ),
+            props.clone(),
+            ui_scale,
+        ));
+    }
+
+    overlay.lines_entity = Some(spawn_hidden_lines_entity(world));
+}
+
+pub fn despawn_ai_overlay(overlay: &mut AiOverlay, world: &mut World) {
+    for mut hud_text in overlay.texts.drain(..) {
+        hud_text_despawn(world, &mut hud_text);
+    }
+    if let Some(entity) = overlay.lines_entity.take() {
+        world.queue_command(WorldCommand::DespawnRecursive { entity });
+    }
+}
+
+fn ai_threat_map(game_world: &GameWorld, faction: Faction) -> HashMap<HexCoord, i32> {
+    let team_layout = game_world.resources.team_layout;
+    let enemies: Vec<(HexCoord, i32)> = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter_map(|entity| {
+            let unit = game_world.get_unit(entity)?;
+            if !factions_hostile(team_layout, faction, unit.faction) {
+                return None;
+            }
+            Some((game_world.get_hex_position(entity)?.0, unit.soldiers))
+        })
+        .collect();
+
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let hex = game_world.get_hex_position(entity)?.0;
+            let threat: i32 = enemies
+                .iter()
+                .filter(|(enemy_hex, _)| {
+                    map_distance(&game_world.resources, hex, *enemy_hex) <= AI_THREAT_RADIUS
+                })
+                .map(|(_, soldiers)| soldiers)
+                .sum();
+            (threat > 0).then_some((hex, threat))
+        })
+        .collect()
+}
+
+fn coord_text(coord: Option<HexCoord>) -> String {
+    coord
+        .map(|coord| format!("({},{})", coord.column, coord.row))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn outline(game_world: &GameWorld, coord: HexCoord, height: f32, color: [f32; 4]) -> Vec<Line> {
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let center = hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+    generate_hex_outline_with_color(
+        center,
+        hex_width,
+        hex_depth,
+        orientation,
+        height,
+        nalgebra_glm::vec4(color[0], color[1], color[2], color[3]),
+    )
+}
+
+pub fn ai_overlay_system(
+    overlay: &mut AiOverlay,
+    game_world: &GameWorld,
+    world: &mut World,
+    ui_scale: f32,
+) {
+    if !overlay.enabled {
+        if !overlay.texts.is_empty() {
+            despawn_ai_overlay(overlay, world);
+        }
+        return;
+    }
+
+    if overlay.texts.is_empty() {
+        spawn_ai_overlay_ui(overlay, world, ui_scale);
+    }
+
+    let trace = &game_world.resources.ai_trace;
+    let threat_map = ai_threat_map(game_world, trace.faction);
+    let max_threat = threat_map.values().copied().max().unwrap_or(0);
+    let hottest = threat_map
+        .iter()
+        .max_by_key(|(coord, threat)| (**threat, -coord.column, -coord.row))
+        .map(|(coord, _)| *coord);
+
+    let odds: Vec<String> = trace
+        .attack_odds
+        .iter()
+        .take(MAX_LISTED_ODDS)
+        .map(|(coord, chance)| {
+            format!(
+                "{} {}%",
+                coord_text(Some(*coord)),
+                (chance * 100.0).round() as i32
+            )
+        })
+        .collect();
+
+    let lines = [
+        format!("AI OVERLAY [F3]  {}", faction_name(trace.faction)),
+        format!(
+            "unit {}  decision: {}",
+            coord_text(trace.unit),
+            if trace.reason.is_empty() {
+                "none yet"
+            } else {
+                trace.reason
+            }
+        ),
+        format!("target {}", coord_text(trace.target)),
+        format!("moves considered: {}", trace.considered_moves.len()),
+        format!(
+            "attack odds: {}",
+            if odds.is_empty() {
+                "none".to_string()
+            } else {
+                odds.join("  ")
+            }
+        ),
+        format!("max threat {} at {}", max_threat, coord_text(hottest)),
+        "red: threat  cyan: moves  orange: odds  yellow: target".to_string(),
+    ];
+    for (hud_text, line) in overlay.texts.iter_mut().zip(lines.iter()) {
+        hud_text_set(world, hud_text, line);
+    }
+
+    let Some(entity) = overlay.lines_entity else {
+        return;
+    };
+
+    let mut outlines = Vec::new();
+    for (coord, threat) in &threat_map {
+        let intensity = *threat as f32 / max_threat.max(1) as f32;
+        outlines.extend(outline(
+            game_world,
+            *coord,
+            THREAT_HEIGHT,
+            [THREAT_COLOR[0], THREAT_COLOR[1], THREAT_COLOR[2], intensity],
+        ));
+    }
+    for coord in &trace.considered_moves {
+        outlines.extend(outline(game_world, *coord, MOVE_HEIGHT, MOVE_COLOR));
+    }
+    for (coord, _) in &trace.attack_odds {
+        outlines.extend(outline(game_world, *coord, MARKER_HEIGHT, ODDS_COLOR));
+    }
+    if let Some(coord) = trace.target {
+        outlines.extend(outline(game_world, coord, MARKER_HEIGHT, TARGET_COLOR));
+    }
+    if let Some(coord) = trace.unit {
+        outlines.extend(outline(game_world, coord, MARKER_HEIGHT, UNIT_COLOR));
+    }
+
+    let has_lines = !outlines.is_empty();
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = outlines;
+        lines_component.mark_dirty();
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = has_lines;
+    }
+}
@@ -0,0 +1,190 @@
+use crate::ecs::TileType;
+use crate::hex::{HexOrientation, hex_orientation_from_key};
+use crate::map_file::{tile_type_from_key, tile_type_key};
+use nightshade::prelude::*;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+pub const ASSET_DIRECTORY: &str = "assets";
+const ASSET_MANIFEST_FILE: &str = "manifest.toml";
+const EMBEDDED_MANIFEST: &str = include_str!("../assets/manifest.toml");
+const EMBEDDED_ASSETS: [(&str, &[u8]); 3] = [
+    (
+        "hexagon_tiles.glb",
+        include_bytes!("../assets/hexagon_tiles.glb"),
+    ),
+    ("grass.glb", include_bytes!("../assets/grass.glb")),
+    ("army.glb", include_bytes!("../assets/army.glb")),
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssetEntry {
+    pub file: String,
+    #[serde(default)]
+    pub node: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AssetManifest {
+    pub reference_tile: Option<TileType>,
+    pub orientation: Option<HexOrientation>,
+    pub tiles: Vec<(TileType, AssetEntry)>,
+    pub unit: Option<AssetEntry>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ManifestDocument {
+    reference_tile: Option<String>,
+    orientation: Option<String>,
+    unit: Option<toml::Value>,
+    #[serde(default)]
+    tiles: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Debug)]
+pub enum AssetManifestError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for AssetManifestError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetManifestError::Io(error) => write!(formatter, "{}", error),
+            AssetManifestError::Toml(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for AssetManifestError {}
+
+impl From<std::io::Error> for AssetManifestError {
+    fn from(error: std::io::Error) -> Self {
+        AssetManifestError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for AssetManifestError {
+    fn from(error: toml::de::Error) -> Self {
+        AssetManifestError::Toml(error)
+    }
+}
+
+fn manifest_entry(name: &str, value: toml::Value) -> Option<AssetEntry> {
+    let parsed: Result<AssetEntry, _> = value.try_into();
+    match parsed {
+        Ok(entry) if !entry.file.is_empty() => Some(entry),
+        Ok(_) => {
+            log::warn!("Asset manifest entry '{}' has no file", name);
+            None
+        }
+        Err(error) => {
+            log::warn!("Skipping asset manifest entry '{}': {}", name, error);
+            None
+        }
+    }
+}
+
+pub fn asset_manifest_from_string(contents: &str) -> Result<AssetManifest, AssetManifestError> {
+    let document: ManifestDocument = toml::from_str(contents)?;
+    let mut manifest = AssetManifest {
+        reference_tile: document.reference_tile.as_deref().and_then(|key| {
+            let tile_type = tile_type_from_key(key);
+            if tile_type.is_none() {
+                log::warn!("Unknown reference tile '{}' in asset manifest", key);
+            }
+            tile_type
+        }),
+        orientation: document.orientation.as_deref().and_then(|key| {
+            let orientation = hex_orientation_from_key(key);
+            if orientation.is_none() {
+                log::warn!("Unknown orientation '{}' in asset manifest", key);
+            }
+            orientation
+        }),
+        tiles: Vec::new(),
+        unit: document
+            .unit
+            .and_then(|value| manifest_entry("unit", value)),
+    };
+
+    for (key, value) in document.tiles {
+        let Some(tile_type) = tile_type_from_key(&key) else {
+            log::warn!("Unknown tile type '{}' in asset manifest", key);
+            continue;
+        };
+        if let Some(entry) = manifest_entry(&format!("tiles.{}", key), value) {
+            manifest.tiles.push((tile_type, entry));
+        }
+    }
+
+    Ok(manifest)
+}
+
+pub fn embedded_asset_manifest() -> AssetManifest {
+    asset_manifest_from_string(EMBEDDED_MANIFEST).unwrap_or_default()
+}
+
+pub fn asset_manifest_tile(manifest: &AssetManifest, tile_type: TileType) -> Option<&AssetEntry> {
+    manifest
+        .tiles
+        .iter()
+        .find(|(entry_type, _)| *entry_type == tile_type)
+        .map(|(_, entry)| entry)
+}
+
+fn asset_manifest_fill_from(manifest: &mut AssetManifest, fallback: &AssetManifest) {
+    manifest.reference_tile = manifest.reference_tile.or(fallback.reference_tile);
+    manifest.orientation = manifest.orientation.or(fallback.orientation);
+    if manifest.unit.is_none() {
+        manifest.unit = fallback.unit.clone();
+    }
+    for (tile_type, entry) in &fallback.tiles {
+        if asset_manifest_tile(manifest, *tile_type).is_none() {
+            log::info!(
+                "Using built-in asset for {} tiles",
+                tile_type_key(*tile_type)
+            );
+            manifest.tiles.push((*tile_type, entry.clone()));
+        }
+    }
+}
+
+pub fn load_asset_manifest() -> AssetManifest {
+    let embedded = embedded_asset_manifest();
+    let path = Path::new(ASSET_DIRECTORY).join(ASSET_MANIFEST_FILE);
+    let loaded = std::fs::read_to_string(&path)
+        .map_err(AssetManifestError::from)
+        .and_then(|contents| asset_manifest_from_string(&contents));
+
+    match loaded {
+        Ok(mut manifest) => {
+            log::info!("Loaded asset manifest from {}", path.display());
+            asset_manifest_fill_from(&mut manifest, &embedded);
+            manifest
+        }
+        Err(error) => {
+            log::warn!(
+                "Using embedded asset manifest ({}: {})",
+                path.display(),
+                error
+            );
+            embedded
+        }
+    }
+}
+
+pub fn asset_bytes(file: &str) -> Option<Cow<'static, [u8]>> {
+    let path = Path::new(ASSET_DIRECTORY).join(file);
+    match std::fs::read(&path) {
+        Ok(bytes) => Some(Cow::Owned(bytes)),
+        Err(_) => EMBEDDED_ASSETS
+            .iter()
+            .find(|(name, _)| *name == file)
+            .map(|(_, bytes)| Cow::Borrowed(*bytes)),
+    }
+}
@@ -0,0 +1,38 @@
+use crate::savegame::{SaveGame, SaveGameError, SaveMetadata, load_save_metadata, save_game_file};
+use std::path::PathBuf;
+
+pub const AUTOSAVE_DIRECTORY: &str = "autosaves";
+pub const AUTOSAVE_SLOTS: usize = 5;
+
+pub struct AutosaveSlot {
+    pub slot: usize,
+    pub metadata: Option<SaveMetadata>,
+}
+
+pub fn autosave_path(slot: usize) -> PathBuf {
+    PathBuf::from(AUTOSAVE_DIRECTORY).join(format!("autosave_{}.hexsave", slot + 1))
+}
+
+pub fn autosave_slot_for_turn(turn_number: u32) -> usize {
+    turn_number.saturating_sub(1) as usize % AUTOSAVE_SLOTS
+}
+
+pub fn write_autosave(save: &SaveGame) -> Result<PathBuf, SaveGameError> {
+    std::fs::create_dir_all(AUTOSAVE_DIRECTORY)?;
+    let path = autosave_path(autosave_slot_for_turn(save.turn_number));
+    save_game_file(&path, save)?;
+    Ok(path)
+}
+
+pub fn list_autosaves() -> Vec<AutosaveSlot> {
+    let mut slots: Vec<AutosaveSlot> = (0..AUTOSAVE_SLOTS)
+        .map(|slot| AutosaveSlot {
+            slot,
+            metadata: load_save_metadata(&autosave_path(slot)).ok(),
+        })
+        .collect();
+    slots.sort_by_key(|slot| {
+        std::cmp::Reverse(slot.metadata.as_ref().map(|metadata| metadata.saved_at))
+    });
+    slots
+}
@@ -0,0 +1,108 @@
+use crate::hex::{HexCoord, HexOrientation, hex_to_world_position};
+use crate::quality::QualitySettings;
+use crate::systems::spawn_clash_burst;
+use nightshade::prelude::*;
+
+const CINEMATIC_DURATION: f32 = 1.6;
+const CINEMATIC_RADIUS: f32 = 1200.0;
+const CLASH_HEIGHT: f32 = 40.0;
+const SLOW_MOTION_SCALE: f32 = 0.25;
+
+pub struct BattleShot {
+    pub previous_focus: Vec3,
+    pub previous_radius: f32,
+    pub remaining: f32,
+}
+
+#[derive(Default)]
+pub struct BattleCinematic {
+    pub shot: Option<BattleShot>,
+}
+
+pub fn battle_cinematic_active(cinematic: &BattleCinematic) -> bool {
+    cinematic.shot.is_some()
+}
+
+pub fn battle_cinematic_time_scale(cinematic: &BattleCinematic) -> f32 {
+    if battle_cinematic_active(cinematic) {
+        SLOW_MOTION_SCALE
+    } else {
+        1.0
+    }
+}
+
+pub fn battle_cinematic_start(
+    cinematic: &mut BattleCinematic,
+    world: &mut World,
+    attacker: HexCoord,
+    defender: HexCoord,
+    hex_width: f32,
+    hex_depth: f32,
+    orientation: HexOrientation,
+    quality: &QualitySettings,
+) {
+    if battle_cinematic_active(cinematic) {
+        return;
+    }
+    let Some(camera_entity) = world.resources.active_camera else {
+        return;
+    };
+    let attacker_position = hex_to_world_position(
+        attacker.column,
+        attacker.row,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
+    let defender_position = hex_to_world_position(
+        defender.column,
+        defender.row,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
+    let midpoint = (attacker_position + defender_position) * 0.5;
+
+    let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity) else {
+        return;
+    };
+    cinematic.shot = Some(BattleShot {
+        previous_focus: pan_orbit.target_focus,
+        previous_radius: pan_orbit.target_radius,
+        remaining: CINEMATIC_DURATION,
+    });
+    pan_orbit.target_focus = nalgebra_glm::vec3(midpoint.x, 0.0, midpoint.z);
+    pan_orbit.target_radius = CINEMATIC_RADIUS;
+
+    spawn_clash_burst(
+        world,
+        nalgebra_glm::vec3(midpoint.x, midpoint.y + CLASH_HEIGHT, midpoint.z),
+        quality,
+    );
+}
+
+pub fn battle_cinematic_skip(cinematic: &mut BattleCinematic, world: &mut World) {
+    let Some(shot) = cinematic.shot.take() else {
+        return;
+    };
+    if let Some(camera_entity) = world.resources.active_camera
+        && let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity)
+    {
+        pan_orbit.target_focus = shot.previous_focus;
+        pan_orbit.target_radius = shot.previous_radius;
+    }
+}
+
+pub fn battle_cinematic_system(
+    cinematic: &mut BattleCinematic,
+    world: &mut World,
+    delta_time: f32,
+) {
+    let Some(shot) = cinematic.shot.as_mut() else {
+        return;
+    };
+    shot.remaining -= delta_time;
+    if shot.remaining <= 0.0 {
+        battle_cinematic_skip(cinematic, world);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::ecs::faction_name;
+use crate::event_log::EventLog;
+use crate::savegame::{SaveGame, savegame_to_string};
+use crate::state_hash::StateHashRecord;
+use crate::wall_clock::unix_millis_now;
+use nightshade::prelude::*;
+use std::path::PathBuf;
+
+pub const BUG_REPORT_DIRECTORY: &str = "bug_reports";
+const BUG_REPORT_HEADER: &str = "hexwar-bugreport 1";
+const MAX_REPORTED_EVENTS: usize = 200;
+const MAX_REPORTED_HASHES: usize = 20;
+
+pub struct BugReport {
+    pub save: SaveGame,
+    pub settings: Vec<(&'static str, String)>,
+    pub recent_events: Vec<String>,
+    pub state_hashes: Vec<StateHashRecord>,
+}
+
+pub fn bug_report_recent_events(log: &EventLog) -> Vec<String> {
+    let skip = log.entries.len().saturating_sub(MAX_REPORTED_EVENTS);
+    log.entries
+        .iter()
+        .skip(skip)
+        .map(|entry| format!("{} {}", entry.faction_tag, entry.message))
+        .collect()
+}
+
+pub fn bug_report_recent_hashes(hashes: &[StateHashRecord]) -> Vec<StateHashRecord> {
+    let skip = hashes.len().saturating_sub(MAX_REPORTED_HASHES);
+    hashes[skip..].to_vec()
+}
+
+pub fn bug_report_to_string(report: &BugReport) -> String {
+    let mut output = String::new();
+    output.push_str(BUG_REPORT_HEADER);
+    output.push('\n');
+    output.push_str(&format!("version {}\n", env!("CARGO_PKG_VERSION")));
+    output.push_str(&format!("seed {}\n", report.save.seed));
+
+    output.push_str("[settings]\n");
+    for (name, value) in &report.settings {
+        output.push_str(&format!("{} {}\n", name, value));
+    }
+
+    output.push_str("[state_hashes]\n");
+    for record in &report.state_hashes {
+        output.push_str(&format!(
+            "{} {} {:016x}\n",
+            record.turn,
+            faction_name(record.faction),
+            record.hash
+        ));
+    }
+
+    output.push_str("[events]\n");
+    for event in &report.recent_events {
+        output.push_str(event);
+        output.push('\n');
+    }
+
+    output.push_str("[save]\n");
+    output.push_str(&savegame_to_string(&report.save));
+    output
+}
+
+pub fn bug_report_path() -> PathBuf {
+    PathBuf::from(BUG_REPORT_DIRECTORY).join(format!("hex_war_{}.hexbug", unix_millis_now()))
+}
+
+pub fn write_bug_report(report: &BugReport) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(BUG_REPORT_DIRECTORY)
+        .map_err(|error| format!("Failed to create {}: {}", BUG_REPORT_DIRECTORY, error))?;
+    let path = bug_report_path();
+    std::fs::write(&path, bug_report_to_string(report))
+        .map_err(|error| format!("Failed to write {}: {}", path.display(), error))?;
+    Ok(path)
+}
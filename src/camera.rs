@@ -1,4 +1,5 @@
-use crate::hex::hex_to_world_position;
+use crate::hex::{HexCoord, HexOrientation, hex_to_world_position};
+use nightshade::ecs::picking::queries::PickingRay;
 use nightshade::prelude::*;
 
 pub struct CameraBounds {
@@ -11,13 +12,25 @@ pub struct CameraBounds {
 pub fn calculate_camera_bounds(
     hex_width: f32,
     hex_depth: f32,
+    orientation: HexOrientation,
     map_width: i32,
     map_height: i32,
+    wrap_columns: bool,
 ) -> CameraBounds {
-    let min_pos = hex_to_world_position(0, 0, hex_width, hex_depth);
-    let max_pos = hex_to_world_position(map_width - 1, map_height - 1, hex_width, hex_depth);
-
-    let padding_x = hex_width * 2.0;
+    let min_pos = hex_to_world_position(0, 0, hex_width, hex_depth, orientation);
+    let max_pos = hex_to_world_position(
+        map_width - 1,
+        map_height - 1,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
+
+    let padding_x = if wrap_columns {
+        (hex_to_world_position(map_width, 0, hex_width, hex_depth, orientation).x - min_pos.x) / 2.0
+    } else {
+        hex_width * 2.0
+    };
     let padding_z = hex_depth * 2.0;
 
     CameraBounds {
@@ -41,6 +54,50 @@ pub fn clamp_camera_to_bounds(world: &mut World, bounds: &CameraBounds) {
     pan_orbit.target_focus.z = pan_orbit.target_focus.z.clamp(bounds.min_z, bounds.max_z);
 }
 
+pub fn zoom_to_cursor_camera_system(world: &mut World) {
+    let Some(camera_entity) = world.resources.active_camera else {
+        pan_orbit_camera_system(world);
+        return;
+    };
+
+    let scrolled = world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::SCROLLED);
+    let previous_radius = world
+        .get_pan_orbit_camera(camera_entity)
+        .map(|pan_orbit| pan_orbit.target_radius);
+    let mouse_position = world.resources.input.mouse.position;
+    let cursor_point = if scrolled {
+        PickingRay::from_screen_position(world, mouse_position)
+            .and_then(|ray| ray.intersect_ground_plane(0.0))
+    } else {
+        None
+    };
+
+    pan_orbit_camera_system(world);
+
+    let (Some(previous_radius), Some(cursor_point)) = (previous_radius, cursor_point) else {
+        return;
+    };
+    let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity) else {
+        return;
+    };
+    if previous_radius <= f32::EPSILON {
+        return;
+    }
+
+    let zoom_fraction = 1.0 - pan_orbit.target_radius / previous_radius;
+    if zoom_fraction.abs() <= f32::EPSILON {
+        return;
+    }
+
+    pan_orbit.target_focus.x += (cursor_point.x - pan_orbit.target_focus.x) * zoom_fraction;
+    pan_orbit.target_focus.z += (cursor_point.z - pan_orbit.target_focus.z) * zoom_fraction;
+}
+
 pub fn world_to_screen(world: &World, world_pos: Vec3) -> Option<Vec2> {
     let camera_entity = world.resources.active_camera?;
     let camera = world.get_camera(camera_entity)?;
@@ -72,6 +129,7 @@ pub fn reset_camera_to_map(
     world: &mut World,
     hex_width: f32,
     hex_depth: f32,
+    orientation: HexOrientation,
     map_width: i32,
     map_height: i32,
 ) {
@@ -84,7 +142,8 @@ pub fn reset_camera_to_map(
 
     let center_column = (map_width - 1) / 2;
     let center_row = (map_height - 1) / 2;
-    let center_pos = hex_to_world_position(center_column, center_row, hex_width, hex_depth);
+    let center_pos =
+        hex_to_world_position(center_column, center_row, hex_width, hex_depth, orientation);
 
     let y_fov_rad = if let Some(camera) = world.get_camera(camera_entity) {
         match &camera.projection {
@@ -116,3 +175,20 @@ pub fn reset_camera_to_map(
     pan_orbit.target_yaw = 0.0;
     pan_orbit.target_pitch = std::f32::consts::FRAC_PI_2 - 0.01;
 }
+
+pub fn focus_camera_on(
+    world: &mut World,
+    coord: HexCoord,
+    hex_width: f32,
+    hex_depth: f32,
+    orientation: HexOrientation,
+) {
+    let Some(camera_entity) = world.resources.active_camera else {
+        return;
+    };
+    let position =
+        hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+    if let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity) {
+        pan_orbit.target_focus = nalgebra_glm::vec3(position.x, 0.0, position.z);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::hex::{HexCoord, HexOrientation, hex_to_world_position};
+use nightshade::prelude::*;
+use std::collections::VecDeque;
+
+const DIRECTOR_TWEEN_TIME: f32 = 0.6;
+const DIRECTOR_HOLD_TIME: f32 = 1.0;
+const DIRECTOR_MAX_QUEUED: usize = 8;
+
+pub struct CameraTween {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub elapsed: f32,
+}
+
+pub struct CameraDirector {
+    pub enabled: bool,
+    pub queue: VecDeque<HexCoord>,
+    pub tween: Option<CameraTween>,
+    pub hold_remaining: f32,
+}
+
+impl Default for CameraDirector {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            queue: VecDeque::new(),
+            tween: None,
+            hold_remaining: 0.0,
+        }
+    }
+}
+
+pub fn camera_director_focus(director: &mut CameraDirector, coord: HexCoord) {
+    if !director.enabled || director.queue.back() == Some(&coord) {
+        return;
+    }
+    director.queue.push_back(coord);
+    if director.queue.len() > DIRECTOR_MAX_QUEUED {
+        director.queue.pop_front();
+    }
+}
+
+pub fn camera_director_toggle(director: &mut CameraDirector) -> bool {
+    director.enabled = !director.enabled;
+    if !director.enabled {
+        director.queue.clear();
+        director.tween = None;
+        director.hold_remaining = 0.0;
+    }
+    director.enabled
+}
+
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+pub fn camera_director_system(
+    director: &mut CameraDirector,
+    world: &mut World,
+    hex_width: f32,
+    hex_depth: f32,
+    orientation: HexOrientation,
+    delta_time: f32,
+) {
+    if !director.enabled {
+        return;
+    }
+    let Some(camera_entity) = world.resources.active_camera else {
+        return;
+    };
+
+    if director.tween.is_none() {
+        director.hold_remaining = (director.hold_remaining - delta_time).max(0.0);
+        if director.hold_remaining > 0.0 {
+            return;
+        }
+        let Some(coord) = director.queue.pop_front() else {
+            return;
+        };
+        let Some(pan_orbit) = world.get_pan_orbit_camera(camera_entity) else {
+            return;
+        };
+        let position =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+        director.tween = Some(CameraTween {
+            from: pan_orbit.target_focus,
+            to: nalgebra_glm::vec3(position.x, 0.0, position.z),
+            elapsed: 0.0,
+        });
+    }
+
+    let Some(tween) = director.tween.as_mut() else {
+        return;
+    };
+    tween.elapsed += delta_time;
+    let progress = smoothstep(tween.elapsed / DIRECTOR_TWEEN_TIME);
+    let focus = nalgebra_glm::lerp(&tween.from, &tween.to, progress);
+    let finished = tween.elapsed >= DIRECTOR_TWEEN_TIME;
+
+    if let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity) {
+        pan_orbit.target_focus = focus;
+    }
+
+    if finished {
+        director.tween = None;
+        director.hold_remaining = DIRECTOR_HOLD_TIME;
+    }
+}
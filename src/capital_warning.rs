@@ -0,0 +1,86 @@
+use crate::constants::CAPITAL_THREAT_RADIUS;
+use crate::ecs::{Faction, GameWorld};
+use crate::hud_text::{
+    HudText, hud_text_despawn, hud_text_set, hud_text_set_color, hud_text_spawn,
+};
+use crate::systems::{CapitalThreat, capital_threat_system};
+use nightshade::prelude::*;
+
+const WARNING_PULSE_SPEED: f32 = 4.0;
+const WARNING_COLOR: [f32; 3] = [1.0, 0.25, 0.15];
+
+#[derive(Default)]
+pub struct CapitalWarning {
+    pub checked_turn: Option<(u32, Faction)>,
+    pub threat: Option<CapitalThreat>,
+    pub text: HudText,
+    pub elapsed: f32,
+}
+
+pub fn capital_warning_check(
+    warning: &mut CapitalWarning,
+    game_world: &GameWorld,
+    faction: Faction,
+) {
+    let turn_key = (
+        game_world.resources.turn_number,
+        game_world.resources.current_faction,
+    );
+    if warning.checked_turn == Some(turn_key) {
+        return;
+    }
+    warning.checked_turn = Some(turn_key);
+    warning.threat = capital_threat_system(game_world, faction);
+}
+
+pub fn despawn_capital_warning(warning: &mut CapitalWarning, world: &mut World) {
+    hud_text_despawn(world, &mut warning.text);
+}
+
+pub fn capital_warning_system(
+    warning: &mut CapitalWarning,
+    world: &mut World,
+    ui_scale: f32,
+    delta_time: f32,
+) {
+    let Some(threat) = warning.threat.as_ref() else {
+        despawn_capital_warning(warning, world);
+        return;
+    };
+
+    let message = format!(
+        "CAPITAL UNDER THREAT: {} enemy units ({} soldiers) within {} hexes  [J] Jump",
+        threat.enemy_units, threat.enemy_soldiers, CAPITAL_THREAT_RADIUS
+    );
+    if warning.text.entity.is_none() {
+        warning.text = hud_text_spawn(
+            world,
+            &message,
+            HudAnchor::TopCenter,
+            nalgebra_glm::vec2(0.0, 100.0),
+            TextProperties {
+                font_size: 24.0,
+                color: nalgebra_glm::vec4(
+                    WARNING_COLOR[0],
+                    WARNING_COLOR[1],
+                    WARNING_COLOR[2],
+                    1.0,
+                ),
+                alignment: TextAlignment::Center,
+                outline_width: 0.06,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                ..Default::default()
+            },
+            ui_scale,
+        );
+    }
+    hud_text_set(world, &mut warning.text, &message);
+
+    warning.elapsed += delta_time;
+    let pulse = 0.6 + 0.4 * (warning.elapsed * WARNING_PULSE_SPEED).sin().abs();
+    hud_text_set_color(
+        world,
+        &mut warning.text,
+        [WARNING_COLOR[0], WARNING_COLOR[1], WARNING_COLOR[2], pulse],
+    );
+}
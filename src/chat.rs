@@ -0,0 +1,240 @@
+use crate::ecs::{Faction, GameWorld, faction_color};
+use crate::hex::{HexCoord, hex_to_world_position};
+use crate::hud_text::spawn_scaled_hud_text;
+use crate::map_generation::spawn_hidden_lines_entity;
+use crate::rendering::generate_hex_outline_with_color;
+use nightshade::prelude::*;
+
+pub const MAX_CHAT_LENGTH: usize = 80;
+pub const PING_DURATION: f32 = 5.0;
+const PING_PULSE_RATE: f32 = 1.2;
+const PING_RING_COUNT: usize = 3;
+const PING_HEIGHT: f32 = 14.0;
+const CHAT_FONT_SIZE: f32 = 18.0;
+const CHAT_PADDING: f32 = 10.0;
+
+const CHAT_KEYS: [(KeyCode, char, char); 47] = [
+    (KeyCode::KeyA, 'a', 'A'),
+    (KeyCode::KeyB, 'b', 'B'),
+    (KeyCode::KeyC, 'c', 'C'),
+    (KeyCode::KeyD, 'd', 'D'),
+    (KeyCode::KeyE, 'e', 'E'),
+    (KeyCode::KeyF, 'f', 'F'),
+    (KeyCode::KeyG, 'g', 'G'),
+    (KeyCode::KeyH, 'h', 'H'),
+    (KeyCode::KeyI, 'i', 'I'),
+    (KeyCode::KeyJ, 'j', 'J'),
+    (KeyCode::KeyK, 'k', 'K'),
+    (KeyCode::KeyL, 'l', 'L'),
+    (KeyCode::KeyM, 'm', 'M'),
+    (KeyCode::KeyN, 'n', 'N'),
+    (KeyCode::KeyO, 'o', 'O'),
+    (KeyCode::KeyP, 'p', 'P'),
+    (KeyCode::KeyQ, 'q', 'Q'),
+    (KeyCode::KeyR, 'r', 'R'),
+    (KeyCode::KeyS, 's', 'S'),
+    (KeyCode::KeyT, 't', 'T'),
+    (KeyCode::KeyU, 'u', 'U'),
+    (KeyCode::KeyV, 'v', 'V'),
+    (KeyCode::KeyW, 'w', 'W'),
+    (KeyCode::KeyX, 'x', 'X'),
+    (KeyCode::KeyY, 'y', 'Y'),
+    (KeyCode::KeyZ, 'z', 'Z'),
+    (KeyCode::Digit0, '0', ')'),
+    (KeyCode::Digit1, '1', '!'),
+    (KeyCode::Digit2, '2', '@'),
+    (KeyCode::Digit3, '3', '#'),
+    (KeyCode::Digit4, '4', '$'),
+    (KeyCode::Digit5, '5', '%'),
+    (KeyCode::Digit6, '6', '^'),
+    (KeyCode::Digit7, '7', '&'),
+    (KeyCode::Digit8, '8', '*'),
+    (KeyCode::Digit9, '9', '('),
+    (KeyCode::Space, ' ', ' '),
+    (KeyCode::Minus, '-', '_'),
+    (KeyCode::Equal, '=', '+'),
+    (KeyCode::Comma, ',', '<'),
+    (KeyCode::Period, '.', '>'),
+    (KeyCode::Slash, '/', '?'),
+    (KeyCode::Semicolon, ';', ':'),
+    (KeyCode::Quote, '\'', '"'),
+    (KeyCode::BracketLeft, '[', '{'),
+    (KeyCode::BracketRight, ']', '}'),
+    (KeyCode::Backslash, '\\', '|'),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MapPing {
+    pub coord: HexCoord,
+    pub faction: Faction,
+    pub age: f32,
+}
+
+#[derive(Default)]
+pub struct Chat {
+    pub open: bool,
+    pub draft: String,
+    pub input_entity: Option<Entity>,
+    pub pings: Vec<MapPing>,
+    pub ping_entity: Option<Entity>,
+}
+
+pub fn chat_key_to_char(key: KeyCode, shift_held: bool) -> Option<char> {
+    CHAT_KEYS
+        .iter()
+        .find(|(chat_key, _, _)| *chat_key == key)
+        .map(|(_, lower, upper)| if shift_held { *upper } else { *lower })
+}
+
+fn chat_input_text(chat: &Chat) -> String {
+    format!("Say: {}_", chat.draft)
+}
+
+fn spawn_chat_input(chat: &mut Chat, world: &mut World, ui_scale: f32, log_height: f32) {
+    let props = TextProperties {
+        font_size: CHAT_FONT_SIZE,
+        color: nalgebra_glm::vec4(1.0, 1.0, 0.85, 1.0),
+        alignment: TextAlignment::Left,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+    chat.input_entity = Some(spawn_scaled_hud_text(
+        world,
+        &chat_input_text(chat),
+        HudAnchor::BottomLeft,
+        nalgebra_glm::vec2(CHAT_PADDING, -(log_height + CHAT_PADDING)),
+        props,
+        ui_scale,
+    ));
+}
+
+fn despawn_chat_input(chat: &mut Chat, world: &mut World) {
+    if let Some(entity) = chat.input_entity.take() {
+        world.despawn_entities(&[entity]);
+    }
+}
+
+pub fn chat_open(chat: &mut Chat, world: &mut World, ui_scale: f32, log_height: f32) {
+    if chat.open {
+        return;
+    }
+    chat.open = true;
+    spawn_chat_input(chat, world, ui_scale, log_height);
+}
+
+pub fn chat_close(chat: &mut Chat, world: &mut World) {
+    chat.open = false;
+    chat.draft.clear();
+    despawn_chat_input(chat, world);
+}
+
+pub fn chat_respawn_ui(chat: &mut Chat, world: &mut World, ui_scale: f32, log_height: f32) {
+    despawn_chat_input(chat, world);
+    if chat.open {
+        spawn_chat_input(chat, world, ui_scale, log_height);
+    }
+}
+
+pub fn chat_type(chat: &mut Chat, key: KeyCode, shift_held: bool) {
+    if key == KeyCode::Backspace {
+        chat.draft.pop();
+    } else if let Some(character) = chat_key_to_char(key, shift_held)
+        && chat.draft.chars().count() < MAX_CHAT_LENGTH
+    {
+        chat.draft.push(character);
+    }
+}
+
+pub fn chat_take_message(chat: &mut Chat) -> Option<String> {
+    let message = chat.draft.trim().to_string();
+    chat.draft.clear();
+    (!message.is_empty()).then_some(message)
+}
+
+pub fn chat_input_system(chat: &Chat, world: &mut World) {
+    let Some(entity) = chat.input_entity else {
+        return;
+    };
+    let Some(text_index) = world.get_hud_text(entity).map(|text| text.text_index) else {
+        return;
+    };
+    world
+        .resources
+        .text_cache
+        .set_text(text_index, chat_input_text(chat));
+    if let Some(hud_text) = world.get_hud_text_mut(entity) {
+        hud_text.dirty = true;
+    }
+}
+
+pub fn ping_add(chat: &mut Chat, coord: HexCoord, faction: Faction) {
+    chat.pings.retain(|ping| ping.coord != coord);
+    chat.pings.push(MapPing {
+        coord,
+        faction,
+        age: 0.0,
+    });
+}
+
+pub fn ping_system(chat: &mut Chat, game_world: &GameWorld, world: &mut World, delta_time: f32) {
+    for ping in &mut chat.pings {
+        ping.age += delta_time;
+    }
+    chat.pings.retain(|ping| ping.age < PING_DURATION);
+
+    if chat.pings.is_empty() && chat.ping_entity.is_none() {
+        return;
+    }
+    let entity = *chat
+        .ping_entity
+        .get_or_insert_with(|| spawn_hidden_lines_entity(world));
+
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let mut lines = Vec::new();
+    for ping in &chat.pings {
+        let center = hex_to_world_position(
+            ping.coord.column,
+            ping.coord.row,
+            hex_width,
+            hex_depth,
+            orientation,
+        );
+        let fade = 1.0 - ping.age / PING_DURATION;
+        let color = faction_color(ping.faction);
+        for ring in 0..PING_RING_COUNT {
+            let phase = (ping.age * PING_PULSE_RATE + ring as f32 / PING_RING_COUNT as f32).fract();
+            let scale = 0.5 + phase * 1.5;
+            let alpha = (1.0 - phase) * fade;
+            lines.extend(generate_hex_outline_with_color(
+                center,
+                hex_width * scale,
+                hex_depth * scale,
+                orientation,
+                PING_HEIGHT,
+                nalgebra_glm::vec4(color[0], color[1], color[2], alpha),
+            ));
+        }
+    }
+
+    let visible = !lines.is_empty();
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = lines;
+        lines_component.mark_dirty();
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = visible;
+    }
+}
+
+pub fn despawn_chat(chat: &mut Chat, world: &mut World) {
+    despawn_chat_input(chat, world);
+    if let Some(entity) = chat.ping_entity.take() {
+        world.queue_command(WorldCommand::DespawnRecursive { entity });
+    }
+    chat.pings.clear();
+    chat.draft.clear();
+    chat.open = false;
+}
@@ -0,0 +1,262 @@
+use crate::chat::chat_key_to_char;
+use crate::ecs::{
+    ALL_FACTIONS, Faction, GameWorld, HEX_POSITION, TILE, faction_name, record_ownership_change,
+};
+use crate::hex::HexCoord;
+use crate::hud_text::{
+    HudText, hud_text_despawn, hud_text_set, hud_text_spawn, spawn_scaled_hud_text,
+};
+use crate::selection::{get_selected_unit, get_unit_at_tile};
+use crate::systems::spawn_unit;
+use nightshade::prelude::*;
+
+pub const MAX_CONSOLE_LENGTH: usize = 80;
+const CONSOLE_HISTORY_LINES: usize = 6;
+const CONSOLE_FONT_SIZE: f32 = 18.0;
+const CONSOLE_TOP: f32 = 60.0;
+const CONSOLE_LINE_HEIGHT: f32 = 22.0;
+pub const CONSOLE_HELP: &str = "give_soldiers <n> | reveal_map | set_owner <faction|none> <col> <row> | end_game [faction] | spawn_unit <faction> <col> <row> | seed";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleCommand {
+    GiveSoldiers(i32),
+    RevealMap,
+    SetOwner {
+        owner: Option<Faction>,
+        coord: HexCoord,
+    },
+    EndGame(Option<Faction>),
+    SpawnUnit {
+        faction: Faction,
+        coord: HexCoord,
+    },
+    Seed,
+    Help,
+}
+
+#[derive(Default)]
+pub struct DebugConsole {
+    pub open: bool,
+    pub draft: String,
+    pub history: Vec<String>,
+    pub input_text: HudText,
+    pub history_entities: Vec<Entity>,
+}
+
+fn console_input_text(console: &DebugConsole) -> String {
+    format!("> {}_", console.draft)
+}
+
+fn console_text_properties(color: [f32; 4]) -> TextProperties {
+    TextProperties {
+        font_size: CONSOLE_FONT_SIZE,
+        color: nalgebra_glm::vec4(color[0], color[1], color[2], color[3]),
+        alignment: TextAlignment::Center,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    }
+}
+
+fn despawn_console_history(console: &mut DebugConsole, world: &mut World) {
+    if !console.history_entities.is_empty() {
+        world.despawn_entities(&console.history_entities);
+        console.history_entities.clear();
+    }
+}
+
+fn spawn_console_ui(console: &mut DebugConsole, world: &mut World, ui_scale: f32) {
+    despawn_console_history(console, world);
+    for (index, line) in console.history.iter().enumerate() {
+        console.history_entities.push(spawn_scaled_hud_text(
+            world,
+            line,
+            HudAnchor::TopCenter,
+            nalgebra_glm::vec2(0.0, CONSOLE_TOP + index as f32 * CONSOLE_LINE_HEIGHT),
+            console_text_properties([0.75, 0.9, 0.75, 1.0]),
+            ui_scale,
+        ));
+    }
+    hud_text_despawn(world, &mut console.input_text);
+    console.input_text = hud_text_spawn(
+        world,
+        &console_input_text(console),
+        HudAnchor::TopCenter,
+        nalgebra_glm::vec2(
+            0.0,
+            CONSOLE_TOP + CONSOLE_HISTORY_LINES as f32 * CONSOLE_LINE_HEIGHT,
+        ),
+        console_text_properties([1.0, 1.0, 0.6, 1.0]),
+        ui_scale,
+    );
+}
+
+pub fn console_open(console: &mut DebugConsole, world: &mut World, ui_scale: f32) {
+    if console.open {
+        return;
+    }
+    console.open = true;
+    spawn_console_ui(console, world, ui_scale);
+}
+
+pub fn console_close(console: &mut DebugConsole, world: &mut World) {
+    console.open = false;
+    console.draft.clear();
+    despawn_console_history(console, world);
+    hud_text_despawn(world, &mut console.input_text);
+}
+
+pub fn console_respawn_ui(console: &mut DebugConsole, world: &mut World, ui_scale: f32) {
+    if console.open {
+        spawn_console_ui(console, world, ui_scale);
+    }
+}
+
+pub fn console_type(console: &mut DebugConsole, key: KeyCode, shift_held: bool) {
+    if key == KeyCode::Backspace {
+        console.draft.pop();
+    } else if let Some(character) = chat_key_to_char(key, shift_held)
+        && console.draft.chars().count() < MAX_CONSOLE_LENGTH
+    {
+        console.draft.push(character);
+    }
+}
+
+pub fn console_take_command(console: &mut DebugConsole) -> Option<String> {
+    let command = console.draft.trim().to_string();
+    console.draft.clear();
+    (!command.is_empty()).then_some(command)
+}
+
+pub fn console_print(
+    console: &mut DebugConsole,
+    world: &mut World,
+    line: impl Into<String>,
+    ui_scale: f32,
+) {
+    console.history.push(line.into());
+    let overflow = console.history.len().saturating_sub(CONSOLE_HISTORY_LINES);
+    console.history.drain(..overflow);
+    if console.open {
+        spawn_console_ui(console, world, ui_scale);
+    }
+}
+
+pub fn console_input_system(console: &mut DebugConsole, world: &mut World) {
+    if !console.open {
+        return;
+    }
+    let text = console_input_text(console);
+    hud_text_set(world, &mut console.input_text, &text);
+}
+
+fn parse_console_faction(value: &str) -> Result<Faction, String> {
+    ALL_FACTIONS
+        .into_iter()
+        .find(|faction| faction_name(*faction).eq_ignore_ascii_case(value))
+        .ok_or_else(|| format!("unknown faction '{}'", value))
+}
+
+fn parse_console_coord(column: &str, row: &str) -> Result<HexCoord, String> {
+    let column = column
+        .parse::<i32>()
+        .map_err(|_| format!("invalid column '{}'", column))?;
+    let row = row
+        .parse::<i32>()
+        .map_err(|_| format!("invalid row '{}'", row))?;
+    Ok(HexCoord { column, row })
+}
+
+pub fn parse_console_command(line: &str) -> Result<ConsoleCommand, String> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    match parts.as_slice() {
+        ["give_soldiers", amount] => amount
+            .parse::<i32>()
+            .map(ConsoleCommand::GiveSoldiers)
+            .map_err(|_| format!("invalid amount '{}'", amount)),
+        ["reveal_map"] => Ok(ConsoleCommand::RevealMap),
+        ["set_owner", owner, column, row] => {
+            let owner = if owner.eq_ignore_ascii_case("none") {
+                None
+            } else {
+                Some(parse_console_faction(owner)?)
+            };
+            Ok(ConsoleCommand::SetOwner {
+                owner,
+                coord: parse_console_coord(column, row)?,
+            })
+        }
+        ["end_game"] => Ok(ConsoleCommand::EndGame(None)),
+        ["end_game", faction] => Ok(ConsoleCommand::EndGame(Some(parse_console_faction(
+            faction,
+        )?))),
+        ["spawn_unit", faction, column, row] => Ok(ConsoleCommand::SpawnUnit {
+            faction: parse_console_faction(faction)?,
+            coord: parse_console_coord(column, row)?,
+        }),
+        ["seed"] => Ok(ConsoleCommand::Seed),
+        ["help"] => Ok(ConsoleCommand::Help),
+        _ => Err(format!("unknown command '{}', try 'help'", line)),
+    }
+}
+
+pub fn console_give_soldiers(game_world: &mut GameWorld, amount: i32) -> String {
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    let Some(entity) = get_selected_unit(game_world) else {
+        return "select a unit first".to_string();
+    };
+    let Some(unit) = game_world.get_unit_mut(entity) else {
+        return "select a unit first".to_string();
+    };
+    unit.soldiers = (unit.soldiers + amount).clamp(1, max_soldiers);
+    format!("unit now has {} soldiers", unit.soldiers)
+}
+
+pub fn console_set_owner(
+    game_world: &mut GameWorld,
+    owner: Option<Faction>,
+    coord: HexCoord,
+) -> String {
+    let tile_entity = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| {
+            game_world
+                .get_hex_position(entity)
+                .is_some_and(|hex| hex.0 == coord)
+        });
+    let Some(tile) = tile_entity.and_then(|entity| game_world.get_tile_mut(entity)) else {
+        return format!("no tile at {},{}", coord.column, coord.row);
+    };
+    tile.faction = owner;
+    record_ownership_change(&mut game_world.resources, coord, owner);
+    format!(
+        "{},{} now owned by {}",
+        coord.column,
+        coord.row,
+        owner.map(faction_name).unwrap_or("nobody")
+    )
+}
+
+pub fn console_spawn_unit(game_world: &mut GameWorld, faction: Faction, coord: HexCoord) -> String {
+    let tile_exists = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .any(|entity| {
+            game_world
+                .get_hex_position(entity)
+                .is_some_and(|hex| hex.0 == coord)
+        });
+    if !tile_exists {
+        return format!("no tile at {},{}", coord.column, coord.row);
+    }
+    if get_unit_at_tile(game_world, coord).is_some() {
+        return format!("{},{} is already occupied", coord.column, coord.row);
+    }
+    let soldiers = game_world.resources.rules.initial_soldiers;
+    spawn_unit(game_world, coord, faction, soldiers);
+    format!(
+        "spawned {} unit at {},{}",
+        faction_name(faction),
+        coord.column,
+        coord.row
+    )
+}
@@ -1,16 +1,75 @@
 pub const ACTIONS_PER_TURN: u8 = 5;
 
 pub const CITY_REINFORCEMENT: i32 = 10;
+pub const BRUTAL_REINFORCEMENT_PERCENT: i32 = 150;
+pub const BRUTAL_STARTING_SOLDIERS: i32 = 10;
 pub const MAX_SOLDIERS: i32 = 99;
 
-pub const SPEECH_MORALE_BOOST: i32 = 5;
+pub const CITY_GROWTH_PER_TIER: u32 = 4;
+pub const CITY_MAX_TIER: u32 = 3;
+pub const CITY_TIER_REINFORCEMENT: i32 = 3;
+pub const CITY_TIER_DEFENSE_BONUS: f32 = 0.05;
+
+pub const IMPROVEMENT_BUILD_TURNS: u32 = 1;
+pub const FORT_DEFENSE_BONUS: f32 = 0.25;
+
+pub const HILLS_MOVEMENT_COST: i32 = 2;
+pub const HILLS_ELEVATION: f32 = 40.0;
+pub const SWAMP_MOVEMENT_COST: i32 = 2;
+pub const SWAMP_ATTRITION: i32 = 2;
+pub const FOREST_AMBUSH_BONUS: f32 = 0.25;
+pub const ENCIRCLEMENT_BONUS: f32 = 0.3;
+pub const ENCIRCLEMENT_MIN_UNITS: usize = 2;
+pub const RUIN_SOLDIERS: i32 = 10;
+pub const RUIN_MORALE: i32 = 8;
+pub const PILLAGE_TURNS: u32 = 3;
+pub const PILLAGE_MORALE: i32 = 4;
+pub const BATTLE_SCAR_THRESHOLD: u32 = 3;
+
+pub const SEASON_LENGTH_TURNS: u32 = 5;
+pub const WINTER_ATTRITION: i32 = 2;
+pub const COLD_LATITUDE_ROWS_FRACTION: f32 = 0.25;
+
+pub const WORLD_EVENT_CHANCE: u32 = 12;
+pub const PLAGUE_LOSS_PERCENT: i32 = 30;
+pub const ERUPTION_LOSS_PERCENT: i32 = 50;
+
+pub const LEADER_ABILITY_COOLDOWN: u32 = 3;
+pub const BLOOD_AND_IRON_ATTACK_BONUS: f32 = 0.25;
+pub const RALLYING_HYMN_MORALE_BOOST: i32 = 10;
+pub const BOUNTIFUL_LANDS_SOLDIERS: i32 = 3;
+
+pub const CAPITAL_RELOCATION_MORALE_COST: i32 = 20;
+
+pub const GENERAL_AURA_BONUS: f32 = 0.15;
+pub const GENERAL_LEVEL_AURA_BONUS: f32 = 0.05;
+pub const GENERAL_MAX_LEVEL: u32 = 5;
+pub const GENERAL_EXPERIENCE_PER_LEVEL: u32 = 2;
+pub const GENERAL_DEATH_MORALE_PENALTY: i32 = 15;
 pub const MAX_MORALE: i32 = 50;
 
 pub const UNIT_HEIGHT_OFFSET: f32 = 10.0;
 pub const UNIT_MOVEMENT_SPEED: f32 = 2.0;
+pub const SHIP_MOVEMENT_SPEED: f32 = 3.5;
+pub const AIRLIFT_MOVEMENT_SPEED: f32 = 0.8;
 pub const UNIT_DEFAULT_MOVEMENT_RANGE: i32 = 2;
+pub const MAX_UNITS_PER_HEX: usize = 3;
+pub const SCOUT_MOVEMENT_RANGE: i32 = 4;
+pub const SCOUT_RECRUIT_COST: i32 = 3;
+pub const SUPPLY_WAGON_MOVEMENT_RANGE: i32 = 3;
+
+pub const AI_MAX_DECISIONS_PER_FRAME: usize = 32;
+pub const AI_THREAT_RADIUS: i32 = 3;
+pub const AI_SURRENDER_STRENGTH_RATIO: i32 = 5;
+pub const AI_MAX_SCOUTS: usize = 1;
 
 pub const MAP_WIDTH: i32 = 31;
 pub const MAP_HEIGHT: i32 = 21;
+pub const MIN_MAP_WIDTH: i32 = 15;
+pub const MIN_MAP_HEIGHT: i32 = 11;
 
 pub const INITIAL_SOLDIERS: i32 = 10;
+
+pub const ATTACK_WARNING_THRESHOLD: f32 = 0.4;
+
+pub const CAPITAL_THREAT_RADIUS: i32 = 2;
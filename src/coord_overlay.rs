@@ -0,0 +1,154 @@
+use crate::camera::world_to_screen;
+use crate::ecs::{GameWorld, HEX_POSITION, TILE};
+use crate::hex::{HexCoord, hex_to_world_position};
+use nightshade::prelude::*;
+
+const MAX_COORD_LABELS: usize = 160;
+const COORD_LABEL_HEIGHT: f32 = 30.0;
+const COORD_LABEL_FONT_SIZE: f32 = 5000.0;
+const SCREEN_MARGIN: f32 = 40.0;
+
+pub struct CoordLabel {
+    pub entity: Entity,
+    pub coord: Option<HexCoord>,
+}
+
+#[derive(Default)]
+pub struct CoordOverlay {
+    pub enabled: bool,
+    pub labels: Vec<CoordLabel>,
+}
+
+pub fn coord_overlay_toggle(overlay: &mut CoordOverlay) -> bool {
+    overlay.enabled = !overlay.enabled;
+    overlay.enabled
+}
+
+pub fn despawn_coord_overlay(overlay: &mut CoordOverlay, world: &mut World) {
+    for label in overlay.labels.drain(..) {
+        world.despawn_entities(&[label.entity]);
+    }
+}
+
+fn spawn_coord_label(world: &mut World) -> CoordLabel {
+    let entity = spawn_3d_billboard_text_with_properties(
+        world,
+        "",
+        nalgebra_glm::vec3(0.0, 0.0, 0.0),
+        TextProperties {
+            font_size: COORD_LABEL_FONT_SIZE,
+            color: nalgebra_glm::vec4(1.0, 1.0, 1.0, 0.9),
+            alignment: TextAlignment::Center,
+            outline_width: 0.15,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            smoothing: 0.15,
+            ..Default::default()
+        },
+    );
+    CoordLabel {
+        entity,
+        coord: None,
+    }
+}
+
+fn set_label_visible(world: &mut World, entity: Entity, visible: bool) {
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = visible;
+    }
+}
+
+fn assign_label(
+    world: &mut World,
+    label: &mut CoordLabel,
+    coord: HexCoord,
+    position: nalgebra_glm::Vec3,
+) {
+    set_label_visible(world, label.entity, true);
+    label.coord = Some(coord);
+
+    if let Some(transform) = world.get_local_transform_mut(label.entity) {
+        transform.translation = position;
+    }
+    mark_local_transform_dirty(world, label.entity);
+
+    if let Some(text_index) = world.get_text(label.entity).map(|text| text.text_index) {
+        world
+            .resources
+            .text_cache
+            .set_text(text_index, format!("{},{}", coord.column, coord.row));
+    }
+    if let Some(text) = world.get_text_mut(label.entity) {
+        text.dirty = true;
+    }
+}
+
+fn visible_tiles(game_world: &GameWorld, world: &World) -> Vec<(HexCoord, nalgebra_glm::Vec3)> {
+    let Some((viewport_width, viewport_height)) = world.resources.window.cached_viewport_size
+    else {
+        return Vec::new();
+    };
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let center =
+                hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+            let position = nalgebra_glm::vec3(center.x, center.y + COORD_LABEL_HEIGHT, center.z);
+            let screen = world_to_screen(world, position)?;
+            let on_screen = (-SCREEN_MARGIN..=viewport_width as f32 + SCREEN_MARGIN)
+                .contains(&screen.x)
+                && (-SCREEN_MARGIN..=viewport_height as f32 + SCREEN_MARGIN).contains(&screen.y);
+            on_screen.then_some((coord, position))
+        })
+        .take(MAX_COORD_LABELS)
+        .collect()
+}
+
+pub fn coord_overlay_system(overlay: &mut CoordOverlay, game_world: &GameWorld, world: &mut World) {
+    if !overlay.enabled {
+        for label in &mut overlay.labels {
+            if label.coord.take().is_some() {
+                set_label_visible(world, label.entity, false);
+            }
+        }
+        return;
+    }
+
+    let tiles = visible_tiles(game_world, world);
+    while overlay.labels.len() < tiles.len() {
+        overlay.labels.push(spawn_coord_label(world));
+    }
+
+    let mut unassigned: Vec<usize> = Vec::new();
+    let mut pending = Vec::new();
+    for (index, label) in overlay.labels.iter().enumerate() {
+        match label.coord {
+            Some(coord) if tiles.iter().any(|(tile, _)| *tile == coord) => {}
+            _ => unassigned.push(index),
+        }
+    }
+    for (coord, position) in &tiles {
+        if !overlay
+            .labels
+            .iter()
+            .any(|label| label.coord == Some(*coord))
+        {
+            pending.push((*coord, *position));
+        }
+    }
+
+    for (slot, index) in unassigned.into_iter().enumerate() {
+        let label = &mut overlay.labels[index];
+        match pending.get(slot) {
+            Some((coord, position)) => assign_label(world, label, *coord, *position),
+            None => {
+                label.coord = None;
+                set_label_visible(world, label.entity, false);
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use crate::ecs::{Faction, GameWorld, HEX_POSITION, TILE, UNIT, faction_name};
+use crate::hex::{HexCoord, hex_to_world_position};
+use crate::hud_text::{HudText, hud_text_despawn, hud_text_set, hud_text_spawn};
+use crate::map_generation::spawn_hidden_lines_entity;
+use crate::rendering::generate_hex_outline_with_color;
+use crate::simulation::{SIMULATION_STAGES, SimulationStage, simulation_stage_name};
+use nightshade::ecs::world::components::Line;
+use nightshade::prelude::*;
+use std::collections::HashMap;
+
+const MAX_CHANGE_LINES: usize = 12;
+const STEPPER_LINE_HEIGHT: f32 = 20.0;
+const CHANGED_TILE_COLOR: [f32; 4] = [1.0, 0.2, 1.0, 1.0];
+
+#[derive(Default)]
+pub struct DebugStepper {
+    pub paused: bool,
+    pub step_frame_requested: bool,
+    pub step_system_requested: bool,
+    pub next_stage_index: usize,
+    pub changes: Vec<String>,
+    pub changed_tiles: Vec<HexCoord>,
+    pub texts: Vec<HudText>,
+    pub highlight_entity: Option<Entity>,
+}
+
+pub struct SimulationSnapshot {
+    units: HashMap<freecs::Entity, (HexCoord, Faction, i32, bool)>,
+    tiles: HashMap<HexCoord, Option<Faction>>,
+    current_faction: Faction,
+    actions_remaining: u8,
+    turn_number: u32,
+}
+
+pub fn debug_stepper_toggle(stepper: &mut DebugStepper) {
+    stepper.paused = !stepper.paused;
+    stepper.next_stage_index = 0;
+    stepper.changes.clear();
+    stepper.changed_tiles.clear();
+}
+
+pub fn debug_stepper_request_frame(stepper: &mut DebugStepper) {
+    if stepper.paused {
+        stepper.step_frame_requested = true;
+    }
+}
+
+pub fn debug_stepper_request_system(stepper: &mut DebugStepper) {
+    if stepper.paused {
+        stepper.step_system_requested = true;
+    }
+}
+
+pub fn debug_stepper_stages_to_run(stepper: &mut DebugStepper) -> Vec<SimulationStage> {
+    if !stepper.paused {
+        return SIMULATION_STAGES.to_vec();
+    }
+
+    if stepper.step_frame_requested {
+        stepper.step_frame_requested = false;
+        stepper.step_system_requested = false;
+        let remaining = SIMULATION_STAGES[stepper.next_stage_index..].to_vec();
+        stepper.next_stage_index = 0;
+        stepper.changes.clear();
+        stepper.changed_tiles.clear();
+        return remaining;
+    }
+
+    if stepper.step_system_requested {
+        stepper.step_system_requested = false;
+        let stage = SIMULATION_STAGES[stepper.next_stage_index];
+        stepper.next_stage_index = (stepper.next_stage_index + 1) % SIMULATION_STAGES.len();
+        stepper.changes.clear();
+        stepper.changed_tiles.clear();
+        return vec![stage];
+    }
+
+    Vec::new()
+}
+
+pub fn capture_simulation_snapshot(game_world: &GameWorld) -> SimulationSnapshot {
+    let units = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter_map(|entity| {
+            let hex = game_world.get_hex_position(entity)?.0;
+            let unit = game_world.get_unit(entity)?;
+            Some((entity, (hex, unit.faction, unit.soldiers, unit.has_moved)))
+        })
+        .collect();
+
+    let tiles = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let hex = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            Some((hex, tile.faction))
+        })
+        .collect();
+
+    SimulationSnapshot {
+        units,
+        tiles,
+        current_faction: game_world.resources.current_faction,
+        actions_remaining: game_world.resources.actions_remaining,
+        turn_number: game_world.resources.turn_number,
+    }
+}
+
+fn owner_name(owner: Option<Faction>) -> &'static str {
+    owner.map(faction_name).unwrap_or("nobody")
+}
+
+pub fn debug_stepper_record_changes(
+    stepper: &mut DebugStepper,
+    stage: SimulationStage,
+    before: &SimulationSnapshot,
+    after: &SimulationSnapshot,
+) {
+    let stage_name = simulation_stage_name(stage);
+    let mut changes = Vec::new();
+
+    for (entity, &(hex, faction, soldiers, has_moved)) in &before.units {
+        match after.units.get(entity) {
+            None => {
+                changes.push(format!(
+                    "{} unit at ({},{}) removed",
+                    faction_name(faction),
+                    hex.column,
+                    hex.row
+                ));
+                stepper.changed_tiles.push(hex);
+            }
+            Some(&(new_hex, _, new_soldiers, new_has_moved)) => {
+                if new_hex != hex {
+                    changes.push(format!(
+                        "{} unit ({},{}) -> ({},{})",
+                        faction_name(faction),
+                        hex.column,
+                        hex.row,
+                        new_hex.column,
+                        new_hex.row
+                    ));
+                    stepper.changed_tiles.push(hex);
+                    stepper.changed_tiles.push(new_hex);
+                }
+                if new_soldiers != soldiers {
+                    changes.push(format!(
+                        "{} unit at ({},{}) soldiers {} -> {}",
+                        faction_name(faction),
+                        new_hex.column,
+                        new_hex.row,
+                        soldiers,
+                        new_soldiers
+                    ));
+                    stepper.changed_tiles.push(new_hex);
+                }
+                if new_has_moved != has_moved {
+                    changes.push(format!(
+                        "{} unit at ({},{}) has_moved = {}",
+                        faction_name(faction),
+                        new_hex.column,
+                        new_hex.row,
+                        new_has_moved
+                    ));
+                }
+            }
+        }
+    }
+
+    for (entity, &(hex, faction, soldiers, _)) in &after.units {
+        if !before.units.contains_key(entity) {
+            changes.push(format!(
+                "{} unit spawned at ({},{}) with {}",
+                faction_name(faction),
+                hex.column,
+                hex.row,
+                soldiers
+            ));
+            stepper.changed_tiles.push(hex);
+        }
+    }
+
+    for (hex, owner) in &after.tiles {
+        let previous_owner = before.tiles.get(hex).copied().flatten();
+        if previous_owner != *owner {
+            changes.push(format!(
+                "tile ({},{}) {} -> {}",
+                hex.column,
+                hex.row,
+                owner_name(previous_owner),
+                owner_name(*owner)
+            ));
+            stepper.changed_tiles.push(*hex);
+        }
+    }
+
+    if before.current_faction != after.current_faction {
+        changes.push(format!(
+            "turn passed {} -> {}",
+            faction_name(before.current_faction),
+            faction_name(after.current_faction)
+        ));
+    }
+    if before.actions_remaining != after.actions_remaining {
+        changes.push(format!(
+            "actions {} -> {}",
+            before.actions_remaining, after.actions_remaining
+        ));
+    }
+    if before.turn_number != after.turn_number {
+        changes.push(format!(
+            "turn number {} -> {}",
+            before.turn_number, after.turn_number
+        ));
+    }
+
+    if changes.is_empty() {
+        stepper.changes.push(format!("{}: no changes", stage_name));
+    } else {
+        for change in changes {
+            stepper.changes.push(format!("{}: {}", stage_name, change));
+        }
+    }
+}
+
+fn spawn_stepper_ui(stepper: &mut DebugStepper, world: &mut World, ui_scale: f32) {
+    let props = TextProperties {
+        font_size: 16.0,
+        color: nalgebra_glm::vec4(1.0, 0.6, 1.0, 1.0),
+        alignment: TextAlignment::Right,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    for index in 0..MAX_CHANGE_LINES + 1 {
+        stepper.texts.push(hud_text_spawn(
+            world,
+            "",
+            HudAnchor::TopRight,
+            nalgebra_glm::vec2(-10.0, 40.0 + index as f32 * STEPPER_LINE_HEIGHT),
+            props.clone(),
+            ui_scale,
+        ));
+    }
+
+    stepper.highlight_entity = Some(spawn_hidden_lines_entity(world));
+}
+
+pub fn despawn_debug_stepper_ui(stepper: &mut DebugStepper, world: &mut World) {
+    for mut hud_text in stepper.texts.drain(..) {
+        hud_text_despawn(world, &mut hud_text);
+    }
+    if let Some(entity) = stepper.highlight_entity.take() {
+        world.queue_command(WorldCommand::DespawnRecursive { entity });
+    }
+}
+
+pub fn debug_stepper_ui_system(
+    stepper: &mut DebugStepper,
+    game_world: &GameWorld,
+    world: &mut World,
+    ui_scale: f32,
+) {
+    if !stepper.paused {
+        if !stepper.texts.is_empty() {
+            despawn_debug_stepper_ui(stepper, world);
+        }
+        return;
+    }
+
+    if stepper.texts.is_empty() {
+        spawn_stepper_ui(stepper, world, ui_scale);
+    }
+
+    let next_stage = simulation_stage_name(SIMULATION_STAGES[stepper.next_stage_index]);
+    let header = format!(
+        "STEPPER PAUSED  next: {}  [F5] Resume [F6] Frame [F7] System",
+        next_stage
+    );
+
+    hud_text_set(world, &mut stepper.texts[0], &header);
+
+    let first_change = stepper.changes.len().saturating_sub(MAX_CHANGE_LINES);
+    for (slot, hud_text) in stepper.texts.iter_mut().skip(1).enumerate() {
+        let line = stepper
+            .changes
+            .get(first_change + slot)
+            .map(String::as_str)
+            .unwrap_or("");
+        hud_text_set(world, hud_text, line);
+    }
+
+    if let Some(entity) = stepper.highlight_entity {
+        let hex_width = game_world.resources.hex_width;
+        let hex_depth = game_world.resources.hex_depth;
+        let orientation = game_world.resources.hex_orientation;
+        let color = nalgebra_glm::vec4(
+            CHANGED_TILE_COLOR[0],
+            CHANGED_TILE_COLOR[1],
+            CHANGED_TILE_COLOR[2],
+            CHANGED_TILE_COLOR[3],
+        );
+        let lines: Vec<Line> = stepper
+            .changed_tiles
+            .iter()
+            .flat_map(|coord| {
+                let center = hex_to_world_position(
+                    coord.column,
+                    coord.row,
+                    hex_width,
+                    hex_depth,
+                    orientation,
+                );
+                generate_hex_outline_with_color(
+                    center,
+                    hex_width,
+                    hex_depth,
+                    orientation,
+                    12.0,
+                    color,
+                )
+            })
+            .collect();
+
+        let has_lines = !lines.is_empty();
+        if let Some(lines_component) = world.get_lines_mut(entity) {
+            lines_component.lines = lines;
+            lines_component.mark_dirty();
+        }
+        if let Some(visibility) = world.get_visibility_mut(entity) {
+            visibility.visible = has_lines;
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::hex::HexCoord;
+use crate::hud_text::spawn_scaled_hud_text;
+use crate::menu::{MenuButton, create_button, update_buttons_hover};
+use nightshade::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmPurpose {
+    EndTurn,
+    Attack(HexCoord),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogChoice {
+    None,
+    Confirm,
+    Cancel,
+}
+
+#[derive(Default)]
+pub struct ConfirmDialog {
+    pub purpose: Option<ConfirmPurpose>,
+    pub message_entity: Option<Entity>,
+    pub hint_entity: Option<Entity>,
+    pub buttons: Vec<MenuButton>,
+    pub hovered_index: Option<usize>,
+}
+
+pub fn dialog_is_open(dialog: &ConfirmDialog) -> bool {
+    dialog.purpose.is_some()
+}
+
+pub fn dialog_open(
+    dialog: &mut ConfirmDialog,
+    world: &mut World,
+    purpose: ConfirmPurpose,
+    message: &str,
+    confirm_label: &str,
+    ui_scale: f32,
+) {
+    dialog_close(dialog, world);
+    dialog.purpose = Some(purpose);
+
+    dialog.message_entity = Some(spawn_scaled_hud_text(
+        world,
+        message,
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, -60.0),
+        TextProperties {
+            font_size: 30.0,
+            color: nalgebra_glm::vec4(1.0, 0.85, 0.4, 1.0),
+            alignment: TextAlignment::Center,
+            outline_width: 0.08,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        },
+        ui_scale,
+    ));
+    dialog.hint_entity = Some(spawn_scaled_hud_text(
+        world,
+        "[ENTER] Confirm  [ESC] Cancel",
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, -25.0),
+        TextProperties {
+            font_size: 18.0,
+            color: nalgebra_glm::vec4(0.8, 0.8, 0.8, 1.0),
+            alignment: TextAlignment::Center,
+            outline_width: 0.05,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        },
+        ui_scale,
+    ));
+
+    dialog.buttons.push(create_button(
+        world,
+        confirm_label,
+        nalgebra_glm::vec2(-120.0, 20.0),
+        HudAnchor::Center,
+        32.0,
+        ui_scale,
+    ));
+    dialog.buttons.push(create_button(
+        world,
+        "CANCEL",
+        nalgebra_glm::vec2(120.0, 20.0),
+        HudAnchor::Center,
+        32.0,
+        ui_scale,
+    ));
+}
+
+pub fn dialog_close(dialog: &mut ConfirmDialog, world: &mut World) {
+    dialog.purpose = None;
+    dialog.hovered_index = None;
+    if let Some(entity) = dialog.message_entity.take() {
+        world.despawn_entities(&[entity]);
+    }
+    if let Some(entity) = dialog.hint_entity.take() {
+        world.despawn_entities(&[entity]);
+    }
+    for button in dialog.buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+}
+
+pub fn dialog_system(
+    dialog: &mut ConfirmDialog,
+    world: &mut World,
+    screen_width: f32,
+    screen_height: f32,
+) -> DialogChoice {
+    if !dialog_is_open(dialog) {
+        return DialogChoice::None;
+    }
+
+    let mouse_x = world.resources.input.mouse.position.x;
+    let mouse_y = world.resources.input.mouse.position.y;
+    let clicked = world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::LEFT_JUST_RELEASED);
+
+    dialog.hovered_index = update_buttons_hover(
+        &dialog.buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        dialog.hovered_index,
+    );
+
+    match (clicked, dialog.hovered_index) {
+        (true, Some(0)) => DialogChoice::Confirm,
+        (true, Some(_)) => DialogChoice::Cancel,
+        _ => DialogChoice::None,
+    }
+}
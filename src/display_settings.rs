@@ -0,0 +1,173 @@
+use nightshade::prelude::*;
+use nightshade::winit::window::Fullscreen;
+use std::path::Path;
+
+pub const DISPLAY_SETTINGS_FILE_PATH: &str = "display.hexsettings";
+const DISPLAY_SETTINGS_HEADER: &str = "hexwar-display 1";
+pub const RESOLUTIONS: [(u32, u32); 5] = [
+    (1280, 720),
+    (1600, 900),
+    (1920, 1080),
+    (2560, 1440),
+    (3840, 2160),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplaySettings {
+    pub fullscreen: bool,
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    pub battle_cinematics: bool,
+    pub screen_shake: bool,
+    pub battle_scars: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            resolution: (1920, 1080),
+            vsync: true,
+            battle_cinematics: false,
+            screen_shake: true,
+            battle_scars: true,
+        }
+    }
+}
+
+pub fn display_mode_label(settings: &DisplaySettings) -> &'static str {
+    if settings.fullscreen {
+        "FULLSCREEN"
+    } else {
+        "WINDOWED"
+    }
+}
+
+pub fn display_resolution_label(settings: &DisplaySettings) -> String {
+    format!("{}x{}", settings.resolution.0, settings.resolution.1)
+}
+
+pub fn display_settings_cycle_resolution(settings: &mut DisplaySettings) {
+    settings.resolution = RESOLUTIONS
+        .iter()
+        .position(|resolution| *resolution == settings.resolution)
+        .map(|index| RESOLUTIONS[(index + 1) % RESOLUTIONS.len()])
+        .unwrap_or(RESOLUTIONS[0]);
+}
+
+pub fn display_settings_to_string(settings: &DisplaySettings) -> String {
+    let mut output = String::new();
+    output.push_str(DISPLAY_SETTINGS_HEADER);
+    output.push('\n');
+    output.push_str(&format!(
+        "mode {}\n",
+        if settings.fullscreen {
+            "fullscreen"
+        } else {
+            "windowed"
+        }
+    ));
+    output.push_str(&format!(
+        "resolution {} {}\n",
+        settings.resolution.0, settings.resolution.1
+    ));
+    output.push_str(&format!(
+        "vsync {}\n",
+        if settings.vsync { "on" } else { "off" }
+    ));
+    output.push_str(&format!(
+        "battle_cinematics {}\n",
+        if settings.battle_cinematics {
+            "on"
+        } else {
+            "off"
+        }
+    ));
+    output.push_str(&format!(
+        "screen_shake {}\n",
+        if settings.screen_shake { "on" } else { "off" }
+    ));
+    output.push_str(&format!(
+        "battle_scars {}\n",
+        if settings.battle_scars { "on" } else { "off" }
+    ));
+    output
+}
+
+fn parse_dimension(line: usize, value: &str) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|dimension| *dimension > 0)
+        .ok_or_else(|| format!("line {}: invalid dimension '{}'", line, value))
+}
+
+pub fn display_settings_from_string(contents: &str) -> Result<DisplaySettings, String> {
+    let mut lines = contents.lines().enumerate();
+
+    match lines.next() {
+        Some((_, header)) if header.trim() == DISPLAY_SETTINGS_HEADER => {}
+        _ => return Err("line 1: missing display settings header".to_string()),
+    }
+
+    let mut settings = DisplaySettings::default();
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [] => {}
+            ["mode", "fullscreen"] => settings.fullscreen = true,
+            ["mode", "windowed"] => settings.fullscreen = false,
+            ["resolution", width, height] => {
+                settings.resolution = (
+                    parse_dimension(line_number, width)?,
+                    parse_dimension(line_number, height)?,
+                );
+            }
+            ["vsync", "on"] => settings.vsync = true,
+            ["vsync", "off"] => settings.vsync = false,
+            ["battle_cinematics", "on"] => settings.battle_cinematics = true,
+            ["battle_cinematics", "off"] => settings.battle_cinematics = false,
+            ["screen_shake", "on"] => settings.screen_shake = true,
+            ["screen_shake", "off"] => settings.screen_shake = false,
+            ["battle_scars", "on"] => settings.battle_scars = true,
+            ["battle_scars", "off"] => settings.battle_scars = false,
+            _ => return Err(format!("line {}: unrecognized entry", line_number)),
+        }
+    }
+
+    Ok(settings)
+}
+
+pub fn save_display_settings(path: &Path, settings: &DisplaySettings) -> Result<(), String> {
+    std::fs::write(path, display_settings_to_string(settings))
+        .map_err(|error| format!("Failed to write {}: {}", path.display(), error))
+}
+
+pub fn display_settings_for_launch() -> Result<DisplaySettings, String> {
+    let path = Path::new(DISPLAY_SETTINGS_FILE_PATH);
+    if !path.exists() {
+        return Ok(DisplaySettings::default());
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+    display_settings_from_string(&contents)
+}
+
+pub fn apply_display_settings(settings: &DisplaySettings, world: &mut World) {
+    if let Some(handle) = world.resources.window.handle.as_ref() {
+        if settings.fullscreen {
+            handle.set_fullscreen(Some(Fullscreen::Borderless(None)));
+        } else {
+            handle.set_fullscreen(None);
+            let _ = handle.request_inner_size(nightshade::winit::dpi::PhysicalSize::new(
+                settings.resolution.0,
+                settings.resolution.1,
+            ));
+        }
+    }
+
+    world.resources.graphics.vsync = settings.vsync;
+    world.resources.graphics.render_graph_dirty = true;
+}
@@ -0,0 +1,147 @@
+use crate::ecs::{Faction, GameEvents};
+use crate::hud_text::{HudText, hud_text_despawn, hud_text_set, hud_text_spawn};
+use nightshade::prelude::*;
+
+const REPORT_LINES: usize = 6;
+const REPORT_LINE_HEIGHT: f32 = 22.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct EconomyTurn {
+    pub turn: u32,
+    pub capital: i32,
+    pub city_count: u32,
+    pub city_soldiers: i32,
+    pub port_rolls: Vec<i32>,
+    pub territory_bonus: i32,
+}
+
+#[derive(Default)]
+pub struct EconomyReport {
+    pub enabled: bool,
+    pub latest: Option<EconomyTurn>,
+    pub texts: Vec<HudText>,
+}
+
+pub fn economy_report_toggle(report: &mut EconomyReport) {
+    report.enabled = !report.enabled;
+}
+
+pub fn economy_report_record_events(
+    report: &mut EconomyReport,
+    events: &GameEvents,
+    player_faction: Faction,
+    turn_number: u32,
+) {
+    for event in &events.reinforcement_events {
+        if event.faction != player_faction {
+            continue;
+        }
+        if report.latest.as_ref().map(|latest| latest.turn) != Some(turn_number) {
+            report.latest = Some(EconomyTurn {
+                turn: turn_number,
+                ..EconomyTurn::default()
+            });
+        }
+        let Some(latest) = report.latest.as_mut() else {
+            continue;
+        };
+        match event.location_name.as_str() {
+            "capital" => latest.capital += event.soldiers,
+            "city" => {
+                latest.city_count += 1;
+                latest.city_soldiers += event.soldiers;
+            }
+            "port" => latest.port_rolls.push(event.soldiers),
+            "territory" => latest.territory_bonus += event.soldiers,
+            _ => {}
+        }
+    }
+}
+
+pub fn economy_report_lines(latest: Option<&EconomyTurn>) -> Vec<String> {
+    let Some(latest) = latest else {
+        return vec![
+            "ECONOMY REPORT [E]".to_string(),
+            "No reinforcements received yet".to_string(),
+        ];
+    };
+
+    let port_total: i32 = latest.port_rolls.iter().sum();
+    let port_line = if latest.port_rolls.is_empty() {
+        "Port rolls: none".to_string()
+    } else {
+        let rolls: Vec<String> = latest
+            .port_rolls
+            .iter()
+            .map(|roll| roll.to_string())
+            .collect();
+        format!("Port rolls: {} (+{})", rolls.join(", "), port_total)
+    };
+    let total = latest.capital + latest.city_soldiers + port_total + latest.territory_bonus;
+
+    vec![
+        format!("ECONOMY REPORT [E]  turn {}", latest.turn),
+        format!("Capital: +{}", latest.capital),
+        format!(
+            "Cities: +{} from {} {}",
+            latest.city_soldiers,
+            latest.city_count,
+            if latest.city_count == 1 {
+                "city"
+            } else {
+                "cities"
+            }
+        ),
+        port_line,
+        format!("Territory bonus: +{}", latest.territory_bonus),
+        format!("Total: +{} soldiers", total),
+    ]
+}
+
+fn spawn_economy_report_ui(report: &mut EconomyReport, world: &mut World, ui_scale: f32) {
+    let props = TextProperties {
+        font_size: 18.0,
+        color: nalgebra_glm::vec4(0.85, 1.0, 0.7, 1.0),
+        alignment: TextAlignment::Right,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    let top = -(REPORT_LINES as f32 * REPORT_LINE_HEIGHT) / 2.0;
+    for index in 0..REPORT_LINES {
+        report.texts.push(hud_text_spawn(
+            world,
+            "",
+            HudAnchor::CenterRight,
+            nalgebra_glm::vec2(-10.0, top + index as f32 * REPORT_LINE_HEIGHT),
+            props.clone(),
+            ui_scale,
+        ));
+    }
+}
+
+pub fn despawn_economy_report(report: &mut EconomyReport, world: &mut World) {
+    for mut hud_text in report.texts.drain(..) {
+        hud_text_despawn(world, &mut hud_text);
+    }
+}
+
+pub fn economy_report_system(report: &mut EconomyReport, world: &mut World, ui_scale: f32) {
+    if !report.enabled {
+        if !report.texts.is_empty() {
+            despawn_economy_report(report, world);
+        }
+        return;
+    }
+
+    if report.texts.is_empty() {
+        spawn_economy_report_ui(report, world, ui_scale);
+    }
+
+    let lines = economy_report_lines(report.latest.as_ref());
+    for (index, hud_text) in report.texts.iter_mut().enumerate() {
+        let line = lines.get(index).map(String::as_str).unwrap_or("");
+        hud_text_set(world, hud_text, line);
+    }
+}
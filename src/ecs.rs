@@ -1,16 +1,67 @@
-use crate::hex::HexCoord;
-use crate::map::MapGenParams;
+use crate::constants::{HILLS_ELEVATION, HILLS_MOVEMENT_COST, SWAMP_MOVEMENT_COST};
+use crate::handicap::Handicap;
+use crate::hex::{HexCoord, HexOrientation, hex_wrap, hex_wrapped_distance, hex_wrapped_neighbors};
+use crate::labels::LabelPool;
+use crate::map::{GeneratedMap, MapGenParams, map_wrap_width};
+use crate::map_balance::MapBalance;
+use crate::map_generation::MapOutlines;
+use crate::rng::GameRng;
+use crate::rules::GameRules;
+use crate::sim_clock::SimulationClock;
+use crate::state_hash::StateHashRecord;
+use crate::teams::TeamLayout;
+use crate::turn_rotation::TurnRotation;
+use crate::turn_timer::TurnTimer;
 use nightshade::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub use freecs::Entity;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum Difficulty {
     #[default]
     Easy,
     Normal,
     Hard,
+    Brutal,
+}
+
+pub const ALL_DIFFICULTIES: [Difficulty; 4] = [
+    Difficulty::Easy,
+    Difficulty::Normal,
+    Difficulty::Hard,
+    Difficulty::Brutal,
+];
+
+pub fn difficulty_key(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "easy",
+        Difficulty::Normal => "normal",
+        Difficulty::Hard => "hard",
+        Difficulty::Brutal => "brutal",
+    }
+}
+
+pub fn difficulty_next(difficulty: Difficulty) -> Difficulty {
+    let index = ALL_DIFFICULTIES
+        .iter()
+        .position(|candidate| *candidate == difficulty)
+        .unwrap_or(0);
+    ALL_DIFFICULTIES[(index + 1) % ALL_DIFFICULTIES.len()]
+}
+
+pub fn difficulty_from_key(key: &str) -> Option<Difficulty> {
+    ALL_DIFFICULTIES
+        .into_iter()
+        .find(|difficulty| difficulty_key(*difficulty) == key)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HealthBarMode {
+    #[default]
+    Always,
+    HoverOrSelection,
+    Hidden,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -22,14 +73,12 @@ pub enum Faction {
     Greenland,
 }
 
-pub fn next_faction(faction: Faction) -> Faction {
-    match faction {
-        Faction::Redosia => Faction::Violetnam,
-        Faction::Violetnam => Faction::Bluegaria,
-        Faction::Bluegaria => Faction::Greenland,
-        Faction::Greenland => Faction::Redosia,
-    }
-}
+pub const ALL_FACTIONS: [Faction; 4] = [
+    Faction::Redosia,
+    Faction::Violetnam,
+    Faction::Bluegaria,
+    Faction::Greenland,
+];
 
 pub fn faction_color(faction: Faction) -> [f32; 4] {
     match faction {
@@ -67,6 +116,7 @@ freecs::ecs! {
         movement: Movement => MOVEMENT,
         tile: Tile => TILE,
         floating_popup: FloatingPopup => FLOATING_POPUP,
+        general: General => GENERAL,
     }
     Tags {
         selected => SELECTED,
@@ -74,26 +124,62 @@ freecs::ecs! {
     GameResources {
         hex_width: f32,
         hex_depth: f32,
+        hex_orientation: HexOrientation,
         rng_seed: u32,
+        settlement_seed: u32,
+        game_rng: GameRng,
         map_params: MapGenParams,
+        rules: GameRules,
+        map_balance: MapBalance,
+        generated_map: GeneratedMap,
+        despawned_visuals: Vec<Entity>,
+        labels: LabelPool,
+        map_outlines: Option<MapOutlines>,
+        ownership_history: HashMap<HexCoord, Vec<OwnershipChange>>,
+        state_hashes: Vec<StateHashRecord>,
         needs_regeneration: bool,
         valid_move_tiles: HashSet<HexCoord>,
         hovered_tile: Option<HexCoord>,
         previously_highlighted: HashSet<HexCoord>,
         previous_hovered_tile: Option<HexCoord>,
+        tinted_owners: HashMap<HexCoord, Faction>,
+        scorched_tiles: HashSet<HexCoord>,
         previous_selected_unit: Option<freecs::Entity>,
         previous_valid_move_count: usize,
+        port_route_origin: Option<HexCoord>,
+        port_route_tiles: HashSet<HexCoord>,
+        airlift_tiles: HashSet<HexCoord>,
+        previous_port_route: Option<(HexCoord, Option<HexCoord>)>,
         current_faction: Faction,
         actions_remaining: u8,
         turn_number: u32,
         faction_eliminated: [bool; 4],
+        turn_rotation: TurnRotation,
+        turn_timer: TurnTimer,
         faction_morale: [i32; 4],
+        casualty_stats: [CasualtyStats; 4],
         capital_owners: [Option<Faction>; 4],
+        capital_coords: [HexCoord; 4],
+        capital_relocated: [bool; 4],
         speech_used: bool,
+        ability_cooldowns: [u32; 4],
+        active_abilities: [bool; 4],
         turn_order: Vec<freecs::Entity>,
         current_unit_index: usize,
         game_speed: f32,
+        sim_clock: SimulationClock,
         difficulty: Difficulty,
+        faction_difficulties: [Difficulty; 4],
+        human_factions: [bool; 4],
+        handicaps: [Handicap; 4],
+        team_layout: TeamLayout,
+        health_bar_mode: HealthBarMode,
+        unit_mesh_name: String,
+        damage_flashes: HashMap<freecs::Entity, f32>,
+        unit_deaths: Vec<UnitDeath>,
+        morale_changes: Vec<(Faction, i32)>,
+        bumper_harvest: Option<HexCoord>,
+        ai_trace: AiDecisionTrace,
     }
 }
 
@@ -101,9 +187,94 @@ pub fn get_faction_morale(resources: &GameResources, faction: Faction) -> i32 {
     resources.faction_morale[faction_index(faction)]
 }
 
+pub fn faction_capital(resources: &GameResources, faction: Faction) -> HexCoord {
+    resources.capital_coords[faction_index(faction)]
+}
+
+pub fn map_distance(resources: &GameResources, from: HexCoord, to: HexCoord) -> i32 {
+    hex_wrapped_distance(
+        from,
+        to,
+        map_wrap_width(&resources.generated_map),
+        resources.generated_map.orientation,
+    )
+}
+
+pub fn map_neighbors(resources: &GameResources, coord: HexCoord) -> [HexCoord; 6] {
+    hex_wrapped_neighbors(
+        coord,
+        map_wrap_width(&resources.generated_map),
+        resources.generated_map.orientation,
+    )
+}
+
+pub fn map_wrap(resources: &GameResources, coord: HexCoord) -> HexCoord {
+    hex_wrap(coord, map_wrap_width(&resources.generated_map))
+}
+
+pub fn faction_is_ai(resources: &GameResources, faction: Faction) -> bool {
+    !resources.human_factions[faction_index(faction)]
+}
+
+pub fn faction_difficulty(resources: &GameResources, faction: Faction) -> Difficulty {
+    resources.faction_difficulties[faction_index(faction)]
+}
+
+pub fn hardest_ai_difficulty(resources: &GameResources) -> Difficulty {
+    ALL_FACTIONS
+        .into_iter()
+        .filter(|faction| faction_is_ai(resources, *faction))
+        .map(|faction| faction_difficulty(resources, faction))
+        .max()
+        .unwrap_or_default()
+}
+
 pub fn modify_faction_morale(resources: &mut GameResources, faction: Faction, delta: i32) {
     let index = faction_index(faction);
-    resources.faction_morale[index] = (resources.faction_morale[index] + delta).clamp(-50, 50);
+    let previous = resources.faction_morale[index];
+    resources.faction_morale[index] = (previous + delta).clamp(-50, 50);
+    let applied = resources.faction_morale[index] - previous;
+    if applied != 0 {
+        resources.morale_changes.push((faction, applied));
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AiDecisionTrace {
+    pub faction: Faction,
+    pub unit: Option<HexCoord>,
+    pub target: Option<HexCoord>,
+    pub considered_moves: Vec<HexCoord>,
+    pub attack_odds: Vec<(HexCoord, f32)>,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CasualtyStats {
+    pub soldiers_lost: i32,
+    pub soldiers_killed: i32,
+    pub units_lost: u32,
+    pub units_destroyed: u32,
+    pub cities_captured: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnershipChange {
+    pub turn: u32,
+    pub owner: Option<Faction>,
+}
+
+pub fn record_ownership_change(
+    resources: &mut GameResources,
+    coord: HexCoord,
+    owner: Option<Faction>,
+) {
+    let turn = resources.turn_number;
+    resources
+        .ownership_history
+        .entry(coord)
+        .or_default()
+        .push(OwnershipChange { turn, owner });
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
@@ -115,12 +286,34 @@ pub struct WorldPosition(pub Vec3);
 #[derive(Debug, Clone, Copy, Default)]
 pub struct HexPosition(pub HexCoord);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitKind {
+    #[default]
+    Infantry,
+    Scout,
+    Supply,
+}
+
+pub fn unit_kind_name(kind: UnitKind) -> &'static str {
+    match kind {
+        UnitKind::Infantry => "infantry",
+        UnitKind::Scout => "scout",
+        UnitKind::Supply => "supply",
+    }
+}
+
+pub fn unit_kind_is_combatant(kind: UnitKind) -> bool {
+    kind == UnitKind::Infantry
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Unit {
     pub faction: Faction,
+    pub kind: UnitKind,
     pub soldiers: i32,
     pub morale: i32,
     pub movement_range: i32,
+    pub movement_left: i32,
     pub has_moved: bool,
     pub text_entity: Option<Entity>,
 }
@@ -131,6 +324,14 @@ pub struct Movement {
     pub current_segment: usize,
     pub segment_progress: f32,
     pub speed: f32,
+    pub trail: Vec<TrailPoint>,
+    pub by_sea: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrailPoint {
+    pub position: Vec3,
+    pub age: f32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -139,6 +340,8 @@ pub enum TileType {
     #[default]
     Land,
     Forest,
+    Hills,
+    Swamp,
     City,
     Port,
     Capital,
@@ -149,15 +352,72 @@ pub fn tile_defense_bonus(tile_type: TileType) -> f32 {
         TileType::Capital => 1.2,
         TileType::City => 1.1,
         TileType::Forest => 1.15,
+        TileType::Hills => 1.25,
+        TileType::Swamp => 1.0,
         TileType::Port => 1.05,
         _ => 1.0,
     }
 }
 
+pub fn tile_movement_cost(tile_type: TileType) -> i32 {
+    match tile_type {
+        TileType::Hills => HILLS_MOVEMENT_COST,
+        TileType::Swamp => SWAMP_MOVEMENT_COST,
+        _ => 1,
+    }
+}
+
+pub fn tile_elevation(tile_type: TileType) -> f32 {
+    match tile_type {
+        TileType::Hills => HILLS_ELEVATION,
+        _ => 0.0,
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Tile {
     pub tile_type: TileType,
     pub faction: Option<Faction>,
+    pub growth: u32,
+    pub improvement: Option<Improvement>,
+    pub construction: Option<Construction>,
+    pub ruin: Option<RuinBonus>,
+    pub pillaged_until: Option<u32>,
+    pub airfield: bool,
+    pub battles: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuinBonus {
+    Soldiers,
+    Morale,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Improvement {
+    Fort,
+    Watchtower,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Construction {
+    pub improvement: Improvement,
+    pub progress: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct General {
+    pub level: u32,
+    pub experience: u32,
+}
+
+impl Default for General {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            experience: 0,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -167,11 +427,132 @@ pub struct FloatingPopup {
 }
 
 #[derive(Debug, Clone, Copy)]
+pub struct UnitDeath {
+    pub faction: Faction,
+    pub position: Vec3,
+    pub radius: f32,
+    pub text_entity: Option<Entity>,
+    pub engine_entity: Option<Entity>,
+    pub elapsed: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatSide {
+    Attacker,
+    Defender,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatModifierKind {
+    Morale,
+    Leader,
+    ForestAmbush,
+    Flanking,
+    Veterancy,
+    Terrain,
+    Fortification,
+    Unarmed,
+}
+
+pub fn combat_modifier_name(kind: CombatModifierKind) -> &'static str {
+    match kind {
+        CombatModifierKind::Morale => "morale",
+        CombatModifierKind::Leader => "leader",
+        CombatModifierKind::ForestAmbush => "forest ambush",
+        CombatModifierKind::Flanking => "flanking",
+        CombatModifierKind::Veterancy => "veterancy",
+        CombatModifierKind::Terrain => "terrain",
+        CombatModifierKind::Fortification => "fortification",
+        CombatModifierKind::Unarmed => "unarmed",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CombatModifier {
+    pub side: CombatSide,
+    pub kind: CombatModifierKind,
+    pub multiplier: f32,
+}
+
+pub fn combat_modifier_label(modifier: &CombatModifier) -> String {
+    format!(
+        "{} {:+}%",
+        combat_modifier_name(modifier.kind),
+        ((modifier.multiplier - 1.0) * 100.0).round() as i32
+    )
+}
+
+#[derive(Debug, Clone)]
 pub struct CombatEvent {
     pub attacker_faction: Faction,
     pub defender_faction: Faction,
     pub attacker_survived: bool,
     pub defender_survived: bool,
+    pub location: HexCoord,
+    pub attacker_location: HexCoord,
+    pub attacker_losses: i32,
+    pub defender_losses: i32,
+    pub wounded_unit: Option<freecs::Entity>,
+    pub general_killed: Option<Faction>,
+    pub modifiers: Vec<CombatModifier>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldEventKind {
+    Plague,
+    Eruption,
+    BumperHarvest,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct WorldEvent {
+    pub kind: WorldEventKind,
+    pub faction: Faction,
+    pub location: HexCoord,
+    pub soldiers_lost: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ImprovementEvent {
+    pub faction: Faction,
+    pub location: HexCoord,
+    pub improvement: Improvement,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CapitalRelocatedEvent {
+    pub faction: Faction,
+    pub from: HexCoord,
+    pub to: HexCoord,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RuinClaimedEvent {
+    pub faction: Faction,
+    pub location: HexCoord,
+    pub bonus: RuinBonus,
+    pub amount: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PillageEvent {
+    pub faction: Faction,
+    pub victim: Faction,
+    pub location: HexCoord,
+    pub tile_type: TileType,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AttritionEvent {
+    pub faction: Faction,
+    pub soldiers: i32,
+    pub cause: AttritionCause,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttritionCause {
+    Winter,
+    Swamp,
 }
 
 #[derive(Debug, Clone)]
@@ -181,20 +562,44 @@ pub struct ReinforcementEvent {
     pub location_name: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeaderAbility {
+    BloodAndIron,
+    RallyingHymn,
+    CallOfTheTides,
+    BountifulLands,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct SpeechEvent {
     pub faction: Faction,
+    pub ability: LeaderAbility,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct FactionEliminatedEvent {
     pub faction: Faction,
+    pub conqueror: Faction,
+    pub defected_units: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SurrenderEvent {
+    pub faction: Faction,
+    pub conqueror: Faction,
 }
 
 #[derive(Default)]
 pub struct GameEvents {
     pub combat_events: Vec<CombatEvent>,
     pub reinforcement_events: Vec<ReinforcementEvent>,
+    pub attrition_events: Vec<AttritionEvent>,
+    pub improvement_events: Vec<ImprovementEvent>,
+    pub ruin_events: Vec<RuinClaimedEvent>,
+    pub pillage_events: Vec<PillageEvent>,
+    pub capital_relocated_events: Vec<CapitalRelocatedEvent>,
+    pub world_events: Vec<WorldEvent>,
     pub speech_events: Vec<SpeechEvent>,
     pub faction_eliminated_events: Vec<FactionEliminatedEvent>,
+    pub surrender_events: Vec<SurrenderEvent>,
 }
@@ -1,21 +1,42 @@
-use crate::ecs::{Faction, faction_color, faction_name};
+use crate::ecs::{
+    AttritionCause, CombatModifier, CombatSide, Faction, Improvement, LeaderAbility, RuinBonus,
+    TileType, combat_modifier_label, faction_color, faction_name,
+};
+use crate::hex::HexCoord;
+use crate::hud_text::spawn_scaled_hud_text;
+use crate::systems::improvement_name;
 use nightshade::prelude::*;
 use std::collections::VecDeque;
 
 const MAX_LOG_ENTRIES: usize = 1000;
-const VISIBLE_ENTRIES: usize = 8;
+const MIN_VISIBLE_ENTRIES: usize = 8;
+const MAX_VISIBLE_ENTRIES: usize = 20;
 const LOG_FONT_SIZE: f32 = 16.0;
 const LOG_LINE_HEIGHT: f32 = 20.0;
 const LOG_PADDING: f32 = 10.0;
 const FACTION_TAG_WIDTH: f32 = 85.0;
-const LOG_WIDTH: f32 = 350.0;
-const LOG_HEIGHT: f32 = VISIBLE_ENTRIES as f32 * LOG_LINE_HEIGHT + LOG_PADDING * 2.0;
+const MIN_LOG_WIDTH: f32 = 350.0;
+const MAX_LOG_WIDTH: f32 = 600.0;
+const LOG_SCREEN_FRACTION: f32 = 0.3;
+const FALLBACK_SCREEN_SIZE: (f32, f32) = (800.0, 600.0);
+const DETAIL_COLOR: [f32; 4] = [0.7, 0.7, 0.7, 1.0];
 
 #[derive(Clone)]
 pub struct LogEntry {
     pub faction_tag: String,
     pub faction_color: [f32; 4],
     pub message: String,
+    pub details: Vec<String>,
+    pub expanded: bool,
+}
+
+#[derive(Clone)]
+pub struct LogRow {
+    pub entry_index: usize,
+    pub faction_tag: String,
+    pub faction_color: [f32; 4],
+    pub message: String,
+    pub message_color: [f32; 4],
 }
 
 pub struct LogLineEntities {
@@ -27,6 +48,9 @@ pub struct EventLog {
     pub entries: VecDeque<LogEntry>,
     pub scroll_offset: usize,
     pub line_entities: Vec<LogLineEntities>,
+    pub ui_scale: f32,
+    pub visible_entries: usize,
+    pub width: f32,
 }
 
 pub fn event_log_new() -> EventLog {
@@ -34,24 +58,120 @@ pub fn event_log_new() -> EventLog {
         entries: VecDeque::new(),
         scroll_offset: 0,
         line_entities: Vec::new(),
+        ui_scale: 1.0,
+        visible_entries: MIN_VISIBLE_ENTRIES,
+        width: MIN_LOG_WIDTH,
     }
 }
 
-fn event_log_add_entry(log: &mut EventLog, faction: Faction, message: String) {
+fn window_size(world: &World) -> (f32, f32) {
+    world
+        .resources
+        .window
+        .handle
+        .as_ref()
+        .map(|handle| {
+            let size = handle.inner_size();
+            (size.width as f32, size.height as f32)
+        })
+        .unwrap_or(FALLBACK_SCREEN_SIZE)
+}
+
+pub fn event_log_height(log: &EventLog) -> f32 {
+    log.visible_entries as f32 * LOG_LINE_HEIGHT + LOG_PADDING * 2.0
+}
+
+pub fn event_log_rows(log: &EventLog) -> Vec<LogRow> {
+    let mut rows = Vec::new();
+    for (entry_index, entry) in log.entries.iter().enumerate() {
+        let marker = match (entry.details.is_empty(), entry.expanded) {
+            (true, _) => "",
+            (false, false) => " [+]",
+            (false, true) => " [-]",
+        };
+        rows.push(LogRow {
+            entry_index,
+            faction_tag: entry.faction_tag.clone(),
+            faction_color: entry.faction_color,
+            message: format!("{}{}", entry.message, marker),
+            message_color: [1.0, 1.0, 1.0, 1.0],
+        });
+        if !entry.expanded {
+            continue;
+        }
+        for detail in &entry.details {
+            rows.push(LogRow {
+                entry_index,
+                faction_tag: String::new(),
+                faction_color: entry.faction_color,
+                message: format!("  {}", detail),
+                message_color: DETAIL_COLOR,
+            });
+        }
+    }
+    rows
+}
+
+fn event_log_row_count(log: &EventLog) -> usize {
+    log.entries
+        .iter()
+        .map(|entry| {
+            if entry.expanded {
+                1 + entry.details.len()
+            } else {
+                1
+            }
+        })
+        .sum()
+}
+
+fn event_log_layout(log: &mut EventLog, screen_width: f32, screen_height: f32) {
+    let logical_width = screen_width / log.ui_scale;
+    let logical_height = screen_height / log.ui_scale;
+    let fitting_lines =
+        ((logical_height * LOG_SCREEN_FRACTION - LOG_PADDING * 2.0) / LOG_LINE_HEIGHT) as usize;
+    log.visible_entries = fitting_lines.clamp(MIN_VISIBLE_ENTRIES, MAX_VISIBLE_ENTRIES);
+    log.width = (logical_width * LOG_SCREEN_FRACTION).clamp(MIN_LOG_WIDTH, MAX_LOG_WIDTH);
+    let max_scroll = event_log_row_count(log).saturating_sub(log.visible_entries);
+    log.scroll_offset = log.scroll_offset.min(max_scroll);
+}
+
+fn event_log_add_detailed_entry(
+    log: &mut EventLog,
+    faction: Faction,
+    message: String,
+    details: Vec<String>,
+) {
     let faction_tag = format!("[{}]", faction_name(faction));
     let faction_color = faction_color(faction);
     log.entries.push_back(LogEntry {
         faction_tag,
         faction_color,
         message,
+        details,
+        expanded: false,
     });
     if log.entries.len() > MAX_LOG_ENTRIES {
         log.entries.pop_front();
-        if log.scroll_offset > 0 {
-            log.scroll_offset -= 1;
-        }
     }
-    log.scroll_offset = log.entries.len().saturating_sub(VISIBLE_ENTRIES);
+    log.scroll_offset = event_log_row_count(log).saturating_sub(log.visible_entries);
+}
+
+fn event_log_add_entry(log: &mut EventLog, faction: Faction, message: String) {
+    event_log_add_detailed_entry(log, faction, message, Vec::new());
+}
+
+fn combat_side_detail(label: &str, modifiers: &[CombatModifier], side: CombatSide) -> String {
+    let labels: Vec<String> = modifiers
+        .iter()
+        .filter(|modifier| modifier.side == side)
+        .map(combat_modifier_label)
+        .collect();
+    if labels.is_empty() {
+        format!("{}: no modifiers", label)
+    } else {
+        format!("{}: {}", label, labels.join(", "))
+    }
 }
 
 pub fn event_log_add_combat(
@@ -60,6 +180,7 @@ pub fn event_log_add_combat(
     defender_faction: Faction,
     attacker_survived: bool,
     defender_survived: bool,
+    modifiers: &[CombatModifier],
 ) {
     let defender_name = faction_name(defender_faction);
 
@@ -70,14 +191,38 @@ pub fn event_log_add_combat(
     } else {
         format!("attacked {}", defender_name)
     };
+    let details = vec![
+        combat_side_detail("attacker", modifiers, CombatSide::Attacker),
+        combat_side_detail("defender", modifiers, CombatSide::Defender),
+    ];
 
-    event_log_add_entry(log, attacker_faction, message);
+    event_log_add_detailed_entry(log, attacker_faction, message, details);
 }
 
 pub fn event_log_add_faction_eliminated(log: &mut EventLog, eliminated_faction: Faction) {
     event_log_add_entry(log, eliminated_faction, "has been eliminated!".to_string());
 }
 
+pub fn event_log_add_surrender(log: &mut EventLog, faction: Faction, conqueror: Faction) {
+    let message = format!("surrendered to {}", faction_name(conqueror));
+    event_log_add_entry(log, faction, message);
+}
+
+pub fn event_log_add_defection(
+    log: &mut EventLog,
+    conqueror: Faction,
+    eliminated_faction: Faction,
+    units: usize,
+) {
+    let message = format!(
+        "absorbed {} defecting {} from {}",
+        units,
+        if units == 1 { "army" } else { "armies" },
+        faction_name(eliminated_faction)
+    );
+    event_log_add_entry(log, conqueror, message);
+}
+
 pub fn event_log_add_reinforcement(
     log: &mut EventLog,
     faction: Faction,
@@ -88,16 +233,86 @@ pub fn event_log_add_reinforcement(
     event_log_add_entry(log, faction, message);
 }
 
+pub fn event_log_add_attrition(
+    log: &mut EventLog,
+    faction: Faction,
+    soldiers: i32,
+    cause: AttritionCause,
+) {
+    let source = match cause {
+        AttritionCause::Winter => "winter",
+        AttritionCause::Swamp => "the swamps",
+    };
+    let message = format!("lost {} soldiers to {}", soldiers, source);
+    event_log_add_entry(log, faction, message);
+}
+
+pub fn event_log_add_capital_relocated(log: &mut EventLog, faction: Faction) {
+    event_log_add_entry(log, faction, "relocated its capital".to_string());
+}
+
+pub fn event_log_add_improvement(log: &mut EventLog, faction: Faction, improvement: Improvement) {
+    let message = format!("built a {}", improvement_name(improvement));
+    event_log_add_entry(log, faction, message);
+}
+
+pub fn event_log_add_pillage(
+    log: &mut EventLog,
+    faction: Faction,
+    victim: Faction,
+    tile_type: TileType,
+) {
+    let target = if tile_type == TileType::Port {
+        "port"
+    } else {
+        "city"
+    };
+    let message = format!("pillaged a {} {}", faction_name(victim), target);
+    event_log_add_entry(log, faction, message);
+}
+
+pub fn event_log_add_ruin(log: &mut EventLog, faction: Faction, bonus: RuinBonus, amount: i32) {
+    let message = match bonus {
+        RuinBonus::Soldiers => format!("found {} recruits in ancient ruins", amount),
+        RuinBonus::Morale => format!("uncovered relics in ancient ruins (+{} morale)", amount),
+    };
+    event_log_add_entry(log, faction, message);
+}
+
+pub fn event_log_add_world_event(log: &mut EventLog, faction: Faction, message: &str) {
+    event_log_add_entry(log, faction, message.to_string());
+}
+
 pub fn event_log_add_turn_start(log: &mut EventLog, turn: u32, faction: Faction) {
     let message = format!("Turn {} begins", turn);
     event_log_add_entry(log, faction, message);
 }
 
-pub fn event_log_add_speech(log: &mut EventLog, faction: Faction) {
-    event_log_add_entry(log, faction, "gave an inspiring speech".to_string());
+pub fn event_log_add_speech(log: &mut EventLog, faction: Faction, ability: LeaderAbility) {
+    let message = match ability {
+        LeaderAbility::BloodAndIron => "Blood and Iron! The legions thirst for battle",
+        LeaderAbility::RallyingHymn => "Rallying Hymn echoes through the ranks",
+        LeaderAbility::CallOfTheTides => "Call of the Tides summons the fleets",
+        LeaderAbility::BountifulLands => "Bountiful Lands feed the armies",
+    };
+    event_log_add_entry(log, faction, message.to_string());
+}
+
+pub fn event_log_add_chat(log: &mut EventLog, faction: Faction, text: &str) {
+    event_log_add_entry(log, faction, format!("says: {}", text));
+}
+
+pub fn event_log_add_ping(log: &mut EventLog, faction: Faction, coord: HexCoord) {
+    let message = format!("pinged ({}, {})", coord.column, coord.row);
+    event_log_add_entry(log, faction, message);
 }
 
-pub fn spawn_event_log_ui(world: &mut World, log: &mut EventLog) {
+pub fn spawn_event_log_ui(world: &mut World, log: &mut EventLog, ui_scale: f32) {
+    log.ui_scale = ui_scale;
+    let (screen_width, screen_height) = window_size(world);
+    event_log_layout(log, screen_width, screen_height);
+    let visible_entries = log.visible_entries;
+
     let faction_props = TextProperties {
         font_size: LOG_FONT_SIZE,
         color: nalgebra_glm::vec4(1.0, 1.0, 1.0, 1.0),
@@ -116,25 +331,27 @@ pub fn spawn_event_log_ui(world: &mut World, log: &mut EventLog) {
         ..Default::default()
     };
 
-    for index in 0..VISIBLE_ENTRIES {
+    for index in 0..visible_entries {
         let y_offset = -(LOG_PADDING
-            + (VISIBLE_ENTRIES - 1 - index) as f32 * LOG_LINE_HEIGHT
+            + (visible_entries - 1 - index) as f32 * LOG_LINE_HEIGHT
             + LOG_LINE_HEIGHT);
 
-        let faction_entity = spawn_hud_text_with_properties(
+        let faction_entity = spawn_scaled_hud_text(
             world,
             "",
             HudAnchor::BottomLeft,
             nalgebra_glm::vec2(LOG_PADDING, y_offset),
             faction_props.clone(),
+            ui_scale,
         );
 
-        let message_entity = spawn_hud_text_with_properties(
+        let message_entity = spawn_scaled_hud_text(
             world,
             "",
             HudAnchor::BottomLeft,
             nalgebra_glm::vec2(LOG_PADDING + FACTION_TAG_WIDTH, y_offset),
             message_props.clone(),
+            ui_scale,
         );
 
         log.line_entities.push(LogLineEntities {
@@ -152,12 +369,10 @@ pub fn despawn_event_log_ui(world: &mut World, log: &mut EventLog) {
 
 pub fn update_event_log_ui(world: &mut World, log: &EventLog) {
     let start_index = log.scroll_offset;
-    let entries_to_show: Vec<_> = log
-        .entries
-        .iter()
+    let entries_to_show: Vec<_> = event_log_rows(log)
+        .into_iter()
         .skip(start_index)
-        .take(VISIBLE_ENTRIES)
-        .cloned()
+        .take(log.visible_entries)
         .collect();
 
     for (slot_index, line) in log.line_entities.iter().enumerate() {
@@ -191,7 +406,12 @@ pub fn update_event_log_ui(world: &mut World, log: &EventLog) {
                     .set_text(text_index, entry.message.clone());
             }
             if let Some(hud_text) = world.get_hud_text_mut(line.message_entity) {
-                hud_text.properties.color = nalgebra_glm::vec4(1.0, 1.0, 1.0, 1.0);
+                hud_text.properties.color = nalgebra_glm::vec4(
+                    entry.message_color[0],
+                    entry.message_color[1],
+                    entry.message_color[2],
+                    entry.message_color[3],
+                );
                 hud_text.dirty = true;
             }
         } else {
@@ -226,18 +446,12 @@ pub fn update_event_log_ui(world: &mut World, log: &EventLog) {
 
 pub fn event_log_scroll_system(log: &mut EventLog, world: &mut World) {
     let mouse_pos = world.resources.input.mouse.position;
-    let screen_height = world
-        .resources
-        .window
-        .handle
-        .as_ref()
-        .map(|h| h.inner_size().height as f32)
-        .unwrap_or(600.0);
+    let (_, screen_height) = window_size(world);
 
     let log_left = 0.0;
-    let log_right = LOG_WIDTH;
+    let log_right = log.width * log.ui_scale;
     let log_bottom = screen_height;
-    let log_top = screen_height - LOG_HEIGHT;
+    let log_top = screen_height - event_log_height(log) * log.ui_scale;
 
     let in_log_area = mouse_pos.x >= log_left
         && mouse_pos.x <= log_right
@@ -250,6 +464,29 @@ pub fn event_log_scroll_system(log: &mut EventLog, world: &mut World) {
         return;
     }
 
+    if world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::LEFT_JUST_RELEASED)
+    {
+        let distance_from_bottom = (screen_height - mouse_pos.y) / log.ui_scale - LOG_PADDING;
+        let slots_from_bottom = (distance_from_bottom / LOG_LINE_HEIGHT).floor();
+        if slots_from_bottom >= 0.0 && (slots_from_bottom as usize) < log.visible_entries {
+            let slot = log.visible_entries - 1 - slots_from_bottom as usize;
+            let rows = event_log_rows(log);
+            if let Some(row) = rows.get(log.scroll_offset + slot)
+                && let Some(entry) = log.entries.get_mut(row.entry_index)
+                && !entry.details.is_empty()
+            {
+                entry.expanded = !entry.expanded;
+                let max_scroll = event_log_row_count(log).saturating_sub(log.visible_entries);
+                log.scroll_offset = log.scroll_offset.min(max_scroll);
+            }
+        }
+    }
+
     if !world
         .resources
         .input
@@ -261,7 +498,7 @@ pub fn event_log_scroll_system(log: &mut EventLog, world: &mut World) {
     }
 
     let scroll_lines = -world.resources.input.mouse.wheel_delta.y.round() as i32;
-    let max_scroll = log.entries.len().saturating_sub(VISIBLE_ENTRIES);
+    let max_scroll = event_log_row_count(log).saturating_sub(log.visible_entries);
 
     if scroll_lines < 0 {
         log.scroll_offset = log
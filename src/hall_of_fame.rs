@@ -0,0 +1,198 @@
+use crate::ecs::{Difficulty, Faction, difficulty_from_key, difficulty_key, faction_name};
+use crate::text_file::{TextFileError, check_file_header, line_error, parse_faction};
+use crate::wall_clock::unix_seconds_now;
+use std::path::Path;
+
+pub const HALL_OF_FAME_FILE_PATH: &str = "halloffame.hexscores";
+const HALL_OF_FAME_FILE_HEADER: &str = "hexwar-scores 1";
+pub const MAX_HALL_OF_FAME_ENTRIES: usize = 10;
+const BASE_VICTORY_SCORE: u32 = 1000;
+const SCORE_PER_UNIT_DESTROYED: u32 = 25;
+const SCORE_PENALTY_PER_TURN: u32 = 10;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreEntry {
+    pub score: u32,
+    pub faction: Faction,
+    pub difficulty: Difficulty,
+    pub seed: u32,
+    pub turns: u32,
+    pub date: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HallOfFame {
+    pub entries: Vec<ScoreEntry>,
+}
+
+pub type HallOfFameError = TextFileError;
+
+fn difficulty_multiplier(difficulty: Difficulty) -> u32 {
+    match difficulty {
+        Difficulty::Easy => 1,
+        Difficulty::Normal => 2,
+        Difficulty::Hard => 3,
+        Difficulty::Brutal => 4,
+    }
+}
+
+pub fn victory_score(difficulty: Difficulty, turns: u32, units_destroyed: u32) -> u32 {
+    let earned = BASE_VICTORY_SCORE + units_destroyed * SCORE_PER_UNIT_DESTROYED;
+    earned.saturating_sub(turns * SCORE_PENALTY_PER_TURN) * difficulty_multiplier(difficulty)
+}
+
+pub fn today_date() -> String {
+    date_from_unix_seconds(unix_seconds_now())
+}
+
+pub fn timestamp_label(seconds: u64) -> String {
+    let seconds_of_day = seconds % SECONDS_PER_DAY;
+    format!(
+        "{} {:02}:{:02}",
+        date_from_unix_seconds(seconds),
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60
+    )
+}
+
+fn date_from_unix_seconds(seconds: u64) -> String {
+    let days = (seconds / SECONDS_PER_DAY) as i64;
+
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+pub fn hall_of_fame_insert(hall_of_fame: &mut HallOfFame, entry: ScoreEntry) -> Option<usize> {
+    let rank = hall_of_fame
+        .entries
+        .iter()
+        .position(|existing| entry.score > existing.score)
+        .unwrap_or(hall_of_fame.entries.len());
+    if rank >= MAX_HALL_OF_FAME_ENTRIES {
+        return None;
+    }
+    hall_of_fame.entries.insert(rank, entry);
+    hall_of_fame.entries.truncate(MAX_HALL_OF_FAME_ENTRIES);
+    Some(rank)
+}
+
+pub fn hall_of_fame_lines(hall_of_fame: &HallOfFame) -> Vec<String> {
+    if hall_of_fame.entries.is_empty() {
+        return vec!["No victories recorded yet".to_string()];
+    }
+    hall_of_fame
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            format!(
+                "{}. {}  {}  {}  seed {}  {} turns  {}",
+                index + 1,
+                entry.score,
+                faction_name(entry.faction),
+                difficulty_key(entry.difficulty).to_uppercase(),
+                entry.seed,
+                entry.turns,
+                entry.date
+            )
+        })
+        .collect()
+}
+
+pub fn hall_of_fame_to_string(hall_of_fame: &HallOfFame) -> String {
+    let mut output = String::new();
+    output.push_str(HALL_OF_FAME_FILE_HEADER);
+    output.push('\n');
+    for entry in &hall_of_fame.entries {
+        output.push_str(&format!(
+            "entry {} {} {} {} {} {}\n",
+            entry.score,
+            faction_name(entry.faction),
+            difficulty_key(entry.difficulty),
+            entry.seed,
+            entry.turns,
+            entry.date
+        ));
+    }
+    output
+}
+
+fn parse_number(line: usize, name: &str, value: &str) -> Result<u32, HallOfFameError> {
+    value
+        .parse::<u32>()
+        .map_err(|_| line_error(line, format!("invalid {} '{}'", name, value)))
+}
+
+pub fn hall_of_fame_from_string(contents: &str) -> Result<HallOfFame, HallOfFameError> {
+    let mut lines = contents.lines().enumerate();
+
+    check_file_header(
+        lines.next().map(|(_, line)| line),
+        HALL_OF_FAME_FILE_HEADER,
+        "hall of fame",
+    )?;
+
+    let mut hall_of_fame = HallOfFame::default();
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [] => {}
+            ["entry", score, faction, difficulty, seed, turns, date] => {
+                let faction = parse_faction(line_number, faction)?;
+                let difficulty = difficulty_from_key(difficulty).ok_or_else(|| {
+                    line_error(line_number, format!("unknown difficulty '{}'", difficulty))
+                })?;
+                hall_of_fame_insert(
+                    &mut hall_of_fame,
+                    ScoreEntry {
+                        score: parse_number(line_number, "score", score)?,
+                        faction,
+                        difficulty,
+                        seed: parse_number(line_number, "seed", seed)?,
+                        turns: parse_number(line_number, "turn count", turns)?,
+                        date: date.to_string(),
+                    },
+                );
+            }
+            _ => return Err(line_error(line_number, "unrecognized entry")),
+        }
+    }
+
+    Ok(hall_of_fame)
+}
+
+pub fn load_hall_of_fame(path: &Path) -> Result<HallOfFame, HallOfFameError> {
+    let contents = std::fs::read_to_string(path)?;
+    hall_of_fame_from_string(&contents)
+}
+
+pub fn save_hall_of_fame(path: &Path, hall_of_fame: &HallOfFame) -> Result<(), HallOfFameError> {
+    std::fs::write(path, hall_of_fame_to_string(hall_of_fame))?;
+    Ok(())
+}
+
+pub fn hall_of_fame_for_launch() -> Result<HallOfFame, HallOfFameError> {
+    let path = Path::new(HALL_OF_FAME_FILE_PATH);
+    if path.exists() {
+        load_hall_of_fame(path)
+    } else {
+        Ok(HallOfFame::default())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::rules::GameRules;
+
+pub const HANDICAP_SOLDIER_STEP: i32 = 5;
+pub const HANDICAP_MAX_SOLDIER_OFFSET: i32 = 30;
+pub const HANDICAP_MAX_CITIES: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Handicap {
+    pub soldiers: i32,
+    pub cities: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandicapKind {
+    Soldiers,
+    Cities,
+}
+
+pub fn handicap_adjust(handicap: &mut Handicap, kind: HandicapKind, delta: i32) {
+    match kind {
+        HandicapKind::Soldiers => {
+            handicap.soldiers = (handicap.soldiers + delta * HANDICAP_SOLDIER_STEP)
+                .clamp(-HANDICAP_MAX_SOLDIER_OFFSET, HANDICAP_MAX_SOLDIER_OFFSET);
+        }
+        HandicapKind::Cities => {
+            handicap.cities = handicap
+                .cities
+                .saturating_add_signed(delta)
+                .min(HANDICAP_MAX_CITIES);
+        }
+    }
+}
+
+pub fn handicap_starting_soldiers(rules: &GameRules, handicap: Handicap) -> i32 {
+    (rules.initial_soldiers + handicap.soldiers).clamp(1, rules.max_soldiers)
+}
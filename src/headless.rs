@@ -0,0 +1,293 @@
+use crate::ecs::{
+    ALL_FACTIONS, Faction, GameEvents, GameWorld, HEX_POSITION, TILE, UNIT, faction_index,
+    faction_name,
+};
+use crate::launch_options::LaunchOptions;
+use crate::map::{map_params_set_wrap, settlement_seed};
+use crate::map_balance::generate_balanced_map;
+use crate::map_generation::populate_game_world;
+use crate::rules::GameRules;
+use crate::scripting::{ScriptHook, ScriptHost, script_run_hook};
+use crate::sim_clock::scaled_delta_time;
+use crate::state_hash::hash_game_state;
+use crate::systems::{
+    GameResult, PendingSpawn, TurnTransition, ai_turn_system, begin_game, can_end_turn, end_turn,
+    movement_system, ruin_system, spawn_pending, supply_delivery_system, tile_ownership_system,
+    victory_system,
+};
+use nightshade::prelude::*;
+
+const HEADLESS_MAX_TURNS: u32 = 500;
+const HEADLESS_MAX_FRAMES: u64 = 2_000_000;
+const HEADLESS_FRAME_DELTA: f32 = 1.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessFactionStats {
+    pub faction: Faction,
+    pub eliminated_on_turn: Option<u32>,
+    pub units: usize,
+    pub soldiers: i32,
+    pub tiles: usize,
+    pub battles_won: u32,
+    pub battles_lost: u32,
+    pub captures: u32,
+    pub soldiers_reinforced: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessReport {
+    pub seed: u32,
+    pub winner: Option<Faction>,
+    pub turns: u32,
+    pub frames: u64,
+    pub battles: u32,
+    pub final_state_hash: u64,
+    pub factions: Vec<HeadlessFactionStats>,
+}
+
+fn record_events(report: &mut HeadlessReport, events: &mut GameEvents, turn_number: u32) {
+    for event in events.combat_events.drain(..) {
+        let attacker_won = event.attacker_survived && !event.defender_survived;
+        let (winner, loser) = if attacker_won {
+            (event.attacker_faction, event.defender_faction)
+        } else {
+            (event.defender_faction, event.attacker_faction)
+        };
+        report.battles += 1;
+        report.factions[faction_index(winner)].battles_won += 1;
+        report.factions[faction_index(loser)].battles_lost += 1;
+    }
+    for event in events.reinforcement_events.drain(..) {
+        report.factions[faction_index(event.faction)].soldiers_reinforced += event.soldiers;
+    }
+    for event in events.faction_eliminated_events.drain(..) {
+        report.factions[faction_index(event.faction)].eliminated_on_turn = Some(turn_number);
+    }
+    events.speech_events.clear();
+    events.attrition_events.clear();
+    events.world_events.clear();
+    events.improvement_events.clear();
+    events.ruin_events.clear();
+    events.pillage_events.clear();
+    events.capital_relocated_events.clear();
+    events.surrender_events.clear();
+}
+
+fn record_final_state(report: &mut HeadlessReport, game_world: &GameWorld) {
+    for entity in game_world.query_entities(UNIT) {
+        if let Some(unit) = game_world.get_unit(entity) {
+            let stats = &mut report.factions[faction_index(unit.faction)];
+            stats.units += 1;
+            stats.soldiers += unit.soldiers;
+        }
+    }
+    for entity in game_world.query_entities(HEX_POSITION | TILE) {
+        if let Some(owner) = game_world.get_tile(entity).and_then(|tile| tile.faction) {
+            report.factions[faction_index(owner)].tiles += 1;
+        }
+    }
+}
+
+fn run_script_hook(
+    script_host: &mut Option<ScriptHost>,
+    game_world: &mut GameWorld,
+    hook: ScriptHook,
+    script_winner: &mut Option<Faction>,
+) {
+    if let Some(script_host) = script_host.as_mut() {
+        let outcome = script_run_hook(script_host, game_world, hook);
+        if outcome.victory.is_some() {
+            *script_winner = outcome.victory;
+        }
+    }
+}
+
+pub fn headless_tick(
+    game_world: &mut GameWorld,
+    events: &mut GameEvents,
+    pending_spawns: &mut Vec<PendingSpawn>,
+    frame_delta: f32,
+) -> Option<TurnTransition> {
+    for pending in pending_spawns.drain(..) {
+        spawn_pending(game_world, pending);
+    }
+
+    let delta_time = scaled_delta_time(&game_world.resources, frame_delta);
+    movement_system(game_world, delta_time);
+
+    let ai_done = ai_turn_system(game_world, None, events);
+    if !ai_done || !can_end_turn(game_world) {
+        return None;
+    }
+    let mut transition = end_turn(game_world, events);
+    pending_spawns.append(&mut transition.pending_spawns);
+    Some(transition)
+}
+
+pub fn run_headless(
+    options: &LaunchOptions,
+    rules: GameRules,
+    mut script_host: Option<ScriptHost>,
+) -> HeadlessReport {
+    let mut game_world = GameWorld::default();
+    let mut events = GameEvents::default();
+    game_world.resources.rules = rules;
+
+    if let Some((map_width, map_height)) = options.map_size {
+        game_world.resources.map_params.map_width = map_width;
+        game_world.resources.map_params.map_height = map_height;
+    }
+    game_world.resources.map_params.random_capitals = options.random_capitals;
+    map_params_set_wrap(&mut game_world.resources.map_params, options.wrap_columns);
+
+    let initial_seed = options.seed.unwrap_or_else(|| rand::rng().random());
+    let (seed, generated, balance) =
+        generate_balanced_map(initial_seed, &game_world.resources.map_params);
+    game_world.resources.rng_seed = seed;
+    game_world.resources.settlement_seed = settlement_seed(seed);
+    game_world.resources.map_balance = balance;
+    game_world.resources.generated_map = generated;
+    populate_game_world(&mut game_world);
+
+    begin_game(&mut game_world, [options.difficulty.unwrap_or_default(); 4]);
+
+    let mut report = HeadlessReport {
+        seed,
+        factions: ALL_FACTIONS
+            .iter()
+            .map(|faction| HeadlessFactionStats {
+                faction: *faction,
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let mut pending_spawns: Vec<PendingSpawn> = Vec::new();
+    let mut script_winner = None;
+
+    while report.frames < HEADLESS_MAX_FRAMES
+        && game_world.resources.turn_number <= HEADLESS_MAX_TURNS
+    {
+        report.frames += 1;
+
+        if let Some(transition) = headless_tick(
+            &mut game_world,
+            &mut events,
+            &mut pending_spawns,
+            HEADLESS_FRAME_DELTA,
+        ) {
+            run_script_hook(
+                &mut script_host,
+                &mut game_world,
+                ScriptHook::TurnStart {
+                    faction: transition.new_faction,
+                    turn: transition.turn_number,
+                },
+                &mut script_winner,
+            );
+            run_script_hook(
+                &mut script_host,
+                &mut game_world,
+                ScriptHook::VictoryCheck,
+                &mut script_winner,
+            );
+        }
+
+        let captures = tile_ownership_system(&mut game_world);
+        ruin_system(&mut game_world, &mut events);
+        supply_delivery_system(&mut game_world, &mut events);
+        for capture in captures {
+            report.factions[faction_index(capture.faction)].captures += 1;
+            run_script_hook(
+                &mut script_host,
+                &mut game_world,
+                ScriptHook::Capture {
+                    faction: capture.faction,
+                    coord: capture.coord,
+                    tile_type: capture.tile_type,
+                },
+                &mut script_winner,
+            );
+        }
+
+        let combat_hooks: Vec<_> = events
+            .combat_events
+            .iter()
+            .map(|event| ScriptHook::CombatResolved {
+                attacker: event.attacker_faction,
+                defender: event.defender_faction,
+                attacker_survived: event.attacker_survived,
+                defender_survived: event.defender_survived,
+            })
+            .collect();
+        for hook in combat_hooks {
+            run_script_hook(&mut script_host, &mut game_world, hook, &mut script_winner);
+        }
+
+        let result = match script_winner.take() {
+            Some(winner) => GameResult::Victory(winner),
+            None => victory_system(&mut game_world, &mut events),
+        };
+        record_events(&mut report, &mut events, game_world.resources.turn_number);
+        game_world.resources.morale_changes.clear();
+        game_world.step();
+
+        if let GameResult::Victory(winner) = result {
+            report.winner = Some(winner);
+            break;
+        }
+    }
+
+    report.turns = game_world.resources.turn_number.min(HEADLESS_MAX_TURNS);
+    record_final_state(&mut report, &game_world);
+    report.final_state_hash = hash_game_state(&game_world);
+    report
+}
+
+pub fn headless_report_to_string(report: &HeadlessReport) -> String {
+    let mut output = String::new();
+    let result = match report.winner {
+        Some(winner) => format!("{} wins", faction_name(winner)),
+        None => format!("no winner after {} turns", HEADLESS_MAX_TURNS),
+    };
+
+    output.push_str(&format!("Seed:    {}\n", report.seed));
+    output.push_str(&format!("Result:  {}\n", result));
+    output.push_str(&format!("Turns:   {}\n", report.turns));
+    output.push_str(&format!("Frames:  {}\n", report.frames));
+    output.push_str(&format!("Battles: {}\n", report.battles));
+    output.push_str(&format!("Hash:    {:016x}\n", report.final_state_hash));
+    output.push('\n');
+    output.push_str(&format!(
+        "{:<10} {:>10} {:>6} {:>9} {:>6} {:>5} {:>6} {:>9} {:>11}\n",
+        "Faction",
+        "Eliminated",
+        "Units",
+        "Soldiers",
+        "Tiles",
+        "Won",
+        "Lost",
+        "Captures",
+        "Reinforced"
+    ));
+    for stats in &report.factions {
+        let eliminated = stats
+            .eliminated_on_turn
+            .map(|turn| format!("turn {}", turn))
+            .unwrap_or_else(|| "-".to_string());
+        output.push_str(&format!(
+            "{:<10} {:>10} {:>6} {:>9} {:>6} {:>5} {:>6} {:>9} {:>11}\n",
+            faction_name(stats.faction),
+            eliminated,
+            stats.units,
+            stats.soldiers,
+            stats.tiles,
+            stats.battles_won,
+            stats.battles_lost,
+            stats.captures,
+            stats.soldiers_reinforced
+        ));
+    }
+
+    output
+}
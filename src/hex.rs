@@ -6,28 +6,157 @@ pub struct HexCoord {
     pub row: i32,
 }
 
-pub fn hex_to_cube(coord: HexCoord) -> (i32, i32, i32) {
-    let x = coord.column;
-    let z = coord.row - (coord.column - (coord.column & 1)) / 2;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HexOrientation {
+    #[default]
+    FlatTop,
+    PointyTop,
+}
+
+pub fn hex_orientation_key(orientation: HexOrientation) -> &'static str {
+    match orientation {
+        HexOrientation::FlatTop => "flat",
+        HexOrientation::PointyTop => "pointy",
+    }
+}
+
+pub fn hex_orientation_from_key(key: &str) -> Option<HexOrientation> {
+    match key {
+        "flat" => Some(HexOrientation::FlatTop),
+        "pointy" => Some(HexOrientation::PointyTop),
+        _ => None,
+    }
+}
+
+pub fn hex_to_cube(coord: HexCoord, orientation: HexOrientation) -> (i32, i32, i32) {
+    let (x, z) = match orientation {
+        HexOrientation::FlatTop => (
+            coord.column,
+            coord.row - (coord.column - (coord.column & 1)) / 2,
+        ),
+        HexOrientation::PointyTop => (coord.column - (coord.row - (coord.row & 1)) / 2, coord.row),
+    };
     let y = -x - z;
     (x, y, z)
 }
 
-pub fn hex_from_cube(x: i32, _y: i32, z: i32) -> HexCoord {
-    let column = x;
-    let row = z + (x - (x & 1)) / 2;
-    HexCoord { column, row }
+pub fn hex_from_cube(x: i32, _y: i32, z: i32, orientation: HexOrientation) -> HexCoord {
+    match orientation {
+        HexOrientation::FlatTop => HexCoord {
+            column: x,
+            row: z + (x - (x & 1)) / 2,
+        },
+        HexOrientation::PointyTop => HexCoord {
+            column: x + (z - (z & 1)) / 2,
+            row: z,
+        },
+    }
 }
 
-pub fn hex_distance(from: HexCoord, to: HexCoord) -> i32 {
-    let (x1, y1, z1) = hex_to_cube(from);
-    let (x2, y2, z2) = hex_to_cube(to);
+pub fn hex_distance(from: HexCoord, to: HexCoord, orientation: HexOrientation) -> i32 {
+    let (x1, y1, z1) = hex_to_cube(from, orientation);
+    let (x2, y2, z2) = hex_to_cube(to, orientation);
     ((x1 - x2).abs() + (y1 - y2).abs() + (z1 - z2).abs()) / 2
 }
 
-pub fn hex_neighbors(coord: HexCoord) -> [HexCoord; 6] {
+pub fn hex_wrap(coord: HexCoord, wrap_width: Option<i32>) -> HexCoord {
+    match wrap_width {
+        Some(width) => HexCoord {
+            column: coord.column.rem_euclid(width),
+            row: coord.row,
+        },
+        None => coord,
+    }
+}
+
+pub fn hex_unwrap_near(coord: HexCoord, anchor: HexCoord, wrap_width: Option<i32>) -> HexCoord {
+    let Some(width) = wrap_width else {
+        return coord;
+    };
+    [-width, 0, width]
+        .into_iter()
+        .map(|offset| HexCoord {
+            column: coord.column + offset,
+            row: coord.row,
+        })
+        .min_by_key(|candidate| (candidate.column - anchor.column).abs())
+        .unwrap_or(coord)
+}
+
+pub fn hex_wrap_copies(coord: HexCoord, wrap_width: Option<i32>) -> Vec<HexCoord> {
+    match wrap_width {
+        Some(width) => [0, -width, width]
+            .into_iter()
+            .map(|offset| HexCoord {
+                column: coord.column + offset,
+                row: coord.row,
+            })
+            .collect(),
+        None => vec![coord],
+    }
+}
+
+pub fn hex_wrapped_distance(
+    from: HexCoord,
+    to: HexCoord,
+    wrap_width: Option<i32>,
+    orientation: HexOrientation,
+) -> i32 {
+    hex_distance(from, hex_unwrap_near(to, from, wrap_width), orientation)
+}
+
+pub fn hex_wrapped_neighbors(
+    coord: HexCoord,
+    wrap_width: Option<i32>,
+    orientation: HexOrientation,
+) -> [HexCoord; 6] {
+    hex_neighbors(coord, orientation).map(|neighbor| hex_wrap(neighbor, wrap_width))
+}
+
+pub fn hex_wrapped_tiles_in_range(
+    center: HexCoord,
+    range: i32,
+    wrap_width: Option<i32>,
+    orientation: HexOrientation,
+) -> Vec<HexCoord> {
+    hex_tiles_in_range(center, range, orientation)
+        .into_iter()
+        .map(|coord| hex_wrap(coord, wrap_width))
+        .collect()
+}
+
+pub fn hex_neighbors(coord: HexCoord, orientation: HexOrientation) -> [HexCoord; 6] {
     let column = coord.column;
     let row = coord.row;
+    if orientation == HexOrientation::PointyTop {
+        let shift = if row.abs() % 2 != 0 { 1 } else { 0 };
+        return [
+            HexCoord {
+                column: column + 1,
+                row,
+            },
+            HexCoord {
+                column: column + shift,
+                row: row - 1,
+            },
+            HexCoord {
+                column: column + shift - 1,
+                row: row - 1,
+            },
+            HexCoord {
+                column: column - 1,
+                row,
+            },
+            HexCoord {
+                column: column + shift - 1,
+                row: row + 1,
+            },
+            HexCoord {
+                column: column + shift,
+                row: row + 1,
+            },
+        ];
+    }
     let is_odd_column = column.abs() % 2 != 0;
 
     if is_odd_column {
@@ -87,35 +216,47 @@ pub fn hex_neighbors(coord: HexCoord) -> [HexCoord; 6] {
     }
 }
 
-pub fn hex_tiles_in_range(center: HexCoord, range: i32) -> Vec<HexCoord> {
+pub fn hex_tiles_in_range(
+    center: HexCoord,
+    range: i32,
+    orientation: HexOrientation,
+) -> Vec<HexCoord> {
     let mut result = Vec::new();
     for distance in 1..=range {
-        result.extend(hex_tiles_at_distance(center, distance));
+        result.extend(hex_tiles_at_distance(center, distance, orientation));
     }
     result
 }
 
-pub fn hex_tiles_at_distance(center: HexCoord, distance: i32) -> Vec<HexCoord> {
+pub fn hex_tiles_at_distance(
+    center: HexCoord,
+    distance: i32,
+    orientation: HexOrientation,
+) -> Vec<HexCoord> {
     if distance == 0 {
         return vec![center];
     }
     let mut result = Vec::new();
-    let (cx, cy, cz) = hex_to_cube(center);
+    let (cx, cy, cz) = hex_to_cube(center, orientation);
     for x in -distance..=distance {
         for y in (-distance).max(-x - distance)..=(distance).min(-x + distance) {
             let z = -x - y;
             if x.abs() + y.abs() + z.abs() == distance * 2 {
-                result.push(hex_from_cube(cx + x, cy + y, cz + z));
+                result.push(hex_from_cube(cx + x, cy + y, cz + z, orientation));
             }
         }
     }
     result
 }
 
-pub fn hex_to_world_position(column: i32, row: i32, hex_width: f32, hex_height: f32) -> Vec3 {
-    let is_flat_top = hex_width > hex_height;
-
-    if is_flat_top {
+pub fn hex_to_world_position(
+    column: i32,
+    row: i32,
+    hex_width: f32,
+    hex_height: f32,
+    orientation: HexOrientation,
+) -> Vec3 {
+    if orientation == HexOrientation::FlatTop {
         let horizontal_spacing = hex_width * 0.75;
         let vertical_spacing = hex_height;
 
@@ -148,10 +289,14 @@ pub fn hex_to_world_position(column: i32, row: i32, hex_width: f32, hex_height:
     }
 }
 
-pub fn world_to_hex(world_x: f32, world_z: f32, hex_width: f32, hex_height: f32) -> HexCoord {
-    let is_flat_top = hex_width > hex_height;
-
-    if is_flat_top {
+pub fn world_to_hex(
+    world_x: f32,
+    world_z: f32,
+    hex_width: f32,
+    hex_height: f32,
+    orientation: HexOrientation,
+) -> HexCoord {
+    if orientation == HexOrientation::FlatTop {
         let horizontal_spacing = hex_width * 0.75;
         let vertical_spacing = hex_height;
 
@@ -175,8 +320,13 @@ pub fn world_to_hex(world_x: f32, world_z: f32, hex_width: f32, hex_height: f32)
                     column: approx_column + dc,
                     row: approx_row + dr,
                 };
-                let candidate_pos =
-                    hex_to_world_position(candidate.column, candidate.row, hex_width, hex_height);
+                let candidate_pos = hex_to_world_position(
+                    candidate.column,
+                    candidate.row,
+                    hex_width,
+                    hex_height,
+                    orientation,
+                );
                 let dist_sq =
                     (candidate_pos.x - world_x).powi(2) + (candidate_pos.z - world_z).powi(2);
                 if dist_sq < best_dist {
@@ -210,8 +360,13 @@ pub fn world_to_hex(world_x: f32, world_z: f32, hex_width: f32, hex_height: f32)
                     column: approx_column + dc,
                     row: approx_row + dr,
                 };
-                let candidate_pos =
-                    hex_to_world_position(candidate.column, candidate.row, hex_width, hex_height);
+                let candidate_pos = hex_to_world_position(
+                    candidate.column,
+                    candidate.row,
+                    hex_width,
+                    hex_height,
+                    orientation,
+                );
                 let dist_sq =
                     (candidate_pos.x - world_x).powi(2) + (candidate_pos.z - world_z).powi(2);
                 if dist_sq < best_dist {
@@ -1,16 +1,71 @@
-use crate::ecs::{Faction, GameWorld, faction_color, faction_name};
+use crate::ecs::{
+    CasualtyStats, CombatModifier, CombatModifierKind, CombatSide, Faction, GameWorld,
+    combat_modifier_label, faction_color, faction_index, faction_name, get_faction_morale,
+};
+use crate::hud_text::{
+    HudText, hud_text_despawn, hud_text_set, hud_text_set_color, hud_text_spawn,
+};
+use crate::season::{season_for_turn, season_name, turns_until_next_season};
+use crate::selection::get_selected_unit;
+use crate::systems::{
+    attack_target, combat_modifiers, combat_win_chance, leader_ability, leader_ability_cooldown,
+    leader_ability_name,
+};
+use crate::turn_rotation::turn_rotation_upcoming;
+use crate::turn_timer::{TURN_TIMER_WARNING_SECONDS, turn_timer_seconds_left};
 use nightshade::prelude::*;
 
 #[derive(Default)]
 pub struct GameHud {
-    pub turn_text: Option<Entity>,
-    pub faction_text: Option<Entity>,
-    pub actions_text: Option<Entity>,
-    pub instructions_text: Option<Entity>,
-    pub speed_text: Option<Entity>,
+    pub turn_text: HudText,
+    pub faction_text: HudText,
+    pub actions_text: HudText,
+    pub instructions_text: HudText,
+    pub speed_text: HudText,
+    pub timer_text: HudText,
+    pub morale_text: HudText,
+    pub casualty_text: HudText,
+    pub unit_text: HudText,
+    pub combat_text: HudText,
+    pub next_up_label: HudText,
+    pub next_up_texts: Vec<HudText>,
+    pub last_snapshot: Option<HudSnapshot>,
 }
 
-pub fn spawn_game_hud(world: &mut World) -> GameHud {
+#[derive(Debug, Clone, PartialEq)]
+pub struct HudSnapshot {
+    pub turn_number: u32,
+    pub current_faction: Faction,
+    pub actions_remaining: u8,
+    pub game_speed: f32,
+    pub timer_seconds: Option<u32>,
+    pub is_player_turn: bool,
+    pub upcoming: Vec<Faction>,
+    pub ability_cooldown: u32,
+    pub ability_used: bool,
+    pub overview_faction: Faction,
+    pub faction_morale: i32,
+    pub casualties: CasualtyStats,
+    pub selected_unit: Option<(i32, i32)>,
+    pub selected_movement: Option<(i32, i32)>,
+    pub combat_preview: Option<(i32, Vec<CombatModifier>)>,
+}
+
+const NEXT_UP_SLOTS: usize = 3;
+const HIGH_MORALE: i32 = 10;
+const LOW_MORALE: i32 = -10;
+
+pub fn morale_color(morale: i32) -> [f32; 4] {
+    if morale >= HIGH_MORALE {
+        [0.4, 1.0, 0.5, 1.0]
+    } else if morale <= LOW_MORALE {
+        [1.0, 0.45, 0.3, 1.0]
+    } else {
+        [0.95, 0.95, 0.8, 1.0]
+    }
+}
+
+pub fn spawn_game_hud(world: &mut World, ui_scale: f32) -> GameHud {
     let turn_props = TextProperties {
         font_size: 28.0,
         color: nalgebra_glm::vec4(1.0, 1.0, 1.0, 1.0),
@@ -38,28 +93,31 @@ pub fn spawn_game_hud(world: &mut World) -> GameHud {
         ..Default::default()
     };
 
-    let turn_text = spawn_hud_text_with_properties(
+    let turn_text = hud_text_spawn(
         world,
         "Turn 1",
         HudAnchor::TopLeft,
         nalgebra_glm::vec2(15.0, 15.0),
         turn_props,
+        ui_scale,
     );
 
-    let faction_text = spawn_hud_text_with_properties(
+    let faction_text = hud_text_spawn(
         world,
         "Redosia",
         HudAnchor::TopLeft,
         nalgebra_glm::vec2(15.0, 50.0),
         faction_props,
+        ui_scale,
     );
 
-    let actions_text = spawn_hud_text_with_properties(
+    let actions_text = hud_text_spawn(
         world,
         "Actions: 5",
         HudAnchor::TopLeft,
         nalgebra_glm::vec2(15.0, 85.0),
         actions_props,
+        ui_scale,
     );
 
     let instructions_props = TextProperties {
@@ -71,12 +129,13 @@ pub fn spawn_game_hud(world: &mut World) -> GameHud {
         ..Default::default()
     };
 
-    let instructions_text = spawn_hud_text_with_properties(
+    let instructions_text = hud_text_spawn(
         world,
-        "[SPACE] End Turn  [S] Speech  [P] Pause  [+/-] Speed",
+        "[SPACE] End Turn  [S] Ability  [B/T] Build  [R] Move Capital  [P] Pause  [ENTER] Chat  [+/-] Speed",
         HudAnchor::TopLeft,
         nalgebra_glm::vec2(15.0, 115.0),
         instructions_props,
+        ui_scale,
     );
 
     let speed_props = TextProperties {
@@ -88,121 +147,355 @@ pub fn spawn_game_hud(world: &mut World) -> GameHud {
         ..Default::default()
     };
 
-    let speed_text = spawn_hud_text_with_properties(
+    let speed_text = hud_text_spawn(
         world,
         "Speed: 1x",
         HudAnchor::TopLeft,
         nalgebra_glm::vec2(15.0, 140.0),
         speed_props,
+        ui_scale,
+    );
+
+    let timer_props = TextProperties {
+        font_size: 24.0,
+        color: nalgebra_glm::vec4(1.0, 1.0, 1.0, 1.0),
+        alignment: TextAlignment::Left,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    let timer_text = hud_text_spawn(
+        world,
+        "",
+        HudAnchor::TopLeft,
+        nalgebra_glm::vec2(15.0, 195.0),
+        timer_props,
+        ui_scale,
+    );
+
+    let morale_props = TextProperties {
+        font_size: 20.0,
+        color: nalgebra_glm::vec4(0.95, 0.95, 0.8, 1.0),
+        alignment: TextAlignment::Left,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    let morale_text = hud_text_spawn(
+        world,
+        "",
+        HudAnchor::TopLeft,
+        nalgebra_glm::vec2(15.0, 230.0),
+        morale_props.clone(),
+        ui_scale,
+    );
+
+    let casualty_text = hud_text_spawn(
+        world,
+        "",
+        HudAnchor::TopLeft,
+        nalgebra_glm::vec2(15.0, 255.0),
+        morale_props.clone(),
+        ui_scale,
+    );
+
+    let unit_text = hud_text_spawn(
+        world,
+        "",
+        HudAnchor::TopLeft,
+        nalgebra_glm::vec2(15.0, 280.0),
+        morale_props.clone(),
+        ui_scale,
+    );
+
+    let combat_text = hud_text_spawn(
+        world,
+        "",
+        HudAnchor::TopLeft,
+        nalgebra_glm::vec2(15.0, 305.0),
+        morale_props,
+        ui_scale,
+    );
+
+    let next_up_props = TextProperties {
+        font_size: 18.0,
+        color: nalgebra_glm::vec4(0.7, 0.7, 0.7, 1.0),
+        alignment: TextAlignment::Left,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    let next_up_label = hud_text_spawn(
+        world,
+        "Next up:",
+        HudAnchor::TopLeft,
+        nalgebra_glm::vec2(15.0, 170.0),
+        next_up_props.clone(),
+        ui_scale,
     );
 
+    let next_up_texts = (0..NEXT_UP_SLOTS)
+        .map(|slot| {
+            hud_text_spawn(
+                world,
+                "",
+                HudAnchor::TopLeft,
+                nalgebra_glm::vec2(95.0 + slot as f32 * 100.0, 170.0),
+                next_up_props.clone(),
+                ui_scale,
+            )
+        })
+        .collect();
+
     GameHud {
-        turn_text: Some(turn_text),
-        faction_text: Some(faction_text),
-        actions_text: Some(actions_text),
-        instructions_text: Some(instructions_text),
-        speed_text: Some(speed_text),
+        turn_text,
+        faction_text,
+        actions_text,
+        instructions_text,
+        speed_text,
+        timer_text,
+        morale_text,
+        casualty_text,
+        unit_text,
+        combat_text,
+        next_up_label,
+        next_up_texts,
+        last_snapshot: None,
     }
 }
 
 pub fn despawn_game_hud(hud: &mut GameHud, world: &mut World) {
-    if let Some(entity) = hud.turn_text.take() {
-        world.despawn_entities(&[entity]);
+    hud_text_despawn(world, &mut hud.turn_text);
+    hud_text_despawn(world, &mut hud.faction_text);
+    hud_text_despawn(world, &mut hud.actions_text);
+    hud_text_despawn(world, &mut hud.instructions_text);
+    hud_text_despawn(world, &mut hud.speed_text);
+    hud_text_despawn(world, &mut hud.timer_text);
+    hud_text_despawn(world, &mut hud.morale_text);
+    hud_text_despawn(world, &mut hud.casualty_text);
+    hud_text_despawn(world, &mut hud.unit_text);
+    hud_text_despawn(world, &mut hud.combat_text);
+    hud_text_despawn(world, &mut hud.next_up_label);
+    for mut hud_text in hud.next_up_texts.drain(..) {
+        hud_text_despawn(world, &mut hud_text);
     }
-    if let Some(entity) = hud.faction_text.take() {
-        world.despawn_entities(&[entity]);
-    }
-    if let Some(entity) = hud.actions_text.take() {
-        world.despawn_entities(&[entity]);
-    }
-    if let Some(entity) = hud.instructions_text.take() {
-        world.despawn_entities(&[entity]);
-    }
-    if let Some(entity) = hud.speed_text.take() {
-        world.despawn_entities(&[entity]);
+    hud.last_snapshot = None;
+}
+
+pub fn game_hud_snapshot(game_world: &GameWorld, player_faction: Option<Faction>) -> HudSnapshot {
+    let resources = &game_world.resources;
+    let overview_faction = player_faction.unwrap_or(resources.current_faction);
+    let selected_unit = get_selected_unit(game_world)
+        .and_then(|entity| game_world.get_unit(entity))
+        .map(|unit| (unit.soldiers, unit.morale));
+    let selected_movement = get_selected_unit(game_world)
+        .and_then(|entity| game_world.get_unit(entity))
+        .filter(|_| resources.rules.movement_points)
+        .map(|unit| (unit.movement_left, unit.movement_range));
+    let combat_preview = resources
+        .hovered_tile
+        .filter(|_| Some(resources.current_faction) == player_faction)
+        .and_then(|coord| attack_target(game_world, coord))
+        .and_then(|(attacker, defender)| {
+            let win_chance = combat_win_chance(game_world, attacker, defender)?;
+            Some((
+                (win_chance * 100.0).round() as i32,
+                combat_modifiers(game_world, attacker, defender)?,
+            ))
+        });
+    HudSnapshot {
+        turn_number: resources.turn_number,
+        current_faction: resources.current_faction,
+        actions_remaining: resources.actions_remaining,
+        game_speed: resources.game_speed,
+        timer_seconds: turn_timer_seconds_left(&resources.turn_timer),
+        is_player_turn: Some(resources.current_faction) == player_faction,
+        upcoming: turn_rotation_upcoming(&resources.turn_rotation, NEXT_UP_SLOTS),
+        ability_cooldown: leader_ability_cooldown(resources, resources.current_faction),
+        ability_used: resources.speech_used,
+        overview_faction,
+        faction_morale: get_faction_morale(resources, overview_faction),
+        casualties: resources.casualty_stats[faction_index(overview_faction)],
+        selected_unit,
+        selected_movement,
+        combat_preview,
     }
 }
 
 pub fn update_game_hud(
-    hud: &GameHud,
+    hud: &mut GameHud,
     game_world: &GameWorld,
     world: &mut World,
-    player_faction: Faction,
+    player_faction: Option<Faction>,
 ) {
-    let is_player_turn = game_world.resources.current_faction == player_faction;
-
-    if let Some(turn_entity) = hud.turn_text
-        && let Some(text_index) = world.get_hud_text(turn_entity).map(|t| t.text_index)
-    {
-        world.resources.text_cache.set_text(
-            text_index,
-            format!("Turn {}", game_world.resources.turn_number),
-        );
-        if let Some(hud_text) = world.get_hud_text_mut(turn_entity) {
-            hud_text.dirty = true;
-        }
+    let snapshot = game_hud_snapshot(game_world, player_faction);
+    if hud.last_snapshot.as_ref() == Some(&snapshot) {
+        return;
     }
 
-    if let Some(faction_entity) = hud.faction_text {
-        let faction = game_world.resources.current_faction;
-        let name = faction_name(faction);
-        let color = faction_color(faction);
+    hud_text_set(
+        world,
+        &mut hud.turn_text,
+        &format!(
+            "Turn {} - {} ({} turns left)",
+            snapshot.turn_number,
+            season_name(season_for_turn(snapshot.turn_number)),
+            turns_until_next_season(snapshot.turn_number)
+        ),
+    );
 
-        if let Some(text_index) = world.get_hud_text(faction_entity).map(|t| t.text_index) {
-            world
-                .resources
-                .text_cache
-                .set_text(text_index, name.to_string());
-        }
-        if let Some(hud_text) = world.get_hud_text_mut(faction_entity) {
-            hud_text.properties.color = nalgebra_glm::vec4(color[0], color[1], color[2], color[3]);
-            hud_text.dirty = true;
-        }
-    }
+    hud_text_set(
+        world,
+        &mut hud.faction_text,
+        faction_name(snapshot.current_faction),
+    );
+    hud_text_set_color(
+        world,
+        &mut hud.faction_text,
+        faction_color(snapshot.current_faction),
+    );
 
-    if let Some(actions_entity) = hud.actions_text
-        && let Some(text_index) = world.get_hud_text(actions_entity).map(|t| t.text_index)
-    {
-        world.resources.text_cache.set_text(
-            text_index,
-            format!("Actions: {}", game_world.resources.actions_remaining),
-        );
-        if let Some(hud_text) = world.get_hud_text_mut(actions_entity) {
-            hud_text.dirty = true;
-        }
-    }
+    hud_text_set(
+        world,
+        &mut hud.actions_text,
+        &format!("Actions: {}", snapshot.actions_remaining),
+    );
 
-    if let Some(instructions_entity) = hud.instructions_text
-        && let Some(text_index) = world
-            .get_hud_text(instructions_entity)
-            .map(|t| t.text_index)
-    {
-        let instructions = if is_player_turn {
-            "[SPACE] End Turn  [S] Speech  [P] Pause  [+/-] Speed"
+    let instructions = if snapshot.is_player_turn {
+        let ability_name = leader_ability_name(leader_ability(snapshot.current_faction));
+        let ability_status = if snapshot.ability_used {
+            " (used)".to_string()
+        } else if snapshot.ability_cooldown > 0 {
+            format!(" ({} turns)", snapshot.ability_cooldown)
         } else {
-            "[P] Pause  [+/-] Speed"
+            String::new()
         };
-        world
-            .resources
-            .text_cache
-            .set_text(text_index, instructions.to_string());
-        if let Some(hud_text) = world.get_hud_text_mut(instructions_entity) {
-            hud_text.dirty = true;
-        }
+        format!(
+            "[SPACE] End Turn  [S] {}{}  [B/T] Build  [R] Move Capital  [P] Pause  [ENTER] Chat  [+/-] Speed",
+            ability_name, ability_status
+        )
+    } else {
+        "[P] Pause  [ENTER] Chat  [+/-] Speed".to_string()
+    };
+    hud_text_set(world, &mut hud.instructions_text, &instructions);
+
+    let speed_text = if snapshot.game_speed >= 1.0 {
+        format!("Speed: {}x", snapshot.game_speed as i32)
+    } else {
+        format!("Speed: {:.2}x", snapshot.game_speed)
+    };
+    hud_text_set(world, &mut hud.speed_text, &speed_text);
+
+    let (timer_text, timer_color) = match snapshot.timer_seconds {
+        Some(seconds) if seconds <= TURN_TIMER_WARNING_SECONDS => (
+            format!("Time: {}:{:02}", seconds / 60, seconds % 60),
+            [1.0, 0.3, 0.2, 1.0],
+        ),
+        Some(seconds) => (
+            format!("Time: {}:{:02}", seconds / 60, seconds % 60),
+            [1.0, 1.0, 1.0, 1.0],
+        ),
+        None => (String::new(), [1.0, 1.0, 1.0, 1.0]),
+    };
+    hud_text_set(world, &mut hud.timer_text, &timer_text);
+    hud_text_set_color(world, &mut hud.timer_text, timer_color);
+
+    hud_text_set(
+        world,
+        &mut hud.morale_text,
+        &format!(
+            "{} morale: {:+} (new armies start at {:+}%)",
+            faction_name(snapshot.overview_faction),
+            snapshot.faction_morale,
+            snapshot.faction_morale
+        ),
+    );
+    hud_text_set_color(
+        world,
+        &mut hud.morale_text,
+        morale_color(snapshot.faction_morale),
+    );
+
+    let casualties = snapshot.casualties;
+    hud_text_set(
+        world,
+        &mut hud.casualty_text,
+        &format!(
+            "Lost {} soldiers ({} armies), killed {} ({} armies), {} cities captured",
+            casualties.soldiers_lost,
+            casualties.units_lost,
+            casualties.soldiers_killed,
+            casualties.units_destroyed,
+            casualties.cities_captured
+        ),
+    );
+
+    let (mut unit_text, unit_color) = match snapshot.selected_unit {
+        Some((soldiers, morale)) => (
+            format!(
+                "Selected army: {} soldiers, morale {:+} ({:+}% strength)",
+                soldiers, morale, morale
+            ),
+            morale_color(morale),
+        ),
+        None => (String::new(), [1.0, 1.0, 1.0, 1.0]),
+    };
+    if let Some((movement_left, movement_range)) = snapshot.selected_movement {
+        unit_text.push_str(&format!(", moves {}/{}", movement_left, movement_range));
     }
+    hud_text_set(world, &mut hud.unit_text, &unit_text);
+    hud_text_set_color(world, &mut hud.unit_text, unit_color);
 
-    if let Some(speed_entity) = hud.speed_text
-        && let Some(text_index) = world.get_hud_text(speed_entity).map(|t| t.text_index)
-    {
-        let speed = game_world.resources.game_speed;
-        let speed_text = if speed >= 1.0 {
-            format!("Speed: {}x", speed as i32)
-        } else {
-            format!("Speed: {:.2}x", speed)
-        };
-        world.resources.text_cache.set_text(text_index, speed_text);
-        if let Some(hud_text) = world.get_hud_text_mut(speed_entity) {
-            hud_text.dirty = true;
+    let (combat_text, combat_color) = match &snapshot.combat_preview {
+        Some((win_percent, modifiers)) => {
+            let maneuver = modifiers.iter().any(|modifier| {
+                matches!(
+                    modifier.kind,
+                    CombatModifierKind::ForestAmbush | CombatModifierKind::Flanking
+                )
+            });
+            let side_text = |side: CombatSide| {
+                let labels: Vec<String> = modifiers
+                    .iter()
+                    .filter(|modifier| modifier.side == side)
+                    .map(combat_modifier_label)
+                    .collect();
+                if labels.is_empty() {
+                    "none".to_string()
+                } else {
+                    labels.join(", ")
+                }
+            };
+            (
+                format!(
+                    "Attack: {}% to win (ours: {} | theirs: {})",
+                    win_percent,
+                    side_text(CombatSide::Attacker),
+                    side_text(CombatSide::Defender)
+                ),
+                if maneuver {
+                    [0.5, 1.0, 0.5, 1.0]
+                } else {
+                    [0.95, 0.95, 0.8, 1.0]
+                },
+            )
         }
+        None => (String::new(), [1.0, 1.0, 1.0, 1.0]),
+    };
+    hud_text_set(world, &mut hud.combat_text, &combat_text);
+    hud_text_set_color(world, &mut hud.combat_text, combat_color);
+
+    for (slot, hud_text) in hud.next_up_texts.iter_mut().enumerate() {
+        let (text, color) = match snapshot.upcoming.get(slot) {
+            Some(&faction) => (faction_name(faction), faction_color(faction)),
+            None => ("", [1.0, 1.0, 1.0, 1.0]),
+        };
+        hud_text_set(world, hud_text, text);
+        hud_text_set_color(world, hud_text, color);
     }
+
+    hud.last_snapshot = Some(snapshot);
 }
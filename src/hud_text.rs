@@ -0,0 +1,80 @@
+use nightshade::prelude::*;
+
+#[derive(Default)]
+pub struct HudText {
+    pub entity: Option<Entity>,
+    pub text: String,
+    pub color: Option<[f32; 4]>,
+}
+
+pub fn spawn_scaled_hud_text(
+    world: &mut World,
+    text: &str,
+    anchor: HudAnchor,
+    position: Vec2,
+    mut properties: TextProperties,
+    ui_scale: f32,
+) -> Entity {
+    properties.font_size *= ui_scale;
+    spawn_hud_text_with_properties(world, text, anchor, position * ui_scale, properties)
+}
+
+pub fn hud_text_spawn(
+    world: &mut World,
+    text: &str,
+    anchor: HudAnchor,
+    position: Vec2,
+    properties: TextProperties,
+    ui_scale: f32,
+) -> HudText {
+    let color = properties.color;
+    HudText {
+        entity: Some(spawn_scaled_hud_text(
+            world, text, anchor, position, properties, ui_scale,
+        )),
+        text: text.to_string(),
+        color: Some([color.x, color.y, color.z, color.w]),
+    }
+}
+
+pub fn hud_text_set(world: &mut World, hud_text: &mut HudText, text: &str) {
+    if hud_text.text == text {
+        return;
+    }
+    let Some(entity) = hud_text.entity else {
+        return;
+    };
+
+    if let Some(text_index) = world.get_hud_text(entity).map(|t| t.text_index) {
+        world.resources.text_cache.set_text(text_index, text);
+    }
+    if let Some(text_component) = world.get_hud_text_mut(entity) {
+        text_component.dirty = true;
+    }
+    hud_text.text.clear();
+    hud_text.text.push_str(text);
+}
+
+pub fn hud_text_set_color(world: &mut World, hud_text: &mut HudText, color: [f32; 4]) {
+    if hud_text.color == Some(color) {
+        return;
+    }
+    let Some(entity) = hud_text.entity else {
+        return;
+    };
+
+    if let Some(text_component) = world.get_hud_text_mut(entity) {
+        text_component.properties.color =
+            nalgebra_glm::vec4(color[0], color[1], color[2], color[3]);
+        text_component.dirty = true;
+    }
+    hud_text.color = Some(color);
+}
+
+pub fn hud_text_despawn(world: &mut World, hud_text: &mut HudText) {
+    if let Some(entity) = hud_text.entity.take() {
+        world.despawn_entities(&[entity]);
+    }
+    hud_text.text.clear();
+    hud_text.color = None;
+}
@@ -0,0 +1,210 @@
+use crate::ecs::{GameWorld, HEX_POSITION, TILE, faction_name, tile_defense_bonus};
+use crate::hex::{HexCoord, hex_to_world_position};
+use crate::hud_text::{HudText, hud_text_despawn, hud_text_set, hud_text_spawn};
+use crate::selection::{get_selected_unit, get_unit_at_tile};
+use crate::systems::find_path;
+use nightshade::prelude::*;
+
+const INSPECTOR_MAX_LINES: usize = 18;
+const INSPECTOR_HISTORY_ENTRIES: usize = 5;
+const INSPECTOR_LINE_HEIGHT: f32 = 18.0;
+const INSPECTOR_PADDING: f32 = 10.0;
+
+#[derive(Default)]
+pub struct TileInspector {
+    pub coord: Option<HexCoord>,
+    pub texts: Vec<HudText>,
+}
+
+pub fn tile_inspector_lines(game_world: &GameWorld, coord: HexCoord) -> Vec<String> {
+    let mut lines = vec![format!(
+        "INSPECT ({},{})  [Ctrl+Click] another tile  [Esc] close",
+        coord.column, coord.row
+    )];
+
+    let tile_entity = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| {
+            game_world
+                .get_hex_position(entity)
+                .map(|hex| hex.0 == coord)
+                .unwrap_or(false)
+        });
+
+    match tile_entity.and_then(|entity| Some((entity, game_world.get_tile(entity)?))) {
+        Some((entity, tile)) => {
+            lines.push(format!("Tile entity: {:?}", entity));
+            lines.push(format!(
+                "Type: {:?}  Owner: {}  Defense: x{:.2}",
+                tile.tile_type,
+                tile.faction.map(faction_name).unwrap_or("none"),
+                tile_defense_bonus(tile.tile_type)
+            ));
+            if let Some(improvement) = tile.improvement {
+                lines.push(format!("Improvement: {:?}", improvement));
+            }
+            if tile.airfield {
+                lines.push("Airfield".to_string());
+            }
+            if let Some(construction) = tile.construction {
+                lines.push(format!(
+                    "Building: {:?} ({} turns in)",
+                    construction.improvement, construction.progress
+                ));
+            }
+            if let Some(until) = tile.pillaged_until {
+                lines.push(format!("Pillaged until turn {}", until));
+            }
+            match tile.battles {
+                0 => {}
+                1 => lines.push("1 battle fought here".to_string()),
+                battles => lines.push(format!("{} battles fought here", battles)),
+            }
+        }
+        None => lines.push("Outside the playable map".to_string()),
+    }
+
+    let position = hex_to_world_position(
+        coord.column,
+        coord.row,
+        game_world.resources.hex_width,
+        game_world.resources.hex_depth,
+        game_world.resources.hex_orientation,
+    );
+    lines.push(format!("World: ({:.0}, {:.0})", position.x, position.z));
+
+    match get_unit_at_tile(game_world, coord) {
+        Some(unit_entity) => {
+            lines.push(format!("Unit entity: {:?}", unit_entity));
+            if let Some(unit) = game_world.get_unit(unit_entity) {
+                lines.push(format!(
+                    "  {} soldiers={} morale={} range={} moved={}",
+                    faction_name(unit.faction),
+                    unit.soldiers,
+                    unit.morale,
+                    unit.movement_range,
+                    unit.has_moved
+                ));
+            }
+            if let Some(movement) = game_world.get_movement(unit_entity) {
+                lines.push(format!(
+                    "  moving segment {}/{} progress {:.2}",
+                    movement.current_segment + 1,
+                    movement.path.len().saturating_sub(1),
+                    movement.segment_progress
+                ));
+            }
+        }
+        None => lines.push("Unit: none".to_string()),
+    }
+
+    match get_selected_unit(game_world)
+        .and_then(|selected| game_world.get_hex_position(selected))
+        .map(|hex| hex.0)
+    {
+        Some(origin) => {
+            let reachable = game_world.resources.valid_move_tiles.contains(&coord);
+            match find_path(game_world, origin, coord) {
+                Some(path) => lines.push(format!(
+                    "Path from selected: {} steps ({})",
+                    path.len().saturating_sub(1),
+                    if reachable {
+                        "reachable"
+                    } else {
+                        "out of range"
+                    }
+                )),
+                None => lines.push("Path from selected: unreachable".to_string()),
+            }
+        }
+        None => lines.push("Path: select a unit to measure".to_string()),
+    }
+
+    let history = game_world
+        .resources
+        .ownership_history
+        .get(&coord)
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    if history.is_empty() {
+        lines.push("Ownership history: never captured".to_string());
+    } else {
+        lines.push(format!("Ownership history ({} changes):", history.len()));
+        let first = history.len().saturating_sub(INSPECTOR_HISTORY_ENTRIES);
+        for change in &history[first..] {
+            lines.push(format!(
+                "  turn {}: {}",
+                change.turn,
+                change.owner.map(faction_name).unwrap_or("released")
+            ));
+        }
+    }
+
+    lines.truncate(INSPECTOR_MAX_LINES);
+    lines
+}
+
+pub fn tile_inspector_open(inspector: &mut TileInspector, game_world: &GameWorld, coord: HexCoord) {
+    inspector.coord = Some(coord);
+    for line in tile_inspector_lines(game_world, coord) {
+        log::info!("{}", line);
+    }
+}
+
+pub fn tile_inspector_close(inspector: &mut TileInspector) {
+    inspector.coord = None;
+}
+
+fn spawn_inspector_ui(inspector: &mut TileInspector, world: &mut World, ui_scale: f32) {
+    let props = TextProperties {
+        font_size: 16.0,
+        color: nalgebra_glm::vec4(0.7, 1.0, 0.9, 1.0),
+        alignment: TextAlignment::Right,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    for index in 0..INSPECTOR_MAX_LINES {
+        let y_offset =
+            -(INSPECTOR_PADDING + (INSPECTOR_MAX_LINES - index) as f32 * INSPECTOR_LINE_HEIGHT);
+        inspector.texts.push(hud_text_spawn(
+            world,
+            "",
+            HudAnchor::BottomRight,
+            nalgebra_glm::vec2(-INSPECTOR_PADDING, y_offset),
+            props.clone(),
+            ui_scale,
+        ));
+    }
+}
+
+pub fn despawn_tile_inspector(inspector: &mut TileInspector, world: &mut World) {
+    for mut hud_text in inspector.texts.drain(..) {
+        hud_text_despawn(world, &mut hud_text);
+    }
+}
+
+pub fn tile_inspector_system(
+    inspector: &mut TileInspector,
+    game_world: &GameWorld,
+    world: &mut World,
+    ui_scale: f32,
+) {
+    let Some(coord) = inspector.coord else {
+        if !inspector.texts.is_empty() {
+            despawn_tile_inspector(inspector, world);
+        }
+        return;
+    };
+
+    if inspector.texts.is_empty() {
+        spawn_inspector_ui(inspector, world, ui_scale);
+    }
+
+    let lines = tile_inspector_lines(game_world, coord);
+    for (index, hud_text) in inspector.texts.iter_mut().enumerate() {
+        let line = lines.get(index).map(String::as_str).unwrap_or("");
+        hud_text_set(world, hud_text, line);
+    }
+}
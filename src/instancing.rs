@@ -1,19 +1,41 @@
-use crate::ecs::TileType;
-use crate::hex::{HexCoord, hex_to_world_position};
+use crate::ecs::{TileType, tile_elevation};
+use crate::hex::{HexCoord, HexOrientation, hex_to_world_position, hex_wrap_copies};
 use nightshade::ecs::prefab::Prefab;
 use nightshade::prelude::*;
 use std::collections::HashMap;
 
+pub const TILE_CHUNK_SIZE: i32 = 16;
+const CHUNK_VIEW_DISTANCE_SCALE: f32 = 3.0;
+const TILE_DETAIL_RADIUS: f32 = 4500.0;
+
 pub struct InstancedTileGroup {
     pub entity: Entity,
-    pub coord_to_instance: HashMap<HexCoord, usize>,
+    pub decorative: bool,
+    pub center: Vec3,
+    pub radius: f32,
+    pub coord_to_instance: HashMap<HexCoord, Vec<usize>>,
+}
+
+#[derive(Default)]
+pub struct InstancedTiles {
+    pub groups: Vec<InstancedTileGroup>,
+    pub coord_groups: HashMap<HexCoord, Vec<usize>>,
 }
 
-type MeshInstanceKey = (String, u64);
-type MeshInstanceValue = (Material, Vec<(HexCoord, InstanceTransform)>);
+type TileChunk = (i32, i32);
+type MeshInstanceKey = (TileChunk, String, u64, bool);
+type MeshInstanceValue = (Material, Vec<(HexCoord, Vec3, InstanceTransform)>);
+
+fn tile_chunk(coord: HexCoord) -> TileChunk {
+    (
+        coord.column.div_euclid(TILE_CHUNK_SIZE),
+        coord.row.div_euclid(TILE_CHUNK_SIZE),
+    )
+}
 
 struct ExtractedMesh {
     mesh_name: String,
+    decorative: bool,
     material: Material,
     local_transform: LocalTransform,
 }
@@ -52,6 +74,7 @@ fn extract_meshes_from_prefab(prefab: &Prefab) -> Vec<ExtractedMesh> {
             let material = node.components.material.clone().unwrap_or_default();
             meshes.push(ExtractedMesh {
                 mesh_name: render_mesh.name.clone(),
+                decorative: !meshes.is_empty(),
                 material,
                 local_transform: combined_transform,
             });
@@ -90,7 +113,9 @@ pub fn create_instanced_tiles(
     tile_positions: &[(HexCoord, TileType)],
     hex_width: f32,
     hex_depth: f32,
-) -> Vec<InstancedTileGroup> {
+    orientation: HexOrientation,
+    wrap_width: Option<i32>,
+) -> InstancedTiles {
     use nightshade::ecs::world::spawn_instanced_mesh_with_material;
 
     let prefab_meshes: HashMap<TileType, Vec<ExtractedMesh>> = tile_prefabs
@@ -105,51 +130,106 @@ pub fn create_instanced_tiles(
             continue;
         };
 
-        let tile_world_pos = hex_to_world_position(coord.column, coord.row, hex_width, hex_depth);
-
-        for extracted in extracted_meshes {
-            let mat_hash = material_hash(&extracted.material);
-            let key = (extracted.mesh_name.clone(), mat_hash);
-
-            let instance = InstanceTransform::new(
-                nalgebra_glm::vec3(
-                    tile_world_pos.x + extracted.local_transform.translation.x,
-                    tile_world_pos.y + extracted.local_transform.translation.y,
-                    tile_world_pos.z + extracted.local_transform.translation.z,
-                ),
-                extracted.local_transform.rotation,
-                extracted.local_transform.scale,
-            );
-
-            let entry = mesh_instances
-                .entry(key)
-                .or_insert_with(|| (extracted.material.clone(), Vec::new()));
-            entry.1.push((*coord, instance));
+        for placement in hex_wrap_copies(*coord, wrap_width) {
+            let tile_world_pos = hex_to_world_position(
+                placement.column,
+                placement.row,
+                hex_width,
+                hex_depth,
+                orientation,
+            ) + nalgebra_glm::vec3(0.0, tile_elevation(*tile_type), 0.0);
+
+            for extracted in extracted_meshes {
+                let mat_hash = material_hash(&extracted.material);
+                let key = (
+                    tile_chunk(placement),
+                    extracted.mesh_name.clone(),
+                    mat_hash,
+                    extracted.decorative,
+                );
+
+                let instance = InstanceTransform::new(
+                    nalgebra_glm::vec3(
+                        tile_world_pos.x + extracted.local_transform.translation.x,
+                        tile_world_pos.y + extracted.local_transform.translation.y,
+                        tile_world_pos.z + extracted.local_transform.translation.z,
+                    ),
+                    extracted.local_transform.rotation,
+                    extracted.local_transform.scale,
+                );
+
+                let entry = mesh_instances
+                    .entry(key)
+                    .or_insert_with(|| (extracted.material.clone(), Vec::new()));
+                entry.1.push((*coord, tile_world_pos, instance));
+            }
         }
     }
 
-    let mut instanced_groups = Vec::new();
+    let mut instanced_tiles = InstancedTiles::default();
 
-    for ((mesh_name, _mat_hash), (material, coord_instances)) in &mesh_instances {
+    for ((_chunk, mesh_name, _mat_hash, decorative), (material, coord_instances)) in &mesh_instances
+    {
         if coord_instances.is_empty() {
             continue;
         }
 
-        let mut coord_to_instance = HashMap::new();
+        let group_index = instanced_tiles.groups.len();
+        let mut coord_to_instance: HashMap<HexCoord, Vec<usize>> = HashMap::new();
         let mut instances = Vec::with_capacity(coord_instances.len());
-
-        for (index, (coord, transform)) in coord_instances.iter().enumerate() {
-            coord_to_instance.insert(*coord, index);
+        let mut min = coord_instances[0].1;
+        let mut max = coord_instances[0].1;
+
+        for (index, (coord, position, transform)) in coord_instances.iter().enumerate() {
+            let indices = coord_to_instance.entry(*coord).or_default();
+            if indices.is_empty() {
+                instanced_tiles
+                    .coord_groups
+                    .entry(*coord)
+                    .or_default()
+                    .push(group_index);
+            }
+            indices.push(index);
             instances.push(*transform);
+            min = nalgebra_glm::min2(&min, position);
+            max = nalgebra_glm::max2(&max, position);
         }
 
         let entity =
             spawn_instanced_mesh_with_material(world, mesh_name, instances, material.clone());
-        instanced_groups.push(InstancedTileGroup {
+        instanced_tiles.groups.push(InstancedTileGroup {
             entity,
+            decorative: *decorative,
+            center: (min + max) * 0.5,
+            radius: nalgebra_glm::distance(&min, &max) * 0.5 + hex_width.max(hex_depth),
             coord_to_instance,
         });
     }
 
-    instanced_groups
+    instanced_tiles
+}
+
+pub fn tile_chunk_culling_system(instanced_tiles: &InstancedTiles, world: &mut World) {
+    let Some(camera_entity) = world.resources.active_camera else {
+        return;
+    };
+    let Some((focus, radius)) = world
+        .get_pan_orbit_camera(camera_entity)
+        .map(|pan_orbit| (pan_orbit.target_focus, pan_orbit.target_radius))
+    else {
+        return;
+    };
+    let view_distance = radius * CHUNK_VIEW_DISTANCE_SCALE;
+    let show_detail = radius <= TILE_DETAIL_RADIUS;
+
+    for group in &instanced_tiles.groups {
+        let offset = nalgebra_glm::vec2(group.center.x - focus.x, group.center.z - focus.z);
+        let visible = (show_detail || !group.decorative)
+            && nalgebra_glm::length(&offset) - group.radius <= view_distance;
+        if let Some(visibility) = world.get_visibility_mut(group.entity)
+            && visibility.visible != visible
+        {
+            visibility.visible = visible;
+        }
+    }
 }
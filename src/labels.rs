@@ -0,0 +1,118 @@
+use crate::camera::world_to_screen;
+use nightshade::prelude::*;
+use std::collections::HashMap;
+
+const LABEL_HIDE_RADIUS: f32 = 5000.0;
+const LABEL_SCREEN_MARGIN: f32 = 64.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct LabelPool {
+    pub texts: HashMap<Entity, String>,
+    pub free: Vec<Entity>,
+    pub released: Vec<Entity>,
+}
+
+pub fn label_acquire(
+    pool: &mut LabelPool,
+    world: &mut World,
+    text: &str,
+    position: Vec3,
+    properties: TextProperties,
+) -> Entity {
+    let Some(entity) = pool.free.pop() else {
+        let entity = spawn_3d_billboard_text_with_properties(world, text, position, properties);
+        pool.texts.insert(entity, text.to_string());
+        return entity;
+    };
+
+    if let Some(transform) = world.get_local_transform_mut(entity) {
+        transform.translation = position;
+    }
+    mark_local_transform_dirty(world, entity);
+    if let Some(text_component) = world.get_text_mut(entity) {
+        text_component.properties = properties;
+        text_component.dirty = true;
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = true;
+    }
+    pool.texts.insert(entity, String::new());
+    label_set_text(pool, world, entity, text);
+    entity
+}
+
+pub fn label_release(pool: &mut LabelPool, entity: Entity) {
+    pool.released.push(entity);
+}
+
+pub fn label_set_text(pool: &mut LabelPool, world: &mut World, entity: Entity, text: &str) {
+    let Some(current) = pool.texts.get_mut(&entity) else {
+        return;
+    };
+    if current == text {
+        return;
+    }
+    let Some(text_index) = world.get_text(entity).map(|t| t.text_index) else {
+        return;
+    };
+    text.clone_into(current);
+    world
+        .resources
+        .text_cache
+        .set_text(text_index, text.to_string());
+    if let Some(text_component) = world.get_text_mut(entity) {
+        text_component.dirty = true;
+    }
+}
+
+pub fn label_set_font_size(world: &mut World, entity: Entity, font_size: f32) {
+    if let Some(text_component) = world.get_text_mut(entity)
+        && text_component.properties.font_size != font_size
+    {
+        text_component.properties.font_size = font_size;
+        text_component.dirty = true;
+    }
+}
+
+pub fn label_release_system(pool: &mut LabelPool, world: &mut World) {
+    for entity in pool.released.drain(..) {
+        if pool.texts.remove(&entity).is_none() {
+            continue;
+        }
+        if let Some(visibility) = world.get_visibility_mut(entity) {
+            visibility.visible = false;
+        }
+        pool.free.push(entity);
+    }
+}
+
+pub fn label_culling_system(pool: &LabelPool, world: &mut World) {
+    let Some(camera_entity) = world.resources.active_camera else {
+        return;
+    };
+    let zoomed_out = world
+        .get_pan_orbit_camera(camera_entity)
+        .is_some_and(|pan_orbit| pan_orbit.target_radius > LABEL_HIDE_RADIUS);
+    let Some((viewport_width, viewport_height)) = world.resources.window.cached_viewport_size
+    else {
+        return;
+    };
+
+    for entity in pool.texts.keys() {
+        let on_screen = !zoomed_out
+            && world
+                .get_local_transform(*entity)
+                .and_then(|transform| world_to_screen(world, transform.translation))
+                .is_some_and(|screen| {
+                    screen.x >= -LABEL_SCREEN_MARGIN
+                        && screen.y >= -LABEL_SCREEN_MARGIN
+                        && screen.x <= viewport_width as f32 + LABEL_SCREEN_MARGIN
+                        && screen.y <= viewport_height as f32 + LABEL_SCREEN_MARGIN
+                });
+        if let Some(visibility) = world.get_visibility_mut(*entity)
+            && visibility.visible != on_screen
+        {
+            visibility.visible = on_screen;
+        }
+    }
+}
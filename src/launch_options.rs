@@ -0,0 +1,213 @@
+use crate::constants::{MIN_MAP_HEIGHT, MIN_MAP_WIDTH};
+use crate::ecs::{Difficulty, Faction};
+use crate::quality::GraphicsQuality;
+use crate::render_scale::{MAX_RENDER_SCALE, MIN_RENDER_SCALE};
+use crate::text_file::faction_from_name;
+use crate::ui_scale::{MAX_UI_SCALE, MIN_UI_SCALE};
+use std::fmt;
+use std::path::PathBuf;
+
+pub const LAUNCH_USAGE: &str = "Usage: hex_war [OPTIONS]
+
+Options:
+  --seed <N>                 Generate the first map from this seed
+  --map-size <W>x<H>         Map dimensions in tiles (minimum 15x11)
+  --random-capitals          Place capitals randomly instead of in the corners
+  --wrap                     Wrap the map east to west (widths round up to even)
+  --difficulty <LEVEL>       easy, normal, hard, or brutal
+  --player-faction <NAME>    Redosia, Violetnam, Bluegaria, or Greenland
+  --quality <LEVEL>          Graphics quality: low, medium, or high
+  --render-scale <F>         Internal render scale from 0.25 to 1.0
+  --ui-scale <F>             Interface scale from 0.5 to 2.0
+  --attack-warning <PCT>     Confirm attacks below this win chance (default: 40, 0 disables)
+  --rules <PATH>             Load game rules from a rules file (default: rules.hexrules)
+  --script <PATH>            Load rhai script hooks from a file (default: scripts/rules.rhai)
+  --port <N>                 Port to host multiplayer games on (default: 7878)
+  --join <ADDRESS>           Host address for JOIN GAME (default: 127.0.0.1:7878)
+  --debug-console            Enable the ` debug console in release builds
+  --skip-menu                Start a game immediately
+  --spectate                 Start a game immediately and watch the AI play every faction
+  --headless                 Run an all-AI match without a window and print stats
+  --help                     Print this message";
+
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    pub seed: Option<u32>,
+    pub map_size: Option<(i32, i32)>,
+    pub difficulty: Option<Difficulty>,
+    pub player_faction: Option<Faction>,
+    pub quality: Option<GraphicsQuality>,
+    pub render_scale: Option<f32>,
+    pub ui_scale: Option<f32>,
+    pub attack_warning_threshold: Option<f32>,
+    pub rules_path: Option<PathBuf>,
+    pub script_path: Option<PathBuf>,
+    pub net_port: Option<u16>,
+    pub join_address: Option<String>,
+    pub random_capitals: bool,
+    pub wrap_columns: bool,
+    pub debug_console: bool,
+    pub skip_menu: bool,
+    pub spectate: bool,
+    pub headless: bool,
+    pub show_help: bool,
+}
+
+#[derive(Debug)]
+pub enum LaunchOptionsError {
+    MissingValue(String),
+    InvalidValue { flag: String, value: String },
+    UnknownFlag(String),
+}
+
+impl fmt::Display for LaunchOptionsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchOptionsError::MissingValue(flag) => {
+                write!(formatter, "missing value for {}", flag)
+            }
+            LaunchOptionsError::InvalidValue { flag, value } => {
+                write!(formatter, "invalid value '{}' for {}", value, flag)
+            }
+            LaunchOptionsError::UnknownFlag(flag) => write!(formatter, "unknown option {}", flag),
+        }
+    }
+}
+
+impl std::error::Error for LaunchOptionsError {}
+
+fn parse_map_size(value: &str) -> Option<(i32, i32)> {
+    let (width, height) = value.split_once(['x', 'X'])?;
+    let width = width.parse::<i32>().ok()?;
+    let height = height.parse::<i32>().ok()?;
+    (width >= MIN_MAP_WIDTH && height >= MIN_MAP_HEIGHT).then_some((width, height))
+}
+
+fn parse_difficulty(value: &str) -> Option<Difficulty> {
+    match value.to_ascii_lowercase().as_str() {
+        "easy" => Some(Difficulty::Easy),
+        "normal" => Some(Difficulty::Normal),
+        "hard" => Some(Difficulty::Hard),
+        "brutal" => Some(Difficulty::Brutal),
+        _ => None,
+    }
+}
+
+fn parse_quality(value: &str) -> Option<GraphicsQuality> {
+    match value.to_ascii_lowercase().as_str() {
+        "low" => Some(GraphicsQuality::Low),
+        "medium" => Some(GraphicsQuality::Medium),
+        "high" => Some(GraphicsQuality::High),
+        _ => None,
+    }
+}
+
+fn parse_render_scale(value: &str) -> Option<f32> {
+    let scale = value.parse::<f32>().ok()?;
+    (MIN_RENDER_SCALE..=MAX_RENDER_SCALE)
+        .contains(&scale)
+        .then_some(scale)
+}
+
+fn parse_ui_scale(value: &str) -> Option<f32> {
+    let scale = value.parse::<f32>().ok()?;
+    (MIN_UI_SCALE..=MAX_UI_SCALE)
+        .contains(&scale)
+        .then_some(scale)
+}
+
+fn parse_attack_warning(value: &str) -> Option<f32> {
+    let percent = value.parse::<u32>().ok()?;
+    (percent <= 100).then_some(percent as f32 / 100.0)
+}
+
+fn parse_flag_value<T>(
+    flag: &str,
+    value: Option<String>,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<T, LaunchOptionsError> {
+    let value = value.ok_or_else(|| LaunchOptionsError::MissingValue(flag.to_string()))?;
+    parse(&value).ok_or_else(|| LaunchOptionsError::InvalidValue {
+        flag: flag.to_string(),
+        value,
+    })
+}
+
+pub fn parse_launch_options(
+    args: impl IntoIterator<Item = String>,
+) -> Result<LaunchOptions, LaunchOptionsError> {
+    let mut options = LaunchOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+
+        match flag.as_str() {
+            "--seed" => {
+                let value = inline_value.or_else(|| args.next());
+                options.seed = Some(parse_flag_value(&flag, value, |v| v.parse().ok())?);
+            }
+            "--map-size" => {
+                let value = inline_value.or_else(|| args.next());
+                options.map_size = Some(parse_flag_value(&flag, value, parse_map_size)?);
+            }
+            "--difficulty" => {
+                let value = inline_value.or_else(|| args.next());
+                options.difficulty = Some(parse_flag_value(&flag, value, parse_difficulty)?);
+            }
+            "--player-faction" => {
+                let value = inline_value.or_else(|| args.next());
+                options.player_faction = Some(parse_flag_value(&flag, value, faction_from_name)?);
+            }
+            "--quality" => {
+                let value = inline_value.or_else(|| args.next());
+                options.quality = Some(parse_flag_value(&flag, value, parse_quality)?);
+            }
+            "--render-scale" => {
+                let value = inline_value.or_else(|| args.next());
+                options.render_scale = Some(parse_flag_value(&flag, value, parse_render_scale)?);
+            }
+            "--ui-scale" => {
+                let value = inline_value.or_else(|| args.next());
+                options.ui_scale = Some(parse_flag_value(&flag, value, parse_ui_scale)?);
+            }
+            "--attack-warning" => {
+                let value = inline_value.or_else(|| args.next());
+                options.attack_warning_threshold =
+                    Some(parse_flag_value(&flag, value, parse_attack_warning)?);
+            }
+            "--rules" => {
+                let value = inline_value.or_else(|| args.next());
+                options.rules_path =
+                    Some(parse_flag_value(&flag, value, |v| Some(PathBuf::from(v)))?);
+            }
+            "--script" => {
+                let value = inline_value.or_else(|| args.next());
+                options.script_path =
+                    Some(parse_flag_value(&flag, value, |v| Some(PathBuf::from(v)))?);
+            }
+            "--port" => {
+                let value = inline_value.or_else(|| args.next());
+                options.net_port = Some(parse_flag_value(&flag, value, |v| v.parse().ok())?);
+            }
+            "--join" => {
+                let value = inline_value.or_else(|| args.next());
+                options.join_address =
+                    Some(parse_flag_value(&flag, value, |v| Some(v.to_string()))?);
+            }
+            "--random-capitals" => options.random_capitals = true,
+            "--wrap" => options.wrap_columns = true,
+            "--debug-console" => options.debug_console = true,
+            "--skip-menu" => options.skip_menu = true,
+            "--spectate" => options.spectate = true,
+            "--headless" => options.headless = true,
+            "--help" | "-h" => options.show_help = true,
+            _ => return Err(LaunchOptionsError::UnknownFlag(flag)),
+        }
+    }
+
+    Ok(options)
+}
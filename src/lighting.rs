@@ -0,0 +1,150 @@
+use crate::ecs::{ENGINE_ENTITY, GameWorld, UNIT, faction_color};
+use nightshade::prelude::*;
+
+pub const TURNS_PER_DAY: u32 = 8;
+const LIGHTING_BLEND_SPEED: f32 = 1.5;
+const NIGHT_SUN_COLOR: [f32; 3] = [0.35, 0.4, 0.75];
+const DUSK_SUN_COLOR: [f32; 3] = [1.0, 0.6, 0.35];
+const DAY_SUN_COLOR: [f32; 3] = [1.0, 0.97, 0.9];
+const NIGHT_INTENSITY: f32 = 0.15;
+const NIGHT_UNIT_GLOW: f32 = 0.6;
+const SUN_MAX_ELEVATION: f32 = 1.2;
+
+pub struct SunBase {
+    pub entity: Entity,
+    pub color: Vec3,
+    pub intensity: f32,
+    pub rotation: Quat,
+}
+
+#[derive(Default)]
+pub struct LightingController {
+    pub enabled: bool,
+    pub sun_base: Option<SunBase>,
+    pub time_of_day: f32,
+    pub restore_pending: bool,
+}
+
+pub fn lighting_toggle(controller: &mut LightingController) -> bool {
+    controller.enabled = !controller.enabled;
+    if !controller.enabled {
+        controller.restore_pending = true;
+    }
+    controller.enabled
+}
+
+pub fn lighting_target_time_of_day(turn_number: u32) -> f32 {
+    (turn_number.saturating_sub(1) % TURNS_PER_DAY) as f32 / TURNS_PER_DAY as f32
+}
+
+pub fn lighting_daylight(time_of_day: f32) -> f32 {
+    let elevation = (time_of_day * std::f32::consts::TAU).cos();
+    ((elevation + 0.2) / 1.2).clamp(0.0, 1.0)
+}
+
+fn lerp3(from: [f32; 3], to: [f32; 3], t: f32) -> Vec3 {
+    nalgebra_glm::vec3(
+        from[0] + (to[0] - from[0]) * t,
+        from[1] + (to[1] - from[1]) * t,
+        from[2] + (to[2] - from[2]) * t,
+    )
+}
+
+fn sun_color(daylight: f32) -> Vec3 {
+    if daylight < 0.5 {
+        lerp3(NIGHT_SUN_COLOR, DUSK_SUN_COLOR, daylight * 2.0)
+    } else {
+        lerp3(DUSK_SUN_COLOR, DAY_SUN_COLOR, (daylight - 0.5) * 2.0)
+    }
+}
+
+fn set_unit_glow(game_world: &GameWorld, world: &mut World, glow: f32) {
+    for entity in game_world.query_entities(UNIT | ENGINE_ENTITY) {
+        let (Some(unit), Some(engine_entity)) = (
+            game_world.get_unit(entity),
+            game_world.get_engine_entity(entity),
+        ) else {
+            continue;
+        };
+        let color = faction_color(unit.faction);
+        if let Some(material) = world.get_material_mut(engine_entity.0) {
+            material.emissive_factor = [color[0] * glow, color[1] * glow, color[2] * glow];
+        }
+    }
+}
+
+fn capture_sun_base(world: &World, sun: Entity) -> Option<SunBase> {
+    let light = world.get_light(sun)?;
+    let transform = world.get_local_transform(sun)?;
+    Some(SunBase {
+        entity: sun,
+        color: light.color,
+        intensity: light.intensity,
+        rotation: transform.rotation,
+    })
+}
+
+fn apply_sun(world: &mut World, entity: Entity, color: Vec3, intensity: f32, rotation: Quat) {
+    if let Some(light) = world.get_light_mut(entity) {
+        light.color = color;
+        light.intensity = intensity;
+    }
+    if let Some(transform) = world.get_local_transform_mut(entity) {
+        transform.rotation = rotation;
+    }
+    mark_local_transform_dirty(world, entity);
+}
+
+pub fn lighting_system(
+    controller: &mut LightingController,
+    game_world: &GameWorld,
+    world: &mut World,
+    sun_entity: Option<Entity>,
+    delta_time: f32,
+) {
+    let Some(sun) = sun_entity else {
+        controller.sun_base = None;
+        return;
+    };
+    if controller.sun_base.as_ref().map(|base| base.entity) != Some(sun) {
+        controller.sun_base = capture_sun_base(world, sun);
+        controller.time_of_day = 0.0;
+    }
+    let Some(base) = controller.sun_base.as_ref() else {
+        return;
+    };
+
+    if !controller.enabled {
+        if controller.restore_pending {
+            controller.restore_pending = false;
+            controller.time_of_day = 0.0;
+            apply_sun(world, sun, base.color, base.intensity, base.rotation);
+            set_unit_glow(game_world, world, 0.0);
+        }
+        return;
+    }
+
+    let target = lighting_target_time_of_day(game_world.resources.turn_number);
+    let mut difference = target - controller.time_of_day;
+    if difference < 0.0 {
+        difference += 1.0;
+    }
+    let step = (LIGHTING_BLEND_SPEED * delta_time / TURNS_PER_DAY as f32).min(difference);
+    controller.time_of_day = (controller.time_of_day + step).fract();
+
+    let daylight = lighting_daylight(controller.time_of_day);
+    let color = sun_color(daylight);
+    let intensity = base.intensity * (NIGHT_INTENSITY + (1.0 - NIGHT_INTENSITY) * daylight);
+    let azimuth = nalgebra_glm::quat_angle_axis(
+        controller.time_of_day * std::f32::consts::TAU,
+        &nalgebra_glm::vec3(0.0, 1.0, 0.0),
+    );
+    let tilt = nalgebra_glm::quat_angle_axis(
+        (1.0 - daylight) * SUN_MAX_ELEVATION * 0.5,
+        &nalgebra_glm::vec3(1.0, 0.0, 0.0),
+    );
+    let rotation = azimuth * tilt * base.rotation;
+
+    apply_sun(world, sun, color, intensity, rotation);
+    set_unit_glow(game_world, world, NIGHT_UNIT_GLOW * (1.0 - daylight));
+}
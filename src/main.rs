@@ -1,51 +1,320 @@
+#[cfg(debug_assertions)]
+mod ai_overlay;
+mod asset_manifest;
+mod autosave;
+mod battle_cinematic;
+mod bug_report;
 mod camera;
+mod camera_director;
+mod capital_warning;
+mod chat;
+mod console;
 mod constants;
+mod coord_overlay;
+#[cfg(debug_assertions)]
+mod debug_stepper;
+mod dialog;
+mod display_settings;
+mod economy_report;
 mod ecs;
 mod event_log;
+mod hall_of_fame;
+mod handicap;
+mod headless;
 mod hex;
 mod hud;
+mod hud_text;
+mod inspector;
 mod instancing;
+mod labels;
+mod launch_options;
+mod lighting;
 mod map;
+mod map_balance;
+mod map_file;
 mod map_generation;
 mod menu;
+mod menu_showcase;
+mod network;
+mod notifications;
 mod prefabs;
+mod profile;
+mod quality;
+mod render_scale;
 mod rendering;
+mod rng;
+mod rules;
+mod save_browser;
+mod savegame;
+mod screen_shake;
+mod screenshot;
+mod scripting;
+mod season;
 mod selection;
+mod sim_clock;
+mod simulation;
+mod stack_popup;
+mod state_hash;
+mod strategic_view;
 mod systems;
+mod teams;
+mod text_file;
 mod tiles;
+mod turn_cue;
+mod turn_rotation;
+mod turn_summary;
+mod turn_timer;
+mod tutorial;
+mod ui_scale;
+mod victory_cinematic;
+mod wall_clock;
 
-use camera::{CameraBounds, calculate_camera_bounds, clamp_camera_to_bounds, reset_camera_to_map};
-use constants::ACTIONS_PER_TURN;
-use ecs::{Faction, GameEvents, GameWorld, TileType, UNIT};
+#[cfg(debug_assertions)]
+use ai_overlay::{AiOverlay, ai_overlay_system, ai_overlay_toggle, despawn_ai_overlay};
+use asset_manifest::load_asset_manifest;
+use autosave::{autosave_path, write_autosave};
+use battle_cinematic::{
+    BattleCinematic, battle_cinematic_active, battle_cinematic_skip, battle_cinematic_start,
+    battle_cinematic_system, battle_cinematic_time_scale,
+};
+use bug_report::{BugReport, bug_report_recent_events, bug_report_recent_hashes, write_bug_report};
+use camera::{
+    CameraBounds, calculate_camera_bounds, clamp_camera_to_bounds, focus_camera_on,
+    reset_camera_to_map, zoom_to_cursor_camera_system,
+};
+use camera_director::{
+    CameraDirector, camera_director_focus, camera_director_system, camera_director_toggle,
+};
+use capital_warning::{
+    CapitalWarning, capital_warning_check, capital_warning_system, despawn_capital_warning,
+};
+use chat::{
+    Chat, chat_close, chat_input_system, chat_open, chat_respawn_ui, chat_take_message, chat_type,
+    despawn_chat, ping_add, ping_system,
+};
+use console::{
+    CONSOLE_HELP, ConsoleCommand, DebugConsole, console_close, console_give_soldiers,
+    console_input_system, console_open, console_print, console_respawn_ui, console_set_owner,
+    console_spawn_unit, console_take_command, console_type, parse_console_command,
+};
+use constants::{ATTACK_WARNING_THRESHOLD, RALLYING_HYMN_MORALE_BOOST};
+use coord_overlay::{
+    CoordOverlay, coord_overlay_system, coord_overlay_toggle, despawn_coord_overlay,
+};
+#[cfg(debug_assertions)]
+use debug_stepper::{
+    DebugStepper, capture_simulation_snapshot, debug_stepper_record_changes,
+    debug_stepper_request_frame, debug_stepper_request_system, debug_stepper_stages_to_run,
+    debug_stepper_toggle, debug_stepper_ui_system, despawn_debug_stepper_ui,
+};
+use dialog::{
+    ConfirmDialog, ConfirmPurpose, DialogChoice, dialog_close, dialog_is_open, dialog_open,
+    dialog_system,
+};
+use display_settings::{
+    DISPLAY_SETTINGS_FILE_PATH, DisplaySettings, apply_display_settings,
+    display_settings_cycle_resolution, display_settings_for_launch, save_display_settings,
+};
+use economy_report::{
+    EconomyReport, despawn_economy_report, economy_report_record_events, economy_report_system,
+    economy_report_toggle,
+};
+use ecs::{
+    ALL_FACTIONS, Difficulty, Faction, GameEvents, GameWorld, HealthBarMode, Improvement,
+    LeaderAbility, TILE, TileType, UNIT, WorldEventKind, difficulty_next, faction_capital,
+    faction_color, faction_index, faction_name,
+};
 use event_log::{
-    EventLog, despawn_event_log_ui, event_log_add_combat, event_log_add_faction_eliminated,
-    event_log_add_reinforcement, event_log_add_speech, event_log_add_turn_start, event_log_new,
-    event_log_scroll_system, spawn_event_log_ui, update_event_log_ui,
+    EventLog, despawn_event_log_ui, event_log_add_attrition, event_log_add_capital_relocated,
+    event_log_add_chat, event_log_add_combat, event_log_add_defection,
+    event_log_add_faction_eliminated, event_log_add_improvement, event_log_add_pillage,
+    event_log_add_ping, event_log_add_reinforcement, event_log_add_ruin, event_log_add_speech,
+    event_log_add_surrender, event_log_add_turn_start, event_log_add_world_event, event_log_height,
+    event_log_new, event_log_scroll_system, spawn_event_log_ui, update_event_log_ui,
 };
-use hex::hex_to_world_position;
+use hall_of_fame::{
+    HALL_OF_FAME_FILE_PATH, HallOfFame, ScoreEntry, hall_of_fame_for_launch, hall_of_fame_insert,
+    hall_of_fame_lines, save_hall_of_fame, today_date, victory_score,
+};
+use handicap::{handicap_adjust, handicap_starting_soldiers};
+use headless::{headless_report_to_string, run_headless};
+use hex::{HexCoord, hex_orientation_key, hex_to_world_position};
 use hud::{GameHud, despawn_game_hud, spawn_game_hud, update_game_hud};
-use map_generation::{MapEntities, generate_game_map};
-use menu::{MenuAction, MenuData, MenuState, game_over_system, map_setup_system};
+use hud_text::spawn_scaled_hud_text;
+use inspector::{
+    TileInspector, despawn_tile_inspector, tile_inspector_close, tile_inspector_open,
+    tile_inspector_system,
+};
+use instancing::tile_chunk_culling_system;
+use labels::{label_culling_system, label_release};
+use launch_options::{LAUNCH_USAGE, LaunchOptions, parse_launch_options};
+use lighting::{LightingController, lighting_system, lighting_toggle};
+use map::{GeneratedMap, MapGenParams, generate_terrain, map_params_set_wrap, place_settlements};
+use map_balance::analyze_map_balance;
+use map_file::{MAP_DIRECTORY, list_saved_maps, load_map_file, save_map_file};
+use map_generation::{MapEntities, generate_game_map, spawn_game_map};
+use menu::{FactionStanding, MenuAction, MenuData, MenuState, game_over_system, map_setup_system};
+use menu_showcase::{
+    MenuShowcase, menu_showcase_new, menu_showcase_tick, showcase_dim_sun, showcase_map_params,
+    showcase_orbit_camera,
+};
+use network::{
+    DEFAULT_NET_PORT, HOST_PEER_ID, NetError, NetGameSetup, NetMessage, NetRole, NetSession,
+    net_claim_faction, net_faction_owner, net_host, net_is_remote_faction, net_join,
+    net_local_faction, net_poll, net_release_faction, net_send,
+};
 use nightshade::ecs::prefab::Prefab;
 use nightshade::prelude::*;
-use prefabs::load_tile_prefabs;
+use notifications::{
+    Notifications, despawn_notification_ui, notification_faction_eliminated, notification_push,
+    notification_system, notification_watch_system, spawn_notification_ui,
+};
+use prefabs::{load_tile_prefabs, load_unit_mesh};
+use profile::{
+    MatchRecord, MatchTally, PROFILE_FILE_PATH, Profile, profile_for_launch, profile_record_match,
+    profile_summary_lines, profile_tally_combat, save_profile,
+};
+use quality::{QualitySettings, quality_settings};
+use render_scale::{
+    RenderScale, render_scale_cycle, render_scale_set, render_scale_system,
+    render_scale_target_size,
+};
+use rules::{RuleKind, rules_adjust, rules_for_launch};
+use save_browser::{SaveBrowser, despawn_save_browser, save_browser_open, save_browser_system};
+use savegame::{
+    IRONMAN_SAVE_PATH, SaveGame, load_game_file, save_game_file, savegame_capture,
+    savegame_prepare_map, savegame_restore,
+};
+use screen_shake::{
+    CAPITAL_CAPTURE_SHAKE, ELIMINATION_SHAKE, ScreenShake, screen_shake_system,
+    screen_shake_trigger,
+};
+use screenshot::request_screenshot;
+use scripting::{ScriptHook, ScriptHost, script_run_hook, scripts_for_launch};
 use selection::clear_selection;
-use std::collections::HashMap;
+#[cfg(not(debug_assertions))]
+use sim_clock::sim_clock_tick;
+use simulation::SIMULATION_STAGES;
+use simulation::SimulationStage;
+use stack_popup::{StackPopup, despawn_stack_popup, stack_popup_hovered, stack_popup_system};
+use std::collections::{HashMap, VecDeque};
+use strategic_view::{
+    StrategicView, despawn_strategic_view, strategic_view_disable, strategic_view_enable,
+    strategic_view_system,
+};
 use systems::{
-    FireworkShell, GameResult, PendingSpawn, ai_turn_system, build_turn_order, can_end_turn,
-    despawn_unit, end_turn, floating_popup_system, hover_outline_system, hover_system,
-    input_system, movement_system, range_lines_system, selection_visual_system,
-    spawn_capture_firework, spawn_capture_popup, spawn_unit, speech_system, tile_highlight_system,
-    tile_ownership_system, unit_text_system, unit_visual_update_system, update_firework_shells,
-    valid_moves_system, victory_system,
+    FireworkShell, GameResult, PendingSpawn, PlayerAction, ai_turn_system, apply_player_action,
+    attack_target, begin_game, can_end_turn, capital_marker_system, city_tier_label_system,
+    combat_win_chance, damage_flash_system, death_effect_system, despawn_unit,
+    despawned_visuals_system, dominant_rival, end_turn, floating_popup_system,
+    general_marker_system, hover_outline_system, hover_system, improvement_name,
+    improvement_visual_system, input_system, movement_system, movement_trail_system,
+    port_route_system, range_lines_system, ruin_bonus_name, ruin_label_system, ruin_system,
+    selection_outline_system, spawn_capture_firework, spawn_capture_popup, spawn_casualty_popups,
+    spawn_morale_popup, spawn_pending, start_damage_flash, supply_delivery_system,
+    surrender_faction, tile_highlight_system, tile_ownership_system, unit_text_system,
+    unit_visual_spawn_system, unit_visual_update_system, update_firework_shells,
+    valid_moves_system, victory_system, world_event_message,
 };
+use teams::{TeamLayout, faction_allies, factions_allied, team_layout_next};
 use tiles::despawn_all_tiles;
+use turn_cue::{TurnCue, despawn_turn_cue, turn_cue_system};
+use turn_summary::{
+    TurnSummaryTracker, turn_summary_dismiss, turn_summary_is_open, turn_summary_record_events,
+    turn_summary_system,
+};
+use turn_timer::{turn_timer_expired, turn_timer_new, turn_timer_next_option, turn_timer_tick};
+use tutorial::{
+    TUTORIAL_PLAYER, TUTORIAL_SEED, Tutorial, despawn_tutorial, tutorial_allows_click,
+    tutorial_allows_end_turn, tutorial_allows_speech, tutorial_begin, tutorial_is_complete,
+    tutorial_map, tutorial_map_params, tutorial_new, tutorial_populate, tutorial_system,
+};
+use ui_scale::{UiScale, ui_scale_cycle, ui_scale_set};
+use victory_cinematic::{VictoryCinematic, victory_cinematic_new, victory_cinematic_system};
+use wall_clock::unix_seconds_now;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    launch(HexWarGame::default())
+    let launch_options = match parse_launch_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            log::error!("{}", error);
+            println!("{}", LAUNCH_USAGE);
+            return Err(error.into());
+        }
+    };
+
+    if launch_options.show_help {
+        println!("{}", LAUNCH_USAGE);
+        return Ok(());
+    }
+
+    let rules = match rules_for_launch(launch_options.rules_path.as_deref()) {
+        Ok(rules) => rules,
+        Err(error) => {
+            log::error!("Failed to load rules: {}", error);
+            return Err(error.into());
+        }
+    };
+
+    let profile = profile_for_launch().unwrap_or_else(|error| {
+        log::warn!("Failed to load profile, starting fresh: {}", error);
+        Profile::default()
+    });
+
+    let display_settings = display_settings_for_launch().unwrap_or_else(|error| {
+        log::warn!("Failed to load display settings, using defaults: {}", error);
+        DisplaySettings::default()
+    });
+
+    let hall_of_fame = hall_of_fame_for_launch().unwrap_or_else(|error| {
+        log::warn!("Failed to load hall of fame, starting fresh: {}", error);
+        HallOfFame::default()
+    });
+
+    let script_host = match scripts_for_launch(launch_options.script_path.as_deref()) {
+        Ok(script_host) => script_host,
+        Err(error) => {
+            log::error!("Failed to load script: {}", error);
+            return Err(error.into());
+        }
+    };
+
+    if launch_options.headless {
+        let report = run_headless(&launch_options, rules, script_host);
+        print!("{}", headless_report_to_string(&report));
+        return Ok(());
+    }
+
+    let quality = quality_settings(launch_options.quality.unwrap_or_default());
+    let mut render_scale = RenderScale::default();
+    if let Some(scale) = launch_options.render_scale {
+        render_scale_set(&mut render_scale, scale);
+    }
+    let mut ui_scale = UiScale::default();
+    if let Some(scale) = launch_options.ui_scale {
+        ui_scale_set(&mut ui_scale, scale);
+    }
+    let attack_warning_threshold = launch_options
+        .attack_warning_threshold
+        .unwrap_or(ATTACK_WARNING_THRESHOLD);
+    let mut game = HexWarGame {
+        quality,
+        render_scale,
+        ui_scale,
+        display_settings,
+        attack_warning_threshold,
+        launch_options,
+        ..HexWarGame::default()
+    };
+    game.game_world.resources.rules = rules;
+    game.script_host = script_host;
+    game.profile = profile;
+    game.hall_of_fame = hall_of_fame;
+    game.menu.ui_scale = ui_scale;
+    launch(game)
 }
 
-fn spawn_fps_display(world: &mut World) -> Entity {
+fn spawn_fps_display(world: &mut World, ui_scale: f32) -> Entity {
     let props = TextProperties {
         font_size: 24.0,
         color: nalgebra_glm::vec4(1.0, 1.0, 1.0, 1.0),
@@ -54,12 +323,13 @@ fn spawn_fps_display(world: &mut World) -> Entity {
         outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
         ..Default::default()
     };
-    spawn_hud_text_with_properties(
+    spawn_scaled_hud_text(
         world,
         "",
         HudAnchor::TopRight,
         nalgebra_glm::vec2(-10.0, 10.0),
         props,
+        ui_scale,
     )
 }
 
@@ -122,11 +392,62 @@ struct HexWarGame {
     fps_entity: Option<Entity>,
     fps_visible: bool,
     sun_entity: Option<Entity>,
+    lighting: LightingController,
     speech_requested: bool,
+    stack_selection_requested: Option<(HexCoord, u8)>,
+    relocation_requested: bool,
+    pillage_requested: bool,
+    scout_requested: bool,
+    build_requested: Option<Improvement>,
+    script_host: Option<ScriptHost>,
+    script_winner: Option<Faction>,
+    net: Option<NetSession>,
+    net_pending: VecDeque<NetMessage>,
     player_faction: Faction,
+    spectating: bool,
+    ironman: bool,
+    ironman_autosave_turn: u32,
+    autosave_turn: u32,
+    save_browser: SaveBrowser,
+    menu_showcase: Option<MenuShowcase>,
+    profile: Profile,
+    hall_of_fame: HallOfFame,
+    match_tally: MatchTally,
     pending_spawns: Vec<PendingSpawn>,
     camera_bounds: Option<CameraBounds>,
     firework_shells: Vec<FireworkShell>,
+    launch_options: LaunchOptions,
+    quality: QualitySettings,
+    render_scale: RenderScale,
+    ui_scale: UiScale,
+    display_settings: DisplaySettings,
+    ctrl_held: bool,
+    alt_held: bool,
+    shift_held: bool,
+    chat: Chat,
+    console: DebugConsole,
+    confirm_dialog: ConfirmDialog,
+    stack_popup: StackPopup,
+    attack_warnings: bool,
+    attack_warning_threshold: f32,
+    tile_inspector: TileInspector,
+    tutorial: Option<Tutorial>,
+    notifications: Notifications,
+    turn_summary: TurnSummaryTracker,
+    economy_report: EconomyReport,
+    camera_director: CameraDirector,
+    battle_cinematic: BattleCinematic,
+    screen_shake: ScreenShake,
+    turn_cue: TurnCue,
+    capital_warning: CapitalWarning,
+    victory_cinematic: Option<VictoryCinematic>,
+    strategic_view: StrategicView,
+    screen_size: (f32, f32),
+    coord_overlay: CoordOverlay,
+    #[cfg(debug_assertions)]
+    debug_stepper: DebugStepper,
+    #[cfg(debug_assertions)]
+    ai_overlay: AiOverlay,
 }
 
 impl Default for HexWarGame {
@@ -142,28 +463,296 @@ impl Default for HexWarGame {
             fps_entity: None,
             fps_visible: false,
             sun_entity: None,
+            lighting: LightingController::default(),
             speech_requested: false,
+            stack_selection_requested: None,
+            relocation_requested: false,
+            pillage_requested: false,
+            scout_requested: false,
+            build_requested: None,
+            script_host: None,
+            script_winner: None,
+            net: None,
+            net_pending: VecDeque::new(),
             player_faction: Faction::default(),
+            spectating: false,
+            ironman: false,
+            ironman_autosave_turn: 0,
+            autosave_turn: 0,
+            save_browser: SaveBrowser::default(),
+            menu_showcase: None,
+            profile: Profile::default(),
+            hall_of_fame: HallOfFame::default(),
+            match_tally: MatchTally::default(),
             pending_spawns: Vec::new(),
             camera_bounds: None,
             firework_shells: Vec::new(),
+            launch_options: LaunchOptions::default(),
+            quality: QualitySettings::default(),
+            render_scale: RenderScale::default(),
+            ui_scale: UiScale::default(),
+            display_settings: DisplaySettings::default(),
+            ctrl_held: false,
+            alt_held: false,
+            shift_held: false,
+            chat: Chat::default(),
+            console: DebugConsole::default(),
+            confirm_dialog: ConfirmDialog::default(),
+            stack_popup: StackPopup::default(),
+            attack_warnings: true,
+            attack_warning_threshold: ATTACK_WARNING_THRESHOLD,
+            tile_inspector: TileInspector::default(),
+            tutorial: None,
+            notifications: Notifications::default(),
+            turn_summary: TurnSummaryTracker::default(),
+            economy_report: EconomyReport::default(),
+            camera_director: CameraDirector::default(),
+            battle_cinematic: BattleCinematic::default(),
+            screen_shake: ScreenShake::default(),
+            turn_cue: TurnCue::default(),
+            capital_warning: CapitalWarning::default(),
+            victory_cinematic: None,
+            strategic_view: StrategicView::default(),
+            screen_size: (0.0, 0.0),
+            coord_overlay: CoordOverlay::default(),
+            #[cfg(debug_assertions)]
+            debug_stepper: DebugStepper::default(),
+            #[cfg(debug_assertions)]
+            ai_overlay: AiOverlay::default(),
         }
     }
 }
 
-fn game_reset_camera(game: &HexWarGame, world: &mut World) {
-    reset_camera_to_map(
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TurnController {
+    Local,
+    Remote,
+    Ai,
+}
+
+fn game_turn_controller(game: &HexWarGame) -> TurnController {
+    let current_faction = game.game_world.resources.current_faction;
+    match game.net.as_ref() {
+        Some(net) if net_is_remote_faction(net, current_faction) => TurnController::Remote,
+        Some(net) if net_faction_owner(net, current_faction).is_some() => TurnController::Local,
+        Some(_) => TurnController::Ai,
+        None if game_human_faction(game) == Some(current_faction) => TurnController::Local,
+        None => TurnController::Ai,
+    }
+}
+
+fn game_ai_reference_faction(game: &HexWarGame) -> Option<Faction> {
+    match game.net.as_ref() {
+        Some(net) => ALL_FACTIONS
+            .iter()
+            .find(|faction| net_faction_owner(net, **faction) == Some(HOST_PEER_ID))
+            .copied(),
+        None => game_human_faction(game),
+    }
+}
+
+fn game_human_faction(game: &HexWarGame) -> Option<Faction> {
+    (!game.spectating).then_some(game.player_faction)
+}
+
+fn game_human_factions(game: &HexWarGame) -> [bool; 4] {
+    ALL_FACTIONS.map(|faction| match game.net.as_ref() {
+        Some(net) => net_faction_owner(net, faction).is_some(),
+        None => game_human_faction(game) == Some(faction),
+    })
+}
+
+fn game_is_winner(game: &HexWarGame, winner: Faction) -> bool {
+    game_human_faction(game).is_some_and(|faction| {
+        faction == winner || factions_allied(game.game_world.resources.team_layout, faction, winner)
+    })
+}
+
+fn game_lobby_labels(net: &NetSession) -> [String; 4] {
+    std::array::from_fn(|index| {
+        let faction = ALL_FACTIONS[index];
+        let owner = match net_faction_owner(net, faction) {
+            None => "OPEN (AI)".to_string(),
+            Some(peer_id) if Some(peer_id) == net.local_peer => "YOU".to_string(),
+            Some(HOST_PEER_ID) => "HOST".to_string(),
+            Some(peer_id) => format!("PLAYER {}", peer_id + 1),
+        };
+        format!("{} - {}", faction_name(faction).to_uppercase(), owner)
+    })
+}
+
+fn game_show_lobby(game: &mut HexWarGame, world: &mut World) {
+    let Some(net) = game.net.as_ref() else {
+        return;
+    };
+    let labels = game_lobby_labels(net);
+    let is_host = net.role == NetRole::Host;
+    menu::setup_lobby_menu(&mut game.menu, world, &labels, is_host);
+}
+
+fn game_enter_lobby(
+    game: &mut HexWarGame,
+    world: &mut World,
+    session: Result<NetSession, NetError>,
+) {
+    match session {
+        Ok(net) => {
+            game.net = Some(net);
+            game.net_pending.clear();
+            game.menu.state = MenuState::Lobby;
+            game_show_lobby(game, world);
+        }
+        Err(error) => {
+            log::error!("Multiplayer connection failed: {}", error);
+            let message = format!("Connection failed: {}", error);
+            menu::show_menu_status(&mut game.menu, world, &message);
+        }
+    }
+}
+
+fn game_leave_network(game: &mut HexWarGame) {
+    game.net = None;
+    game.net_pending.clear();
+}
+
+fn game_network_system(game: &mut HexWarGame, world: &mut World) {
+    let Some(net) = game.net.as_mut() else {
+        return;
+    };
+    let messages = net_poll(net);
+    let connected = net.connected;
+
+    for message in messages {
+        match message {
+            NetMessage::Lobby { .. } if game.menu.state == MenuState::Lobby => {
+                game_show_lobby(game, world);
+            }
+            NetMessage::Start(setup)
+                if game.menu.state == MenuState::Lobby
+                    && setup.orientation != game.game_world.resources.hex_orientation =>
+            {
+                game_leave_network(game);
+                game.menu.state = MenuState::MainMenu;
+                menu::setup_main_menu(&mut game.menu, world);
+                let message = format!(
+                    "Host map needs {}-top tiles",
+                    hex_orientation_key(setup.orientation)
+                );
+                menu::show_menu_status(&mut game.menu, world, &message);
+            }
+            NetMessage::Start(setup) if game.menu.state == MenuState::Lobby => {
+                game_start_network_game(game, world, setup);
+            }
+            NetMessage::Reject { version } => {
+                game_leave_network(game);
+                game.menu.state = MenuState::MainMenu;
+                menu::setup_main_menu(&mut game.menu, world);
+                let message = format!("Host needs a build with network protocol {}", version);
+                menu::show_menu_status(&mut game.menu, world, &message);
+                return;
+            }
+            NetMessage::Action { .. } | NetMessage::EndTurn { .. } => {
+                game.net_pending.push_back(message);
+            }
+            NetMessage::Chat { faction, text } => {
+                event_log_add_chat(&mut game.event_log, faction, &text);
+            }
+            NetMessage::Ping { faction, coord } => {
+                ping_add(&mut game.chat, coord, faction);
+                event_log_add_ping(&mut game.event_log, faction, coord);
+            }
+            NetMessage::Leave { peer_id } if connected => {
+                if game.menu.state == MenuState::Lobby {
+                    game_show_lobby(game, world);
+                } else {
+                    notification_push(
+                        &mut game.notifications,
+                        format!("Player {} left; the AI takes over", peer_id + 1),
+                        [1.0, 0.6, 0.3, 1.0],
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if connected {
+        return;
+    }
+    game_leave_network(game);
+    if game.menu.state == MenuState::Lobby {
+        game.menu.state = MenuState::MainMenu;
+        menu::setup_main_menu(&mut game.menu, world);
+        menu::show_menu_status(&mut game.menu, world, "Lost connection to host");
+    } else {
+        notification_push(
+            &mut game.notifications,
+            "Lost connection to host; the AI takes over".to_string(),
+            [1.0, 0.35, 0.3, 1.0],
+        );
+    }
+}
+
+fn game_start_network_game(game: &mut HexWarGame, world: &mut World, setup: NetGameSetup) {
+    let local_faction = game.net.as_ref().and_then(net_local_faction);
+    game.game_world.resources.map_params.map_width = setup.map_width;
+    game.game_world.resources.map_params.map_height = setup.map_height;
+    game.game_world.resources.map_params.random_capitals = setup.random_capitals;
+    game.menu.random_capitals = setup.random_capitals;
+    game.game_world.resources.map_params.wrap_columns = setup.wrap_columns;
+    game.game_world.resources.map_params.orientation = setup.orientation;
+    game.menu.wrap_columns = setup.wrap_columns;
+    game.game_world.resources.rules = setup.rules;
+    game.game_world.resources.handicaps = Default::default();
+
+    world.resources.graphics.atmosphere = Atmosphere::Nebula;
+    game.sun_entity = Some(spawn_sun(world));
+    game.map_entities = Some(generate_game_map(
+        &mut game.game_world,
         world,
+        &game.tile_prefabs,
+        setup.seed,
+    ));
+    game.camera_bounds = Some(calculate_camera_bounds(
         game.game_world.resources.hex_width,
         game.game_world.resources.hex_depth,
+        game.game_world.resources.hex_orientation,
         game.game_world.resources.map_params.map_width,
         game.game_world.resources.map_params.map_height,
+        game.game_world.resources.map_params.wrap_columns,
+    ));
+    game_reset_camera(game, world);
+
+    game.spectating = local_faction.is_none();
+    if let Some(faction) = local_faction {
+        game.player_faction = faction;
+    }
+    game.net_pending.clear();
+    game_begin_match(
+        game,
+        world,
+        setup.difficulties,
+        setup.team_layout,
+        setup.turn_seconds,
     );
 }
 
-fn game_regenerate_map(game: &mut HexWarGame, world: &mut World) {
-    game_cleanup_map(game, world);
-    game.map_entities = Some(generate_game_map(
+fn game_spawn_match_ui(game: &mut HexWarGame, world: &mut World, turn: u32, faction: Faction) {
+    game.menu.state = MenuState::Playing;
+    menu::despawn_menu_elements(&mut game.menu, world);
+    game.match_tally = MatchTally::default();
+
+    game.event_log = event_log_new();
+    spawn_event_log_ui(world, &mut game.event_log, game.ui_scale.scale);
+    event_log_add_turn_start(&mut game.event_log, turn, faction);
+}
+
+fn game_resume_save(game: &mut HexWarGame, world: &mut World, save: SaveGame) {
+    world.resources.graphics.atmosphere = Atmosphere::Nebula;
+    game.sun_entity = Some(spawn_sun(world));
+    savegame_prepare_map(&mut game.game_world, &save);
+    game.game_world.resources.map_balance = analyze_map_balance(&save.map);
+    game.map_entities = Some(spawn_game_map(
         &mut game.game_world,
         world,
         &game.tile_prefabs,
@@ -171,126 +760,1745 @@ fn game_regenerate_map(game: &mut HexWarGame, world: &mut World) {
     game.camera_bounds = Some(calculate_camera_bounds(
         game.game_world.resources.hex_width,
         game.game_world.resources.hex_depth,
+        game.game_world.resources.hex_orientation,
         game.game_world.resources.map_params.map_width,
         game.game_world.resources.map_params.map_height,
+        game.game_world.resources.map_params.wrap_columns,
     ));
+    game_reset_camera(game, world);
+
+    let restored = savegame_restore(&mut game.game_world, &save);
+    game.player_faction = save.player_faction;
+    game.spectating = false;
+    game.game_world.resources.human_factions = game_human_factions(game);
+    game.ironman = save.ironman;
+    game.ironman_autosave_turn = save.turn_number;
+    game.autosave_turn = save.turn_number;
+    game_spawn_match_ui(game, world, save.turn_number, save.current_faction);
+
+    game.game_hud = spawn_game_hud(world, game.ui_scale.scale);
+    spawn_notification_ui(&mut game.notifications, world, game.ui_scale.scale);
+    let message = if save.ironman {
+        format!("Iron-man campaign resumed on turn {}", save.turn_number)
+    } else {
+        format!("Autosave loaded on turn {}", save.turn_number)
+    };
+    notification_push(&mut game.notifications, message, [1.0, 0.5, 0.4, 1.0]);
+    if let Err(mismatch) = restored {
+        log::error!("Loaded save diverged: {}", mismatch);
+        notification_push(
+            &mut game.notifications,
+            "Save file appears corrupted: game state does not match its checksum".to_string(),
+            [1.0, 0.3, 0.3, 1.0],
+        );
+    }
 }
 
-fn game_cleanup_map(game: &mut HexWarGame, world: &mut World) {
-    if let Some(mut entities) = game.map_entities.take() {
-        map_generation::despawn_map_entities(world, &mut entities);
+fn game_write_ironman_save(game: &HexWarGame) {
+    let save = savegame_capture(
+        &game.game_world,
+        game.player_faction,
+        true,
+        unix_seconds_now(),
+    );
+    if let Err(error) = save_game_file(std::path::Path::new(IRONMAN_SAVE_PATH), &save) {
+        log::error!("Failed to write iron-man autosave: {}", error);
     }
+}
 
-    let unit_entities: Vec<_> = game.game_world.query_entities(UNIT).collect();
-    for entity in unit_entities {
-        despawn_unit(&mut game.game_world, world, entity);
+fn game_ironman_autosave_system(game: &mut HexWarGame) {
+    if !game.ironman
+        || game.game_world.resources.turn_number == game.ironman_autosave_turn
+        || game_turn_controller(game) != TurnController::Local
+        || !can_end_turn(&game.game_world)
+    {
+        return;
     }
+    game.ironman_autosave_turn = game.game_world.resources.turn_number;
+    game_write_ironman_save(game);
+}
 
-    despawn_all_tiles(&mut game.game_world);
-    clear_selection(&mut game.game_world);
-    game.game_world.resources.hovered_tile = None;
-    game.game_world.resources.previously_highlighted.clear();
-    game.game_world.resources.previous_hovered_tile = None;
-    game.game_world.resources.previous_selected_unit = None;
-    game.game_world.resources.previous_valid_move_count = 0;
+fn game_autosave_system(game: &mut HexWarGame) {
+    if game.ironman
+        || game.net.is_some()
+        || game.tutorial.is_some()
+        || game.spectating
+        || game.game_world.resources.turn_number == game.autosave_turn
+        || game_turn_controller(game) != TurnController::Local
+        || !can_end_turn(&game.game_world)
+    {
+        return;
+    }
+    game.autosave_turn = game.game_world.resources.turn_number;
+    let save = savegame_capture(
+        &game.game_world,
+        game.player_faction,
+        false,
+        unix_seconds_now(),
+    );
+    if let Err(error) = write_autosave(&save) {
+        log::error!("Failed to write autosave: {}", error);
+    }
 }
 
-fn game_end_turn(game: &mut HexWarGame) {
-    let transition = end_turn(&mut game.game_world, &mut game.game_events);
-    event_log_add_turn_start(
-        &mut game.event_log,
-        transition.turn_number,
-        transition.new_faction,
+fn game_turn_timer_system(game: &mut HexWarGame, delta_time: f32) {
+    turn_timer_tick(&mut game.game_world.resources, delta_time);
+    if !turn_timer_expired(&game.game_world.resources.turn_timer)
+        || game_turn_controller(game) != TurnController::Local
+        || !can_end_turn(&game.game_world)
+    {
+        return;
+    }
+    notification_push(
+        &mut game.notifications,
+        "Time's up! Your turn has ended".to_string(),
+        [1.0, 0.4, 0.3, 1.0],
     );
-    game.pending_spawns = transition.pending_spawns;
+    game_player_end_turn(game);
 }
 
-fn game_cleanup_game_world(game: &mut HexWarGame, world: &mut World) {
-    world.resources.graphics.atmosphere = Atmosphere::None;
+fn game_end_ironman(game: &mut HexWarGame) {
+    if !game.ironman {
+        return;
+    }
+    game.ironman = false;
+    if let Err(error) = std::fs::remove_file(IRONMAN_SAVE_PATH)
+        && error.kind() != std::io::ErrorKind::NotFound
+    {
+        log::error!("Failed to delete iron-man autosave: {}", error);
+    }
+}
 
-    if let Some(sun) = game.sun_entity.take() {
-        world.queue_command(WorldCommand::DespawnRecursive { entity: sun });
+fn game_begin_match(
+    game: &mut HexWarGame,
+    world: &mut World,
+    difficulties: [Difficulty; 4],
+    team_layout: TeamLayout,
+    turn_seconds: u32,
+) {
+    game.game_world.resources.team_layout = team_layout;
+    game.game_world.resources.turn_timer = turn_timer_new(turn_seconds);
+    game.ironman = game.menu.ironman && !game.spectating && game.net.is_none();
+    game.ironman_autosave_turn = 0;
+    game.autosave_turn = 0;
+    game.game_world.resources.human_factions = game_human_factions(game);
+    let first_faction = begin_game(&mut game.game_world, difficulties);
+    game_spawn_match_ui(game, world, 1, first_faction);
+
+    game.game_hud = spawn_game_hud(world, game.ui_scale.scale);
+    spawn_notification_ui(&mut game.notifications, world, game.ui_scale.scale);
+    if game.spectating {
+        notification_push(
+            &mut game.notifications,
+            "Spectating - the AI and remote players control every faction".to_string(),
+            [0.8, 0.9, 1.0, 1.0],
+        );
     }
+}
 
-    despawn_game_hud(&mut game.game_hud, world);
-    despawn_event_log_ui(world, &mut game.event_log);
-    game_cleanup_map(game, world);
+fn game_record_profile(game: &mut HexWarGame, winner: Faction) {
+    if game.tutorial.is_some() {
+        return;
+    }
+    let Some(faction) = game_human_faction(game) else {
+        return;
+    };
+    let record = MatchRecord {
+        faction,
+        difficulty: game.game_world.resources.difficulty,
+        seed: game.game_world.resources.rng_seed,
+        won: game_is_winner(game, winner),
+        tally: game.match_tally,
+    };
+    profile_record_match(&mut game.profile, record);
+    if let Err(error) = save_profile(std::path::Path::new(PROFILE_FILE_PATH), &game.profile) {
+        log::error!("Failed to save profile: {}", error);
+    }
 }
 
-fn game_handle_menu_action(game: &mut HexWarGame, world: &mut World, action: MenuAction) {
-    match action {
-        MenuAction::None => {}
-        MenuAction::EnterMapSetup => {
-            game.menu.state = MenuState::MapSetup;
-            menu::despawn_menu_elements(&mut game.menu, world);
+fn game_record_high_score(game: &mut HexWarGame, winner: Faction) -> Option<usize> {
+    if game.tutorial.is_some() || !game_is_winner(game, winner) {
+        return None;
+    }
+    let faction = game_human_faction(game)?;
+    let difficulty = game.game_world.resources.difficulty;
+    let turns = game.game_world.resources.turn_number;
+    let entry = ScoreEntry {
+        score: victory_score(difficulty, turns, game.match_tally.units_destroyed),
+        faction,
+        difficulty,
+        seed: game.game_world.resources.rng_seed,
+        turns,
+        date: today_date(),
+    };
+    let rank = hall_of_fame_insert(&mut game.hall_of_fame, entry)?;
+    if let Err(error) = save_hall_of_fame(
+        std::path::Path::new(HALL_OF_FAME_FILE_PATH),
+        &game.hall_of_fame,
+    ) {
+        log::error!("Failed to save hall of fame: {}", error);
+    }
+    Some(rank)
+}
 
-            world.resources.graphics.atmosphere = Atmosphere::Nebula;
-            game.sun_entity = Some(spawn_sun(world));
-            game.map_entities = Some(generate_game_map(
-                &mut game.game_world,
+fn game_apply_local_action(game: &mut HexWarGame, world: &mut World, action: PlayerAction) {
+    apply_player_action(&mut game.game_world, world, action, &mut game.game_events);
+    let faction = game.game_world.resources.current_faction;
+    if let Some(net) = game.net.as_mut() {
+        net_send(net, &NetMessage::Action { faction, action });
+    }
+}
+
+fn game_apply_remote_action(game: &mut HexWarGame, world: &mut World) {
+    if !can_end_turn(&game.game_world) {
+        return;
+    }
+    let Some(message) = game.net_pending.pop_front() else {
+        return;
+    };
+    let current_faction = game.game_world.resources.current_faction;
+    match message {
+        NetMessage::Action { faction, action } if faction == current_faction => {
+            apply_player_action(&mut game.game_world, world, action, &mut game.game_events);
+        }
+        NetMessage::EndTurn {
+            faction,
+            turn,
+            hash,
+        } if faction == current_faction => {
+            game_end_turn(game);
+            let local_hash = game
+                .game_world
+                .resources
+                .state_hashes
+                .last()
+                .map(|record| record.hash);
+            if local_hash != Some(hash) {
+                log::error!(
+                    "Desync after {} turn {}: local {:?}, remote {:016x}",
+                    faction_name(faction),
+                    turn,
+                    local_hash,
+                    hash
+                );
+                notification_push(
+                    &mut game.notifications,
+                    format!("Desync detected on turn {}", turn),
+                    [1.0, 0.35, 0.3, 1.0],
+                );
+            }
+        }
+        message => log::warn!("Dropping out-of-turn message {:?}", message),
+    }
+}
+
+fn game_send_chat(game: &mut HexWarGame, text: String) {
+    let faction = game.player_faction;
+    event_log_add_chat(&mut game.event_log, faction, &text);
+    if let Some(net) = game.net.as_mut() {
+        net_send(net, &NetMessage::Chat { faction, text });
+    }
+}
+
+fn game_send_ping(game: &mut HexWarGame, coord: HexCoord) {
+    let faction = game.player_faction;
+    ping_add(&mut game.chat, coord, faction);
+    event_log_add_ping(&mut game.event_log, faction, coord);
+    if let Some(net) = game.net.as_mut() {
+        net_send(net, &NetMessage::Ping { faction, coord });
+    }
+}
+
+fn game_chat_key(game: &mut HexWarGame, world: &mut World, key: KeyCode) {
+    match key {
+        KeyCode::Escape => chat_close(&mut game.chat, world),
+        KeyCode::Enter | KeyCode::NumpadEnter => {
+            if let Some(text) = chat_take_message(&mut game.chat) {
+                game_send_chat(game, text);
+            }
+            chat_close(&mut game.chat, world);
+        }
+        _ => chat_type(&mut game.chat, key, game.shift_held),
+    }
+}
+
+fn game_console_enabled(game: &HexWarGame) -> bool {
+    cfg!(debug_assertions) || game.launch_options.debug_console
+}
+
+fn game_run_console_command(
+    game: &mut HexWarGame,
+    world: &mut World,
+    command: ConsoleCommand,
+) -> String {
+    if game.net.is_some() {
+        return "console commands are disabled in network games".to_string();
+    }
+    match command {
+        ConsoleCommand::GiveSoldiers(amount) => console_give_soldiers(&mut game.game_world, amount),
+        ConsoleCommand::RevealMap => {
+            let Some(map_entities) = game.map_entities.as_ref() else {
+                return "no map loaded".to_string();
+            };
+            strategic_view_enable(
+                &mut game.strategic_view,
+                &game.game_world,
                 world,
-                &game.tile_prefabs,
-            ));
-            game.camera_bounds = Some(calculate_camera_bounds(
-                game.game_world.resources.hex_width,
-                game.game_world.resources.hex_depth,
-                game.game_world.resources.map_params.map_width,
-                game.game_world.resources.map_params.map_height,
-            ));
-            game_reset_camera(game, world);
+                map_entities,
+            );
+            "showing the whole map in strategic view".to_string()
+        }
+        ConsoleCommand::SetOwner { owner, coord } => {
+            console_set_owner(&mut game.game_world, owner, coord)
+        }
+        ConsoleCommand::EndGame(faction) => {
+            let winner = faction.unwrap_or(game.player_faction);
+            game.script_winner = Some(winner);
+            format!("ending game, {} wins", faction_name(winner))
+        }
+        ConsoleCommand::SpawnUnit { faction, coord } => {
+            console_spawn_unit(&mut game.game_world, faction, coord)
+        }
+        ConsoleCommand::Seed => format!("seed {}", game.game_world.resources.rng_seed),
+        ConsoleCommand::Help => CONSOLE_HELP.to_string(),
+    }
+}
 
+fn game_console_key(game: &mut HexWarGame, world: &mut World, key: KeyCode) {
+    match key {
+        KeyCode::Escape | KeyCode::Backquote => console_close(&mut game.console, world),
+        KeyCode::Enter | KeyCode::NumpadEnter => {
+            let Some(line) = console_take_command(&mut game.console) else {
+                return;
+            };
+            let output = match parse_console_command(&line) {
+                Ok(command) => game_run_console_command(game, world, command),
+                Err(error) => error,
+            };
+            let ui_scale = game.ui_scale.scale;
+            console_print(&mut game.console, world, format!("> {}", line), ui_scale);
+            console_print(&mut game.console, world, output, ui_scale);
+        }
+        _ => console_type(&mut game.console, key, game.shift_held),
+    }
+}
+
+fn game_write_bug_report(game: &HexWarGame) -> Result<std::path::PathBuf, String> {
+    let resources = &game.game_world.resources;
+    let report = BugReport {
+        save: savegame_capture(
+            &game.game_world,
+            game.player_faction,
+            game.ironman,
+            unix_seconds_now(),
+        ),
+        settings: vec![
+            ("player", faction_name(game.player_faction).to_string()),
+            ("spectating", game.spectating.to_string()),
+            ("network", game.net.is_some().to_string()),
+            ("tutorial", game.tutorial.is_some().to_string()),
+            (
+                "quality",
+                format!("{:?}", game.launch_options.quality.unwrap_or_default()),
+            ),
+            ("render_scale", game.render_scale.scale.to_string()),
+            ("ui_scale", game.ui_scale.scale.to_string()),
+            ("game_speed", resources.game_speed.to_string()),
+            ("attack_warnings", game.attack_warnings.to_string()),
+            (
+                "attack_warning_threshold",
+                game.attack_warning_threshold.to_string(),
+            ),
+            (
+                "arguments",
+                std::env::args().skip(1).collect::<Vec<_>>().join(" "),
+            ),
+        ],
+        recent_events: bug_report_recent_events(&game.event_log),
+        state_hashes: bug_report_recent_hashes(&resources.state_hashes),
+    };
+    write_bug_report(&report)
+}
+
+fn game_idle_unit_count(game: &HexWarGame) -> usize {
+    let faction = game.game_world.resources.current_faction;
+    game.game_world
+        .query_entities(UNIT)
+        .filter_map(|entity| game.game_world.get_unit(entity))
+        .filter(|unit| unit.faction == faction && !unit.has_moved)
+        .count()
+}
+
+fn game_request_end_turn(game: &mut HexWarGame, world: &mut World) {
+    let idle_units = game_idle_unit_count(game);
+    if idle_units == 0 || game.game_world.resources.actions_remaining == 0 {
+        game_player_end_turn(game);
+        return;
+    }
+    let message = if idle_units == 1 {
+        "1 unit has not moved yet. End turn anyway?".to_string()
+    } else {
+        format!("{} units have not moved yet. End turn anyway?", idle_units)
+    };
+    dialog_open(
+        &mut game.confirm_dialog,
+        world,
+        ConfirmPurpose::EndTurn,
+        &message,
+        "END TURN",
+        game.ui_scale.scale,
+    );
+}
+
+fn game_warn_risky_attack(game: &mut HexWarGame, world: &mut World, action: PlayerAction) -> bool {
+    let PlayerAction::Click(coord) = action else {
+        return false;
+    };
+    if !game.attack_warnings {
+        return false;
+    }
+    let Some(win_chance) = attack_target(&game.game_world, coord)
+        .and_then(|(attacker, defender)| combat_win_chance(&game.game_world, attacker, defender))
+    else {
+        return false;
+    };
+    if win_chance >= game.attack_warning_threshold {
+        return false;
+    }
+    let message = format!(
+        "Only {}% chance to win. Attack anyway?",
+        (win_chance * 100.0).round() as i32
+    );
+    dialog_open(
+        &mut game.confirm_dialog,
+        world,
+        ConfirmPurpose::Attack(coord),
+        &message,
+        "ATTACK",
+        game.ui_scale.scale,
+    );
+    true
+}
+
+fn game_resolve_dialog(game: &mut HexWarGame, world: &mut World, choice: DialogChoice) {
+    let Some(purpose) = game.confirm_dialog.purpose else {
+        return;
+    };
+    if choice == DialogChoice::None {
+        return;
+    }
+    dialog_close(&mut game.confirm_dialog, world);
+    if choice != DialogChoice::Confirm || game_turn_controller(game) != TurnController::Local {
+        return;
+    }
+    match purpose {
+        ConfirmPurpose::EndTurn => game_player_end_turn(game),
+        ConfirmPurpose::Attack(coord) => {
+            game_apply_local_action(game, world, PlayerAction::Click(coord))
+        }
+    }
+}
+
+fn game_player_end_turn(game: &mut HexWarGame) {
+    if game.net.is_some() && !can_end_turn(&game.game_world) {
+        return;
+    }
+    let faction = game.game_world.resources.current_faction;
+    let turn = game.game_world.resources.turn_number;
+    game_end_turn(game);
+    let hash = game
+        .game_world
+        .resources
+        .state_hashes
+        .last()
+        .map(|record| record.hash)
+        .unwrap_or_default();
+    if let Some(net) = game.net.as_mut() {
+        net_send(
+            net,
+            &NetMessage::EndTurn {
+                faction,
+                turn,
+                hash,
+            },
+        );
+    }
+}
+
+fn game_reset_camera(game: &HexWarGame, world: &mut World) {
+    reset_camera_to_map(
+        world,
+        game.game_world.resources.hex_width,
+        game.game_world.resources.hex_depth,
+        game.game_world.resources.hex_orientation,
+        game.game_world.resources.map_params.map_width,
+        game.game_world.resources.map_params.map_height,
+    );
+}
+
+fn game_next_map_seed(game: &mut HexWarGame) -> u32 {
+    game.launch_options
+        .seed
+        .take()
+        .unwrap_or_else(|| rand::rng().random())
+}
+
+fn game_apply_launch_options(game: &mut HexWarGame, world: &mut World) {
+    if let Some((map_width, map_height)) = game.launch_options.map_size {
+        game.game_world.resources.map_params.map_width = map_width;
+        game.game_world.resources.map_params.map_height = map_height;
+    }
+    if game.launch_options.random_capitals {
+        game.game_world.resources.map_params.random_capitals = true;
+        game.menu.random_capitals = true;
+    }
+    if game.launch_options.wrap_columns {
+        map_params_set_wrap(&mut game.game_world.resources.map_params, true);
+        game.menu.wrap_columns = true;
+    }
+    if let Some(difficulty) = game.launch_options.difficulty {
+        game.menu.selected_difficulty = difficulty;
+        game.menu.faction_difficulties = [difficulty; 4];
+    }
+    if let Some(faction) = game.launch_options.player_faction {
+        game.player_faction = faction;
+    }
+    if game.launch_options.spectate {
+        game_handle_menu_action(game, world, MenuAction::EnterMapSetup);
+        game_handle_menu_action(game, world, MenuAction::StartSpectating);
+    } else if game.launch_options.skip_menu {
+        game_handle_menu_action(game, world, MenuAction::EnterMapSetup);
+        game_handle_menu_action(game, world, MenuAction::StartGame);
+    }
+}
+
+fn game_regenerate_map(game: &mut HexWarGame, world: &mut World) {
+    game_cleanup_map(game, world);
+    let seed = game_next_map_seed(game);
+    game.map_entities = Some(generate_game_map(
+        &mut game.game_world,
+        world,
+        &game.tile_prefabs,
+        seed,
+    ));
+    game.camera_bounds = Some(calculate_camera_bounds(
+        game.game_world.resources.hex_width,
+        game.game_world.resources.hex_depth,
+        game.game_world.resources.hex_orientation,
+        game.game_world.resources.map_params.map_width,
+        game.game_world.resources.map_params.map_height,
+        game.game_world.resources.map_params.wrap_columns,
+    ));
+}
+
+fn game_reroll_terrain(game: &mut HexWarGame, world: &mut World) {
+    let seed = game_next_map_seed(game);
+    let resources = &mut game.game_world.resources;
+    let mut generated = generate_terrain(seed, &resources.map_params);
+    place_settlements(&mut generated, resources.settlement_seed);
+    resources.rng_seed = seed;
+    resources.map_balance = analyze_map_balance(&generated);
+    resources.generated_map = generated;
+    game_respawn_map(game, world);
+}
+
+fn game_reroll_settlements(game: &mut HexWarGame, world: &mut World) {
+    let seed = game_next_map_seed(game);
+    let resources = &mut game.game_world.resources;
+    place_settlements(&mut resources.generated_map, seed);
+    resources.settlement_seed = seed;
+    resources.map_balance = analyze_map_balance(&resources.generated_map);
+    game_respawn_map(game, world);
+}
+
+fn game_respawn_map(game: &mut HexWarGame, world: &mut World) {
+    game_cleanup_map(game, world);
+    game.map_entities = Some(spawn_game_map(
+        &mut game.game_world,
+        world,
+        &game.tile_prefabs,
+    ));
+}
+
+fn game_save_map(game: &mut HexWarGame, world: &mut World) {
+    let message = match save_map_file(
+        game.game_world.resources.rng_seed,
+        &game.game_world.resources.generated_map,
+    ) {
+        Ok(path) => format!("Saved {}", path.display()),
+        Err(error) => {
+            log::error!("Failed to save map: {}", error);
+            format!("Save failed: {}", error)
+        }
+    };
+    menu::show_menu_status(&mut game.menu, world, &message);
+}
+
+fn game_load_next_saved_map(game: &mut HexWarGame, world: &mut World) {
+    let saved_maps = list_saved_maps();
+    if saved_maps.is_empty() {
+        let message = format!("No saved maps in {}/", MAP_DIRECTORY);
+        menu::show_menu_status(&mut game.menu, world, &message);
+        return;
+    }
+
+    let path = &saved_maps[game.menu.saved_map_index % saved_maps.len()];
+    game.menu.saved_map_index = game.menu.saved_map_index.wrapping_add(1);
+
+    let loaded = match load_map_file(path) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            log::error!("Failed to load map {}: {}", path.display(), error);
+            let message = format!("Load failed: {}", error);
+            menu::show_menu_status(&mut game.menu, world, &message);
+            return;
+        }
+    };
+    if loaded.generated.orientation != game.game_world.resources.hex_orientation {
+        let message = format!(
+            "Load failed: map needs {}-top tiles",
+            hex_orientation_key(loaded.generated.orientation)
+        );
+        menu::show_menu_status(&mut game.menu, world, &message);
+        return;
+    }
+
+    game_cleanup_map(game, world);
+    game.game_world.resources.rng_seed = loaded.seed;
+    game.game_world.resources.map_params.map_width = loaded.generated.width;
+    game.game_world.resources.map_params.map_height = loaded.generated.height;
+    game.game_world.resources.map_params.wrap_columns = loaded.generated.wrap_columns;
+    game.menu.wrap_columns = loaded.generated.wrap_columns;
+    game.game_world.resources.map_balance = analyze_map_balance(&loaded.generated);
+    game.game_world.resources.generated_map = loaded.generated;
+    game.map_entities = Some(spawn_game_map(
+        &mut game.game_world,
+        world,
+        &game.tile_prefabs,
+    ));
+    game.camera_bounds = Some(calculate_camera_bounds(
+        game.game_world.resources.hex_width,
+        game.game_world.resources.hex_depth,
+        game.game_world.resources.hex_orientation,
+        game.game_world.resources.map_params.map_width,
+        game.game_world.resources.map_params.map_height,
+        game.game_world.resources.map_params.wrap_columns,
+    ));
+
+    menu::show_map_balance(
+        &mut game.menu,
+        world,
+        &game.game_world.resources.map_balance,
+    );
+    let message = format!("Loaded {}", path.display());
+    menu::show_menu_status(&mut game.menu, world, &message);
+}
+
+fn game_cleanup_map(game: &mut HexWarGame, world: &mut World) {
+    if let Some(mut entities) = game.map_entities.take() {
+        map_generation::despawn_map_entities(
+            world,
+            &mut entities,
+            &mut game.game_world.resources.labels,
+        );
+    }
+
+    let unit_entities: Vec<_> = game.game_world.query_entities(UNIT).collect();
+    for entity in unit_entities {
+        despawn_unit(&mut game.game_world, entity);
+    }
+    let resources = &mut game.game_world.resources;
+    for death in resources.unit_deaths.drain(..) {
+        resources.despawned_visuals.extend(death.engine_entity);
+        if let Some(text_entity) = death.text_entity {
+            label_release(&mut resources.labels, text_entity);
+        }
+    }
+    despawned_visuals_system(&mut game.game_world, world);
+
+    despawn_all_tiles(&mut game.game_world);
+    game.game_world.resources.ownership_history.clear();
+    clear_selection(&mut game.game_world);
+    game.game_world.resources.hovered_tile = None;
+    game.game_world.resources.previously_highlighted.clear();
+    game.game_world.resources.scorched_tiles.clear();
+    game.game_world.resources.previous_hovered_tile = None;
+    game.game_world.resources.previous_selected_unit = None;
+    game.game_world.resources.damage_flashes.clear();
+    game.game_world.resources.morale_changes.clear();
+    game.game_world.resources.previous_valid_move_count = 0;
+}
+
+fn game_start_menu_showcase(game: &mut HexWarGame, world: &mut World) {
+    let saved_map_params = std::mem::replace(
+        &mut game.game_world.resources.map_params,
+        MapGenParams {
+            orientation: game.game_world.resources.hex_orientation,
+            ..showcase_map_params()
+        },
+    );
+    game.game_world.resources.team_layout = TeamLayout::FreeForAll;
+    game.game_world.resources.human_factions = [false; 4];
+    game.game_world.resources.handicaps = Default::default();
+    world.resources.graphics.atmosphere = Atmosphere::Nebula;
+    let sun = spawn_sun(world);
+    showcase_dim_sun(world, sun);
+    game.sun_entity = Some(sun);
+    game.map_entities = Some(generate_game_map(
+        &mut game.game_world,
+        world,
+        &game.tile_prefabs,
+        rand::rng().random(),
+    ));
+    game_reset_camera(game, world);
+    begin_game(&mut game.game_world, [Difficulty::Hard; 4]);
+    game.menu_showcase = Some(menu_showcase_new(saved_map_params));
+}
+
+fn game_stop_menu_showcase(game: &mut HexWarGame, world: &mut World) {
+    let Some(showcase) = game.menu_showcase.take() else {
+        return;
+    };
+    world.resources.graphics.atmosphere = Atmosphere::None;
+    if let Some(sun) = game.sun_entity.take() {
+        world.queue_command(WorldCommand::DespawnRecursive { entity: sun });
+    }
+    game_cleanup_map(game, world);
+    game.game_world.resources.map_params = showcase.saved_map_params;
+}
+
+fn game_menu_showcase_system(game: &mut HexWarGame, world: &mut World) {
+    let Some(showcase) = game.menu_showcase.as_mut() else {
+        if game.map_entities.is_none() {
+            game_start_menu_showcase(game, world);
+        }
+        return;
+    };
+    let delta_time = world.resources.window.timing.delta_time;
+    let finished = menu_showcase_tick(showcase, &mut game.game_world, delta_time);
+    showcase_orbit_camera(world, delta_time);
+
+    game_animation_systems(game, world);
+    unit_visual_spawn_system(&mut game.game_world, world);
+    despawned_visuals_system(&mut game.game_world, world);
+    if let Some(map_entities) = &game.map_entities {
+        tile_highlight_system(
+            &mut game.game_world,
+            world,
+            &map_entities.instanced_tiles,
+            game.display_settings.battle_scars,
+        );
+    }
+    unit_text_system(&mut game.game_world, world);
+    unit_visual_update_system(&game.game_world, world, game_health_bar_entity(game));
+    nightshade::ecs::text::systems::sync_text_meshes_system(world);
+
+    if finished {
+        game_stop_menu_showcase(game, world);
+    }
+}
+
+fn game_end_turn(game: &mut HexWarGame) {
+    let transition = end_turn(&mut game.game_world, &mut game.game_events);
+    event_log_add_turn_start(
+        &mut game.event_log,
+        transition.turn_number,
+        transition.new_faction,
+    );
+    game.pending_spawns = transition.pending_spawns;
+    game_run_script_hook(
+        game,
+        ScriptHook::TurnStart {
+            faction: transition.new_faction,
+            turn: transition.turn_number,
+        },
+    );
+    game_run_script_hook(game, ScriptHook::VictoryCheck);
+}
+
+fn game_rallying_hymn_popups(game_world: &mut GameWorld, world: &mut World, faction: Faction) {
+    let positions: Vec<_> = game_world
+        .query_entities(UNIT)
+        .filter(|&entity| {
+            game_world
+                .get_unit(entity)
+                .is_some_and(|unit| unit.faction == faction)
+        })
+        .filter_map(|entity| {
+            game_world
+                .get_world_position(entity)
+                .map(|position| position.0)
+        })
+        .collect();
+    for position in positions {
+        spawn_morale_popup(game_world, world, position, RALLYING_HYMN_MORALE_BOOST);
+    }
+}
+
+fn game_morale_popups(game_world: &mut GameWorld, world: &mut World) {
+    let mut totals: Vec<(Faction, i32)> = Vec::new();
+    for (faction, amount) in game_world.resources.morale_changes.drain(..) {
+        match totals.iter_mut().find(|(existing, _)| *existing == faction) {
+            Some((_, total)) => *total += amount,
+            None => totals.push((faction, amount)),
+        }
+    }
+    for (faction, amount) in totals {
+        if amount == 0 {
+            continue;
+        }
+        let capital = faction_capital(&game_world.resources, faction);
+        let position = hex_to_world_position(
+            capital.column,
+            capital.row,
+            game_world.resources.hex_width,
+            game_world.resources.hex_depth,
+            game_world.resources.hex_orientation,
+        );
+        spawn_morale_popup(game_world, world, position, amount);
+    }
+}
+
+fn game_run_script_hook(game: &mut HexWarGame, hook: ScriptHook) {
+    let Some(script_host) = game.script_host.as_mut() else {
+        return;
+    };
+    let outcome = script_run_hook(script_host, &mut game.game_world, hook);
+    for message in outcome.messages {
+        notification_push(&mut game.notifications, message, [0.8, 0.9, 1.0, 1.0]);
+    }
+    if outcome.victory.is_some() {
+        game.script_winner = outcome.victory;
+    }
+}
+
+fn game_cleanup_game_world(game: &mut HexWarGame, world: &mut World) {
+    world.resources.graphics.atmosphere = Atmosphere::None;
+
+    if let Some(sun) = game.sun_entity.take() {
+        world.queue_command(WorldCommand::DespawnRecursive { entity: sun });
+    }
+
+    despawn_game_hud(&mut game.game_hud, world);
+    despawn_event_log_ui(world, &mut game.event_log);
+    despawn_tile_inspector(&mut game.tile_inspector, world);
+    game.tile_inspector = TileInspector::default();
+    despawn_notification_ui(&mut game.notifications, world);
+    game.notifications = Notifications::default();
+    turn_summary_dismiss(&mut game.turn_summary, world);
+    game.turn_summary = TurnSummaryTracker::default();
+    despawn_economy_report(&mut game.economy_report, world);
+    game.economy_report = EconomyReport::default();
+    despawn_chat(&mut game.chat, world);
+    console_close(&mut game.console, world);
+    despawn_coord_overlay(&mut game.coord_overlay, world);
+    dialog_close(&mut game.confirm_dialog, world);
+    despawn_stack_popup(&mut game.stack_popup, world);
+    game.stack_selection_requested = None;
+    game.script_winner = None;
+    game.battle_cinematic = BattleCinematic::default();
+    game.screen_shake = ScreenShake::default();
+    despawn_turn_cue(&mut game.turn_cue, world);
+    game.turn_cue = TurnCue::default();
+    despawn_capital_warning(&mut game.capital_warning, world);
+    game.capital_warning = CapitalWarning::default();
+    game.victory_cinematic = None;
+    game.camera_director = CameraDirector {
+        enabled: game.camera_director.enabled,
+        ..CameraDirector::default()
+    };
+    #[cfg(debug_assertions)]
+    {
+        despawn_debug_stepper_ui(&mut game.debug_stepper, world);
+        game.debug_stepper = DebugStepper::default();
+        despawn_ai_overlay(&mut game.ai_overlay, world);
+        game.ai_overlay = AiOverlay::default();
+    }
+    strategic_view_disable(
+        &mut game.strategic_view,
+        &game.game_world,
+        world,
+        game.map_entities.as_ref(),
+    );
+    despawn_strategic_view(&mut game.strategic_view, world);
+    game_end_tutorial(game, world);
+    game_cleanup_map(game, world);
+}
+
+fn game_end_tutorial(game: &mut HexWarGame, world: &mut World) {
+    if let Some(mut tutorial) = game.tutorial.take() {
+        despawn_tutorial(&mut tutorial, world);
+        game.game_world.resources.map_params = tutorial.previous_map_params;
+        game.player_faction = tutorial.previous_player_faction;
+    }
+}
+
+fn game_start_tutorial(game: &mut HexWarGame, world: &mut World) {
+    game.menu.state = MenuState::Playing;
+    menu::despawn_menu_elements(&mut game.menu, world);
+    game_cleanup_map(game, world);
+
+    world.resources.graphics.atmosphere = Atmosphere::Nebula;
+    game.sun_entity = Some(spawn_sun(world));
+
+    let previous_map_params = game.game_world.resources.map_params.clone();
+    let previous_player_faction = game.player_faction;
+    let orientation = game.game_world.resources.hex_orientation;
+    game.game_world.resources.map_params = MapGenParams {
+        orientation,
+        ..tutorial_map_params()
+    };
+    game.game_world.resources.rng_seed = TUTORIAL_SEED;
+    game.game_world.resources.generated_map = GeneratedMap {
+        orientation,
+        ..tutorial_map()
+    };
+    game.game_world.resources.handicaps = Default::default();
+    game.game_world.resources.map_balance =
+        analyze_map_balance(&game.game_world.resources.generated_map);
+    game.map_entities = Some(spawn_game_map(
+        &mut game.game_world,
+        world,
+        &game.tile_prefabs,
+    ));
+    tutorial_populate(&mut game.game_world);
+    game.camera_bounds = Some(calculate_camera_bounds(
+        game.game_world.resources.hex_width,
+        game.game_world.resources.hex_depth,
+        game.game_world.resources.hex_orientation,
+        game.game_world.resources.map_params.map_width,
+        game.game_world.resources.map_params.map_height,
+        game.game_world.resources.map_params.wrap_columns,
+    ));
+    game_reset_camera(game, world);
+
+    game.player_faction = TUTORIAL_PLAYER;
+    game.spectating = false;
+    game.game_world.resources.team_layout = TeamLayout::FreeForAll;
+    game.game_world.resources.turn_timer = turn_timer_new(0);
+    game.game_world.resources.human_factions = game_human_factions(game);
+    let first_faction = begin_game(&mut game.game_world, [Difficulty::Easy; 4]);
+    tutorial_begin(&mut game.game_world);
+
+    game.event_log = event_log_new();
+    spawn_event_log_ui(world, &mut game.event_log, game.ui_scale.scale);
+    event_log_add_turn_start(&mut game.event_log, 1, first_faction);
+
+    game.game_hud = spawn_game_hud(world, game.ui_scale.scale);
+    spawn_notification_ui(&mut game.notifications, world, game.ui_scale.scale);
+    game.tutorial = Some(tutorial_new(
+        world,
+        previous_map_params,
+        previous_player_faction,
+        game.ui_scale.scale,
+    ));
+}
+
+fn game_apply_ui_scale(game: &mut HexWarGame, world: &mut World) {
+    game.menu.ui_scale = game.ui_scale;
+    let ui_scale = game.ui_scale.scale;
+
+    if let Some(fps_entity) = game.fps_entity.take() {
+        world.despawn_entities(&[fps_entity]);
+        game.fps_entity = Some(spawn_fps_display(world, ui_scale));
+    }
+
+    if !game.event_log.line_entities.is_empty() {
+        despawn_event_log_ui(world, &mut game.event_log);
+        spawn_event_log_ui(world, &mut game.event_log, ui_scale);
+    }
+
+    match game.menu.state {
+        MenuState::MainMenu => menu::setup_main_menu(&mut game.menu, world),
+        MenuState::MapSetup => {
+            menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+        MenuState::Playing => {
+            despawn_game_hud(&mut game.game_hud, world);
+            game.game_hud = spawn_game_hud(world, ui_scale);
+            despawn_tile_inspector(&mut game.tile_inspector, world);
+            despawn_economy_report(&mut game.economy_report, world);
+            chat_respawn_ui(
+                &mut game.chat,
+                world,
+                ui_scale,
+                event_log_height(&game.event_log),
+            );
+            console_respawn_ui(&mut game.console, world, ui_scale);
+            dialog_close(&mut game.confirm_dialog, world);
+            despawn_stack_popup(&mut game.stack_popup, world);
+        }
+        MenuState::Lobby => game_show_lobby(game, world),
+        MenuState::Statistics => menu::setup_report_menu(
+            &mut game.menu,
+            world,
+            "STATISTICS",
+            &profile_summary_lines(&game.profile),
+        ),
+        MenuState::HallOfFame => menu::setup_report_menu(
+            &mut game.menu,
+            world,
+            "HALL OF FAME",
+            &hall_of_fame_lines(&game.hall_of_fame),
+        ),
+        MenuState::Settings => {
+            menu::setup_settings_menu(&mut game.menu, world, &game.display_settings)
+        }
+        MenuState::Paused => game_show_pause_menu(game, world),
+        MenuState::MapReview => menu::setup_map_review_menu(&mut game.menu, world),
+        MenuState::SaveBrowser => {
+            save_browser_open(&mut game.save_browser, world, game.ui_scale.scale)
+        }
+        MenuState::GameOver => {
+            if game.victory_cinematic.is_none()
+                && let Some(winner) = game.menu.game_over_winner
+            {
+                game_show_game_over_menu(game, world, winner, None);
+            }
+        }
+    }
+
+    if !game.notifications.texts.is_empty() {
+        despawn_notification_ui(&mut game.notifications, world);
+        spawn_notification_ui(&mut game.notifications, world, ui_scale);
+    }
+}
+
+fn game_can_concede(game: &HexWarGame) -> bool {
+    game.net.is_none() && game.tutorial.is_none() && game_human_faction(game).is_some()
+}
+
+fn game_show_pause_menu(game: &mut HexWarGame, world: &mut World) {
+    let can_concede = game_can_concede(game);
+    menu::setup_pause_menu(
+        &mut game.menu,
+        world,
+        game.ironman,
+        game.attack_warnings,
+        can_concede,
+    );
+}
+
+fn game_concede(game: &mut HexWarGame, world: &mut World) {
+    if !game_can_concede(game) {
+        return;
+    }
+    let Some(faction) = game_human_faction(game) else {
+        return;
+    };
+    let Some(rival) = dominant_rival(&game.game_world, faction) else {
+        return;
+    };
+    surrender_faction(&mut game.game_world, faction, rival, &mut game.game_events);
+    game.script_winner = Some(rival);
+    game.menu.state = MenuState::Playing;
+    menu::despawn_menu_elements(&mut game.menu, world);
+    game.game_hud = spawn_game_hud(world, game.ui_scale.scale);
+}
+
+fn game_faction_standings(game: &HexWarGame, winner: Faction) -> Vec<FactionStanding> {
+    let resources = &game.game_world.resources;
+    ALL_FACTIONS
+        .into_iter()
+        .map(|faction| {
+            let allied_to_winner = factions_allied(resources.team_layout, winner, faction);
+            let eliminated = resources.faction_eliminated[faction_index(faction)];
+            let units: Vec<i32> = game
+                .game_world
+                .query_entities(UNIT)
+                .filter_map(|entity| game.game_world.get_unit(entity))
+                .filter(|unit| unit.faction == faction)
+                .map(|unit| unit.soldiers)
+                .collect();
+            FactionStanding {
+                faction,
+                victorious: !eliminated && (faction == winner || allied_to_winner),
+                allied_to_winner,
+                eliminated,
+                territory: game
+                    .game_world
+                    .query_entities(TILE)
+                    .filter_map(|entity| game.game_world.get_tile(entity))
+                    .filter(|tile| tile.faction == Some(faction))
+                    .count(),
+                armies: units.len(),
+                soldiers: units.iter().sum(),
+                casualties: resources.casualty_stats[faction_index(faction)],
+            }
+        })
+        .collect()
+}
+
+fn game_show_game_over_menu(
+    game: &mut HexWarGame,
+    world: &mut World,
+    winner: Faction,
+    high_score_rank: Option<usize>,
+) {
+    let is_player_winner = game_is_winner(game, winner);
+    let surviving_allies: Vec<Faction> =
+        faction_allies(game.game_world.resources.team_layout, winner)
+            .into_iter()
+            .filter(|ally| !game.game_world.resources.faction_eliminated[faction_index(*ally)])
+            .collect();
+    let standings = game_faction_standings(game, winner);
+    menu::setup_game_over_menu(
+        &mut game.menu,
+        world,
+        winner,
+        &surviving_allies,
+        is_player_winner,
+        &standings,
+    );
+    if let Some(rank) = high_score_rank {
+        menu::show_menu_status(
+            &mut game.menu,
+            world,
+            &format!("New hall of fame entry: #{}", rank + 1),
+        );
+    }
+}
+
+fn game_update_display_settings(
+    game: &mut HexWarGame,
+    world: &mut World,
+    update: impl FnOnce(&mut DisplaySettings),
+) {
+    let previous = game.display_settings;
+    update(&mut game.display_settings);
+    let current = game.display_settings;
+    if (current.fullscreen, current.resolution, current.vsync)
+        != (previous.fullscreen, previous.resolution, previous.vsync)
+    {
+        apply_display_settings(&current, world);
+    }
+    if let Err(error) = save_display_settings(
+        std::path::Path::new(DISPLAY_SETTINGS_FILE_PATH),
+        &game.display_settings,
+    ) {
+        log::error!("Failed to save display settings: {}", error);
+    }
+    if game.menu.state == MenuState::Settings {
+        menu::setup_settings_menu(&mut game.menu, world, &game.display_settings);
+    }
+}
+
+fn game_handle_menu_action(game: &mut HexWarGame, world: &mut World, action: MenuAction) {
+    if matches!(
+        action,
+        MenuAction::EnterMapSetup
+            | MenuAction::StartTutorial
+            | MenuAction::HostGame
+            | MenuAction::JoinGame
+            | MenuAction::ContinueIronman
+            | MenuAction::LoadAutosave(_)
+    ) {
+        game_stop_menu_showcase(game, world);
+    }
+    match action {
+        MenuAction::None => {}
+        MenuAction::EnterMapSetup => {
+            game_end_tutorial(game, world);
+            game.menu.state = MenuState::MapSetup;
+            menu::despawn_menu_elements(&mut game.menu, world);
+
+            world.resources.graphics.atmosphere = Atmosphere::Nebula;
+            game.sun_entity = Some(spawn_sun(world));
+            let seed = game_next_map_seed(game);
+            game.map_entities = Some(generate_game_map(
+                &mut game.game_world,
+                world,
+                &game.tile_prefabs,
+                seed,
+            ));
+            game.camera_bounds = Some(calculate_camera_bounds(
+                game.game_world.resources.hex_width,
+                game.game_world.resources.hex_depth,
+                game.game_world.resources.hex_orientation,
+                game.game_world.resources.map_params.map_width,
+                game.game_world.resources.map_params.map_height,
+                game.game_world.resources.map_params.wrap_columns,
+            ));
+            game_reset_camera(game, world);
+
+            menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+        MenuAction::RegenerateMap => {
+            game_regenerate_map(game, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+        }
+        MenuAction::RerollTerrain => {
+            game_reroll_terrain(game, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+        }
+        MenuAction::RerollSettlements => {
+            game_reroll_settlements(game, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+        }
+        MenuAction::SaveMap => {
+            game_save_map(game, world);
+        }
+        MenuAction::AdjustHandicap(faction, kind, delta) => {
+            handicap_adjust(
+                &mut game.game_world.resources.handicaps[faction_index(faction)],
+                kind,
+                delta,
+            );
+            game_respawn_map(game, world);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+        MenuAction::LoadMap => {
+            game_load_next_saved_map(game, world);
+        }
+        MenuAction::StartGame => {
+            game.spectating = false;
+            game_begin_match(
+                game,
+                world,
+                game.menu.faction_difficulties,
+                game.menu.team_layout,
+                game.menu.turn_seconds,
+            );
+        }
+        MenuAction::StartSpectating => {
+            game.spectating = true;
+            game_begin_match(
+                game,
+                world,
+                game.menu.faction_difficulties,
+                game.menu.team_layout,
+                game.menu.turn_seconds,
+            );
+        }
+        MenuAction::StartTutorial => {
+            game_start_tutorial(game, world);
+        }
+        MenuAction::ShowSettings => {
+            game.menu.state = MenuState::Settings;
+            menu::setup_settings_menu(&mut game.menu, world, &game.display_settings);
+        }
+        MenuAction::ToggleFullscreen => {
+            game_update_display_settings(game, world, |settings| {
+                settings.fullscreen = !settings.fullscreen
+            });
+        }
+        MenuAction::CycleResolution => {
+            game_update_display_settings(game, world, display_settings_cycle_resolution);
+        }
+        MenuAction::ToggleVsync => {
+            game_update_display_settings(game, world, |settings| settings.vsync = !settings.vsync);
+        }
+        MenuAction::ToggleBattleCinematics => {
+            game_update_display_settings(game, world, |settings| {
+                settings.battle_cinematics = !settings.battle_cinematics
+            });
+        }
+        MenuAction::ToggleScreenShake => {
+            game_update_display_settings(game, world, |settings| {
+                settings.screen_shake = !settings.screen_shake
+            });
+        }
+        MenuAction::ToggleBattleScars => {
+            game_update_display_settings(game, world, |settings| {
+                settings.battle_scars = !settings.battle_scars
+            });
+        }
+        MenuAction::ToggleAttackWarnings => {
+            game.attack_warnings = !game.attack_warnings;
+            game_show_pause_menu(game, world);
+        }
+        MenuAction::ResumeGame => {
+            game.menu.state = MenuState::Playing;
+            menu::despawn_menu_elements(&mut game.menu, world);
+            game.game_hud = spawn_game_hud(world, game.ui_scale.scale);
+        }
+        MenuAction::Concede => game_concede(game, world),
+        MenuAction::ReviewMap => {
+            game.menu.state = MenuState::MapReview;
+            menu::setup_map_review_menu(&mut game.menu, world);
+        }
+        MenuAction::EndMapReview => {
+            game.menu.state = MenuState::GameOver;
+            if let Some(winner) = game.menu.game_over_winner {
+                game_show_game_over_menu(game, world, winner, None);
+            }
+        }
+        MenuAction::ReturnToMainMenu => {
+            if game.ironman && can_end_turn(&game.game_world) {
+                game_write_ironman_save(game);
+            }
+            game.ironman = false;
+            despawn_save_browser(&mut game.save_browser, world);
+            game_leave_network(game);
+            game_cleanup_game_world(game, world);
+            game.menu.state = MenuState::MainMenu;
+            menu::setup_main_menu(&mut game.menu, world);
+        }
+        MenuAction::QuitGame => {
+            world.resources.window.should_exit = true;
+        }
+        MenuAction::SetDifficulty(difficulty) => {
+            game.menu.selected_difficulty = difficulty;
+            game.menu.faction_difficulties = [difficulty; 4];
+            menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+        MenuAction::CycleFactionDifficulty(faction) => {
+            let difficulty = &mut game.menu.faction_difficulties[faction_index(faction)];
+            *difficulty = difficulty_next(*difficulty);
+            menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+        MenuAction::HostGame => {
+            let port = game.launch_options.net_port.unwrap_or(DEFAULT_NET_PORT);
+            game_enter_lobby(game, world, net_host(port));
+        }
+        MenuAction::JoinGame => {
+            let address = game
+                .launch_options
+                .join_address
+                .clone()
+                .unwrap_or_else(|| format!("127.0.0.1:{}", DEFAULT_NET_PORT));
+            game_enter_lobby(game, world, net_join(&address));
+        }
+        MenuAction::ShowStatistics => {
+            game.menu.state = MenuState::Statistics;
+            menu::setup_report_menu(
+                &mut game.menu,
+                world,
+                "STATISTICS",
+                &profile_summary_lines(&game.profile),
+            );
+        }
+        MenuAction::ShowHallOfFame => {
+            game.menu.state = MenuState::HallOfFame;
+            menu::setup_report_menu(
+                &mut game.menu,
+                world,
+                "HALL OF FAME",
+                &hall_of_fame_lines(&game.hall_of_fame),
+            );
+        }
+        MenuAction::SpectateLobby => {
+            if let Some(net) = game.net.as_mut() {
+                net_release_faction(net);
+            }
+            game_show_lobby(game, world);
+        }
+        MenuAction::ClaimFaction(faction) => {
+            if let Some(net) = game.net.as_mut() {
+                net_claim_faction(net, faction);
+            }
+            game_show_lobby(game, world);
+        }
+        MenuAction::StartNetworkGame => {
+            let setup = NetGameSetup {
+                seed: game_next_map_seed(game),
+                map_width: game.game_world.resources.map_params.map_width,
+                map_height: game.game_world.resources.map_params.map_height,
+                random_capitals: game.game_world.resources.map_params.random_capitals,
+                wrap_columns: game.game_world.resources.map_params.wrap_columns,
+                orientation: game.game_world.resources.map_params.orientation,
+                difficulties: game.menu.faction_difficulties,
+                rules: game.game_world.resources.rules,
+                team_layout: game.menu.team_layout,
+                turn_seconds: game.menu.turn_seconds,
+            };
+            if let Some(net) = game.net.as_mut() {
+                net_send(net, &NetMessage::Start(setup));
+            }
+            game_start_network_game(game, world, setup);
+        }
+        MenuAction::CycleTurnTimer => {
+            game.menu.turn_seconds = turn_timer_next_option(game.menu.turn_seconds);
+            menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+        MenuAction::ToggleRandomCapitals => {
+            game.menu.random_capitals = !game.menu.random_capitals;
+            game.game_world.resources.map_params.random_capitals = game.menu.random_capitals;
+            game_regenerate_map(game, world);
             menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
         }
-        MenuAction::RegenerateMap => {
+        MenuAction::ToggleWrapColumns => {
+            game.menu.wrap_columns = !game.menu.wrap_columns;
+            map_params_set_wrap(
+                &mut game.game_world.resources.map_params,
+                game.menu.wrap_columns,
+            );
             game_regenerate_map(game, world);
+            game_reset_camera(game, world);
+            menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
         }
-        MenuAction::StartGame => {
-            game.menu.state = MenuState::Playing;
+        MenuAction::ToggleIronman => {
+            game.menu.ironman = !game.menu.ironman;
+            menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+        MenuAction::ContinueIronman => {
+            match load_game_file(std::path::Path::new(IRONMAN_SAVE_PATH)) {
+                Ok(save) => game_resume_save(game, world, save),
+                Err(error) => {
+                    log::error!("Failed to load iron-man autosave: {}", error);
+                    let message = format!("Continue failed: {}", error);
+                    menu::show_menu_status(&mut game.menu, world, &message);
+                }
+            }
+        }
+        MenuAction::ShowAutosaves => {
             menu::despawn_menu_elements(&mut game.menu, world);
+            game.menu.state = MenuState::SaveBrowser;
+            save_browser_open(&mut game.save_browser, world, game.ui_scale.scale);
+        }
+        MenuAction::LoadAutosave(slot) => {
+            despawn_save_browser(&mut game.save_browser, world);
+            match load_game_file(&autosave_path(slot)) {
+                Ok(save) => game_resume_save(game, world, save),
+                Err(error) => {
+                    log::error!("Failed to load autosave: {}", error);
+                    game.menu.state = MenuState::MainMenu;
+                    menu::setup_main_menu(&mut game.menu, world);
+                    let message = format!("Load failed: {}", error);
+                    menu::show_menu_status(&mut game.menu, world, &message);
+                }
+            }
+        }
+        MenuAction::CycleTeams => {
+            game.menu.team_layout = team_layout_next(game.menu.team_layout);
+            menu::setup_map_setup_menu(&mut game.menu, world);
+            menu::show_map_balance(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.map_balance,
+            );
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+        MenuAction::AdjustRule(rule, delta) => {
+            rules_adjust(&mut game.game_world.resources.rules, rule, delta);
+            if matches!(rule, RuleKind::InitialSoldiers | RuleKind::MaxSoldiers) {
+                let rules = game.game_world.resources.rules;
+                let handicaps = game.game_world.resources.handicaps;
+                for entity in game.game_world.query_entities(UNIT).collect::<Vec<_>>() {
+                    if let Some(unit) = game.game_world.get_unit_mut(entity) {
+                        unit.soldiers = handicap_starting_soldiers(
+                            &rules,
+                            handicaps[faction_index(unit.faction)],
+                        );
+                    }
+                }
+            }
+            menu::show_game_rules(&mut game.menu, world, &game.game_world.resources.rules);
+            menu::show_handicaps(
+                &mut game.menu,
+                world,
+                &game.game_world.resources.rules,
+                &game.game_world.resources.handicaps,
+            );
+        }
+    }
+}
 
-            game.game_world.resources.current_faction = Faction::Redosia;
-            game.game_world.resources.actions_remaining = ACTIONS_PER_TURN;
-            game.game_world.resources.turn_number = 1;
-            game.game_world.resources.faction_eliminated = [false; 4];
-            game.game_world.resources.game_speed = 1.0;
-            game.game_world.resources.difficulty = game.menu.selected_difficulty;
-
-            build_turn_order(&mut game.game_world);
+fn game_animation_systems(game: &mut HexWarGame, world: &mut World) {
+    let delta_time = game.game_world.resources.sim_clock.scaled_delta_time;
+    update_particle_emitters(world, delta_time);
+    update_firework_shells(&mut game.firework_shells, world, delta_time);
+    floating_popup_system(&mut game.game_world, world, delta_time);
+    damage_flash_system(&mut game.game_world, world, delta_time);
+    death_effect_system(&mut game.game_world, world, &game.quality, delta_time);
+}
 
-            game.event_log = event_log_new();
-            spawn_event_log_ui(world, &mut game.event_log);
-            event_log_add_turn_start(&mut game.event_log, 1, Faction::Redosia);
+fn game_run_stage(game: &mut HexWarGame, world: &mut World, stage: SimulationStage) -> GameResult {
+    let hex_width = game.game_world.resources.hex_width;
+    let hex_depth = game.game_world.resources.hex_depth;
+    let orientation = game.game_world.resources.hex_orientation;
+    let controller = game_turn_controller(game);
 
-            game.game_hud = spawn_game_hud(world);
+    match stage {
+        SimulationStage::PendingSpawns => {
+            for pending in game.pending_spawns.drain(..) {
+                spawn_pending(&mut game.game_world, pending);
+            }
         }
-        MenuAction::ResumeGame => {
-            game.menu.state = MenuState::Playing;
-            menu::despawn_menu_elements(&mut game.menu, world);
-            game.game_hud = spawn_game_hud(world);
+        SimulationStage::Movement => {
+            let delta_time = game.game_world.resources.sim_clock.scaled_delta_time;
+            movement_system(&mut game.game_world, delta_time);
         }
-        MenuAction::ReturnToMainMenu => {
-            game_cleanup_game_world(game, world);
-            game.menu.state = MenuState::MainMenu;
-            menu::setup_main_menu(&mut game.menu, world);
+        SimulationStage::AiTurn => {
+            if controller == TurnController::Ai && !game.game_world.resources.sim_clock.paused {
+                let reference_faction = game_ai_reference_faction(game);
+                let ai_done = ai_turn_system(
+                    &mut game.game_world,
+                    reference_faction,
+                    &mut game.game_events,
+                );
+                if ai_done && can_end_turn(&game.game_world) {
+                    game_end_turn(game);
+                }
+            }
         }
-        MenuAction::QuitGame => {
-            world.resources.window.should_exit = true;
+        SimulationStage::PlayerInput => {
+            let left_clicked = world
+                .resources
+                .input
+                .mouse
+                .state
+                .contains(MouseState::LEFT_JUST_PRESSED);
+            let click_allowed = !left_clicked
+                || !stack_popup_hovered(&game.stack_popup)
+                    && game.tutorial.as_ref().is_none_or(|tutorial| {
+                        tutorial_allows_click(tutorial, game.game_world.resources.hovered_tile)
+                    });
+            let summary_open = turn_summary_is_open(&game.turn_summary);
+            if summary_open && left_clicked {
+                turn_summary_dismiss(&mut game.turn_summary, world);
+            }
+            if controller == TurnController::Remote {
+                game_apply_remote_action(game, world);
+            } else if controller == TurnController::Local
+                && !game.ctrl_held
+                && !game.alt_held
+                && !summary_open
+                && !dialog_is_open(&game.confirm_dialog)
+                && !battle_cinematic_active(&game.battle_cinematic)
+            {
+                let ready = game.net.is_none() || can_end_turn(&game.game_world);
+                let mut actions = Vec::new();
+                if ready {
+                    if click_allowed
+                        && let Some(action) =
+                            input_system(&game.game_world, world, game.build_requested)
+                    {
+                        actions.push(action);
+                    }
+                    if game.speech_requested {
+                        actions.push(PlayerAction::Speech);
+                    }
+                    if let Some((coord, index)) = game.stack_selection_requested {
+                        actions.push(PlayerAction::SelectStacked(coord, index));
+                    }
+                    if game.relocation_requested
+                        && let Some(coord) = game.game_world.resources.hovered_tile
+                    {
+                        actions.push(PlayerAction::RelocateCapital(coord));
+                    }
+                    if game.pillage_requested {
+                        actions.push(PlayerAction::Pillage);
+                    }
+                    if game.scout_requested {
+                        actions.push(PlayerAction::RecruitScout);
+                    }
+                }
+                game.build_requested = None;
+                game.speech_requested = false;
+                game.stack_selection_requested = None;
+                game.relocation_requested = false;
+                game.pillage_requested = false;
+                game.scout_requested = false;
+                for action in actions {
+                    if game_warn_risky_attack(game, world, action) {
+                        continue;
+                    }
+                    game_apply_local_action(game, world, action);
+                }
+            }
         }
-        MenuAction::SetDifficulty(difficulty) => {
-            game.menu.selected_difficulty = difficulty;
-            menu::setup_map_setup_menu(&mut game.menu, world);
+        SimulationStage::TileOwnership => {
+            let captures = tile_ownership_system(&mut game.game_world);
+            ruin_system(&mut game.game_world, &mut game.game_events);
+            supply_delivery_system(&mut game.game_world, &mut game.game_events);
+            for capture in captures {
+                if game_human_faction(game) != Some(capture.faction) {
+                    camera_director_focus(&mut game.camera_director, capture.coord);
+                }
+                let position = hex_to_world_position(
+                    capture.coord.column,
+                    capture.coord.row,
+                    hex_width,
+                    hex_depth,
+                    orientation,
+                );
+                spawn_capture_popup(&mut game.game_world, world, position, capture.tile_type);
+                if capture.tile_type == TileType::Capital && game.display_settings.screen_shake {
+                    screen_shake_trigger(&mut game.screen_shake, CAPITAL_CAPTURE_SHAKE);
+                }
+                spawn_capture_firework(
+                    &mut game.firework_shells,
+                    world,
+                    position,
+                    capture.tile_type,
+                    capture.faction,
+                    &game.quality,
+                );
+                game_run_script_hook(
+                    game,
+                    ScriptHook::Capture {
+                        faction: capture.faction,
+                        coord: capture.coord,
+                        tile_type: capture.tile_type,
+                    },
+                );
+            }
+        }
+        SimulationStage::Victory => {
+            if let Some(winner) = game.script_winner.take() {
+                return GameResult::Victory(winner);
+            }
+            return victory_system(&mut game.game_world, &mut game.game_events);
         }
     }
+
+    GameResult::Ongoing
 }
 
 fn game_range_lines_entity(game: &HexWarGame) -> Option<Entity> {
     game.map_entities.as_ref().map(|e| e.range_lines_entity)
 }
 
+fn game_movement_trail_entity(game: &HexWarGame) -> Option<Entity> {
+    game.map_entities.as_ref().map(|e| e.movement_trail_entity)
+}
+
+fn game_port_route_entity(game: &HexWarGame) -> Option<Entity> {
+    game.map_entities.as_ref().map(|e| e.port_route_entity)
+}
+
 fn game_hover_outline_entity(game: &HexWarGame) -> Option<Entity> {
     game.map_entities.as_ref().map(|e| e.hover_outline_entity)
 }
 
+fn game_selection_outline_entity(game: &HexWarGame) -> Option<Entity> {
+    game.map_entities
+        .as_ref()
+        .map(|e| e.selection_outline_entity)
+}
+
+fn game_health_bar_entity(game: &HexWarGame) -> Option<Entity> {
+    game.map_entities.as_ref().map(|e| e.health_bar_entity)
+}
+
+fn game_general_marker_entity(game: &HexWarGame) -> Option<Entity> {
+    game.map_entities.as_ref().map(|e| e.general_marker_entity)
+}
+
+fn game_capital_marker_entity(game: &HexWarGame) -> Option<Entity> {
+    game.map_entities.as_ref().map(|e| e.capital_marker_entity)
+}
+
 impl State for HexWarGame {
     fn title(&self) -> &str {
         "Hex War"
@@ -301,10 +2509,16 @@ impl State for HexWarGame {
         world.resources.graphics.show_grid = false;
         world.resources.graphics.atmosphere = Atmosphere::None;
 
-        if let Some(loaded) = load_tile_prefabs(world) {
+        let asset_manifest = load_asset_manifest();
+        if let Some(loaded) = load_tile_prefabs(world, &asset_manifest) {
             self.tile_prefabs = loaded.tile_prefabs;
             self.game_world.resources.hex_width = loaded.hex_width;
             self.game_world.resources.hex_depth = loaded.hex_depth;
+            self.game_world.resources.hex_orientation = loaded.hex_orientation;
+            self.game_world.resources.map_params.orientation = loaded.hex_orientation;
+        }
+        if let Some(mesh_name) = load_unit_mesh(world, &asset_manifest) {
+            self.game_world.resources.unit_mesh_name = mesh_name;
         }
 
         let camera_entity = spawn_pan_orbit_camera(
@@ -323,22 +2537,40 @@ impl State for HexWarGame {
             pan_orbit.pitch_lower_limit = 0.1;
         }
 
-        self.fps_entity = Some(spawn_fps_display(world));
+        apply_display_settings(&self.display_settings, world);
+        self.fps_entity = Some(spawn_fps_display(world, self.ui_scale.scale));
         menu::setup_main_menu(&mut self.menu, world);
+        game_apply_launch_options(self, world);
     }
 
     fn run_systems(&mut self, world: &mut World) {
         let (screen_width, screen_height) = get_screen_size(world);
+        if self.screen_size != (screen_width, screen_height) {
+            let resized = self.screen_size != (0.0, 0.0);
+            self.screen_size = (screen_width, screen_height);
+            if resized {
+                game_apply_ui_scale(self, world);
+            }
+        }
+        game_network_system(self, world);
+
+        sim_clock_tick(
+            &mut self.game_world.resources,
+            world.resources.window.timing.delta_time
+                * battle_cinematic_time_scale(&self.battle_cinematic),
+            self.menu.state == MenuState::Paused,
+        );
 
         match self.menu.state {
             MenuState::MainMenu => {
+                game_menu_showcase_system(self, world);
                 let action =
                     menu::main_menu_system(&mut self.menu, world, screen_width, screen_height);
                 game_handle_menu_action(self, world, action);
                 return;
             }
             MenuState::MapSetup => {
-                pan_orbit_camera_system(world);
+                zoom_to_cursor_camera_system(world);
                 if let Some(bounds) = &self.camera_bounds {
                     clamp_camera_to_bounds(world, bounds);
                 }
@@ -346,14 +2578,69 @@ impl State for HexWarGame {
                 game_handle_menu_action(self, world, action);
                 return;
             }
+            MenuState::Lobby => {
+                let action = menu::lobby_system(&mut self.menu, world, screen_width, screen_height);
+                game_handle_menu_action(self, world, action);
+                return;
+            }
+            MenuState::Statistics | MenuState::HallOfFame => {
+                game_menu_showcase_system(self, world);
+                let action =
+                    menu::report_menu_system(&mut self.menu, world, screen_width, screen_height);
+                game_handle_menu_action(self, world, action);
+                return;
+            }
+            MenuState::Settings => {
+                game_menu_showcase_system(self, world);
+                let action =
+                    menu::settings_menu_system(&mut self.menu, world, screen_width, screen_height);
+                game_handle_menu_action(self, world, action);
+                return;
+            }
+            MenuState::SaveBrowser => {
+                game_menu_showcase_system(self, world);
+                let action = save_browser_system(
+                    &mut self.save_browser,
+                    world,
+                    screen_width,
+                    screen_height,
+                    self.ui_scale.scale,
+                );
+                game_handle_menu_action(self, world, action);
+                return;
+            }
             MenuState::Paused => {
+                game_animation_systems(self, world);
                 let action =
                     menu::pause_menu_system(&mut self.menu, world, screen_width, screen_height);
                 game_handle_menu_action(self, world, action);
                 return;
             }
             MenuState::GameOver => {
-                pan_orbit_camera_system(world);
+                game_animation_systems(self, world);
+                if let Some(cinematic) = self.victory_cinematic.as_mut() {
+                    let skipped = world
+                        .resources
+                        .input
+                        .mouse
+                        .state
+                        .contains(MouseState::LEFT_JUST_PRESSED);
+                    let finished = victory_cinematic_system(
+                        cinematic,
+                        &mut self.firework_shells,
+                        world,
+                        &self.quality,
+                        world.resources.window.timing.delta_time,
+                    );
+                    if finished || skipped {
+                        let winner = cinematic.winner;
+                        let high_score_rank = cinematic.high_score_rank;
+                        self.victory_cinematic = None;
+                        game_show_game_over_menu(self, world, winner, high_score_rank);
+                    }
+                    return;
+                }
+                zoom_to_cursor_camera_system(world);
                 if let Some(bounds) = &self.camera_bounds {
                     clamp_camera_to_bounds(world, bounds);
                 }
@@ -361,114 +2648,412 @@ impl State for HexWarGame {
                 game_handle_menu_action(self, world, action);
                 return;
             }
+            MenuState::MapReview => {
+                game_animation_systems(self, world);
+                zoom_to_cursor_camera_system(world);
+                if let Some(bounds) = &self.camera_bounds {
+                    clamp_camera_to_bounds(world, bounds);
+                }
+                event_log_scroll_system(&mut self.event_log, world);
+                update_event_log_ui(world, &self.event_log);
+                let action =
+                    menu::map_review_system(&mut self.menu, world, screen_width, screen_height);
+                game_handle_menu_action(self, world, action);
+                return;
+            }
             MenuState::Playing => {}
         }
 
-        pan_orbit_camera_system(world);
+        zoom_to_cursor_camera_system(world);
         if let Some(bounds) = &self.camera_bounds {
             clamp_camera_to_bounds(world, bounds);
         }
 
-        let hex_width = self.game_world.resources.hex_width;
-        let hex_depth = self.game_world.resources.hex_depth;
-        for pending in self.pending_spawns.drain(..) {
-            spawn_unit(
-                &mut self.game_world,
-                world,
-                pending.coord,
-                hex_width,
-                hex_depth,
-                pending.faction,
-                pending.soldiers,
-            );
+        if dialog_is_open(&self.confirm_dialog)
+            && game_turn_controller(self) != TurnController::Local
+        {
+            dialog_close(&mut self.confirm_dialog, world);
         }
-
-        let delta_time = world.resources.window.timing.delta_time;
-        update_particle_emitters(world, delta_time);
-        update_firework_shells(&mut self.firework_shells, world, delta_time);
-        movement_system(&mut self.game_world, world, delta_time);
-
-        let is_ai_turn = self.game_world.resources.current_faction != self.player_faction;
-        if is_ai_turn {
-            let ai_done = ai_turn_system(
-                &mut self.game_world,
-                world,
-                self.player_faction,
-                &mut self.game_events,
-            );
-            if ai_done && can_end_turn(&self.game_world) {
-                game_end_turn(self);
-            }
+        let dialog_choice =
+            dialog_system(&mut self.confirm_dialog, world, screen_width, screen_height);
+        game_resolve_dialog(self, world, dialog_choice);
+        let stack_faction = (game_turn_controller(self) == TurnController::Local
+            && !self.spectating
+            && !dialog_is_open(&self.confirm_dialog))
+        .then_some(self.game_world.resources.current_faction);
+        if let Some(selection) = stack_popup_system(
+            &mut self.stack_popup,
+            &self.game_world,
+            world,
+            stack_faction,
+            screen_width,
+            screen_height,
+            self.ui_scale.scale,
+        ) {
+            self.stack_selection_requested = Some(selection);
         }
 
+        let frame_delta = world.resources.window.timing.delta_time;
+        let delta_time = self.game_world.resources.sim_clock.delta_time;
+        render_scale_system(&mut self.render_scale, world);
+        game_animation_systems(self, world);
+
         if let Some(fps_entity) = self.fps_entity {
             fps_display_system(world, fps_entity, self.fps_visible);
         }
 
         let range_lines_entity = game_range_lines_entity(self);
         let hover_outline_entity = game_hover_outline_entity(self);
+        let selection_outline_entity = game_selection_outline_entity(self);
+        let health_bar_entity = game_health_bar_entity(self);
+        let general_marker_entity = game_general_marker_entity(self);
+        let capital_marker_entity = game_capital_marker_entity(self);
+        let movement_trail_entity = game_movement_trail_entity(self);
+        let port_route_entity = game_port_route_entity(self);
 
-        if !is_ai_turn {
+        if game_turn_controller(self) == TurnController::Local
+            || self.spectating
+            || self.ctrl_held
+            || self.alt_held
+        {
             hover_system(&mut self.game_world, world);
-            input_system(&mut self.game_world, world, &mut self.game_events);
-            speech_system(
-                &mut self.game_world,
-                self.speech_requested,
-                &mut self.game_events,
-            );
-            self.speech_requested = false;
         }
 
-        let captures = tile_ownership_system(&mut self.game_world);
-        for capture in captures {
-            let position = hex_to_world_position(
-                capture.coord.column,
-                capture.coord.row,
-                hex_width,
-                hex_depth,
-            );
-            spawn_capture_popup(&mut self.game_world, world, position, capture.tile_type);
-            spawn_capture_firework(
-                &mut self.firework_shells,
-                world,
-                position,
-                capture.tile_type,
-                capture.faction,
-            );
+        let left_clicked = world
+            .resources
+            .input
+            .mouse
+            .state
+            .contains(MouseState::LEFT_JUST_PRESSED);
+        if self.ctrl_held
+            && left_clicked
+            && let Some(coord) = self.game_world.resources.hovered_tile
+        {
+            tile_inspector_open(&mut self.tile_inspector, &self.game_world, coord);
+        }
+        if self.alt_held
+            && left_clicked
+            && let Some(coord) = self.game_world.resources.hovered_tile
+        {
+            game_send_ping(self, coord);
         }
 
-        selection_visual_system(&self.game_world, world);
+        let cinematic_skipped = left_clicked && battle_cinematic_active(&self.battle_cinematic);
+
+        #[cfg(debug_assertions)]
+        let stages = debug_stepper_stages_to_run(&mut self.debug_stepper);
+        #[cfg(not(debug_assertions))]
+        let stages = SIMULATION_STAGES.to_vec();
+
+        let mut game_result = GameResult::Ongoing;
+        for stage in stages {
+            #[cfg(debug_assertions)]
+            let before = self
+                .debug_stepper
+                .paused
+                .then(|| capture_simulation_snapshot(&self.game_world));
+
+            if let GameResult::Victory(winner) = game_run_stage(self, world, stage) {
+                game_result = GameResult::Victory(winner);
+            }
+
+            #[cfg(debug_assertions)]
+            if let Some(before) = before {
+                let after = capture_simulation_snapshot(&self.game_world);
+                debug_stepper_record_changes(&mut self.debug_stepper, stage, &before, &after);
+            }
+        }
+
+        if let Some(tutorial) = self.tutorial.as_mut() {
+            tutorial_system(tutorial, &self.game_world, &self.game_events, world);
+        }
+
+        #[cfg(debug_assertions)]
+        debug_stepper_ui_system(
+            &mut self.debug_stepper,
+            &self.game_world,
+            world,
+            self.ui_scale.scale,
+        );
+        #[cfg(debug_assertions)]
+        ai_overlay_system(
+            &mut self.ai_overlay,
+            &self.game_world,
+            world,
+            self.ui_scale.scale,
+        );
+        tile_inspector_system(
+            &mut self.tile_inspector,
+            &self.game_world,
+            world,
+            self.ui_scale.scale,
+        );
+        economy_report_system(&mut self.economy_report, world, self.ui_scale.scale);
+
+        unit_visual_spawn_system(&mut self.game_world, world);
+        despawned_visuals_system(&mut self.game_world, world);
         valid_moves_system(&mut self.game_world);
         range_lines_system(&mut self.game_world, world, range_lines_entity);
+        movement_trail_system(&self.game_world, world, movement_trail_entity);
+        port_route_system(&mut self.game_world, world, port_route_entity);
 
         if let Some(ref map_entities) = self.map_entities {
             tile_highlight_system(
                 &mut self.game_world,
                 world,
-                &map_entities.instanced_tile_groups,
+                &map_entities.instanced_tiles,
+                self.display_settings.battle_scars,
             );
+            if !self.strategic_view.enabled {
+                tile_chunk_culling_system(&map_entities.instanced_tiles, world);
+                label_culling_system(&self.game_world.resources.labels, world);
+            }
         }
         hover_outline_system(&self.game_world, world, hover_outline_entity);
-        unit_text_system(&self.game_world, world);
-        unit_visual_update_system(&self.game_world, world);
-        floating_popup_system(&mut self.game_world, world, delta_time);
+        unit_text_system(&mut self.game_world, world);
+        unit_visual_update_system(&self.game_world, world, health_bar_entity);
+        strategic_view_system(&mut self.strategic_view, &self.game_world, world);
+        selection_outline_system(&self.game_world, world, selection_outline_entity);
+        general_marker_system(&self.game_world, world, general_marker_entity);
+        capital_marker_system(&self.game_world, world, capital_marker_entity);
+        if let Some(map_entities) = &self.map_entities {
+            city_tier_label_system(
+                &mut self.game_world,
+                world,
+                &map_entities.city_label_entities,
+            );
+        }
+        if let Some(map_entities) = &mut self.map_entities {
+            ruin_label_system(
+                &mut self.game_world,
+                world,
+                &mut map_entities.ruin_label_entities,
+            );
+            improvement_visual_system(
+                &self.game_world,
+                world,
+                &mut map_entities.improvement_visuals,
+            );
+        }
+        ping_system(&mut self.chat, &self.game_world, world, delta_time);
+        chat_input_system(&self.chat, world);
+        console_input_system(&mut self.console, world);
+        coord_overlay_system(&mut self.coord_overlay, &self.game_world, world);
+        lighting_system(
+            &mut self.lighting,
+            &self.game_world,
+            world,
+            self.sun_entity,
+            delta_time,
+        );
         nightshade::ecs::text::systems::sync_text_meshes_system(world);
-        update_game_hud(&self.game_hud, &self.game_world, world, self.player_faction);
+        update_game_hud(
+            &mut self.game_hud,
+            &self.game_world,
+            world,
+            game_human_faction(self),
+        );
+        let local_faction = (game_turn_controller(self) == TurnController::Local
+            && !self.spectating)
+            .then_some(self.game_world.resources.current_faction);
+        turn_cue_system(
+            &mut self.turn_cue,
+            world,
+            local_faction,
+            self.ui_scale.scale,
+            frame_delta,
+        );
+        if let Some(human_faction) = game_human_faction(self) {
+            capital_warning_check(&mut self.capital_warning, &self.game_world, human_faction);
+        }
+        capital_warning_system(
+            &mut self.capital_warning,
+            world,
+            self.ui_scale.scale,
+            frame_delta,
+        );
 
-        let game_result = victory_system(&mut self.game_world, world, &mut self.game_events);
+        if let Some(human_faction) = game_human_faction(self) {
+            turn_summary_record_events(&mut self.turn_summary, &self.game_events, human_faction);
+            economy_report_record_events(
+                &mut self.economy_report,
+                &self.game_events,
+                human_faction,
+                self.game_world.resources.turn_number,
+            );
+            turn_summary_system(
+                &mut self.turn_summary,
+                &self.game_world,
+                world,
+                human_faction,
+                self.ui_scale.scale,
+            );
+        }
 
+        let mut script_hooks = Vec::new();
         for event in self.game_events.combat_events.drain(..) {
+            script_hooks.push(ScriptHook::CombatResolved {
+                attacker: event.attacker_faction,
+                defender: event.defender_faction,
+                attacker_survived: event.attacker_survived,
+                defender_survived: event.defender_survived,
+            });
+            if let Some(faction) = game_human_faction(self) {
+                profile_tally_combat(&mut self.match_tally, &event, faction);
+            }
+            spawn_casualty_popups(&mut self.game_world, world, &event);
+            if let Some(unit_entity) = event.wounded_unit {
+                start_damage_flash(&mut self.game_world, unit_entity);
+            }
+            if let Some(faction) = event.general_killed {
+                notification_push(
+                    &mut self.notifications,
+                    format!("{}'s general has fallen!", faction_name(faction)),
+                    [1.0, 0.8, 0.2, 1.0],
+                );
+            }
+            if self.display_settings.battle_cinematics {
+                battle_cinematic_start(
+                    &mut self.battle_cinematic,
+                    world,
+                    event.attacker_location,
+                    event.location,
+                    self.game_world.resources.hex_width,
+                    self.game_world.resources.hex_depth,
+                    self.game_world.resources.hex_orientation,
+                    &self.quality,
+                );
+            } else if game_human_faction(self) != Some(event.attacker_faction) {
+                camera_director_focus(&mut self.camera_director, event.location);
+            }
             event_log_add_combat(
                 &mut self.event_log,
                 event.attacker_faction,
                 event.defender_faction,
                 event.attacker_survived,
                 event.defender_survived,
+                &event.modifiers,
+            );
+        }
+        for hook in script_hooks {
+            game_run_script_hook(self, hook);
+        }
+        for event in self.game_events.world_events.drain(..) {
+            let message = world_event_message(&event);
+            event_log_add_world_event(&mut self.event_log, event.faction, &message);
+            let color = match event.kind {
+                WorldEventKind::BumperHarvest => [0.5, 1.0, 0.4, 1.0],
+                WorldEventKind::Plague | WorldEventKind::Eruption => [1.0, 0.6, 0.3, 1.0],
+            };
+            notification_push(
+                &mut self.notifications,
+                format!("{}: {}", faction_name(event.faction), message),
+                color,
+            );
+        }
+        for event in self.game_events.surrender_events.drain(..) {
+            event_log_add_surrender(&mut self.event_log, event.faction, event.conqueror);
+            notification_push(
+                &mut self.notifications,
+                format!(
+                    "{} surrendered to {}",
+                    faction_name(event.faction),
+                    faction_name(event.conqueror)
+                ),
+                faction_color(event.conqueror),
+            );
+        }
+        for event in self.game_events.capital_relocated_events.drain(..) {
+            camera_director_focus(&mut self.camera_director, event.to);
+            event_log_add_capital_relocated(&mut self.event_log, event.faction);
+            notification_push(
+                &mut self.notifications,
+                format!(
+                    "{} moved its capital from ({}, {}) to ({}, {})",
+                    faction_name(event.faction),
+                    event.from.column,
+                    event.from.row,
+                    event.to.column,
+                    event.to.row
+                ),
+                faction_color(event.faction),
+            );
+        }
+        for event in self.game_events.improvement_events.drain(..) {
+            event_log_add_improvement(&mut self.event_log, event.faction, event.improvement);
+            if game_human_faction(self) == Some(event.faction) {
+                notification_push(
+                    &mut self.notifications,
+                    format!(
+                        "Finished building a {}",
+                        improvement_name(event.improvement)
+                    ),
+                    [0.8, 0.7, 0.5, 1.0],
+                );
+            }
+        }
+        for event in self.game_events.pillage_events.drain(..) {
+            event_log_add_pillage(
+                &mut self.event_log,
+                event.faction,
+                event.victim,
+                event.tile_type,
+            );
+            if game_human_faction(self) == Some(event.victim) {
+                camera_director_focus(&mut self.camera_director, event.location);
+                notification_push(
+                    &mut self.notifications,
+                    format!(
+                        "{} pillaged our {} at ({}, {})",
+                        faction_name(event.faction),
+                        if event.tile_type == TileType::Port {
+                            "port"
+                        } else {
+                            "city"
+                        },
+                        event.location.column,
+                        event.location.row
+                    ),
+                    faction_color(event.faction),
+                );
+            }
+        }
+        for event in self.game_events.ruin_events.drain(..) {
+            event_log_add_ruin(
+                &mut self.event_log,
+                event.faction,
+                event.bonus,
+                event.amount,
+            );
+            if game_human_faction(self) == Some(event.faction) {
+                notification_push(
+                    &mut self.notifications,
+                    format!(
+                        "Ruins at ({}, {}) yielded {} {}",
+                        event.location.column,
+                        event.location.row,
+                        event.amount,
+                        ruin_bonus_name(event.bonus)
+                    ),
+                    [0.8, 0.7, 0.55, 1.0],
+                );
+            }
+        }
+        for event in self.game_events.attrition_events.drain(..) {
+            event_log_add_attrition(
+                &mut self.event_log,
+                event.faction,
+                event.soldiers,
+                event.cause,
             );
         }
         for event in self.game_events.speech_events.drain(..) {
-            event_log_add_speech(&mut self.event_log, event.faction);
+            event_log_add_speech(&mut self.event_log, event.faction, event.ability);
+            if event.ability == LeaderAbility::RallyingHymn {
+                game_rallying_hymn_popups(&mut self.game_world, world, event.faction);
+            }
         }
+        game_morale_popups(&mut self.game_world, world);
         for event in self.game_events.reinforcement_events.drain(..) {
             event_log_add_reinforcement(
                 &mut self.event_log,
@@ -478,17 +3063,75 @@ impl State for HexWarGame {
             );
         }
         for event in self.game_events.faction_eliminated_events.drain(..) {
+            if self.display_settings.screen_shake {
+                screen_shake_trigger(&mut self.screen_shake, ELIMINATION_SHAKE);
+            }
+            camera_director_focus(
+                &mut self.camera_director,
+                faction_capital(&self.game_world.resources, event.faction),
+            );
             event_log_add_faction_eliminated(&mut self.event_log, event.faction);
+            if event.defected_units > 0 {
+                event_log_add_defection(
+                    &mut self.event_log,
+                    event.conqueror,
+                    event.faction,
+                    event.defected_units,
+                );
+            }
+            notification_faction_eliminated(
+                &mut self.notifications,
+                event.faction,
+                game_human_faction(self),
+            );
+        }
+        if let Some(human_faction) = game_human_faction(self) {
+            notification_watch_system(&mut self.notifications, &self.game_world, human_faction);
+        }
+        notification_system(&mut self.notifications, world, frame_delta);
+        if cinematic_skipped {
+            battle_cinematic_skip(&mut self.battle_cinematic, world);
+        }
+        if battle_cinematic_active(&self.battle_cinematic) {
+            battle_cinematic_system(&mut self.battle_cinematic, world, frame_delta);
+        } else {
+            camera_director_system(
+                &mut self.camera_director,
+                world,
+                self.game_world.resources.hex_width,
+                self.game_world.resources.hex_depth,
+                self.game_world.resources.hex_orientation,
+                frame_delta,
+            );
         }
+        screen_shake_system(&mut self.screen_shake, world, frame_delta);
 
         event_log_scroll_system(&mut self.event_log, world);
         update_event_log_ui(world, &self.event_log);
+        game_turn_timer_system(self, delta_time);
+        game_ironman_autosave_system(self);
+        game_autosave_system(self);
 
         match game_result {
             GameResult::Victory(winner) => {
-                let is_player_winner = winner == self.player_faction;
+                game_record_profile(self, winner);
+                game_end_ironman(self);
+                let high_score_rank = game_record_high_score(self, winner);
                 despawn_game_hud(&mut self.game_hud, world);
-                menu::setup_game_over_menu(&mut self.menu, world, winner, is_player_winner);
+                despawn_notification_ui(&mut self.notifications, world);
+                turn_summary_dismiss(&mut self.turn_summary, world);
+                console_close(&mut self.console, world);
+                battle_cinematic_skip(&mut self.battle_cinematic, world);
+                let capital = faction_capital(&self.game_world.resources, winner);
+                let center = hex_to_world_position(
+                    capital.column,
+                    capital.row,
+                    self.game_world.resources.hex_width,
+                    self.game_world.resources.hex_depth,
+                    self.game_world.resources.hex_orientation,
+                );
+                self.victory_cinematic =
+                    Some(victory_cinematic_new(winner, center, high_score_rank));
                 self.menu.state = MenuState::GameOver;
             }
             GameResult::Ongoing => {}
@@ -498,47 +3141,317 @@ impl State for HexWarGame {
     }
 
     fn on_keyboard_input(&mut self, world: &mut World, key: KeyCode, state: KeyState) {
+        if matches!(key, KeyCode::ControlLeft | KeyCode::ControlRight) {
+            self.ctrl_held = state == KeyState::Pressed;
+        }
+        if matches!(key, KeyCode::AltLeft | KeyCode::AltRight) {
+            self.alt_held = state == KeyState::Pressed;
+        }
+        if matches!(key, KeyCode::ShiftLeft | KeyCode::ShiftRight) {
+            self.shift_held = state == KeyState::Pressed;
+        }
+
         if state != KeyState::Pressed {
             return;
         }
 
+        if self.console.open && self.menu.state == MenuState::Playing {
+            game_console_key(self, world, key);
+            return;
+        }
+
+        if self.chat.open && self.menu.state == MenuState::Playing {
+            game_chat_key(self, world, key);
+            return;
+        }
+
+        if dialog_is_open(&self.confirm_dialog) && self.menu.state == MenuState::Playing {
+            let choice = match key {
+                KeyCode::Enter | KeyCode::NumpadEnter => DialogChoice::Confirm,
+                KeyCode::Escape => DialogChoice::Cancel,
+                _ => DialogChoice::None,
+            };
+            if choice != DialogChoice::None {
+                game_resolve_dialog(self, world, choice);
+                return;
+            }
+        }
+
         match key {
             KeyCode::KeyP => match self.menu.state {
                 MenuState::Playing => {
                     self.menu.state = MenuState::Paused;
+                    dialog_close(&mut self.confirm_dialog, world);
                     despawn_game_hud(&mut self.game_hud, world);
-                    menu::setup_pause_menu(&mut self.menu, world);
+                    game_show_pause_menu(self, world);
                 }
                 MenuState::Paused => {
                     self.menu.state = MenuState::Playing;
                     menu::despawn_menu_elements(&mut self.menu, world);
-                    self.game_hud = spawn_game_hud(world);
+                    self.game_hud = spawn_game_hud(world, self.ui_scale.scale);
                 }
-                MenuState::MainMenu | MenuState::MapSetup | MenuState::GameOver => {}
+                MenuState::MainMenu
+                | MenuState::MapSetup
+                | MenuState::Lobby
+                | MenuState::Statistics
+                | MenuState::HallOfFame
+                | MenuState::Settings
+                | MenuState::GameOver
+                | MenuState::MapReview
+                | MenuState::SaveBrowser => {}
             },
-            KeyCode::Space if self.menu.state == MenuState::Playing => {
-                let is_player_turn =
-                    self.game_world.resources.current_faction == self.player_faction;
+            KeyCode::Enter | KeyCode::NumpadEnter if self.alt_held => {
+                game_update_display_settings(self, world, |settings| {
+                    settings.fullscreen = !settings.fullscreen
+                });
+            }
+            KeyCode::Enter | KeyCode::NumpadEnter
+                if self.menu.state == MenuState::Playing
+                    && turn_summary_is_open(&self.turn_summary) =>
+            {
+                turn_summary_dismiss(&mut self.turn_summary, world);
+            }
+            KeyCode::Enter | KeyCode::NumpadEnter if self.menu.state == MenuState::Playing => {
+                chat_open(
+                    &mut self.chat,
+                    world,
+                    self.ui_scale.scale,
+                    event_log_height(&self.event_log),
+                );
+            }
+            KeyCode::Backquote
+                if self.menu.state == MenuState::Playing && game_console_enabled(self) =>
+            {
+                console_open(&mut self.console, world, self.ui_scale.scale);
+            }
+            KeyCode::Space
+                if self.menu.state == MenuState::Playing
+                    && !turn_summary_is_open(&self.turn_summary) =>
+            {
+                let is_player_turn = game_turn_controller(self) == TurnController::Local;
+                let allowed = self.tutorial.as_ref().is_none_or(tutorial_allows_end_turn);
+                if is_player_turn && allowed {
+                    game_request_end_turn(self, world);
+                }
+            }
+            KeyCode::KeyS
+                if self.menu.state == MenuState::Playing
+                    && !turn_summary_is_open(&self.turn_summary) =>
+            {
+                let is_player_turn = game_turn_controller(self) == TurnController::Local;
+                let allowed = self.tutorial.as_ref().is_none_or(tutorial_allows_speech);
+                if is_player_turn && allowed {
+                    self.speech_requested = true;
+                }
+            }
+            KeyCode::KeyR
+                if self.menu.state == MenuState::Playing
+                    && !turn_summary_is_open(&self.turn_summary) =>
+            {
+                let is_player_turn = game_turn_controller(self) == TurnController::Local;
                 if is_player_turn {
-                    game_end_turn(self);
+                    self.relocation_requested = true;
                 }
             }
-            KeyCode::KeyS if self.menu.state == MenuState::Playing => {
-                let is_player_turn =
-                    self.game_world.resources.current_faction == self.player_faction;
+            KeyCode::KeyX
+                if self.menu.state == MenuState::Playing
+                    && !turn_summary_is_open(&self.turn_summary) =>
+            {
+                let is_player_turn = game_turn_controller(self) == TurnController::Local;
                 if is_player_turn {
-                    self.speech_requested = true;
+                    self.pillage_requested = true;
                 }
             }
+            KeyCode::KeyU
+                if self.menu.state == MenuState::Playing
+                    && !turn_summary_is_open(&self.turn_summary) =>
+            {
+                let is_player_turn = game_turn_controller(self) == TurnController::Local;
+                if is_player_turn {
+                    self.scout_requested = true;
+                }
+            }
+            KeyCode::KeyB | KeyCode::KeyT
+                if self.menu.state == MenuState::Playing
+                    && !turn_summary_is_open(&self.turn_summary) =>
+            {
+                self.build_requested = Some(if key == KeyCode::KeyB {
+                    Improvement::Fort
+                } else {
+                    Improvement::Watchtower
+                });
+            }
+            KeyCode::Escape if self.tile_inspector.coord.is_some() => {
+                tile_inspector_close(&mut self.tile_inspector);
+            }
+            KeyCode::Escape
+                if self.menu.state == MenuState::Playing
+                    && self.tutorial.as_ref().is_some_and(tutorial_is_complete) =>
+            {
+                game_handle_menu_action(self, world, MenuAction::ReturnToMainMenu);
+            }
             KeyCode::Home | KeyCode::KeyC if self.menu.state == MenuState::Playing => {
                 game_reset_camera(self, world);
             }
+            KeyCode::KeyJ if self.menu.state == MenuState::Playing => {
+                if let Some(threat) = self.capital_warning.threat.as_ref() {
+                    focus_camera_on(
+                        world,
+                        threat.capital,
+                        self.game_world.resources.hex_width,
+                        self.game_world.resources.hex_depth,
+                        self.game_world.resources.hex_orientation,
+                    );
+                }
+            }
+            KeyCode::KeyE if self.menu.state == MenuState::Playing => {
+                economy_report_toggle(&mut self.economy_report);
+            }
+            KeyCode::KeyM if self.menu.state == MenuState::Playing => {
+                let message = if self.strategic_view.enabled {
+                    strategic_view_disable(
+                        &mut self.strategic_view,
+                        &self.game_world,
+                        world,
+                        self.map_entities.as_ref(),
+                    );
+                    "Strategic view off"
+                } else if let Some(map_entities) = self.map_entities.as_ref() {
+                    strategic_view_enable(
+                        &mut self.strategic_view,
+                        &self.game_world,
+                        world,
+                        map_entities,
+                    );
+                    "Strategic view on"
+                } else {
+                    return;
+                };
+                notification_push(
+                    &mut self.notifications,
+                    message.to_string(),
+                    [0.8, 0.9, 1.0, 1.0],
+                );
+            }
+            KeyCode::KeyH if self.menu.state == MenuState::Playing => {
+                let resources = &mut self.game_world.resources;
+                let (mode, message) = match resources.health_bar_mode {
+                    HealthBarMode::Always => (
+                        HealthBarMode::HoverOrSelection,
+                        "Health bars: hover and selection only",
+                    ),
+                    HealthBarMode::HoverOrSelection => {
+                        (HealthBarMode::Hidden, "Health bars: hidden")
+                    }
+                    HealthBarMode::Hidden => (HealthBarMode::Always, "Health bars: always shown"),
+                };
+                resources.health_bar_mode = mode;
+                notification_push(
+                    &mut self.notifications,
+                    message.to_string(),
+                    [0.8, 0.9, 1.0, 1.0],
+                );
+            }
+            KeyCode::KeyN if self.menu.state == MenuState::Playing => {
+                let message = if lighting_toggle(&mut self.lighting) {
+                    "Day/night cycle enabled"
+                } else {
+                    "Day/night cycle disabled"
+                };
+                notification_push(
+                    &mut self.notifications,
+                    message.to_string(),
+                    [0.8, 0.9, 1.0, 1.0],
+                );
+            }
+            KeyCode::KeyG if self.menu.state == MenuState::Playing => {
+                let message = if coord_overlay_toggle(&mut self.coord_overlay) {
+                    "Tile coordinates shown"
+                } else {
+                    "Tile coordinates hidden"
+                };
+                notification_push(
+                    &mut self.notifications,
+                    message.to_string(),
+                    [0.8, 0.9, 1.0, 1.0],
+                );
+            }
+            KeyCode::KeyV if self.menu.state == MenuState::Playing => {
+                let enabled = camera_director_toggle(&mut self.camera_director);
+                let message = if enabled {
+                    "Camera follows the action"
+                } else {
+                    "Camera follow disabled"
+                };
+                notification_push(
+                    &mut self.notifications,
+                    message.to_string(),
+                    [0.8, 0.9, 1.0, 1.0],
+                );
+            }
             KeyCode::KeyF => {
                 self.fps_visible = !self.fps_visible;
                 if let Some(fps_entity) = self.fps_entity {
                     toggle_fps_display(world, fps_entity, self.fps_visible);
                 }
             }
+            KeyCode::F8 => {
+                let scale = render_scale_cycle(&mut self.render_scale);
+                notification_push(
+                    &mut self.notifications,
+                    format!("Render scale: {}%", (scale * 100.0).round() as u32),
+                    [0.8, 0.9, 1.0, 1.0],
+                );
+            }
+            KeyCode::F9 => {
+                let scale = ui_scale_cycle(&mut self.ui_scale);
+                game_apply_ui_scale(self, world);
+                notification_push(
+                    &mut self.notifications,
+                    format!("UI scale: {}%", (scale * 100.0).round() as u32),
+                    [0.8, 0.9, 1.0, 1.0],
+                );
+            }
+            KeyCode::F10 if self.menu.state == MenuState::Playing => {
+                match game_write_bug_report(self) {
+                    Ok(path) => notification_push(
+                        &mut self.notifications,
+                        format!("Bug report saved to {}", path.display()),
+                        [0.8, 0.9, 1.0, 1.0],
+                    ),
+                    Err(error) => {
+                        log::error!("{}", error);
+                        notification_push(&mut self.notifications, error, [1.0, 0.35, 0.3, 1.0]);
+                    }
+                }
+            }
+            KeyCode::F12 => match request_screenshot(world) {
+                Ok(path) => notification_push(
+                    &mut self.notifications,
+                    format!("Screenshot saved to {}", path.display()),
+                    [0.8, 0.9, 1.0, 1.0],
+                ),
+                Err(error) => {
+                    log::error!("{}", error);
+                    notification_push(&mut self.notifications, error, [1.0, 0.35, 0.3, 1.0]);
+                }
+            },
+            #[cfg(debug_assertions)]
+            KeyCode::F3 if self.menu.state == MenuState::Playing => {
+                ai_overlay_toggle(&mut self.ai_overlay);
+            }
+            #[cfg(debug_assertions)]
+            KeyCode::F5 if self.menu.state == MenuState::Playing => {
+                debug_stepper_toggle(&mut self.debug_stepper);
+            }
+            #[cfg(debug_assertions)]
+            KeyCode::F6 if self.menu.state == MenuState::Playing => {
+                debug_stepper_request_frame(&mut self.debug_stepper);
+            }
+            #[cfg(debug_assertions)]
+            KeyCode::F7 if self.menu.state == MenuState::Playing => {
+                debug_stepper_request_system(&mut self.debug_stepper);
+            }
             KeyCode::BracketRight | KeyCode::Equal if self.menu.state == MenuState::Playing => {
                 let current = self.game_world.resources.game_speed;
                 self.game_world.resources.game_speed = (current * 2.0).min(8.0);
@@ -564,9 +3477,11 @@ impl State for HexWarGame {
             .slot("color", resources.scene_color)
             .slot("depth", resources.depth);
 
-        let (width, height) = (1920, 1080);
-        let bloom_width = width / 2;
-        let bloom_height = height / 2;
+        let (width, height) = render_scale_target_size(&self.render_scale);
+        self.render_scale.configured_size = (width, height);
+        let bloom_divisor = self.quality.bloom_resolution_divisor.max(1);
+        let bloom_width = width / bloom_divisor;
+        let bloom_height = height / bloom_divisor;
 
         let bloom_texture = graph
             .add_color_texture("bloom")
@@ -575,13 +3490,14 @@ impl State for HexWarGame {
             .clear_color(wgpu::Color::BLACK)
             .transient();
 
-        let bloom_pass = passes::BloomPass::new(device, width, height);
+        let bloom_pass = passes::BloomPass::new(device, bloom_width * 2, bloom_height * 2);
         graph
             .pass(Box::new(bloom_pass))
             .read("hdr", resources.scene_color)
             .write("bloom", bloom_texture);
 
-        let postprocess_pass = passes::PostProcessPass::new(device, surface_format, 0.005);
+        let postprocess_pass =
+            passes::PostProcessPass::new(device, surface_format, self.quality.bloom_intensity);
         graph
             .pass(Box::new(postprocess_pass))
             .read("hdr", resources.scene_color)
@@ -1,19 +1,105 @@
 use crate::constants::{MAP_HEIGHT, MAP_WIDTH};
-use crate::ecs::{Faction, TileType};
-use crate::hex::{HexCoord, hex_distance, hex_from_cube, hex_tiles_in_range, hex_to_cube};
+use crate::ecs::{
+    ALL_FACTIONS, Faction, RuinBonus, TileType, faction_index, faction_name, tile_elevation,
+};
+use crate::hex::{
+    HexCoord, HexOrientation, hex_distance, hex_from_cube, hex_to_cube, hex_unwrap_near, hex_wrap,
+    hex_wrapped_distance, hex_wrapped_neighbors, hex_wrapped_tiles_in_range,
+};
+use crate::rng::{
+    GameRng, game_rng_new, game_rng_next_u32, game_rng_percent, game_rng_range, game_rng_shuffle,
+};
+use nightshade::prelude::*;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 
-pub const CAPITAL_POSITIONS: [(i32, i32, Faction); 4] = [
-    (2, 2, Faction::Redosia),
-    (28, 2, Faction::Violetnam),
-    (28, 18, Faction::Bluegaria),
-    (2, 18, Faction::Greenland),
-];
+const CAPITAL_INSET: i32 = 2;
+const RANDOM_CAPITAL_ATTEMPTS: u32 = 64;
+const SETTLEMENT_SEED_SALT: u32 = 0x5e77_1e5d;
+const TERRAIN_RETRY_SALT: u32 = 0x7e22_a170;
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct MapGenParams {
     pub map_width: i32,
     pub map_height: i32,
+    pub random_capitals: bool,
+    pub wrap_columns: bool,
+    pub orientation: HexOrientation,
+    pub config: MapGenConfig,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MapGenConfig {
+    pub capital_land_radius: i32,
+    pub grow_passes: u32,
+    pub grow_chance: u32,
+    pub carve_min_capital_distance: i32,
+    pub carve_chance: u32,
+    pub forest_chance: u32,
+    pub hill_clusters: u32,
+    pub hill_cluster_size: u32,
+    pub swamp_chance: u32,
+    pub min_land_fraction: f32,
+    pub max_attempts: u32,
+}
+
+impl Default for MapGenConfig {
+    fn default() -> Self {
+        Self {
+            capital_land_radius: 2,
+            grow_passes: 8,
+            grow_chance: 25,
+            carve_min_capital_distance: 5,
+            carve_chance: 15,
+            forest_chance: 20,
+            hill_clusters: 4,
+            hill_cluster_size: 4,
+            swamp_chance: 15,
+            min_land_fraction: 0.25,
+            max_attempts: 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MapInvariantError {
+    NoCapitals,
+    DisconnectedCapital(Faction),
+    LandFraction(f32),
+}
+
+impl fmt::Display for MapInvariantError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapInvariantError::NoCapitals => write!(formatter, "map has no capitals"),
+            MapInvariantError::DisconnectedCapital(faction) => write!(
+                formatter,
+                "{} capital is cut off from the others",
+                faction_name(*faction)
+            ),
+            MapInvariantError::LandFraction(fraction) => {
+                write!(
+                    formatter,
+                    "only {:.0}% of the map is land",
+                    fraction * 100.0
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapInvariantError {}
+
+pub struct MapGenerator {
+    pub width: i32,
+    pub height: i32,
+    pub wrap_width: Option<i32>,
+    pub orientation: HexOrientation,
+    pub config: MapGenConfig,
+    pub rng: GameRng,
+    pub capitals: Vec<(HexCoord, Faction)>,
+    pub tiles: HashMap<HexCoord, TileType>,
+    pub features: HashMap<HexCoord, TileFeature>,
 }
 
 impl Default for MapGenParams {
@@ -21,102 +107,131 @@ impl Default for MapGenParams {
         Self {
             map_width: MAP_WIDTH,
             map_height: MAP_HEIGHT,
+            random_capitals: false,
+            wrap_columns: false,
+            orientation: HexOrientation::FlatTop,
+            config: MapGenConfig::default(),
         }
     }
 }
 
+#[derive(Clone, Default)]
 pub struct GeneratedMap {
+    pub width: i32,
+    pub height: i32,
     pub tiles: HashMap<HexCoord, TileType>,
     pub features: HashMap<HexCoord, TileFeature>,
+    pub wrap_columns: bool,
+    pub orientation: HexOrientation,
+}
+
+pub fn map_wrap_width(generated: &GeneratedMap) -> Option<i32> {
+    generated.wrap_columns.then_some(generated.width)
+}
+
+pub fn map_tile_elevation(generated: &GeneratedMap, coord: HexCoord) -> f32 {
+    generated
+        .tiles
+        .get(&coord)
+        .map(|tile_type| tile_elevation(*tile_type))
+        .unwrap_or(0.0)
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+pub fn map_params_set_wrap(params: &mut MapGenParams, wrap_columns: bool) {
+    params.wrap_columns = wrap_columns;
+    if wrap_columns {
+        params.map_width += params.map_width % 2;
+    }
+}
+
+pub fn capital_positions(params: &MapGenParams) -> [(i32, i32, Faction); 4] {
+    let far_column = params.map_width - 1 - CAPITAL_INSET;
+    let far_row = params.map_height - 1 - CAPITAL_INSET;
+    if params.wrap_columns {
+        let spacing = params.map_width / 4;
+        let first_column = spacing / 2;
+        return [
+            (first_column, CAPITAL_INSET, Faction::Redosia),
+            (first_column + spacing, far_row, Faction::Violetnam),
+            (
+                first_column + spacing * 2,
+                CAPITAL_INSET,
+                Faction::Bluegaria,
+            ),
+            (first_column + spacing * 3, far_row, Faction::Greenland),
+        ];
+    }
+    [
+        (CAPITAL_INSET, CAPITAL_INSET, Faction::Redosia),
+        (far_column, CAPITAL_INSET, Faction::Violetnam),
+        (far_column, far_row, Faction::Bluegaria),
+        (CAPITAL_INSET, far_row, Faction::Greenland),
+    ]
+}
+
+pub fn random_capital_min_distance(params: &MapGenParams) -> i32 {
+    (params.map_width.min(params.map_height) / 2).max(CAPITAL_INSET * 2)
+}
+
+pub fn random_capital_positions(
+    params: &MapGenParams,
+    rng: &mut GameRng,
+) -> Option<[(i32, i32, Faction); 4]> {
+    let min_distance = random_capital_min_distance(params);
+    let wrap_width = params.wrap_columns.then_some(params.map_width);
+    let orientation = params.orientation;
+    let mut candidates: Vec<HexCoord> = (CAPITAL_INSET..params.map_width - CAPITAL_INSET)
+        .flat_map(|column| {
+            (CAPITAL_INSET..params.map_height - CAPITAL_INSET)
+                .map(move |row| HexCoord { column, row })
+        })
+        .collect();
+
+    for _ in 0..RANDOM_CAPITAL_ATTEMPTS {
+        game_rng_shuffle(rng, &mut candidates);
+        let mut chosen: Vec<HexCoord> = Vec::new();
+        for &candidate in &candidates {
+            if chosen.iter().all(|coord| {
+                hex_wrapped_distance(*coord, candidate, wrap_width, orientation) >= min_distance
+            }) {
+                chosen.push(candidate);
+                if chosen.len() == ALL_FACTIONS.len() {
+                    break;
+                }
+            }
+        }
+        if chosen.len() == ALL_FACTIONS.len() {
+            let mut positions = capital_positions(params);
+            for (position, coord) in positions.iter_mut().zip(chosen) {
+                position.0 = coord.column;
+                position.1 = coord.row;
+            }
+            return Some(positions);
+        }
+    }
+    None
+}
+
+pub fn capital_coord(params: &MapGenParams, faction: Faction) -> HexCoord {
+    let (column, row, _) = capital_positions(params)[faction_index(faction)];
+    HexCoord { column, row }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TileFeature {
     Capital(Faction),
     City,
     Port,
+    Ruin(RuinBonus),
+    Airfield,
 }
 
-struct Rng {
-    state: u32,
-}
-
-fn rng_next(rng: &mut Rng) -> u32 {
-    rng.state = rng.state.wrapping_mul(1103515245).wrapping_add(12345);
-    rng.state
-}
-
-fn rng_next_range(rng: &mut Rng, max: u32) -> u32 {
-    rng_next(rng) % max
-}
-
-fn rng_shuffle<T>(rng: &mut Rng, slice: &mut [T]) {
-    for index in (1..slice.len()).rev() {
-        let swap_index = rng_next_range(rng, (index + 1) as u32) as usize;
-        slice.swap(index, swap_index);
-    }
-}
-
-fn get_hex_neighbors(coord: HexCoord) -> Vec<HexCoord> {
-    let column = coord.column;
-    let row = coord.row;
-    let is_odd_column = column.abs() % 2 != 0;
-
-    if is_odd_column {
-        vec![
-            HexCoord {
-                column,
-                row: row - 1,
-            },
-            HexCoord {
-                column: column + 1,
-                row,
-            },
-            HexCoord {
-                column: column + 1,
-                row: row + 1,
-            },
-            HexCoord {
-                column,
-                row: row + 1,
-            },
-            HexCoord {
-                column: column - 1,
-                row: row + 1,
-            },
-            HexCoord {
-                column: column - 1,
-                row,
-            },
-        ]
-    } else {
-        vec![
-            HexCoord {
-                column,
-                row: row - 1,
-            },
-            HexCoord {
-                column: column + 1,
-                row: row - 1,
-            },
-            HexCoord {
-                column: column + 1,
-                row,
-            },
-            HexCoord {
-                column,
-                row: row + 1,
-            },
-            HexCoord {
-                column: column - 1,
-                row,
-            },
-            HexCoord {
-                column: column - 1,
-                row: row - 1,
-            },
-        ]
-    }
+fn get_hex_neighbors(
+    coord: HexCoord,
+    wrap_width: Option<i32>,
+    orientation: HexOrientation,
+) -> Vec<HexCoord> {
+    hex_wrapped_neighbors(coord, wrap_width, orientation).to_vec()
 }
 
 fn is_in_bounds(coord: HexCoord, width: i32, height: i32) -> bool {
@@ -128,9 +243,11 @@ fn count_land_neighbors(
     tiles: &HashMap<HexCoord, TileType>,
     width: i32,
     height: i32,
+    wrap_width: Option<i32>,
+    orientation: HexOrientation,
 ) -> i32 {
     let mut count = 0;
-    for neighbor in get_hex_neighbors(coord) {
+    for neighbor in get_hex_neighbors(coord, wrap_width, orientation) {
         if is_in_bounds(neighbor, width, height) && tiles.get(&neighbor) == Some(&TileType::Land) {
             count += 1;
         }
@@ -143,11 +260,13 @@ fn flood_fill_land(
     tiles: &HashMap<HexCoord, TileType>,
     width: i32,
     height: i32,
+    wrap_width: Option<i32>,
+    orientation: HexOrientation,
 ) -> HashSet<HexCoord> {
     let mut visited = HashSet::new();
     let mut queue = VecDeque::new();
 
-    if tiles.get(&start) != Some(&TileType::Land) {
+    if !tiles.get(&start).copied().is_some_and(is_passable_land) {
         return visited;
     }
 
@@ -155,10 +274,10 @@ fn flood_fill_land(
     visited.insert(start);
 
     while let Some(current) = queue.pop_front() {
-        for neighbor in get_hex_neighbors(current) {
+        for neighbor in get_hex_neighbors(current, wrap_width, orientation) {
             if is_in_bounds(neighbor, width, height)
                 && !visited.contains(&neighbor)
-                && tiles.get(&neighbor) == Some(&TileType::Land)
+                && tiles.get(&neighbor).copied().is_some_and(is_passable_land)
             {
                 visited.insert(neighbor);
                 queue.push_back(neighbor);
@@ -175,45 +294,49 @@ fn find_path_between_landmasses(
     tiles: &HashMap<HexCoord, TileType>,
     width: i32,
     height: i32,
+    wrap_width: Option<i32>,
+    orientation: HexOrientation,
 ) -> Vec<HexCoord> {
     let mut best_path = Vec::new();
     let mut best_distance = i32::MAX;
 
     for &from_coord in from_land {
         for &to_coord in to_land {
-            let distance = hex_distance(from_coord, to_coord);
+            let to_coord = hex_unwrap_near(to_coord, from_coord, wrap_width);
+            let distance = hex_distance(from_coord, to_coord, orientation);
             if distance < best_distance {
                 best_distance = distance;
-                best_path = get_line_between(from_coord, to_coord);
+                best_path = get_line_between(from_coord, to_coord, orientation);
             }
         }
     }
 
     best_path
         .into_iter()
+        .map(|coord| hex_wrap(coord, wrap_width))
         .filter(|coord| {
             is_in_bounds(*coord, width, height) && tiles.get(coord) == Some(&TileType::Sea)
         })
         .collect()
 }
 
-fn get_line_between(from: HexCoord, to: HexCoord) -> Vec<HexCoord> {
+fn get_line_between(from: HexCoord, to: HexCoord, orientation: HexOrientation) -> Vec<HexCoord> {
     let mut result = Vec::new();
-    let distance = hex_distance(from, to);
+    let distance = hex_distance(from, to, orientation);
 
     if distance == 0 {
         return vec![from];
     }
 
-    let (from_x, from_y, from_z) = hex_to_cube(from);
-    let (to_x, to_y, to_z) = hex_to_cube(to);
+    let (from_x, from_y, from_z) = hex_to_cube(from, orientation);
+    let (to_x, to_y, to_z) = hex_to_cube(to, orientation);
 
     for step in 0..=distance {
         let t = step as f32 / distance as f32;
         let x = lerp(from_x as f32, to_x as f32, t).round() as i32;
         let y = lerp(from_y as f32, to_y as f32, t).round() as i32;
         let z = lerp(from_z as f32, to_z as f32, t).round() as i32;
-        result.push(hex_from_cube(x, y, z));
+        result.push(hex_from_cube(x, y, z, orientation));
     }
 
     result
@@ -228,8 +351,10 @@ fn is_adjacent_to_sea(
     tiles: &HashMap<HexCoord, TileType>,
     width: i32,
     height: i32,
+    wrap_width: Option<i32>,
+    orientation: HexOrientation,
 ) -> bool {
-    for neighbor in get_hex_neighbors(coord) {
+    for neighbor in get_hex_neighbors(coord, wrap_width, orientation) {
         if !is_in_bounds(neighbor, width, height) || tiles.get(&neighbor) == Some(&TileType::Sea) {
             return true;
         }
@@ -238,40 +363,99 @@ fn is_adjacent_to_sea(
 }
 
 fn is_passable_land(tile_type: TileType) -> bool {
+    matches!(
+        tile_type,
+        TileType::Land | TileType::Forest | TileType::Hills | TileType::Swamp
+    )
+}
+
+fn is_settlement_site(tile_type: TileType) -> bool {
     matches!(tile_type, TileType::Land | TileType::Forest)
 }
 
-pub fn generate_map(seed: u32) -> GeneratedMap {
-    let width = MAP_WIDTH;
-    let height = MAP_HEIGHT;
-    let mut rng = Rng { state: seed };
-    let mut tiles: HashMap<HexCoord, TileType> = HashMap::new();
+pub fn settlement_seed(seed: u32) -> u32 {
+    game_rng_next_u32(&mut game_rng_new(seed ^ SETTLEMENT_SEED_SALT))
+}
 
-    let capital_coords: Vec<HexCoord> = CAPITAL_POSITIONS
-        .iter()
-        .map(|(col, row, _)| HexCoord {
-            column: *col,
-            row: *row,
-        })
-        .collect();
+pub fn generate_map(seed: u32, params: &MapGenParams) -> GeneratedMap {
+    let mut generated = generate_terrain(seed, params);
+    place_settlements(&mut generated, settlement_seed(seed));
+    generated
+}
 
+pub fn map_generator_new(seed: u32, params: &MapGenParams) -> MapGenerator {
+    let width = params.map_width;
+    let height = params.map_height;
+    let mut rng = game_rng_new(seed);
+    let capitals = if params.random_capitals {
+        random_capital_positions(params, &mut rng).unwrap_or_else(|| capital_positions(params))
+    } else {
+        capital_positions(params)
+    };
+    let mut tiles: HashMap<HexCoord, TileType> = HashMap::new();
     for row in 0..height {
         for column in 0..width {
             tiles.insert(HexCoord { column, row }, TileType::Sea);
         }
     }
 
-    for &capital in &capital_coords {
-        tiles.insert(capital, TileType::Land);
-        for neighbor in hex_tiles_in_range(capital, 2) {
-            if is_in_bounds(neighbor, width, height) {
-                tiles.insert(neighbor, TileType::Land);
+    MapGenerator {
+        width,
+        height,
+        wrap_width: params.wrap_columns.then_some(width),
+        orientation: params.orientation,
+        config: params.config,
+        rng,
+        capitals: capitals
+            .iter()
+            .map(|(column, row, faction)| {
+                (
+                    HexCoord {
+                        column: *column,
+                        row: *row,
+                    },
+                    *faction,
+                )
+            })
+            .collect(),
+        tiles,
+        features: HashMap::new(),
+    }
+}
+
+fn map_generator_capital_coords(generator: &MapGenerator) -> Vec<HexCoord> {
+    generator.capitals.iter().map(|(coord, _)| *coord).collect()
+}
+
+pub fn map_generator_seed_landmass(generator: &mut MapGenerator) {
+    for capital in map_generator_capital_coords(generator) {
+        generator.tiles.insert(capital, TileType::Land);
+        for neighbor in hex_wrapped_tiles_in_range(
+            capital,
+            generator.config.capital_land_radius,
+            generator.wrap_width,
+            generator.orientation,
+        ) {
+            if is_in_bounds(neighbor, generator.width, generator.height) {
+                generator.tiles.insert(neighbor, TileType::Land);
             }
         }
     }
+}
+
+pub fn map_generator_grow(generator: &mut MapGenerator) {
+    let MapGenerator {
+        width,
+        height,
+        wrap_width,
+        orientation,
+        config,
+        ..
+    } = *generator;
 
-    for _ in 0..8 {
-        let mut sea_hexes: Vec<HexCoord> = tiles
+    for _ in 0..config.grow_passes {
+        let mut sea_hexes: Vec<HexCoord> = generator
+            .tiles
             .iter()
             .filter(|(coord, tile_type)| {
                 **tile_type == TileType::Sea && is_in_bounds(**coord, width, height)
@@ -279,21 +463,41 @@ pub fn generate_map(seed: u32) -> GeneratedMap {
             .map(|(coord, _)| *coord)
             .collect();
 
-        rng_shuffle(&mut rng, &mut sea_hexes);
+        game_rng_shuffle(&mut generator.rng, &mut sea_hexes);
 
         for coord in sea_hexes {
-            let land_neighbors = count_land_neighbors(coord, &tiles, width, height);
+            let land_neighbors = count_land_neighbors(
+                coord,
+                &generator.tiles,
+                width,
+                height,
+                wrap_width,
+                orientation,
+            );
 
             if land_neighbors >= 1 {
-                let conversion_chance = (land_neighbors as u32) * 25;
-                if rng_next_range(&mut rng, 100) < conversion_chance {
-                    tiles.insert(coord, TileType::Land);
+                let conversion_chance = (land_neighbors as u32) * config.grow_chance;
+                if game_rng_percent(&mut generator.rng, conversion_chance) {
+                    generator.tiles.insert(coord, TileType::Land);
                 }
             }
         }
     }
+}
 
-    let land_hexes: Vec<HexCoord> = tiles
+pub fn map_generator_carve(generator: &mut MapGenerator) {
+    let MapGenerator {
+        width,
+        height,
+        wrap_width,
+        orientation,
+        config,
+        ..
+    } = *generator;
+    let capital_coords = map_generator_capital_coords(generator);
+
+    let land_hexes: Vec<HexCoord> = generator
+        .tiles
         .iter()
         .filter(|(_, tile_type)| **tile_type == TileType::Land)
         .map(|(coord, _)| *coord)
@@ -302,17 +506,25 @@ pub fn generate_map(seed: u32) -> GeneratedMap {
     for coord in land_hexes {
         let min_capital_dist = capital_coords
             .iter()
-            .map(|cap| hex_distance(coord, *cap))
+            .map(|cap| hex_wrapped_distance(coord, *cap, wrap_width, orientation))
             .min()
             .unwrap_or(0);
 
-        if min_capital_dist > 5 {
-            let land_neighbors = count_land_neighbors(coord, &tiles, width, height);
+        if min_capital_dist > config.carve_min_capital_distance {
+            let land_neighbors = count_land_neighbors(
+                coord,
+                &generator.tiles,
+                width,
+                height,
+                wrap_width,
+                orientation,
+            );
 
-            let should_carve = land_neighbors <= 1 && rng_next_range(&mut rng, 100) < 15;
+            let should_carve =
+                land_neighbors <= 1 && game_rng_percent(&mut generator.rng, config.carve_chance);
 
             if should_carve {
-                tiles.insert(coord, TileType::Sea);
+                generator.tiles.insert(coord, TileType::Sea);
             }
         }
     }
@@ -320,33 +532,48 @@ pub fn generate_map(seed: u32) -> GeneratedMap {
     for row in 0..height {
         for column in 0..width {
             let coord = HexCoord { column, row };
-            if column == 0 || column == width - 1 || row == 0 || row == height - 1 {
-                tiles.insert(coord, TileType::Sea);
+            let column_edge = wrap_width.is_none() && (column == 0 || column == width - 1);
+            if column_edge || row == 0 || row == height - 1 {
+                generator.tiles.insert(coord, TileType::Sea);
             }
         }
     }
 
-    for &capital in &capital_coords {
-        tiles.insert(capital, TileType::Land);
-        for neighbor in hex_tiles_in_range(capital, 2) {
-            if is_in_bounds(neighbor, width, height) {
-                tiles.insert(neighbor, TileType::Land);
-            }
-        }
-    }
+    map_generator_seed_landmass(generator);
+}
+
+pub fn map_generator_connect(generator: &mut MapGenerator) {
+    let MapGenerator {
+        width,
+        height,
+        wrap_width,
+        orientation,
+        ..
+    } = *generator;
+    let capital_coords = map_generator_capital_coords(generator);
+    let tiles = &mut generator.tiles;
 
     let first_capital = capital_coords[0];
-    let mut connected_land = flood_fill_land(first_capital, &tiles, width, height);
+    let mut connected_land =
+        flood_fill_land(first_capital, tiles, width, height, wrap_width, orientation);
 
     for &capital in &capital_coords[1..] {
         if !connected_land.contains(&capital) {
-            let capital_land = flood_fill_land(capital, &tiles, width, height);
-            let bridge =
-                find_path_between_landmasses(&connected_land, &capital_land, &tiles, width, height);
+            let capital_land =
+                flood_fill_land(capital, tiles, width, height, wrap_width, orientation);
+            let bridge = find_path_between_landmasses(
+                &connected_land,
+                &capital_land,
+                tiles,
+                width,
+                height,
+                wrap_width,
+                orientation,
+            );
             for coord in &bridge {
                 tiles.insert(*coord, TileType::Land);
                 connected_land.insert(*coord);
-                for neighbor in get_hex_neighbors(*coord) {
+                for neighbor in get_hex_neighbors(*coord, wrap_width, orientation) {
                     if is_in_bounds(neighbor, width, height)
                         && tiles.get(&neighbor) == Some(&TileType::Sea)
                     {
@@ -364,59 +591,271 @@ pub fn generate_map(seed: u32) -> GeneratedMap {
     let all_coords: Vec<HexCoord> = tiles.keys().copied().collect();
     for coord in all_coords {
         if tiles.get(&coord) == Some(&TileType::Land) {
-            let land_neighbors = count_land_neighbors(coord, &tiles, width, height);
+            let land_neighbors =
+                count_land_neighbors(coord, tiles, width, height, wrap_width, orientation);
             if land_neighbors == 0 {
                 tiles.insert(coord, TileType::Sea);
             }
         } else if tiles.get(&coord) == Some(&TileType::Sea) {
-            let sea_neighbors = 6 - count_land_neighbors(coord, &tiles, width, height);
+            let sea_neighbors =
+                6 - count_land_neighbors(coord, tiles, width, height, wrap_width, orientation);
             if sea_neighbors == 0 {
                 tiles.insert(coord, TileType::Land);
             }
         }
     }
+}
+
+fn map_generator_hills(generator: &mut MapGenerator, capital_coords: &[HexCoord]) {
+    let MapGenerator {
+        wrap_width,
+        orientation,
+        config,
+        ..
+    } = *generator;
 
-    let land_tiles: Vec<HexCoord> = tiles
+    let mut candidates: Vec<HexCoord> = generator
+        .tiles
         .iter()
         .filter(|(coord, tile_type)| {
             **tile_type == TileType::Land
-                && capital_coords
-                    .iter()
-                    .all(|cap| hex_distance(**coord, *cap) > 2)
+                && capital_coords.iter().all(|cap| {
+                    hex_wrapped_distance(**coord, *cap, wrap_width, orientation)
+                        > config.capital_land_radius
+                })
+        })
+        .map(|(coord, _)| *coord)
+        .collect();
+    let eligible: HashSet<HexCoord> = candidates.iter().copied().collect();
+
+    game_rng_shuffle(&mut generator.rng, &mut candidates);
+
+    for &start in candidates.iter().take(config.hill_clusters as usize) {
+        if generator.tiles.get(&start) != Some(&TileType::Land) {
+            continue;
+        }
+        generator.tiles.insert(start, TileType::Hills);
+        let mut cluster = vec![start];
+
+        while cluster.len() < config.hill_cluster_size as usize {
+            let frontier: Vec<HexCoord> = cluster
+                .iter()
+                .flat_map(|coord| get_hex_neighbors(*coord, wrap_width, orientation))
+                .filter(|neighbor| {
+                    eligible.contains(neighbor)
+                        && generator.tiles.get(neighbor) == Some(&TileType::Land)
+                })
+                .collect();
+            if frontier.is_empty() {
+                break;
+            }
+            let next = frontier[game_rng_range(&mut generator.rng, frontier.len() as u32) as usize];
+            generator.tiles.insert(next, TileType::Hills);
+            cluster.push(next);
+        }
+    }
+}
+
+fn map_generator_swamps(generator: &mut MapGenerator, capital_coords: &[HexCoord]) {
+    let MapGenerator {
+        width,
+        height,
+        wrap_width,
+        orientation,
+        config,
+        ..
+    } = *generator;
+
+    let coastal_land: Vec<HexCoord> = generator
+        .tiles
+        .iter()
+        .filter(|(coord, tile_type)| {
+            **tile_type == TileType::Land
+                && capital_coords.iter().all(|cap| {
+                    hex_wrapped_distance(**coord, *cap, wrap_width, orientation)
+                        > config.capital_land_radius
+                })
+                && is_adjacent_to_sea(
+                    **coord,
+                    &generator.tiles,
+                    width,
+                    height,
+                    wrap_width,
+                    orientation,
+                )
+        })
+        .map(|(coord, _)| *coord)
+        .collect();
+
+    for coord in coastal_land {
+        if game_rng_percent(&mut generator.rng, config.swamp_chance) {
+            generator.tiles.insert(coord, TileType::Swamp);
+        }
+    }
+}
+
+pub fn map_generator_features(generator: &mut MapGenerator) {
+    let MapGenerator {
+        wrap_width,
+        orientation,
+        config,
+        ..
+    } = *generator;
+    let capital_coords = map_generator_capital_coords(generator);
+
+    map_generator_hills(generator, &capital_coords);
+    map_generator_swamps(generator, &capital_coords);
+
+    let land_tiles: Vec<HexCoord> = generator
+        .tiles
+        .iter()
+        .filter(|(coord, tile_type)| {
+            **tile_type == TileType::Land
+                && capital_coords.iter().all(|cap| {
+                    hex_wrapped_distance(**coord, *cap, wrap_width, orientation)
+                        > config.capital_land_radius
+                })
         })
         .map(|(coord, _)| *coord)
         .collect();
 
     for coord in &land_tiles {
-        let roll = rng_next_range(&mut rng, 100);
-        if roll < 20 {
-            tiles.insert(*coord, TileType::Forest);
+        if game_rng_percent(&mut generator.rng, config.forest_chance) {
+            generator.tiles.insert(*coord, TileType::Forest);
         }
     }
 
-    let mut features: HashMap<HexCoord, TileFeature> = HashMap::new();
+    for (coord, faction) in &generator.capitals {
+        generator
+            .features
+            .insert(*coord, TileFeature::Capital(*faction));
+    }
+}
+
+pub fn map_generator_finish(generator: MapGenerator) -> GeneratedMap {
+    GeneratedMap {
+        width: generator.width,
+        height: generator.height,
+        tiles: generator.tiles,
+        features: generator.features,
+        wrap_columns: generator.wrap_width.is_some(),
+        orientation: generator.orientation,
+    }
+}
+
+pub fn map_land_fraction(generated: &GeneratedMap) -> f32 {
+    let total = (generated.width * generated.height).max(1) as f32;
+    let land = generated
+        .tiles
+        .values()
+        .filter(|tile_type| is_passable_land(**tile_type))
+        .count();
+    land as f32 / total
+}
 
-    for (col, row, faction) in CAPITAL_POSITIONS {
-        features.insert(HexCoord { column: col, row }, TileFeature::Capital(faction));
+pub fn check_map_invariants(
+    generated: &GeneratedMap,
+    config: &MapGenConfig,
+) -> Result<(), MapInvariantError> {
+    let land_fraction = map_land_fraction(generated);
+    if land_fraction < config.min_land_fraction {
+        return Err(MapInvariantError::LandFraction(land_fraction));
     }
 
-    let target_cities = 8 + rng_next_range(&mut rng, 5) as i32;
+    let mut capitals: Vec<(HexCoord, Faction)> = generated
+        .features
+        .iter()
+        .filter_map(|(coord, feature)| match feature {
+            TileFeature::Capital(faction) => Some((*coord, *faction)),
+            _ => None,
+        })
+        .collect();
+    capitals.sort_by_key(|(_, faction)| faction_index(*faction));
+    let Some((first_capital, _)) = capitals.first().copied() else {
+        return Err(MapInvariantError::NoCapitals);
+    };
+
+    let connected = flood_fill_land(
+        first_capital,
+        &generated.tiles,
+        generated.width,
+        generated.height,
+        map_wrap_width(generated),
+        generated.orientation,
+    );
+    match capitals
+        .iter()
+        .find(|(coord, _)| !connected.contains(coord))
+    {
+        Some((_, faction)) => Err(MapInvariantError::DisconnectedCapital(*faction)),
+        None => Ok(()),
+    }
+}
+
+fn generate_terrain_attempt(seed: u32, params: &MapGenParams) -> GeneratedMap {
+    let mut generator = map_generator_new(seed, params);
+    map_generator_seed_landmass(&mut generator);
+    map_generator_grow(&mut generator);
+    map_generator_carve(&mut generator);
+    map_generator_connect(&mut generator);
+    map_generator_features(&mut generator);
+    map_generator_finish(generator)
+}
+
+pub fn generate_terrain(seed: u32, params: &MapGenParams) -> GeneratedMap {
+    let mut attempt_seed = seed;
+    let mut retry_rng = game_rng_new(seed ^ TERRAIN_RETRY_SALT);
+    let mut generated = generate_terrain_attempt(attempt_seed, params);
+
+    for _ in 1..params.config.max_attempts.max(1) {
+        match check_map_invariants(&generated, &params.config) {
+            Ok(()) => return generated,
+            Err(error) => log::warn!("Rejected terrain seed {}: {}", attempt_seed, error),
+        }
+        attempt_seed = game_rng_next_u32(&mut retry_rng);
+        generated = generate_terrain_attempt(attempt_seed, params);
+    }
+
+    if let Err(error) = check_map_invariants(&generated, &params.config) {
+        log::error!(
+            "No valid terrain after {} attempts from seed {}: {}",
+            params.config.max_attempts,
+            seed,
+            error
+        );
+    }
+    generated
+}
+
+pub fn place_settlements(generated: &mut GeneratedMap, seed: u32) {
+    let width = generated.width;
+    let height = generated.height;
+    let wrap_width = map_wrap_width(generated);
+    let orientation = generated.orientation;
+    let tiles = &generated.tiles;
+    let features = &mut generated.features;
+    let mut rng = game_rng_new(seed);
+
+    features.retain(|_, feature| matches!(feature, TileFeature::Capital(_)));
+    let capital_coords: Vec<HexCoord> = features.keys().copied().collect();
+
+    let target_cities = 8 + game_rng_range(&mut rng, 5) as i32;
     let mut city_coords: Vec<HexCoord> = Vec::new();
 
     let mut candidate_hexes: Vec<HexCoord> = tiles
         .iter()
         .filter(|(coord, tile_type)| {
-            is_passable_land(**tile_type)
+            is_settlement_site(**tile_type)
                 && !features.contains_key(coord)
                 && capital_coords
                     .iter()
-                    .all(|cap| hex_distance(**coord, *cap) > 1)
-                && !is_adjacent_to_sea(**coord, &tiles, width, height)
+                    .all(|cap| hex_wrapped_distance(**coord, *cap, wrap_width, orientation) > 1)
+                && !is_adjacent_to_sea(**coord, &tiles, width, height, wrap_width, orientation)
         })
         .map(|(coord, _)| *coord)
         .collect();
 
-    rng_shuffle(&mut rng, &mut candidate_hexes);
+    game_rng_shuffle(&mut rng, &mut candidate_hexes);
 
     for coord in candidate_hexes {
         if city_coords.len() >= target_cities as usize {
@@ -425,27 +864,27 @@ pub fn generate_map(seed: u32) -> GeneratedMap {
 
         let far_enough_from_cities = city_coords
             .iter()
-            .all(|city| hex_distance(coord, *city) > 2);
+            .all(|city| hex_wrapped_distance(coord, *city, wrap_width, orientation) > 2);
         if far_enough_from_cities {
             features.insert(coord, TileFeature::City);
             city_coords.push(coord);
         }
     }
 
-    let target_ports = 4 + rng_next_range(&mut rng, 3) as i32;
+    let target_ports = 4 + game_rng_range(&mut rng, 3) as i32;
     let mut port_coords: Vec<HexCoord> = Vec::new();
 
     let mut coastal_hexes: Vec<HexCoord> = tiles
         .iter()
         .filter(|(coord, tile_type)| {
-            is_passable_land(**tile_type)
+            is_settlement_site(**tile_type)
                 && !features.contains_key(coord)
-                && is_adjacent_to_sea(**coord, &tiles, width, height)
+                && is_adjacent_to_sea(**coord, &tiles, width, height, wrap_width, orientation)
         })
         .map(|(coord, _)| *coord)
         .collect();
 
-    rng_shuffle(&mut rng, &mut coastal_hexes);
+    game_rng_shuffle(&mut rng, &mut coastal_hexes);
 
     for coord in coastal_hexes {
         if port_coords.len() >= target_ports as usize {
@@ -454,12 +893,313 @@ pub fn generate_map(seed: u32) -> GeneratedMap {
 
         let far_enough_from_ports = port_coords
             .iter()
-            .all(|port| hex_distance(coord, *port) > 2);
+            .all(|port| hex_wrapped_distance(coord, *port, wrap_width, orientation) > 2);
         if far_enough_from_ports {
             features.insert(coord, TileFeature::Port);
             port_coords.push(coord);
         }
     }
 
-    GeneratedMap { tiles, features }
+    let target_ruins = 2 + game_rng_range(&mut rng, 3) as i32;
+    let mut ruin_coords: Vec<HexCoord> = Vec::new();
+
+    let mut ruin_hexes: Vec<HexCoord> = tiles
+        .iter()
+        .filter(|(coord, tile_type)| {
+            is_settlement_site(**tile_type)
+                && features.keys().all(|feature_coord| {
+                    hex_wrapped_distance(**coord, *feature_coord, wrap_width, orientation) > 1
+                })
+                && capital_coords
+                    .iter()
+                    .all(|cap| hex_wrapped_distance(**coord, *cap, wrap_width, orientation) > 3)
+        })
+        .map(|(coord, _)| *coord)
+        .collect();
+
+    game_rng_shuffle(&mut rng, &mut ruin_hexes);
+
+    for coord in ruin_hexes {
+        if ruin_coords.len() >= target_ruins as usize {
+            break;
+        }
+
+        let far_enough_from_ruins = ruin_coords
+            .iter()
+            .all(|ruin| hex_wrapped_distance(coord, *ruin, wrap_width, orientation) > 3);
+        if far_enough_from_ruins {
+            let bonus = if game_rng_percent(&mut rng, 50) {
+                RuinBonus::Soldiers
+            } else {
+                RuinBonus::Morale
+            };
+            features.insert(coord, TileFeature::Ruin(bonus));
+            ruin_coords.push(coord);
+        }
+    }
+
+    let target_airfields = 2 + game_rng_range(&mut rng, 2) as i32;
+    let mut airfield_coords: Vec<HexCoord> = Vec::new();
+
+    let mut airfield_hexes: Vec<HexCoord> = tiles
+        .iter()
+        .filter(|(coord, tile_type)| {
+            **tile_type == TileType::Land
+                && features.keys().all(|feature_coord| {
+                    hex_wrapped_distance(**coord, *feature_coord, wrap_width, orientation) > 1
+                })
+                && !is_adjacent_to_sea(**coord, &tiles, width, height, wrap_width, orientation)
+        })
+        .map(|(coord, _)| *coord)
+        .collect();
+
+    game_rng_shuffle(&mut rng, &mut airfield_hexes);
+
+    for coord in airfield_hexes {
+        if airfield_coords.len() >= target_airfields as usize {
+            break;
+        }
+
+        let far_enough_from_airfields = airfield_coords
+            .iter()
+            .all(|airfield| hex_wrapped_distance(coord, *airfield, wrap_width, orientation) > 6);
+        if far_enough_from_airfields {
+            features.insert(coord, TileFeature::Airfield);
+            airfield_coords.push(coord);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SEED: u32 = 0x00c0_ffee;
+
+    fn land_count(generator: &MapGenerator) -> usize {
+        generator
+            .tiles
+            .values()
+            .filter(|tile_type| is_passable_land(**tile_type))
+            .count()
+    }
+
+    fn capitals_connected(generator: &MapGenerator) -> bool {
+        let capital_coords = map_generator_capital_coords(generator);
+        let connected = flood_fill_land(
+            capital_coords[0],
+            &generator.tiles,
+            generator.width,
+            generator.height,
+            generator.wrap_width,
+            generator.orientation,
+        );
+        capital_coords
+            .iter()
+            .all(|capital| connected.contains(capital))
+    }
+
+    fn flat_map(width: i32, height: i32, tile_type: TileType) -> GeneratedMap {
+        let mut tiles = HashMap::new();
+        for row in 0..height {
+            for column in 0..width {
+                tiles.insert(HexCoord { column, row }, tile_type);
+            }
+        }
+        GeneratedMap {
+            width,
+            height,
+            tiles,
+            ..GeneratedMap::default()
+        }
+    }
+
+    #[test]
+    fn new_generator_starts_as_open_sea_with_four_capitals() {
+        let params = MapGenParams::default();
+        let generator = map_generator_new(TEST_SEED, &params);
+        assert_eq!(
+            generator.tiles.len(),
+            (params.map_width * params.map_height) as usize
+        );
+        assert_eq!(land_count(&generator), 0);
+        assert_eq!(generator.capitals.len(), ALL_FACTIONS.len());
+        for (coord, _) in &generator.capitals {
+            assert!(is_in_bounds(*coord, generator.width, generator.height));
+        }
+    }
+
+    #[test]
+    fn seed_landmass_raises_land_around_every_capital() {
+        let mut generator = map_generator_new(TEST_SEED, &MapGenParams::default());
+        map_generator_seed_landmass(&mut generator);
+        let radius = generator.config.capital_land_radius;
+        for capital in map_generator_capital_coords(&generator) {
+            for coord in hex_wrapped_tiles_in_range(
+                capital,
+                radius,
+                generator.wrap_width,
+                generator.orientation,
+            ) {
+                if is_in_bounds(coord, generator.width, generator.height) {
+                    assert_eq!(generator.tiles.get(&coord), Some(&TileType::Land));
+                }
+            }
+        }
+        assert!(land_count(&generator) > 0);
+    }
+
+    #[test]
+    fn grow_only_adds_land() {
+        let mut generator = map_generator_new(TEST_SEED, &MapGenParams::default());
+        map_generator_seed_landmass(&mut generator);
+        let seeded = land_count(&generator);
+        map_generator_grow(&mut generator);
+        assert!(land_count(&generator) > seeded);
+    }
+
+    #[test]
+    fn carve_keeps_edges_at_sea_and_capitals_on_land() {
+        let mut generator = map_generator_new(TEST_SEED, &MapGenParams::default());
+        map_generator_seed_landmass(&mut generator);
+        map_generator_grow(&mut generator);
+        map_generator_carve(&mut generator);
+        let capital_coords = map_generator_capital_coords(&generator);
+        let radius = generator.config.capital_land_radius;
+        for (coord, tile_type) in &generator.tiles {
+            let on_edge = coord.column == 0
+                || coord.column == generator.width - 1
+                || coord.row == 0
+                || coord.row == generator.height - 1;
+            let near_capital = capital_coords.iter().any(|capital| {
+                hex_wrapped_distance(
+                    *coord,
+                    *capital,
+                    generator.wrap_width,
+                    generator.orientation,
+                ) <= radius
+            });
+            if on_edge && !near_capital {
+                assert_eq!(*tile_type, TileType::Sea, "edge tile {:?}", coord);
+            }
+        }
+        for capital in capital_coords {
+            assert_eq!(generator.tiles.get(&capital), Some(&TileType::Land));
+        }
+    }
+
+    #[test]
+    fn connect_joins_every_capital() {
+        let mut generator = map_generator_new(TEST_SEED, &MapGenParams::default());
+        map_generator_seed_landmass(&mut generator);
+        map_generator_grow(&mut generator);
+        map_generator_carve(&mut generator);
+        map_generator_connect(&mut generator);
+        assert!(capitals_connected(&generator));
+    }
+
+    #[test]
+    fn features_place_a_capital_per_faction() {
+        let mut generator = map_generator_new(TEST_SEED, &MapGenParams::default());
+        map_generator_seed_landmass(&mut generator);
+        map_generator_grow(&mut generator);
+        map_generator_carve(&mut generator);
+        map_generator_connect(&mut generator);
+        map_generator_features(&mut generator);
+        for faction in ALL_FACTIONS {
+            let placed = generator
+                .features
+                .values()
+                .filter(|feature| **feature == TileFeature::Capital(faction))
+                .count();
+            assert_eq!(placed, 1, "{} capital", faction_name(faction));
+        }
+        for capital in map_generator_capital_coords(&generator) {
+            assert_eq!(generator.tiles.get(&capital), Some(&TileType::Land));
+        }
+        assert!(capitals_connected(&generator));
+    }
+
+    #[test]
+    fn generation_is_deterministic_for_a_seed() {
+        let params = MapGenParams::default();
+        let first = generate_map(TEST_SEED, &params);
+        let second = generate_map(TEST_SEED, &params);
+        assert_eq!(first.tiles, second.tiles);
+        assert_eq!(first.features, second.features);
+    }
+
+    #[test]
+    fn invariants_reject_a_map_without_capitals() {
+        let generated = flat_map(10, 6, TileType::Land);
+        assert_eq!(
+            check_map_invariants(&generated, &MapGenConfig::default()),
+            Err(MapInvariantError::NoCapitals)
+        );
+    }
+
+    #[test]
+    fn invariants_reject_too_little_land() {
+        let mut generated = flat_map(10, 10, TileType::Sea);
+        let capital = HexCoord { column: 4, row: 4 };
+        generated.tiles.insert(capital, TileType::Land);
+        generated
+            .features
+            .insert(capital, TileFeature::Capital(Faction::Redosia));
+        assert_eq!(
+            check_map_invariants(&generated, &MapGenConfig::default()),
+            Err(MapInvariantError::LandFraction(0.01))
+        );
+    }
+
+    #[test]
+    fn invariants_reject_a_disconnected_capital() {
+        let mut generated = flat_map(10, 6, TileType::Land);
+        for row in 0..generated.height {
+            generated
+                .tiles
+                .insert(HexCoord { column: 5, row }, TileType::Sea);
+        }
+        generated.features.insert(
+            HexCoord { column: 1, row: 2 },
+            TileFeature::Capital(Faction::Redosia),
+        );
+        generated.features.insert(
+            HexCoord { column: 8, row: 2 },
+            TileFeature::Capital(Faction::Bluegaria),
+        );
+        assert_eq!(
+            check_map_invariants(&generated, &MapGenConfig::default()),
+            Err(MapInvariantError::DisconnectedCapital(Faction::Bluegaria))
+        );
+
+        generated
+            .tiles
+            .insert(HexCoord { column: 5, row: 2 }, TileType::Hills);
+        assert_eq!(
+            check_map_invariants(&generated, &MapGenConfig::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn generate_terrain_retries_until_the_map_is_valid() {
+        let mut params = MapGenParams::default();
+        params.config.max_attempts = 64;
+        let (seed, fraction) = (0..16)
+            .map(|seed| {
+                let attempt = generate_terrain_attempt(seed, &params);
+                (seed, map_land_fraction(&attempt))
+            })
+            .min_by(|(_, left), (_, right)| left.total_cmp(right))
+            .unwrap();
+        params.config.min_land_fraction = fraction + f32::EPSILON;
+
+        let first_attempt = generate_terrain_attempt(seed, &params);
+        assert!(check_map_invariants(&first_attempt, &params.config).is_err());
+
+        let generated = generate_terrain(seed, &params);
+        assert_eq!(check_map_invariants(&generated, &params.config), Ok(()));
+        assert!(map_land_fraction(&generated) >= params.config.min_land_fraction);
+    }
 }
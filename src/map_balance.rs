@@ -0,0 +1,252 @@
+use crate::ecs::{Faction, TileType, faction_index};
+use crate::hex::{HexCoord, hex_wrapped_neighbors};
+use crate::map::{GeneratedMap, MapGenParams, TileFeature, generate_map, map_wrap_width};
+use crate::rng::{game_rng_new, game_rng_next_u32};
+use nightshade::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+const MAX_BALANCE_ATTEMPTS: u32 = 12;
+const LAND_AREA_TOLERANCE: f32 = 0.35;
+const FEATURE_DISTANCE_TOLERANCE: i32 = 4;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CapitalBalance {
+    pub faction: Faction,
+    pub reachable_land: usize,
+    pub nearest_city_distance: Option<i32>,
+    pub nearest_port_distance: Option<i32>,
+    pub chokepoints: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MapBalance {
+    pub capitals: Vec<CapitalBalance>,
+    pub land_area_spread: f32,
+    pub city_distance_spread: i32,
+    pub port_distance_spread: i32,
+    pub balanced: bool,
+    pub rerolls: u32,
+}
+
+fn is_in_bounds(generated: &GeneratedMap, coord: HexCoord) -> bool {
+    coord.column >= 0
+        && coord.column < generated.width
+        && coord.row >= 0
+        && coord.row < generated.height
+}
+
+fn is_land(generated: &GeneratedMap, coord: HexCoord) -> bool {
+    is_in_bounds(generated, coord)
+        && generated
+            .tiles
+            .get(&coord)
+            .map(|tile_type| *tile_type != TileType::Sea)
+            .unwrap_or(false)
+}
+
+fn land_neighbors(generated: &GeneratedMap, coord: HexCoord) -> Vec<HexCoord> {
+    hex_wrapped_neighbors(coord, map_wrap_width(generated), generated.orientation)
+        .into_iter()
+        .filter(|neighbor| is_land(generated, *neighbor))
+        .collect()
+}
+
+fn capital_coords(generated: &GeneratedMap) -> Vec<(HexCoord, Faction)> {
+    let mut capitals: Vec<(HexCoord, Faction)> = generated
+        .features
+        .iter()
+        .filter_map(|(coord, feature)| match feature {
+            TileFeature::Capital(faction) => Some((*coord, *faction)),
+            _ => None,
+        })
+        .collect();
+    capitals.sort_by_key(|(_, faction)| faction_index(*faction));
+    capitals
+}
+
+fn land_distances_from(generated: &GeneratedMap, start: HexCoord) -> HashMap<HexCoord, i32> {
+    let mut distances: HashMap<HexCoord, i32> = HashMap::new();
+    let mut queue: VecDeque<HexCoord> = VecDeque::new();
+
+    distances.insert(start, 0);
+    queue.push_back(start);
+
+    while let Some(current) = queue.pop_front() {
+        let current_distance = distances[&current];
+        for neighbor in land_neighbors(generated, current) {
+            if distances.contains_key(&neighbor) {
+                continue;
+            }
+            distances.insert(neighbor, current_distance + 1);
+            queue.push_back(neighbor);
+        }
+    }
+
+    distances
+}
+
+fn is_chokepoint(generated: &GeneratedMap, coord: HexCoord) -> bool {
+    let neighbors = land_neighbors(generated, coord);
+    if neighbors.len() != 2 {
+        return false;
+    }
+    !hex_wrapped_neighbors(
+        neighbors[0],
+        map_wrap_width(generated),
+        generated.orientation,
+    )
+    .contains(&neighbors[1])
+}
+
+fn nearest_feature_distance(
+    generated: &GeneratedMap,
+    distances: &HashMap<HexCoord, i32>,
+    matches_feature: fn(TileFeature) -> bool,
+) -> Option<i32> {
+    generated
+        .features
+        .iter()
+        .filter(|(_, feature)| matches_feature(**feature))
+        .filter_map(|(coord, _)| distances.get(coord).copied())
+        .min()
+}
+
+fn spread(values: impl Iterator<Item = i32>) -> i32 {
+    let values: Vec<i32> = values.collect();
+    let min = values.iter().copied().min().unwrap_or(0);
+    let max = values.iter().copied().max().unwrap_or(0);
+    max - min
+}
+
+pub fn analyze_map_balance(generated: &GeneratedMap) -> MapBalance {
+    let capitals = capital_coords(generated);
+
+    let distances_per_capital: Vec<HashMap<HexCoord, i32>> = capitals
+        .iter()
+        .map(|(coord, _)| land_distances_from(generated, *coord))
+        .collect();
+
+    let mut owned_land = vec![Vec::new(); capitals.len()];
+    for (&coord, &tile_type) in &generated.tiles {
+        if tile_type == TileType::Sea || !is_in_bounds(generated, coord) {
+            continue;
+        }
+
+        let closest = distances_per_capital
+            .iter()
+            .enumerate()
+            .filter_map(|(index, distances)| distances.get(&coord).map(|d| (index, *d)))
+            .min_by_key(|(_, distance)| *distance);
+
+        if let Some((index, _)) = closest {
+            owned_land[index].push(coord);
+        }
+    }
+
+    let capital_balances: Vec<CapitalBalance> = capitals
+        .iter()
+        .enumerate()
+        .map(|(index, (_, faction))| {
+            let distances = &distances_per_capital[index];
+            CapitalBalance {
+                faction: *faction,
+                reachable_land: owned_land[index].len(),
+                nearest_city_distance: nearest_feature_distance(generated, distances, |f| {
+                    f == TileFeature::City
+                }),
+                nearest_port_distance: nearest_feature_distance(generated, distances, |f| {
+                    f == TileFeature::Port
+                }),
+                chokepoints: owned_land[index]
+                    .iter()
+                    .filter(|coord| is_chokepoint(generated, **coord))
+                    .count(),
+            }
+        })
+        .collect();
+
+    let max_land = capital_balances
+        .iter()
+        .map(|capital| capital.reachable_land)
+        .max()
+        .unwrap_or(0);
+    let min_land = capital_balances
+        .iter()
+        .map(|capital| capital.reachable_land)
+        .min()
+        .unwrap_or(0);
+    let land_area_spread = if max_land > 0 {
+        (max_land - min_land) as f32 / max_land as f32
+    } else {
+        1.0
+    };
+
+    let unreachable = i32::MAX / 2;
+    let city_distance_spread = spread(
+        capital_balances
+            .iter()
+            .map(|capital| capital.nearest_city_distance.unwrap_or(unreachable)),
+    );
+    let port_distance_spread = spread(
+        capital_balances
+            .iter()
+            .map(|capital| capital.nearest_port_distance.unwrap_or(unreachable)),
+    );
+
+    let balanced = land_area_spread <= LAND_AREA_TOLERANCE
+        && city_distance_spread <= FEATURE_DISTANCE_TOLERANCE
+        && port_distance_spread <= FEATURE_DISTANCE_TOLERANCE;
+
+    MapBalance {
+        capitals: capital_balances,
+        land_area_spread,
+        city_distance_spread,
+        port_distance_spread,
+        balanced,
+        rerolls: 0,
+    }
+}
+
+fn balance_score(balance: &MapBalance) -> f32 {
+    balance.land_area_spread * 10.0
+        + balance.city_distance_spread.min(100) as f32
+        + balance.port_distance_spread.min(100) as f32
+}
+
+pub fn generate_balanced_map(
+    initial_seed: u32,
+    params: &MapGenParams,
+) -> (u32, GeneratedMap, MapBalance) {
+    let mut seed = initial_seed;
+    let mut reroll_rng = game_rng_new(initial_seed);
+    let mut best: Option<(u32, GeneratedMap, MapBalance)> = None;
+
+    for attempt in 0..MAX_BALANCE_ATTEMPTS {
+        let generated = generate_map(seed, params);
+        let mut balance = analyze_map_balance(&generated);
+        balance.rerolls = attempt;
+
+        if balance.balanced {
+            return (seed, generated, balance);
+        }
+
+        let is_better = best
+            .as_ref()
+            .map(|(_, _, best_balance)| balance_score(&balance) < balance_score(best_balance))
+            .unwrap_or(true);
+        if is_better {
+            best = Some((seed, generated, balance));
+        }
+
+        seed = game_rng_next_u32(&mut reroll_rng);
+    }
+
+    let (seed, generated, mut balance) = best.expect("at least one map attempt was generated");
+    balance.rerolls = MAX_BALANCE_ATTEMPTS - 1;
+    log::warn!(
+        "No balanced map found after {} attempts, using best seed {}",
+        MAX_BALANCE_ATTEMPTS,
+        seed
+    );
+    (seed, generated, balance)
+}
@@ -0,0 +1,265 @@
+use crate::constants::{MIN_MAP_HEIGHT, MIN_MAP_WIDTH};
+use crate::ecs::{ALL_FACTIONS, RuinBonus, TileType, faction_name};
+use crate::hex::{HexCoord, HexOrientation, hex_orientation_from_key, hex_orientation_key};
+use crate::map::{GeneratedMap, TileFeature};
+use crate::text_file::{TextFileError, check_file_header, faction_from_name, line_error};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const MAP_DIRECTORY: &str = "maps";
+const MAP_FILE_EXTENSION: &str = "hexmap";
+const MAP_FILE_HEADER: &str = "hexwar-map 1";
+
+pub type MapFileError = TextFileError;
+
+pub struct LoadedMapFile {
+    pub seed: u32,
+    pub generated: GeneratedMap,
+}
+
+pub fn tile_type_key(tile_type: TileType) -> &'static str {
+    match tile_type {
+        TileType::Sea => "sea",
+        TileType::Land => "land",
+        TileType::Forest => "forest",
+        TileType::Hills => "hills",
+        TileType::Swamp => "swamp",
+        TileType::City => "city",
+        TileType::Port => "port",
+        TileType::Capital => "capital",
+    }
+}
+
+pub fn tile_type_from_key(key: &str) -> Option<TileType> {
+    match key {
+        "sea" => Some(TileType::Sea),
+        "land" => Some(TileType::Land),
+        "forest" => Some(TileType::Forest),
+        "hills" => Some(TileType::Hills),
+        "swamp" => Some(TileType::Swamp),
+        "city" => Some(TileType::City),
+        "port" => Some(TileType::Port),
+        "capital" => Some(TileType::Capital),
+        _ => None,
+    }
+}
+
+fn feature_key(feature: TileFeature) -> String {
+    match feature {
+        TileFeature::Capital(faction) => format!("capital {}", faction_name(faction)),
+        TileFeature::City => "city".to_string(),
+        TileFeature::Port => "port".to_string(),
+        TileFeature::Ruin(RuinBonus::Soldiers) => "ruin soldiers".to_string(),
+        TileFeature::Ruin(RuinBonus::Morale) => "ruin morale".to_string(),
+        TileFeature::Airfield => "airfield".to_string(),
+    }
+}
+
+fn feature_from_parts(parts: &[&str]) -> Option<TileFeature> {
+    match parts {
+        ["city"] => Some(TileFeature::City),
+        ["port"] => Some(TileFeature::Port),
+        ["ruin", "soldiers"] => Some(TileFeature::Ruin(RuinBonus::Soldiers)),
+        ["ruin", "morale"] => Some(TileFeature::Ruin(RuinBonus::Morale)),
+        ["airfield"] => Some(TileFeature::Airfield),
+        ["capital", name] => faction_from_name(name).map(TileFeature::Capital),
+        _ => None,
+    }
+}
+
+pub fn map_file_to_string(seed: u32, generated: &GeneratedMap) -> String {
+    let mut output = String::new();
+    output.push_str(MAP_FILE_HEADER);
+    output.push('\n');
+    output.push_str(&format!("size {} {}\n", generated.width, generated.height));
+    output.push_str(&format!("seed {}\n", seed));
+    if generated.wrap_columns {
+        output.push_str("wrap\n");
+    }
+    if generated.orientation != HexOrientation::FlatTop {
+        output.push_str(&format!(
+            "orientation {}\n",
+            hex_orientation_key(generated.orientation)
+        ));
+    }
+
+    let mut tiles: Vec<(&HexCoord, &TileType)> = generated.tiles.iter().collect();
+    tiles.sort_by_key(|(coord, _)| (coord.row, coord.column));
+    for (coord, tile_type) in tiles {
+        output.push_str(&format!(
+            "tile {} {} {}\n",
+            coord.column,
+            coord.row,
+            tile_type_key(*tile_type)
+        ));
+    }
+
+    let mut features: Vec<(&HexCoord, &TileFeature)> = generated.features.iter().collect();
+    features.sort_by_key(|(coord, _)| (coord.row, coord.column));
+    for (coord, feature) in features {
+        output.push_str(&format!(
+            "feature {} {} {}\n",
+            coord.column,
+            coord.row,
+            feature_key(*feature)
+        ));
+    }
+
+    output
+}
+
+fn parse_coord(
+    line: usize,
+    size: Option<(i32, i32)>,
+    column: &str,
+    row: &str,
+) -> Result<HexCoord, MapFileError> {
+    let Some((width, height)) = size else {
+        return Err(line_error(line, "map size must be declared before tiles"));
+    };
+    let column = column
+        .parse::<i32>()
+        .map_err(|_| line_error(line, format!("invalid column '{}'", column)))?;
+    let row = row
+        .parse::<i32>()
+        .map_err(|_| line_error(line, format!("invalid row '{}'", row)))?;
+    if column < 0 || column >= width || row < 0 || row >= height {
+        return Err(line_error(line, "coordinate outside the map"));
+    }
+    Ok(HexCoord { column, row })
+}
+
+pub fn map_file_from_string(contents: &str) -> Result<LoadedMapFile, MapFileError> {
+    let mut lines = contents.lines().enumerate();
+
+    check_file_header(
+        lines.next().map(|(_, line)| line),
+        MAP_FILE_HEADER,
+        "map file",
+    )?;
+
+    let mut seed = 0;
+    let mut size: Option<(i32, i32)> = None;
+    let mut wrap_columns = false;
+    let mut orientation = HexOrientation::FlatTop;
+    let mut tiles: HashMap<HexCoord, TileType> = HashMap::new();
+    let mut features: HashMap<HexCoord, TileFeature> = HashMap::new();
+
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [] => {}
+            ["size", width, height] => {
+                let parsed = width.parse::<i32>().ok().zip(height.parse::<i32>().ok());
+                let supported = parsed
+                    .filter(|(width, height)| *width >= MIN_MAP_WIDTH && *height >= MIN_MAP_HEIGHT);
+                if supported.is_none() {
+                    return Err(line_error(
+                        line_number,
+                        format!("unsupported map size {}x{}", width, height),
+                    ));
+                }
+                size = supported;
+            }
+            ["seed", value] => {
+                seed = value
+                    .parse::<u32>()
+                    .map_err(|_| line_error(line_number, "invalid seed"))?;
+            }
+            ["wrap"] => wrap_columns = true,
+            ["orientation", key] => {
+                orientation = hex_orientation_from_key(key).ok_or_else(|| {
+                    line_error(line_number, format!("unknown orientation '{}'", key))
+                })?;
+            }
+            ["tile", column, row, key] => {
+                let coord = parse_coord(line_number, size, column, row)?;
+                let tile_type = tile_type_from_key(key).ok_or_else(|| {
+                    line_error(line_number, format!("unknown tile type '{}'", key))
+                })?;
+                tiles.insert(coord, tile_type);
+            }
+            ["feature", column, row, rest @ ..] => {
+                let coord = parse_coord(line_number, size, column, row)?;
+                let feature = feature_from_parts(rest)
+                    .ok_or_else(|| line_error(line_number, "unknown feature"))?;
+                features.insert(coord, feature);
+            }
+            _ => return Err(line_error(line_number, "unrecognized entry")),
+        }
+    }
+
+    let Some((width, height)) = size else {
+        return Err(line_error(0, "missing map size"));
+    };
+
+    if wrap_columns && width % 2 != 0 {
+        return Err(line_error(0, "wrapping maps need an even width"));
+    }
+
+    let expected_tiles = (width * height) as usize;
+    if tiles.len() != expected_tiles {
+        return Err(line_error(
+            0,
+            format!("expected {} tiles, found {}", expected_tiles, tiles.len()),
+        ));
+    }
+
+    let capital_count = features
+        .values()
+        .filter(|feature| matches!(feature, TileFeature::Capital(_)))
+        .count();
+    if capital_count != ALL_FACTIONS.len() {
+        return Err(line_error(
+            0,
+            format!(
+                "expected {} capitals, found {}",
+                ALL_FACTIONS.len(),
+                capital_count
+            ),
+        ));
+    }
+
+    Ok(LoadedMapFile {
+        seed,
+        generated: GeneratedMap {
+            width,
+            height,
+            tiles,
+            features,
+            wrap_columns,
+            orientation,
+        },
+    })
+}
+
+pub fn save_map_file(seed: u32, generated: &GeneratedMap) -> Result<PathBuf, MapFileError> {
+    std::fs::create_dir_all(MAP_DIRECTORY)?;
+    let path = Path::new(MAP_DIRECTORY).join(format!("map_{}.{}", seed, MAP_FILE_EXTENSION));
+    std::fs::write(&path, map_file_to_string(seed, generated))?;
+    Ok(path)
+}
+
+pub fn load_map_file(path: &Path) -> Result<LoadedMapFile, MapFileError> {
+    let contents = std::fs::read_to_string(path)?;
+    map_file_from_string(&contents)
+}
+
+pub fn list_saved_maps() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(MAP_DIRECTORY) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .map(|extension| extension == MAP_FILE_EXTENSION)
+                .unwrap_or(false)
+        })
+        .collect();
+    paths.sort();
+    paths
+}
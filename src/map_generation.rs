@@ -1,10 +1,18 @@
-use crate::constants::INITIAL_SOLDIERS;
-use crate::ecs::{GameWorld, TileType};
-use crate::hex::{HexCoord, hex_to_world_position};
-use crate::instancing::{InstancedTileGroup, create_instanced_tiles};
-use crate::map::{CAPITAL_POSITIONS, GeneratedMap, TileFeature, generate_map};
-use crate::rendering::generate_hex_outline;
-use crate::systems::spawn_unit;
+use crate::ecs::{
+    ALL_FACTIONS, Faction, GameWorld, General, HEX_POSITION, TILE, TileType, faction_index,
+    map_distance, record_ownership_change,
+};
+use crate::handicap::handicap_starting_soldiers;
+use crate::hex::{HexCoord, HexOrientation, hex_to_world_position, hex_wrap_copies, world_to_hex};
+use crate::instancing::{InstancedTiles, create_instanced_tiles};
+use crate::labels::{LabelPool, label_acquire, label_release, label_release_system};
+use crate::map::{GeneratedMap, TileFeature, map_wrap_width, settlement_seed};
+use crate::map_balance::generate_balanced_map;
+use crate::rendering::{generate_hex_outline, generate_hex_outline_with_color};
+use crate::systems::{
+    ImprovementVisuals, despawn_improvement_visuals, make_general, spawn_unit,
+    unit_visual_spawn_system,
+};
 use crate::tiles::spawn_tile;
 use nightshade::ecs::prefab::Prefab;
 use nightshade::ecs::world::components::Line;
@@ -17,31 +25,88 @@ use std::collections::HashMap;
 const HEX_OUTLINE_HEIGHT: f32 = 5.0;
 const SEA_EXTENSION: i32 = 30;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MapOutlineKey {
+    pub width: i32,
+    pub height: i32,
+    pub wrap_width: Option<i32>,
+    pub hex_width: f32,
+    pub hex_depth: f32,
+    pub orientation: HexOrientation,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MapOutlines {
+    pub key: MapOutlineKey,
+    pub lines_entity: Entity,
+    pub boundary_lines_entity: Entity,
+}
+
 pub struct MapEntities {
-    pub instanced_tile_groups: Vec<InstancedTileGroup>,
+    pub instanced_tiles: InstancedTiles,
     pub lines_entity: Entity,
     pub boundary_lines_entity: Entity,
     pub range_lines_entity: Entity,
+    pub movement_trail_entity: Entity,
+    pub port_route_entity: Entity,
     pub hover_outline_entity: Entity,
+    pub selection_outline_entity: Entity,
+    pub health_bar_entity: Entity,
+    pub general_marker_entity: Entity,
+    pub capital_marker_entity: Entity,
     pub port_label_entities: Vec<Entity>,
+    pub airfield_label_entities: Vec<Entity>,
+    pub city_label_entities: Vec<(HexCoord, Entity)>,
+    pub ruin_label_entities: Vec<(HexCoord, Entity)>,
+    pub improvement_visuals: ImprovementVisuals,
 }
 
 pub fn generate_game_map(
     game_world: &mut GameWorld,
     world: &mut World,
     tile_prefabs: &HashMap<TileType, Prefab>,
+    initial_seed: u32,
 ) -> MapEntities {
-    use crate::constants::{MAP_HEIGHT, MAP_WIDTH};
+    let (seed, generated, balance) =
+        generate_balanced_map(initial_seed, &game_world.resources.map_params);
+    game_world.resources.rng_seed = seed;
+    game_world.resources.settlement_seed = settlement_seed(seed);
+    game_world.resources.map_balance = balance;
+    game_world.resources.generated_map = generated;
+
+    spawn_game_map(game_world, world, tile_prefabs)
+}
 
-    game_world.resources.rng_seed = rand::rng().random();
-    let generated = generate_map(game_world.resources.rng_seed);
+pub fn spawn_game_map(
+    game_world: &mut GameWorld,
+    world: &mut World,
+    tile_prefabs: &HashMap<TileType, Prefab>,
+) -> MapEntities {
+    game_world.resources.tinted_owners.clear();
+    game_world.resources.scorched_tiles.clear();
+    populate_game_world(game_world);
+    unit_visual_spawn_system(game_world, world);
+
+    let generated = game_world.resources.generated_map.clone();
 
     let hex_width = game_world.resources.hex_width;
     let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let wrap_width = map_wrap_width(&generated);
 
-    let mut all_hex_lines: Vec<Line> = Vec::new();
     let mut tile_positions: Vec<(HexCoord, TileType)> = Vec::new();
     let mut port_coords: Vec<HexCoord> = Vec::new();
+    let mut city_coords: Vec<HexCoord> = Vec::new();
+    let ruin_coords: Vec<HexCoord> = generated
+        .features
+        .iter()
+        .filter_map(|(coord, feature)| matches!(feature, TileFeature::Ruin(_)).then_some(*coord))
+        .collect();
+    let airfield_coords: Vec<HexCoord> = generated
+        .features
+        .iter()
+        .filter_map(|(coord, feature)| (*feature == TileFeature::Airfield).then_some(*coord))
+        .collect();
 
     for (&coord, &base_type) in &generated.tiles {
         let tile_type = determine_tile_type(base_type, coord, &generated);
@@ -50,51 +115,172 @@ pub fn generate_game_map(
         if tile_type == TileType::Port {
             port_coords.push(coord);
         }
-
-        spawn_tile(game_world, coord, tile_type);
-
-        let position = hex_to_world_position(coord.column, coord.row, hex_width, hex_depth);
-        let hex_lines = generate_hex_outline(position, hex_width, hex_depth, HEX_OUTLINE_HEIGHT);
-        all_hex_lines.extend(hex_lines);
+        if matches!(tile_type, TileType::City | TileType::Capital) {
+            city_coords.push(coord);
+        }
     }
 
-    for column in -SEA_EXTENSION..(MAP_WIDTH + SEA_EXTENSION) {
-        for row in -SEA_EXTENSION..(MAP_HEIGHT + SEA_EXTENSION) {
-            let coord = HexCoord { column, row };
-            if generated.tiles.contains_key(&coord) {
-                continue;
-            }
+    let outline_key = MapOutlineKey {
+        width: generated.width,
+        height: generated.height,
+        wrap_width,
+        hex_width,
+        hex_depth,
+        orientation,
+    };
+    for coord in outline_coords(&outline_key) {
+        if !generated.tiles.contains_key(&coord) {
             tile_positions.push((coord, TileType::Sea));
-
-            let position = hex_to_world_position(coord.column, coord.row, hex_width, hex_depth);
-            let hex_lines =
-                generate_hex_outline(position, hex_width, hex_depth, HEX_OUTLINE_HEIGHT);
-            all_hex_lines.extend(hex_lines);
         }
     }
 
-    let instanced_tile_groups =
-        create_instanced_tiles(world, tile_prefabs, &tile_positions, hex_width, hex_depth);
-
-    let lines_entity = spawn_lines_entity(world, all_hex_lines);
-    let boundary_lines =
-        generate_playable_boundary_lines(MAP_WIDTH, MAP_HEIGHT, hex_width, hex_depth);
-    let boundary_lines_entity = spawn_lines_entity(world, boundary_lines);
+    let instanced_tiles = create_instanced_tiles(
+        world,
+        tile_prefabs,
+        &tile_positions,
+        hex_width,
+        hex_depth,
+        orientation,
+        wrap_width,
+    );
+
+    let outlines = match game_world.resources.map_outlines.take() {
+        Some(outlines) if outlines.key == outline_key => {
+            for entity in [outlines.lines_entity, outlines.boundary_lines_entity] {
+                if let Some(visibility) = world.get_visibility_mut(entity) {
+                    visibility.visible = true;
+                }
+            }
+            outlines
+        }
+        previous => {
+            if let Some(previous) = previous {
+                despawn_map_outlines(world, &previous);
+            }
+            spawn_map_outlines(world, outline_key)
+        }
+    };
+    game_world.resources.map_outlines = Some(outlines);
+    let lines_entity = outlines.lines_entity;
+    let boundary_lines_entity = outlines.boundary_lines_entity;
     let range_lines_entity = spawn_hidden_lines_entity(world);
     let hover_outline_entity = spawn_hidden_lines_entity(world);
-
-    let port_label_entities = spawn_port_labels(world, &port_coords, hex_width, hex_depth);
-
-    spawn_initial_units(game_world, world, hex_width, hex_depth);
+    let selection_outline_entity = spawn_hidden_lines_entity(world);
+    let health_bar_entity = spawn_hidden_lines_entity(world);
+    let general_marker_entity = spawn_hidden_lines_entity(world);
+    let capital_marker_entity = spawn_hidden_lines_entity(world);
+    let movement_trail_entity = spawn_hidden_lines_entity(world);
+    let port_route_entity = spawn_hidden_lines_entity(world);
+
+    let labels = &mut game_world.resources.labels;
+    let port_label_entities = spawn_port_labels(
+        labels,
+        world,
+        &port_coords,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
+    let city_label_entities = spawn_city_labels(
+        labels,
+        world,
+        &city_coords,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
+    let airfield_label_entities = spawn_airfield_labels(
+        labels,
+        world,
+        &airfield_coords,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
+    let ruin_label_entities = spawn_ruin_labels(
+        labels,
+        world,
+        &ruin_coords,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
 
     MapEntities {
-        instanced_tile_groups,
+        instanced_tiles,
         lines_entity,
         boundary_lines_entity,
         range_lines_entity,
+        movement_trail_entity,
+        port_route_entity,
         hover_outline_entity,
+        selection_outline_entity,
+        health_bar_entity,
+        general_marker_entity,
+        capital_marker_entity,
         port_label_entities,
+        airfield_label_entities,
+        city_label_entities,
+        ruin_label_entities,
+        improvement_visuals: ImprovementVisuals::default(),
+    }
+}
+
+fn outline_coords(key: &MapOutlineKey) -> Vec<HexCoord> {
+    let columns = if key.wrap_width.is_some() {
+        0..key.width
+    } else {
+        -SEA_EXTENSION..(key.width + SEA_EXTENSION)
+    };
+    columns
+        .flat_map(|column| {
+            (-SEA_EXTENSION..(key.height + SEA_EXTENSION)).map(move |row| HexCoord { column, row })
+        })
+        .collect()
+}
+
+fn spawn_map_outlines(world: &mut World, key: MapOutlineKey) -> MapOutlines {
+    let mut hex_lines: Vec<Line> = Vec::new();
+    for coord in outline_coords(&key) {
+        for placement in hex_wrap_copies(coord, key.wrap_width) {
+            let position = hex_to_world_position(
+                placement.column,
+                placement.row,
+                key.hex_width,
+                key.hex_depth,
+                key.orientation,
+            );
+            hex_lines.extend(generate_hex_outline(
+                position,
+                key.hex_width,
+                key.hex_depth,
+                key.orientation,
+                HEX_OUTLINE_HEIGHT,
+            ));
+        }
     }
+    let boundary_lines = generate_playable_boundary_lines(
+        key.width,
+        key.height,
+        key.hex_width,
+        key.hex_depth,
+        key.orientation,
+    );
+
+    MapOutlines {
+        key,
+        lines_entity: spawn_lines_entity(world, hex_lines),
+        boundary_lines_entity: spawn_lines_entity(world, boundary_lines),
+    }
+}
+
+fn despawn_map_outlines(world: &mut World, outlines: &MapOutlines) {
+    world.queue_command(WorldCommand::DespawnRecursive {
+        entity: outlines.lines_entity,
+    });
+    world.queue_command(WorldCommand::DespawnRecursive {
+        entity: outlines.boundary_lines_entity,
+    });
 }
 
 fn determine_tile_type(base_type: TileType, coord: HexCoord, generated: &GeneratedMap) -> TileType {
@@ -106,7 +292,7 @@ fn determine_tile_type(base_type: TileType, coord: HexCoord, generated: &Generat
         Some(TileFeature::Capital(_)) => TileType::Capital,
         Some(TileFeature::City) => TileType::City,
         Some(TileFeature::Port) => TileType::Port,
-        None => base_type,
+        Some(TileFeature::Ruin(_) | TileFeature::Airfield) | None => base_type,
     }
 }
 
@@ -122,7 +308,7 @@ fn spawn_lines_entity(world: &mut World, lines: Vec<Line>) -> Entity {
     entity
 }
 
-fn spawn_hidden_lines_entity(world: &mut World) -> Entity {
+pub fn spawn_hidden_lines_entity(world: &mut World) -> Entity {
     let entity = world.spawn_entities(
         LINES | VISIBILITY | LOCAL_TRANSFORM | GLOBAL_TRANSFORM | LOCAL_TRANSFORM_DIRTY,
         1,
@@ -140,19 +326,23 @@ fn spawn_hidden_lines_entity(world: &mut World) -> Entity {
 const PORT_LABEL_HEIGHT: f32 = 100.0;
 
 fn spawn_port_labels(
+    labels: &mut LabelPool,
     world: &mut World,
     port_coords: &[HexCoord],
     hex_width: f32,
     hex_depth: f32,
+    orientation: HexOrientation,
 ) -> Vec<Entity> {
     let mut entities = Vec::new();
 
     for coord in port_coords {
-        let position = hex_to_world_position(coord.column, coord.row, hex_width, hex_depth);
+        let position =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
         let label_position =
             nalgebra_glm::vec3(position.x, position.y + PORT_LABEL_HEIGHT, position.z);
 
-        let entity = spawn_3d_billboard_text_with_properties(
+        let entity = label_acquire(
+            labels,
             world,
             "PORT",
             label_position,
@@ -173,24 +363,207 @@ fn spawn_port_labels(
     entities
 }
 
-fn spawn_initial_units(
-    game_world: &mut GameWorld,
+const AIRFIELD_LABEL_HEIGHT: f32 = 100.0;
+
+fn spawn_airfield_labels(
+    labels: &mut LabelPool,
     world: &mut World,
+    airfield_coords: &[HexCoord],
     hex_width: f32,
     hex_depth: f32,
-) {
-    for (col, row, faction) in CAPITAL_POSITIONS {
-        let coord = HexCoord { column: col, row };
-        spawn_unit(
-            game_world,
+    orientation: HexOrientation,
+) -> Vec<Entity> {
+    let mut entities = Vec::new();
+
+    for coord in airfield_coords {
+        let position =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+        let label_position =
+            nalgebra_glm::vec3(position.x, position.y + AIRFIELD_LABEL_HEIGHT, position.z);
+
+        let entity = label_acquire(
+            labels,
             world,
-            coord,
-            hex_width,
-            hex_depth,
-            faction,
-            INITIAL_SOLDIERS,
+            "AIRFIELD",
+            label_position,
+            TextProperties {
+                font_size: 8000.0,
+                color: nalgebra_glm::vec4(0.85, 0.85, 0.95, 1.0),
+                alignment: TextAlignment::Center,
+                outline_width: 0.15,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                smoothing: 0.15,
+                ..Default::default()
+            },
         );
+
+        entities.push(entity);
     }
+
+    entities
+}
+
+const CITY_LABEL_HEIGHT: f32 = 60.0;
+
+fn spawn_city_labels(
+    labels: &mut LabelPool,
+    world: &mut World,
+    city_coords: &[HexCoord],
+    hex_width: f32,
+    hex_depth: f32,
+    orientation: HexOrientation,
+) -> Vec<(HexCoord, Entity)> {
+    let mut entities = Vec::new();
+
+    for coord in city_coords {
+        let position =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+        let label_position =
+            nalgebra_glm::vec3(position.x, position.y + CITY_LABEL_HEIGHT, position.z);
+
+        let entity = label_acquire(
+            labels,
+            world,
+            "",
+            label_position,
+            TextProperties {
+                font_size: 6000.0,
+                color: nalgebra_glm::vec4(1.0, 0.85, 0.4, 1.0),
+                alignment: TextAlignment::Center,
+                outline_width: 0.15,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                smoothing: 0.15,
+                ..Default::default()
+            },
+        );
+
+        entities.push((*coord, entity));
+    }
+
+    entities
+}
+
+const RUIN_LABEL_HEIGHT: f32 = 80.0;
+
+fn spawn_ruin_labels(
+    labels: &mut LabelPool,
+    world: &mut World,
+    ruin_coords: &[HexCoord],
+    hex_width: f32,
+    hex_depth: f32,
+    orientation: HexOrientation,
+) -> Vec<(HexCoord, Entity)> {
+    let mut entities = Vec::new();
+
+    for coord in ruin_coords {
+        let position =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+        let label_position =
+            nalgebra_glm::vec3(position.x, position.y + RUIN_LABEL_HEIGHT, position.z);
+
+        let entity = label_acquire(
+            labels,
+            world,
+            "RUINS",
+            label_position,
+            TextProperties {
+                font_size: 7000.0,
+                color: nalgebra_glm::vec4(0.8, 0.7, 0.55, 1.0),
+                alignment: TextAlignment::Center,
+                outline_width: 0.15,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                smoothing: 0.15,
+                ..Default::default()
+            },
+        );
+
+        entities.push((*coord, entity));
+    }
+
+    entities
+}
+
+fn spawn_initial_units(game_world: &mut GameWorld) {
+    let mut capitals: Vec<(HexCoord, Faction)> = game_world
+        .resources
+        .generated_map
+        .features
+        .iter()
+        .filter_map(|(&coord, feature)| match feature {
+            TileFeature::Capital(faction) => Some((coord, *faction)),
+            _ => None,
+        })
+        .collect();
+    capitals.sort_by_key(|(_, faction)| faction_index(*faction));
+
+    for &(coord, faction) in &capitals {
+        game_world.resources.capital_coords[faction_index(faction)] = coord;
+    }
+    game_world.resources.capital_relocated = [false; 4];
+
+    let rules = game_world.resources.rules;
+    for (coord, faction) in capitals {
+        let handicap = game_world.resources.handicaps[faction_index(faction)];
+        let soldiers = handicap_starting_soldiers(&rules, handicap);
+        let entity = spawn_unit(game_world, coord, faction, soldiers);
+        make_general(game_world, entity, General::default());
+    }
+}
+
+fn claim_starting_cities(game_world: &mut GameWorld) {
+    let mut cities: Vec<(HexCoord, freecs::Entity)> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let tile = game_world.get_tile(entity)?;
+            (tile.tile_type == TileType::City)
+                .then_some((game_world.get_hex_position(entity)?.0, entity))
+        })
+        .collect();
+    cities.sort_by_key(|(coord, _)| (coord.row, coord.column));
+
+    for faction in ALL_FACTIONS {
+        let index = faction_index(faction);
+        let count = game_world.resources.handicaps[index].cities as usize;
+        if count == 0 {
+            continue;
+        }
+        let capital = game_world.resources.capital_coords[index];
+        let mut unowned: Vec<(HexCoord, freecs::Entity)> = cities
+            .iter()
+            .copied()
+            .filter(|(_, entity)| {
+                game_world
+                    .get_tile(*entity)
+                    .is_some_and(|tile| tile.faction.is_none())
+            })
+            .collect();
+        unowned.sort_by_key(|(coord, _)| map_distance(&game_world.resources, capital, *coord));
+        for (coord, entity) in unowned.into_iter().take(count) {
+            if let Some(tile) = game_world.get_tile_mut(entity) {
+                tile.faction = Some(faction);
+            }
+            record_ownership_change(&mut game_world.resources, coord, Some(faction));
+        }
+    }
+}
+
+pub fn populate_game_world(game_world: &mut GameWorld) {
+    let generated = game_world.resources.generated_map.clone();
+
+    for (&coord, &base_type) in &generated.tiles {
+        let tile_type = determine_tile_type(base_type, coord, &generated);
+        let entity = spawn_tile(game_world, coord, tile_type);
+        if let Some(tile) = game_world.get_tile_mut(entity) {
+            match generated.features.get(&coord) {
+                Some(TileFeature::Ruin(bonus)) => tile.ruin = Some(*bonus),
+                Some(TileFeature::Airfield) => tile.airfield = true,
+                _ => {}
+            }
+        }
+    }
+
+    spawn_initial_units(game_world);
+    claim_starting_cities(game_world);
 }
 
 fn generate_playable_boundary_lines(
@@ -198,14 +571,13 @@ fn generate_playable_boundary_lines(
     map_height: i32,
     hex_width: f32,
     hex_depth: f32,
+    orientation: HexOrientation,
 ) -> Vec<Line> {
     let mut lines = Vec::new();
     let boundary_color = nalgebra_glm::vec4(1.0, 0.5, 0.0, 1.0);
     let boundary_y = 10.0;
 
-    let is_flat_top = hex_width > hex_depth;
-
-    if is_flat_top {
+    if orientation == HexOrientation::FlatTop {
         let half_width = hex_width / 2.0;
         let quarter_width = hex_width / 4.0;
         let half_depth = hex_depth / 2.0;
@@ -363,30 +735,82 @@ fn generate_playable_boundary_lines(
                 color: boundary_color,
             });
         }
+    } else {
+        let in_bounds = |coord: HexCoord| {
+            (0..map_width).contains(&coord.column) && (0..map_height).contains(&coord.row)
+        };
+        for row in 0..map_height {
+            for column in 0..map_width {
+                let center = hex_to_world_position(column, row, hex_width, hex_depth, orientation);
+                for line in generate_hex_outline_with_color(
+                    center,
+                    hex_width,
+                    hex_depth,
+                    orientation,
+                    boundary_y,
+                    boundary_color,
+                ) {
+                    let across = line.start + line.end - center;
+                    let neighbor =
+                        world_to_hex(across.x, across.z, hex_width, hex_depth, orientation);
+                    if !in_bounds(neighbor) {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
     }
 
     lines
 }
 
-pub fn despawn_map_entities(world: &mut World, entities: &mut MapEntities) {
-    for group in entities.instanced_tile_groups.drain(..) {
+pub fn despawn_map_entities(world: &mut World, entities: &mut MapEntities, labels: &mut LabelPool) {
+    for group in entities.instanced_tiles.groups.drain(..) {
         world.queue_command(WorldCommand::DespawnRecursive {
             entity: group.entity,
         });
     }
+    for entity in [entities.lines_entity, entities.boundary_lines_entity] {
+        if let Some(visibility) = world.get_visibility_mut(entity) {
+            visibility.visible = false;
+        }
+    }
     world.queue_command(WorldCommand::DespawnRecursive {
-        entity: entities.lines_entity,
+        entity: entities.range_lines_entity,
     });
     world.queue_command(WorldCommand::DespawnRecursive {
-        entity: entities.boundary_lines_entity,
+        entity: entities.movement_trail_entity,
     });
     world.queue_command(WorldCommand::DespawnRecursive {
-        entity: entities.range_lines_entity,
+        entity: entities.port_route_entity,
     });
     world.queue_command(WorldCommand::DespawnRecursive {
         entity: entities.hover_outline_entity,
     });
+    world.queue_command(WorldCommand::DespawnRecursive {
+        entity: entities.selection_outline_entity,
+    });
+    world.queue_command(WorldCommand::DespawnRecursive {
+        entity: entities.health_bar_entity,
+    });
+    world.queue_command(WorldCommand::DespawnRecursive {
+        entity: entities.general_marker_entity,
+    });
+    world.queue_command(WorldCommand::DespawnRecursive {
+        entity: entities.capital_marker_entity,
+    });
     for entity in entities.port_label_entities.drain(..) {
-        world.queue_command(WorldCommand::DespawnRecursive { entity });
+        label_release(labels, entity);
+    }
+    for entity in entities.airfield_label_entities.drain(..) {
+        label_release(labels, entity);
+    }
+    for (_, entity) in entities.city_label_entities.drain(..) {
+        label_release(labels, entity);
+    }
+    for (_, entity) in entities.ruin_label_entities.drain(..) {
+        label_release(labels, entity);
     }
+    label_release_system(labels, world);
+    despawn_improvement_visuals(world, &mut entities.improvement_visuals);
 }
@@ -1,4 +1,16 @@
-use crate::ecs::{Difficulty, Faction, faction_color, faction_name};
+use crate::display_settings::{DisplaySettings, display_mode_label, display_resolution_label};
+use crate::ecs::{
+    ALL_FACTIONS, CasualtyStats, Difficulty, Faction, difficulty_key, faction_color, faction_index,
+    faction_name,
+};
+use crate::handicap::{Handicap, HandicapKind, handicap_starting_soldiers};
+use crate::hud_text::spawn_scaled_hud_text;
+use crate::map_balance::MapBalance;
+use crate::rules::{ALL_RULES, GameRules, RuleKind, rule_label, rule_value_label};
+use crate::savegame::IRONMAN_SAVE_PATH;
+use crate::teams::{TeamLayout, team_layout_name};
+use crate::turn_timer::turn_timer_label;
+use crate::ui_scale::UiScale;
 use nightshade::prelude::*;
 
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
@@ -6,9 +18,15 @@ pub enum MenuState {
     #[default]
     MainMenu,
     MapSetup,
+    Lobby,
+    Statistics,
+    HallOfFame,
+    Settings,
     Playing,
     Paused,
     GameOver,
+    MapReview,
+    SaveBrowser,
 }
 
 #[derive(Default)]
@@ -19,24 +37,93 @@ pub struct MenuData {
     pub pause_menu_buttons: Vec<MenuButton>,
     pub game_over_buttons: Vec<MenuButton>,
     pub difficulty_buttons: Vec<MenuButton>,
+    pub rules_buttons: Vec<MenuButton>,
+    pub handicap_buttons: Vec<MenuButton>,
+    pub lobby_buttons: Vec<MenuButton>,
+    pub report_buttons: Vec<MenuButton>,
+    pub settings_buttons: Vec<MenuButton>,
+    pub report_entities: Vec<Entity>,
+    pub lobby_is_host: bool,
+    pub rules_label_entities: Vec<Entity>,
+    pub handicap_label_entities: Vec<Entity>,
     pub title_entity: Option<Entity>,
     pub subtitle_entity: Option<Entity>,
     pub difficulty_label_entity: Option<Entity>,
+    pub balance_entities: Vec<Entity>,
+    pub status_entity: Option<Entity>,
+    pub saved_map_index: usize,
     pub game_over_winner: Option<Faction>,
     pub hovered_button_index: Option<usize>,
     pub hovered_difficulty_index: Option<usize>,
+    pub hovered_rules_index: Option<usize>,
+    pub hovered_handicap_index: Option<usize>,
     pub selected_difficulty: Difficulty,
+    pub faction_difficulties: [Difficulty; 4],
+    pub team_layout: TeamLayout,
+    pub ironman: bool,
+    pub turn_seconds: u32,
+    pub random_capitals: bool,
+    pub wrap_columns: bool,
+    pub ironman_save_available: bool,
+    pub ui_scale: UiScale,
 }
 
 pub enum MenuAction {
     None,
     StartGame,
+    StartSpectating,
+    StartTutorial,
     EnterMapSetup,
     RegenerateMap,
+    RerollTerrain,
+    RerollSettlements,
+    SaveMap,
+    LoadMap,
     ResumeGame,
     ReturnToMainMenu,
+    Concede,
+    ReviewMap,
+    EndMapReview,
     QuitGame,
+    HostGame,
+    JoinGame,
+    ShowStatistics,
+    ShowHallOfFame,
+    ShowSettings,
+    ClaimFaction(Faction),
+    SpectateLobby,
+    StartNetworkGame,
     SetDifficulty(Difficulty),
+    CycleFactionDifficulty(Faction),
+    CycleTeams,
+    ToggleIronman,
+    CycleTurnTimer,
+    ToggleRandomCapitals,
+    ToggleWrapColumns,
+    ContinueIronman,
+    ShowAutosaves,
+    LoadAutosave(usize),
+    ToggleAttackWarnings,
+    ToggleFullscreen,
+    CycleResolution,
+    ToggleVsync,
+    ToggleBattleCinematics,
+    ToggleScreenShake,
+    ToggleBattleScars,
+    AdjustRule(RuleKind, i32),
+    AdjustHandicap(Faction, HandicapKind, i32),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FactionStanding {
+    pub faction: Faction,
+    pub victorious: bool,
+    pub allied_to_winner: bool,
+    pub eliminated: bool,
+    pub territory: usize,
+    pub armies: usize,
+    pub soldiers: i32,
+    pub casualties: CasualtyStats,
 }
 
 pub struct MenuButton {
@@ -59,6 +146,12 @@ pub fn despawn_menu_elements(menu: &mut MenuData, world: &mut World) {
     if let Some(entity) = menu.difficulty_label_entity.take() {
         world.despawn_entities(&[entity]);
     }
+    if let Some(entity) = menu.status_entity.take() {
+        world.despawn_entities(&[entity]);
+    }
+    for entity in menu.balance_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
     for button in menu.main_menu_buttons.drain(..) {
         world.despawn_entities(&[button.entity]);
     }
@@ -74,6 +167,30 @@ pub fn despawn_menu_elements(menu: &mut MenuData, world: &mut World) {
     for button in menu.difficulty_buttons.drain(..) {
         world.despawn_entities(&[button.entity]);
     }
+    for button in menu.rules_buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+    for entity in menu.rules_label_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
+    for button in menu.handicap_buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+    for entity in menu.handicap_label_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
+    for button in menu.lobby_buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+    for button in menu.report_buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+    for button in menu.settings_buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+    for entity in menu.report_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
 }
 
 pub fn setup_main_menu(menu: &mut MenuData, world: &mut World) {
@@ -88,31 +205,101 @@ pub fn setup_main_menu(menu: &mut MenuData, world: &mut World) {
         ..Default::default()
     };
 
-    menu.title_entity = Some(spawn_hud_text_with_properties(
+    menu.title_entity = Some(spawn_scaled_hud_text(
         world,
         "HEX WAR",
         HudAnchor::Center,
         nalgebra_glm::vec2(0.0, -100.0),
         title_props,
+        menu.ui_scale.scale,
     ));
 
+    menu.ironman_save_available = std::path::Path::new(IRONMAN_SAVE_PATH).exists();
+    if menu.ironman_save_available {
+        menu.main_menu_buttons.push(create_button(
+            world,
+            "CONTINUE IRON-MAN",
+            nalgebra_glm::vec2(0.0, -45.0),
+            HudAnchor::Center,
+            32.0,
+            menu.ui_scale.scale,
+        ));
+    }
     menu.main_menu_buttons.push(create_button(
         world,
         "NEW GAME",
         nalgebra_glm::vec2(0.0, 0.0),
         HudAnchor::Center,
         48.0,
+        menu.ui_scale.scale,
+    ));
+    menu.main_menu_buttons.push(create_button(
+        world,
+        "TUTORIAL",
+        nalgebra_glm::vec2(0.0, 50.0),
+        HudAnchor::Center,
+        48.0,
+        menu.ui_scale.scale,
+    ));
+    menu.main_menu_buttons.push(create_button(
+        world,
+        "LOAD AUTOSAVE",
+        nalgebra_glm::vec2(0.0, 95.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+    menu.main_menu_buttons.push(create_button(
+        world,
+        "HOST GAME",
+        nalgebra_glm::vec2(0.0, 140.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+    menu.main_menu_buttons.push(create_button(
+        world,
+        "JOIN GAME",
+        nalgebra_glm::vec2(0.0, 185.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+    menu.main_menu_buttons.push(create_button(
+        world,
+        "STATISTICS",
+        nalgebra_glm::vec2(0.0, 230.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+    menu.main_menu_buttons.push(create_button(
+        world,
+        "HALL OF FAME",
+        nalgebra_glm::vec2(0.0, 275.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+    menu.main_menu_buttons.push(create_button(
+        world,
+        "SETTINGS",
+        nalgebra_glm::vec2(0.0, 320.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
     ));
     menu.main_menu_buttons.push(create_button(
         world,
         "QUIT",
-        nalgebra_glm::vec2(0.0, 60.0),
+        nalgebra_glm::vec2(0.0, 370.0),
         HudAnchor::Center,
         48.0,
+        menu.ui_scale.scale,
     ));
 }
 
-pub fn setup_pause_menu(menu: &mut MenuData, world: &mut World) {
+pub fn setup_report_menu(menu: &mut MenuData, world: &mut World, title: &str, lines: &[String]) {
     despawn_menu_elements(menu, world);
 
     let title_props = TextProperties {
@@ -124,20 +311,279 @@ pub fn setup_pause_menu(menu: &mut MenuData, world: &mut World) {
         ..Default::default()
     };
 
-    menu.title_entity = Some(spawn_hud_text_with_properties(
+    menu.title_entity = Some(spawn_scaled_hud_text(
+        world,
+        title,
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, -230.0),
+        title_props,
+        menu.ui_scale.scale,
+    ));
+
+    for (index, line) in lines.iter().enumerate() {
+        menu.report_entities.push(spawn_scaled_hud_text(
+            world,
+            line,
+            HudAnchor::Center,
+            nalgebra_glm::vec2(0.0, -160.0 + index as f32 * 26.0),
+            TextProperties {
+                font_size: 22.0,
+                color: nalgebra_glm::vec4(0.9, 0.9, 0.9, 1.0),
+                alignment: TextAlignment::Center,
+                outline_width: 0.05,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                ..Default::default()
+            },
+            menu.ui_scale.scale,
+        ));
+    }
+
+    menu.report_buttons.push(create_button(
+        world,
+        "BACK",
+        nalgebra_glm::vec2(0.0, 200.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+}
+
+pub fn report_menu_system(
+    menu: &mut MenuData,
+    world: &mut World,
+    screen_width: f32,
+    screen_height: f32,
+) -> MenuAction {
+    let mouse_x = world.resources.input.mouse.position.x;
+    let mouse_y = world.resources.input.mouse.position.y;
+    let clicked = world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::LEFT_JUST_RELEASED);
+
+    menu.hovered_button_index = update_buttons_hover(
+        &menu.report_buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        menu.hovered_button_index,
+    );
+
+    if clicked && menu.hovered_button_index == Some(0) {
+        return MenuAction::ReturnToMainMenu;
+    }
+
+    MenuAction::None
+}
+
+pub fn setup_settings_menu(menu: &mut MenuData, world: &mut World, display: &DisplaySettings) {
+    despawn_menu_elements(menu, world);
+
+    let title_props = TextProperties {
+        font_size: 56.0,
+        color: nalgebra_glm::vec4(1.0, 0.5, 0.2, 1.0),
+        alignment: TextAlignment::Center,
+        outline_width: 0.06,
+        outline_color: nalgebra_glm::vec4(0.3, 0.1, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    menu.title_entity = Some(spawn_scaled_hud_text(
+        world,
+        "SETTINGS",
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, -150.0),
+        title_props,
+        menu.ui_scale.scale,
+    ));
+
+    menu.subtitle_entity = Some(spawn_scaled_hud_text(
+        world,
+        "[ALT+ENTER] Toggle fullscreen",
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, -100.0),
+        TextProperties {
+            font_size: 22.0,
+            color: nalgebra_glm::vec4(0.8, 0.8, 0.8, 1.0),
+            alignment: TextAlignment::Center,
+            outline_width: 0.05,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        },
+        menu.ui_scale.scale,
+    ));
+
+    menu.settings_buttons.push(create_button(
+        world,
+        &format!("DISPLAY: {}", display_mode_label(display)),
+        nalgebra_glm::vec2(0.0, -40.0),
+        HudAnchor::Center,
+        36.0,
+        menu.ui_scale.scale,
+    ));
+    menu.settings_buttons.push(create_button(
+        world,
+        &format!("RESOLUTION: {}", display_resolution_label(display)),
+        nalgebra_glm::vec2(0.0, 15.0),
+        HudAnchor::Center,
+        36.0,
+        menu.ui_scale.scale,
+    ));
+    menu.settings_buttons.push(create_button(
+        world,
+        if display.vsync {
+            "VSYNC: ON"
+        } else {
+            "VSYNC: OFF"
+        },
+        nalgebra_glm::vec2(0.0, 70.0),
+        HudAnchor::Center,
+        36.0,
+        menu.ui_scale.scale,
+    ));
+    menu.settings_buttons.push(create_button(
+        world,
+        if display.battle_cinematics {
+            "BATTLE CINEMATICS: ON"
+        } else {
+            "BATTLE CINEMATICS: OFF"
+        },
+        nalgebra_glm::vec2(0.0, 125.0),
+        HudAnchor::Center,
+        36.0,
+        menu.ui_scale.scale,
+    ));
+    menu.settings_buttons.push(create_button(
+        world,
+        if display.screen_shake {
+            "SCREEN SHAKE: ON"
+        } else {
+            "SCREEN SHAKE: OFF"
+        },
+        nalgebra_glm::vec2(0.0, 180.0),
+        HudAnchor::Center,
+        36.0,
+        menu.ui_scale.scale,
+    ));
+    menu.settings_buttons.push(create_button(
+        world,
+        if display.battle_scars {
+            "BATTLE SCARS: ON"
+        } else {
+            "BATTLE SCARS: OFF"
+        },
+        nalgebra_glm::vec2(0.0, 235.0),
+        HudAnchor::Center,
+        36.0,
+        menu.ui_scale.scale,
+    ));
+    menu.settings_buttons.push(create_button(
+        world,
+        "BACK",
+        nalgebra_glm::vec2(0.0, 315.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+}
+
+pub fn settings_menu_system(
+    menu: &mut MenuData,
+    world: &mut World,
+    screen_width: f32,
+    screen_height: f32,
+) -> MenuAction {
+    let mouse_x = world.resources.input.mouse.position.x;
+    let mouse_y = world.resources.input.mouse.position.y;
+    let clicked = world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::LEFT_JUST_RELEASED);
+
+    menu.hovered_button_index = update_buttons_hover(
+        &menu.settings_buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        menu.hovered_button_index,
+    );
+
+    if clicked && let Some(index) = menu.hovered_button_index {
+        return match index {
+            0 => MenuAction::ToggleFullscreen,
+            1 => MenuAction::CycleResolution,
+            2 => MenuAction::ToggleVsync,
+            3 => MenuAction::ToggleBattleCinematics,
+            4 => MenuAction::ToggleScreenShake,
+            5 => MenuAction::ToggleBattleScars,
+            6 => MenuAction::ReturnToMainMenu,
+            _ => MenuAction::None,
+        };
+    }
+
+    MenuAction::None
+}
+
+pub fn setup_pause_menu(
+    menu: &mut MenuData,
+    world: &mut World,
+    ironman: bool,
+    attack_warnings: bool,
+    can_concede: bool,
+) {
+    despawn_menu_elements(menu, world);
+
+    let title_props = TextProperties {
+        font_size: 56.0,
+        color: nalgebra_glm::vec4(1.0, 0.5, 0.2, 1.0),
+        alignment: TextAlignment::Center,
+        outline_width: 0.06,
+        outline_color: nalgebra_glm::vec4(0.3, 0.1, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    menu.title_entity = Some(spawn_scaled_hud_text(
         world,
         "PAUSED",
         HudAnchor::Center,
         nalgebra_glm::vec2(0.0, -100.0),
         title_props,
+        menu.ui_scale.scale,
     ));
 
+    if ironman {
+        menu.subtitle_entity = Some(spawn_scaled_hud_text(
+            world,
+            "IRON-MAN: progress is autosaved every turn",
+            HudAnchor::Center,
+            nalgebra_glm::vec2(0.0, -60.0),
+            TextProperties {
+                font_size: 22.0,
+                color: nalgebra_glm::vec4(1.0, 0.4, 0.3, 1.0),
+                alignment: TextAlignment::Center,
+                outline_width: 0.05,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                ..Default::default()
+            },
+            menu.ui_scale.scale,
+        ));
+    }
+
     menu.pause_menu_buttons.push(create_button(
         world,
         "RESUME",
         nalgebra_glm::vec2(0.0, -20.0),
         HudAnchor::Center,
         40.0,
+        menu.ui_scale.scale,
     ));
     menu.pause_menu_buttons.push(create_button(
         world,
@@ -145,7 +591,30 @@ pub fn setup_pause_menu(menu: &mut MenuData, world: &mut World) {
         nalgebra_glm::vec2(0.0, 40.0),
         HudAnchor::Center,
         40.0,
+        menu.ui_scale.scale,
     ));
+    menu.pause_menu_buttons.push(create_button(
+        world,
+        if attack_warnings {
+            "ATTACK WARNINGS: ON"
+        } else {
+            "ATTACK WARNINGS: OFF"
+        },
+        nalgebra_glm::vec2(0.0, 100.0),
+        HudAnchor::Center,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+    if can_concede {
+        menu.pause_menu_buttons.push(create_button(
+            world,
+            "CONCEDE DEFEAT",
+            nalgebra_glm::vec2(0.0, 150.0),
+            HudAnchor::Center,
+            28.0,
+            menu.ui_scale.scale,
+        ));
+    }
 }
 
 pub fn main_menu_system(
@@ -174,9 +643,20 @@ pub fn main_menu_system(
     );
 
     if clicked && let Some(index) = menu.hovered_button_index {
-        return match index {
+        let offset = menu.ironman_save_available as usize;
+        if index < offset {
+            return MenuAction::ContinueIronman;
+        }
+        return match index - offset {
             0 => MenuAction::EnterMapSetup,
-            1 => MenuAction::QuitGame,
+            1 => MenuAction::StartTutorial,
+            2 => MenuAction::ShowAutosaves,
+            3 => MenuAction::HostGame,
+            4 => MenuAction::JoinGame,
+            5 => MenuAction::ShowStatistics,
+            6 => MenuAction::ShowHallOfFame,
+            7 => MenuAction::ShowSettings,
+            8 => MenuAction::QuitGame,
             _ => MenuAction::None,
         };
     }
@@ -196,12 +676,13 @@ pub fn setup_map_setup_menu(menu: &mut MenuData, world: &mut World) {
         ..Default::default()
     };
 
-    menu.title_entity = Some(spawn_hud_text_with_properties(
+    menu.title_entity = Some(spawn_scaled_hud_text(
         world,
         "MAP SETUP",
         HudAnchor::Center,
         nalgebra_glm::vec2(0.0, -150.0),
         title_props,
+        menu.ui_scale.scale,
     ));
 
     let difficulty_label_props = TextProperties {
@@ -213,38 +694,65 @@ pub fn setup_map_setup_menu(menu: &mut MenuData, world: &mut World) {
         ..Default::default()
     };
 
-    menu.difficulty_label_entity = Some(spawn_hud_text_with_properties(
+    menu.difficulty_label_entity = Some(spawn_scaled_hud_text(
         world,
         "DIFFICULTY",
         HudAnchor::Center,
         nalgebra_glm::vec2(0.0, -100.0),
         difficulty_label_props,
+        menu.ui_scale.scale,
     ));
 
     menu.difficulty_buttons.push(create_difficulty_button(
         world,
         "EASY",
-        nalgebra_glm::vec2(-120.0, -60.0),
+        nalgebra_glm::vec2(-180.0, -60.0),
         HudAnchor::Center,
         32.0,
         menu.selected_difficulty == Difficulty::Easy,
+        menu.ui_scale.scale,
     ));
     menu.difficulty_buttons.push(create_difficulty_button(
         world,
         "NORMAL",
-        nalgebra_glm::vec2(0.0, -60.0),
+        nalgebra_glm::vec2(-60.0, -60.0),
         HudAnchor::Center,
         32.0,
         menu.selected_difficulty == Difficulty::Normal,
+        menu.ui_scale.scale,
     ));
     menu.difficulty_buttons.push(create_difficulty_button(
         world,
         "HARD",
-        nalgebra_glm::vec2(120.0, -60.0),
+        nalgebra_glm::vec2(60.0, -60.0),
         HudAnchor::Center,
         32.0,
         menu.selected_difficulty == Difficulty::Hard,
+        menu.ui_scale.scale,
+    ));
+    menu.difficulty_buttons.push(create_difficulty_button(
+        world,
+        "BRUTAL",
+        nalgebra_glm::vec2(180.0, -60.0),
+        HudAnchor::Center,
+        32.0,
+        menu.selected_difficulty == Difficulty::Brutal,
+        menu.ui_scale.scale,
     ));
+    for (index, faction) in ALL_FACTIONS.iter().enumerate() {
+        menu.difficulty_buttons.push(create_button(
+            world,
+            &format!(
+                "{}: {}",
+                faction_name(*faction).to_uppercase(),
+                difficulty_key(menu.faction_difficulties[faction_index(*faction)]).to_uppercase()
+            ),
+            nalgebra_glm::vec2(-240.0 + index as f32 * 160.0, -28.0),
+            HudAnchor::Center,
+            20.0,
+            menu.ui_scale.scale,
+        ));
+    }
 
     menu.map_setup_buttons.push(create_button(
         world,
@@ -252,6 +760,7 @@ pub fn setup_map_setup_menu(menu: &mut MenuData, world: &mut World) {
         nalgebra_glm::vec2(0.0, 0.0),
         HudAnchor::Center,
         48.0,
+        menu.ui_scale.scale,
     ));
     menu.map_setup_buttons.push(create_button(
         world,
@@ -259,6 +768,7 @@ pub fn setup_map_setup_menu(menu: &mut MenuData, world: &mut World) {
         nalgebra_glm::vec2(0.0, 60.0),
         HudAnchor::Center,
         48.0,
+        menu.ui_scale.scale,
     ));
     menu.map_setup_buttons.push(create_button(
         world,
@@ -266,9 +776,320 @@ pub fn setup_map_setup_menu(menu: &mut MenuData, world: &mut World) {
         nalgebra_glm::vec2(0.0, 120.0),
         HudAnchor::Center,
         40.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        "SAVE MAP",
+        nalgebra_glm::vec2(-230.0, 0.0),
+        HudAnchor::Center,
+        32.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        "LOAD MAP",
+        nalgebra_glm::vec2(230.0, 0.0),
+        HudAnchor::Center,
+        32.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        "SPECTATE",
+        nalgebra_glm::vec2(0.0, 175.0),
+        HudAnchor::Center,
+        32.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        &format!("TEAMS: {}", team_layout_name(menu.team_layout)),
+        nalgebra_glm::vec2(-160.0, 230.0),
+        HudAnchor::Center,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        if menu.random_capitals {
+            "CAPITALS: RANDOM"
+        } else {
+            "CAPITALS: FIXED"
+        },
+        nalgebra_glm::vec2(160.0, 230.0),
+        HudAnchor::Center,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        if menu.ironman {
+            "IRON-MAN: ON"
+        } else {
+            "IRON-MAN: OFF"
+        },
+        nalgebra_glm::vec2(-160.0, 270.0),
+        HudAnchor::Center,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        &turn_timer_label(menu.turn_seconds),
+        nalgebra_glm::vec2(160.0, 270.0),
+        HudAnchor::Center,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        if menu.wrap_columns {
+            "MAP WRAP: ON"
+        } else {
+            "MAP WRAP: OFF"
+        },
+        nalgebra_glm::vec2(-230.0, 60.0),
+        HudAnchor::Center,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        "REROLL TERRAIN",
+        nalgebra_glm::vec2(230.0, 60.0),
+        HudAnchor::Center,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+    menu.map_setup_buttons.push(create_button(
+        world,
+        "REROLL CITIES/PORTS",
+        nalgebra_glm::vec2(230.0, 120.0),
+        HudAnchor::Center,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+}
+
+pub fn show_menu_status(menu: &mut MenuData, world: &mut World, message: &str) {
+    if let Some(entity) = menu.status_entity.take() {
+        world.despawn_entities(&[entity]);
+    }
+
+    menu.status_entity = Some(spawn_scaled_hud_text(
+        world,
+        message,
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, 320.0),
+        TextProperties {
+            font_size: 22.0,
+            color: nalgebra_glm::vec4(0.8, 0.8, 0.8, 1.0),
+            alignment: TextAlignment::Center,
+            outline_width: 0.05,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        },
+        menu.ui_scale.scale,
     ));
 }
 
+pub fn show_game_rules(menu: &mut MenuData, world: &mut World, rules: &GameRules) {
+    for button in menu.rules_buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+    for entity in menu.rules_label_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
+    menu.hovered_rules_index = None;
+
+    menu.rules_label_entities.push(spawn_scaled_hud_text(
+        world,
+        "GAME RULES",
+        HudAnchor::TopRight,
+        nalgebra_glm::vec2(-160.0, 20.0),
+        TextProperties {
+            font_size: 22.0,
+            color: nalgebra_glm::vec4(0.8, 1.0, 0.8, 1.0),
+            alignment: TextAlignment::Center,
+            outline_width: 0.05,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        },
+        menu.ui_scale.scale,
+    ));
+
+    for (index, rule) in ALL_RULES.iter().enumerate() {
+        let y = 55.0 + index as f32 * 30.0;
+        let line = format!("{}  {}", rule_label(*rule), rule_value_label(rules, *rule));
+        menu.rules_label_entities.push(spawn_scaled_hud_text(
+            world,
+            &line,
+            HudAnchor::TopRight,
+            nalgebra_glm::vec2(-200.0, y),
+            TextProperties {
+                font_size: 18.0,
+                color: nalgebra_glm::vec4(0.85, 0.85, 0.85, 1.0),
+                alignment: TextAlignment::Center,
+                outline_width: 0.05,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                ..Default::default()
+            },
+            menu.ui_scale.scale,
+        ));
+        menu.rules_buttons.push(create_button(
+            world,
+            "-",
+            nalgebra_glm::vec2(-70.0, y),
+            HudAnchor::TopRight,
+            26.0,
+            menu.ui_scale.scale,
+        ));
+        menu.rules_buttons.push(create_button(
+            world,
+            "+",
+            nalgebra_glm::vec2(-35.0, y),
+            HudAnchor::TopRight,
+            26.0,
+            menu.ui_scale.scale,
+        ));
+    }
+}
+
+pub fn show_handicaps(
+    menu: &mut MenuData,
+    world: &mut World,
+    rules: &GameRules,
+    handicaps: &[Handicap; 4],
+) {
+    for button in menu.handicap_buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+    for entity in menu.handicap_label_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
+    menu.hovered_handicap_index = None;
+
+    let text_props = |font_size: f32, color: Vec4| TextProperties {
+        font_size,
+        color,
+        alignment: TextAlignment::Left,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    menu.handicap_label_entities.push(spawn_scaled_hud_text(
+        world,
+        "HANDICAPS",
+        HudAnchor::BottomLeft,
+        nalgebra_glm::vec2(15.0, -160.0),
+        text_props(22.0, nalgebra_glm::vec4(0.8, 1.0, 0.8, 1.0)),
+        menu.ui_scale.scale,
+    ));
+
+    for (index, faction) in ALL_FACTIONS.iter().enumerate() {
+        let y = -130.0 + index as f32 * 30.0;
+        let handicap = handicaps[faction_index(*faction)];
+        let color = faction_color(*faction);
+        let labels = [
+            (faction_name(*faction).to_uppercase(), 15.0),
+            (
+                format!("ARMY {}", handicap_starting_soldiers(rules, handicap)),
+                140.0,
+            ),
+            (format!("CITIES {}", handicap.cities), 310.0),
+        ];
+        for (label, x) in labels {
+            menu.handicap_label_entities.push(spawn_scaled_hud_text(
+                world,
+                &label,
+                HudAnchor::BottomLeft,
+                nalgebra_glm::vec2(x, y),
+                text_props(18.0, nalgebra_glm::vec4(color[0], color[1], color[2], 1.0)),
+                menu.ui_scale.scale,
+            ));
+        }
+        for (label, x) in [("-", 250.0), ("+", 280.0), ("-", 420.0), ("+", 450.0)] {
+            menu.handicap_buttons.push(create_button(
+                world,
+                label,
+                nalgebra_glm::vec2(x, y),
+                HudAnchor::BottomLeft,
+                26.0,
+                menu.ui_scale.scale,
+            ));
+        }
+    }
+}
+
+fn format_distance(distance: Option<i32>) -> String {
+    distance
+        .map(|distance| distance.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+pub fn show_map_balance(menu: &mut MenuData, world: &mut World, balance: &MapBalance) {
+    for entity in menu.balance_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
+
+    let header_color = if balance.balanced {
+        nalgebra_glm::vec4(0.6, 1.0, 0.6, 1.0)
+    } else {
+        nalgebra_glm::vec4(1.0, 0.6, 0.3, 1.0)
+    };
+    let header_text = if balance.balanced {
+        format!("MAP BALANCE: FAIR ({} rerolls)", balance.rerolls)
+    } else {
+        format!("MAP BALANCE: UNEVEN ({} rerolls)", balance.rerolls)
+    };
+
+    menu.balance_entities.push(spawn_scaled_hud_text(
+        world,
+        &header_text,
+        HudAnchor::TopLeft,
+        nalgebra_glm::vec2(15.0, 15.0),
+        TextProperties {
+            font_size: 22.0,
+            color: header_color,
+            alignment: TextAlignment::Left,
+            outline_width: 0.05,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        },
+        menu.ui_scale.scale,
+    ));
+
+    for (index, capital) in balance.capitals.iter().enumerate() {
+        let color = faction_color(capital.faction);
+        let line = format!(
+            "{}: land {}  city {}  port {}  chokepoints {}",
+            faction_name(capital.faction),
+            capital.reachable_land,
+            format_distance(capital.nearest_city_distance),
+            format_distance(capital.nearest_port_distance),
+            capital.chokepoints,
+        );
+
+        menu.balance_entities.push(spawn_scaled_hud_text(
+            world,
+            &line,
+            HudAnchor::TopLeft,
+            nalgebra_glm::vec2(15.0, 45.0 + index as f32 * 24.0),
+            TextProperties {
+                font_size: 18.0,
+                color: nalgebra_glm::vec4(color[0], color[1], color[2], 1.0),
+                alignment: TextAlignment::Left,
+                outline_width: 0.05,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                ..Default::default()
+            },
+            menu.ui_scale.scale,
+        ));
+    }
+}
+
 pub fn map_setup_system(
     menu: &mut MenuData,
     world: &mut World,
@@ -304,12 +1125,58 @@ pub fn map_setup_system(
         menu.hovered_difficulty_index,
     );
 
+    menu.hovered_rules_index = update_buttons_hover(
+        &menu.rules_buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        menu.hovered_rules_index,
+    );
+
+    menu.hovered_handicap_index = update_buttons_hover(
+        &menu.handicap_buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        menu.hovered_handicap_index,
+    );
+
     if clicked {
+        if let Some(index) = menu.hovered_handicap_index
+            && let Some(faction) = ALL_FACTIONS.get(index / 4)
+        {
+            let kind = if index % 4 < 2 {
+                HandicapKind::Soldiers
+            } else {
+                HandicapKind::Cities
+            };
+            let delta = if index % 2 == 0 { -1 } else { 1 };
+            return MenuAction::AdjustHandicap(*faction, kind, delta);
+        }
+
+        if let Some(index) = menu.hovered_rules_index
+            && let Some(rule) = ALL_RULES.get(index / 2)
+        {
+            let delta = if index % 2 == 0 { -1 } else { 1 };
+            return MenuAction::AdjustRule(*rule, delta);
+        }
+
         if let Some(index) = menu.hovered_difficulty_index {
+            if let Some(faction) = index
+                .checked_sub(4)
+                .and_then(|index| ALL_FACTIONS.get(index))
+            {
+                return MenuAction::CycleFactionDifficulty(*faction);
+            }
             let difficulty = match index {
                 0 => Difficulty::Easy,
                 1 => Difficulty::Normal,
                 2 => Difficulty::Hard,
+                3 => Difficulty::Brutal,
                 _ => menu.selected_difficulty,
             };
             return MenuAction::SetDifficulty(difficulty);
@@ -320,6 +1187,16 @@ pub fn map_setup_system(
                 0 => MenuAction::RegenerateMap,
                 1 => MenuAction::StartGame,
                 2 => MenuAction::ReturnToMainMenu,
+                3 => MenuAction::SaveMap,
+                4 => MenuAction::LoadMap,
+                5 => MenuAction::StartSpectating,
+                6 => MenuAction::CycleTeams,
+                7 => MenuAction::ToggleRandomCapitals,
+                8 => MenuAction::ToggleIronman,
+                9 => MenuAction::CycleTurnTimer,
+                10 => MenuAction::ToggleWrapColumns,
+                11 => MenuAction::RerollTerrain,
+                12 => MenuAction::RerollSettlements,
                 _ => MenuAction::None,
             };
         }
@@ -328,6 +1205,117 @@ pub fn map_setup_system(
     MenuAction::None
 }
 
+pub fn setup_lobby_menu(
+    menu: &mut MenuData,
+    world: &mut World,
+    slot_labels: &[String; 4],
+    is_host: bool,
+) {
+    despawn_menu_elements(menu, world);
+    menu.lobby_is_host = is_host;
+
+    let title_props = TextProperties {
+        font_size: 56.0,
+        color: nalgebra_glm::vec4(0.8, 0.9, 1.0, 1.0),
+        alignment: TextAlignment::Center,
+        outline_width: 0.06,
+        outline_color: nalgebra_glm::vec4(0.1, 0.1, 0.3, 1.0),
+        ..Default::default()
+    };
+
+    menu.title_entity = Some(spawn_scaled_hud_text(
+        world,
+        "LOBBY",
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, -180.0),
+        title_props,
+        menu.ui_scale.scale,
+    ));
+
+    for (index, label) in slot_labels.iter().enumerate() {
+        let mut button = create_button(
+            world,
+            label,
+            nalgebra_glm::vec2(0.0, -110.0 + index as f32 * 45.0),
+            HudAnchor::Center,
+            32.0,
+            menu.ui_scale.scale,
+        );
+        let color = faction_color(ALL_FACTIONS[index]);
+        button.base_color = nalgebra_glm::vec4(color[0], color[1], color[2], 1.0);
+        if let Some(hud_text) = world.get_hud_text_mut(button.entity) {
+            hud_text.properties.color = button.base_color;
+            hud_text.dirty = true;
+        }
+        menu.lobby_buttons.push(button);
+    }
+
+    if is_host {
+        menu.lobby_buttons.push(create_button(
+            world,
+            "START",
+            nalgebra_glm::vec2(0.0, 110.0),
+            HudAnchor::Center,
+            48.0,
+            menu.ui_scale.scale,
+        ));
+    }
+    menu.lobby_buttons.push(create_button(
+        world,
+        "SPECTATE",
+        nalgebra_glm::vec2(0.0, 165.0),
+        HudAnchor::Center,
+        32.0,
+        menu.ui_scale.scale,
+    ));
+    menu.lobby_buttons.push(create_button(
+        world,
+        "LEAVE",
+        nalgebra_glm::vec2(0.0, 215.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+}
+
+pub fn lobby_system(
+    menu: &mut MenuData,
+    world: &mut World,
+    screen_width: f32,
+    screen_height: f32,
+) -> MenuAction {
+    let mouse_x = world.resources.input.mouse.position.x;
+    let mouse_y = world.resources.input.mouse.position.y;
+    let clicked = world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::LEFT_JUST_RELEASED);
+
+    menu.hovered_button_index = update_buttons_hover(
+        &menu.lobby_buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        menu.hovered_button_index,
+    );
+
+    if clicked && let Some(index) = menu.hovered_button_index {
+        let spectate_index = if menu.lobby_is_host { 5 } else { 4 };
+        return match index {
+            0..4 => MenuAction::ClaimFaction(ALL_FACTIONS[index]),
+            4 if menu.lobby_is_host => MenuAction::StartNetworkGame,
+            index if index == spectate_index => MenuAction::SpectateLobby,
+            _ => MenuAction::ReturnToMainMenu,
+        };
+    }
+
+    MenuAction::None
+}
+
 pub fn pause_menu_system(
     menu: &mut MenuData,
     world: &mut World,
@@ -357,6 +1345,8 @@ pub fn pause_menu_system(
         return match index {
             0 => MenuAction::ResumeGame,
             1 => MenuAction::ReturnToMainMenu,
+            2 => MenuAction::ToggleAttackWarnings,
+            3 => MenuAction::Concede,
             _ => MenuAction::None,
         };
     }
@@ -364,13 +1354,16 @@ pub fn pause_menu_system(
     MenuAction::None
 }
 
-fn create_button(
+pub fn create_button(
     world: &mut World,
     label: &str,
     position: nalgebra_glm::Vec2,
     anchor: HudAnchor,
     font_size: f32,
+    ui_scale: f32,
 ) -> MenuButton {
+    let font_size = font_size * ui_scale;
+    let position = position * ui_scale;
     let base_color = nalgebra_glm::vec4(0.8, 0.8, 0.8, 1.0);
     let hover_color = nalgebra_glm::vec4(1.0, 0.9, 0.3, 1.0);
 
@@ -407,7 +1400,10 @@ fn create_difficulty_button(
     anchor: HudAnchor,
     font_size: f32,
     selected: bool,
+    ui_scale: f32,
 ) -> MenuButton {
+    let font_size = font_size * ui_scale;
+    let position = position * ui_scale;
     let base_color = if selected {
         nalgebra_glm::vec4(1.0, 0.9, 0.3, 1.0)
     } else {
@@ -441,7 +1437,7 @@ fn create_difficulty_button(
     }
 }
 
-fn update_buttons_hover(
+pub fn update_buttons_hover(
     buttons: &[MenuButton],
     world: &mut World,
     mouse_x: f32,
@@ -514,7 +1510,9 @@ pub fn setup_game_over_menu(
     menu: &mut MenuData,
     world: &mut World,
     winner: Faction,
+    allies: &[Faction],
     is_player_winner: bool,
+    standings: &[FactionStanding],
 ) {
     despawn_menu_elements(menu, world);
     menu.game_over_winner = Some(winner);
@@ -534,20 +1532,26 @@ pub fn setup_game_over_menu(
         ..Default::default()
     };
 
-    menu.title_entity = Some(spawn_hud_text_with_properties(
+    menu.title_entity = Some(spawn_scaled_hud_text(
         world,
         title_text,
         HudAnchor::Center,
         nalgebra_glm::vec2(0.0, -120.0),
         title_props,
+        menu.ui_scale.scale,
     ));
 
-    let name = faction_name(winner);
-
-    let subtitle_text = if is_player_winner {
-        format!("{} conquers all!", name)
-    } else {
-        format!("{} has conquered the world!", name)
+    let names: Vec<&str> = std::iter::once(winner)
+        .chain(allies.iter().copied())
+        .map(faction_name)
+        .collect();
+    let name = names.join(" & ");
+
+    let subtitle_text = match (is_player_winner, allies.is_empty()) {
+        (true, true) => format!("{} conquers all!", name),
+        (true, false) => format!("{} conquer all!", name),
+        (false, true) => format!("{} has conquered the world!", name),
+        (false, false) => format!("{} have conquered the world!", name),
     };
 
     let color = faction_color(winner);
@@ -560,12 +1564,13 @@ pub fn setup_game_over_menu(
         ..Default::default()
     };
 
-    menu.subtitle_entity = Some(spawn_hud_text_with_properties(
+    menu.subtitle_entity = Some(spawn_scaled_hud_text(
         world,
         &subtitle_text,
         HudAnchor::Center,
         nalgebra_glm::vec2(0.0, -60.0),
         subtitle_props,
+        menu.ui_scale.scale,
     ));
 
     menu.game_over_buttons.push(create_button(
@@ -574,6 +1579,7 @@ pub fn setup_game_over_menu(
         nalgebra_glm::vec2(0.0, 20.0),
         HudAnchor::Center,
         48.0,
+        menu.ui_scale.scale,
     ));
     menu.game_over_buttons.push(create_button(
         world,
@@ -581,7 +1587,88 @@ pub fn setup_game_over_menu(
         nalgebra_glm::vec2(0.0, 80.0),
         HudAnchor::Center,
         40.0,
+        menu.ui_scale.scale,
+    ));
+    menu.game_over_buttons.push(create_button(
+        world,
+        "HALL OF FAME",
+        nalgebra_glm::vec2(0.0, 135.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
+    ));
+    menu.game_over_buttons.push(create_button(
+        world,
+        "REVIEW MAP",
+        nalgebra_glm::vec2(0.0, 190.0),
+        HudAnchor::Center,
+        40.0,
+        menu.ui_scale.scale,
     ));
+
+    show_faction_breakdown(menu, world, standings);
+}
+
+fn faction_standing_status(standing: &FactionStanding) -> &'static str {
+    match (
+        standing.victorious,
+        standing.eliminated,
+        standing.allied_to_winner,
+    ) {
+        (true, _, _) => "VICTOR",
+        (false, true, true) => "FALLEN ALLY",
+        (false, true, false) => "ELIMINATED",
+        (false, false, _) => "SURVIVED",
+    }
+}
+
+fn show_faction_breakdown(menu: &mut MenuData, world: &mut World, standings: &[FactionStanding]) {
+    menu.report_entities.push(spawn_scaled_hud_text(
+        world,
+        "FACTION BREAKDOWN",
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, 250.0),
+        TextProperties {
+            font_size: 24.0,
+            color: nalgebra_glm::vec4(0.9, 0.9, 0.9, 1.0),
+            alignment: TextAlignment::Center,
+            outline_width: 0.05,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        },
+        menu.ui_scale.scale,
+    ));
+
+    for (index, standing) in standings.iter().enumerate() {
+        let color = faction_color(standing.faction);
+        let line = format!(
+            "{}: {}  territory {}  armies {}  soldiers {}  lost {}  killed {}  destroyed {}  cities {}",
+            faction_name(standing.faction),
+            faction_standing_status(standing),
+            standing.territory,
+            standing.armies,
+            standing.soldiers,
+            standing.casualties.soldiers_lost,
+            standing.casualties.soldiers_killed,
+            standing.casualties.units_destroyed,
+            standing.casualties.cities_captured,
+        );
+        menu.report_entities.push(spawn_scaled_hud_text(
+            world,
+            &line,
+            HudAnchor::Center,
+            nalgebra_glm::vec2(0.0, 280.0 + index as f32 * 26.0),
+            TextProperties {
+                font_size: 20.0,
+                color: nalgebra_glm::vec4(color[0], color[1], color[2], 1.0),
+                alignment: TextAlignment::Center,
+                outline_width: 0.05,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                ..Default::default()
+            },
+            menu.ui_scale.scale,
+        ));
+    }
 }
 
 pub fn game_over_system(
@@ -613,9 +1700,71 @@ pub fn game_over_system(
         return match index {
             0 => MenuAction::EnterMapSetup,
             1 => MenuAction::ReturnToMainMenu,
+            2 => MenuAction::ShowHallOfFame,
+            3 => MenuAction::ReviewMap,
             _ => MenuAction::None,
         };
     }
 
     MenuAction::None
 }
+
+pub fn setup_map_review_menu(menu: &mut MenuData, world: &mut World) {
+    despawn_menu_elements(menu, world);
+
+    menu.subtitle_entity = Some(spawn_scaled_hud_text(
+        world,
+        "MAP REVIEW - fly the camera freely, scroll the event log",
+        HudAnchor::TopCenter,
+        nalgebra_glm::vec2(0.0, 20.0),
+        TextProperties {
+            font_size: 22.0,
+            color: nalgebra_glm::vec4(0.8, 0.9, 1.0, 1.0),
+            alignment: TextAlignment::Center,
+            outline_width: 0.05,
+            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+            ..Default::default()
+        },
+        menu.ui_scale.scale,
+    ));
+    menu.game_over_buttons.push(create_button(
+        world,
+        "BACK TO RESULTS",
+        nalgebra_glm::vec2(0.0, 60.0),
+        HudAnchor::TopCenter,
+        28.0,
+        menu.ui_scale.scale,
+    ));
+}
+
+pub fn map_review_system(
+    menu: &mut MenuData,
+    world: &mut World,
+    screen_width: f32,
+    screen_height: f32,
+) -> MenuAction {
+    let mouse_x = world.resources.input.mouse.position.x;
+    let mouse_y = world.resources.input.mouse.position.y;
+    let clicked = world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::LEFT_JUST_RELEASED);
+
+    menu.hovered_button_index = update_buttons_hover(
+        &menu.game_over_buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        menu.hovered_button_index,
+    );
+
+    if clicked && menu.hovered_button_index == Some(0) {
+        return MenuAction::EndMapReview;
+    }
+
+    MenuAction::None
+}
@@ -0,0 +1,76 @@
+use crate::ecs::{GameEvents, GameWorld};
+use crate::headless::headless_tick;
+use crate::map::MapGenParams;
+use crate::systems::{
+    GameResult, PendingSpawn, ruin_system, supply_delivery_system, tile_ownership_system,
+    victory_system,
+};
+use nightshade::prelude::*;
+
+pub const SHOWCASE_MAP_WIDTH: i32 = 14;
+pub const SHOWCASE_MAP_HEIGHT: i32 = 10;
+const SHOWCASE_MAX_TURNS: u32 = 80;
+const SHOWCASE_SUN_DIMMING: f32 = 0.3;
+const SHOWCASE_ORBIT_SPEED: f32 = 0.04;
+
+pub struct MenuShowcase {
+    pub saved_map_params: MapGenParams,
+    pub events: GameEvents,
+    pub pending_spawns: Vec<PendingSpawn>,
+}
+
+pub fn menu_showcase_new(saved_map_params: MapGenParams) -> MenuShowcase {
+    MenuShowcase {
+        saved_map_params,
+        events: GameEvents::default(),
+        pending_spawns: Vec::new(),
+    }
+}
+
+pub fn showcase_map_params() -> MapGenParams {
+    MapGenParams {
+        map_width: SHOWCASE_MAP_WIDTH,
+        map_height: SHOWCASE_MAP_HEIGHT,
+        random_capitals: false,
+        wrap_columns: false,
+        ..Default::default()
+    }
+}
+
+pub fn showcase_dim_sun(world: &mut World, sun: Entity) {
+    if let Some(light) = world.get_light_mut(sun) {
+        light.intensity *= SHOWCASE_SUN_DIMMING;
+    }
+}
+
+pub fn showcase_orbit_camera(world: &mut World, delta_time: f32) {
+    let Some(camera) = world.resources.active_camera else {
+        return;
+    };
+    if let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera) {
+        pan_orbit.target_yaw += SHOWCASE_ORBIT_SPEED * delta_time;
+    }
+}
+
+pub fn menu_showcase_tick(
+    showcase: &mut MenuShowcase,
+    game_world: &mut GameWorld,
+    frame_delta: f32,
+) -> bool {
+    headless_tick(
+        game_world,
+        &mut showcase.events,
+        &mut showcase.pending_spawns,
+        frame_delta,
+    );
+    tile_ownership_system(game_world);
+    ruin_system(game_world, &mut showcase.events);
+    supply_delivery_system(game_world, &mut showcase.events);
+    let result = victory_system(game_world, &mut showcase.events);
+    showcase.events = GameEvents::default();
+    game_world.resources.morale_changes.clear();
+    game_world.step();
+
+    matches!(result, GameResult::Victory(_))
+        || game_world.resources.turn_number > SHOWCASE_MAX_TURNS
+}
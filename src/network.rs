@@ -0,0 +1,721 @@
+use crate::ecs::{
+    ALL_FACTIONS, Difficulty, Faction, Improvement, difficulty_from_key, difficulty_key,
+    faction_index, faction_name,
+};
+use crate::hex::{HexCoord, HexOrientation, hex_orientation_from_key, hex_orientation_key};
+use crate::rules::GameRules;
+use crate::systems::{PlayerAction, improvement_name};
+use crate::teams::{TeamLayout, team_layout_from_key, team_layout_key};
+use crate::text_file::faction_from_name;
+use nightshade::prelude::*;
+use std::fmt;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::str::FromStr;
+
+pub const DEFAULT_NET_PORT: u16 = 7878;
+pub const HOST_PEER_ID: u8 = 0;
+const NET_PROTOCOL_VERSION: u32 = 13;
+const NET_READ_CHUNK: usize = 4096;
+const NET_MAX_LINE_BYTES: usize = 65_536;
+
+#[derive(Debug)]
+pub enum NetError {
+    Io(std::io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetError::Io(error) => write!(formatter, "{}", error),
+            NetError::Parse(message) => write!(formatter, "bad message: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+impl From<std::io::Error> for NetError {
+    fn from(error: std::io::Error) -> Self {
+        NetError::Io(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetRole {
+    Host,
+    Client,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetGameSetup {
+    pub seed: u32,
+    pub map_width: i32,
+    pub map_height: i32,
+    pub random_capitals: bool,
+    pub wrap_columns: bool,
+    pub orientation: HexOrientation,
+    pub difficulties: [Difficulty; 4],
+    pub rules: GameRules,
+    pub team_layout: TeamLayout,
+    pub turn_seconds: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetMessage {
+    Hello {
+        version: u32,
+    },
+    Reject {
+        version: u32,
+    },
+    Welcome {
+        peer_id: u8,
+    },
+    Claim {
+        peer_id: u8,
+        faction: Faction,
+    },
+    Release {
+        peer_id: u8,
+    },
+    Lobby {
+        slots: [Option<u8>; 4],
+    },
+    Start(NetGameSetup),
+    Action {
+        faction: Faction,
+        action: PlayerAction,
+    },
+    EndTurn {
+        faction: Faction,
+        turn: u32,
+        hash: u64,
+    },
+    Chat {
+        faction: Faction,
+        text: String,
+    },
+    Ping {
+        faction: Faction,
+        coord: HexCoord,
+    },
+    Leave {
+        peer_id: u8,
+    },
+}
+
+pub struct NetPeer {
+    pub peer_id: u8,
+    pub stream: TcpStream,
+    pub buffer: Vec<u8>,
+    pub outgoing: Vec<u8>,
+}
+
+pub struct NetSession {
+    pub role: NetRole,
+    pub local_peer: Option<u8>,
+    pub listener: Option<TcpListener>,
+    pub peers: Vec<NetPeer>,
+    pub next_peer_id: u8,
+    pub slots: [Option<u8>; 4],
+    pub connected: bool,
+}
+
+fn parse_field<T: FromStr>(fields: &[&str], index: usize, name: &str) -> Result<T, NetError> {
+    fields
+        .get(index)
+        .and_then(|field| field.parse().ok())
+        .ok_or_else(|| NetError::Parse(format!("invalid {}", name)))
+}
+
+fn parse_faction(fields: &[&str], index: usize) -> Result<Faction, NetError> {
+    let name = fields
+        .get(index)
+        .ok_or_else(|| NetError::Parse("missing faction".to_string()))?;
+    faction_from_name(name).ok_or_else(|| NetError::Parse(format!("unknown faction '{}'", name)))
+}
+
+fn parse_coord(fields: &[&str], index: usize) -> Result<HexCoord, NetError> {
+    Ok(HexCoord {
+        column: parse_field(fields, index, "column")?,
+        row: parse_field(fields, index + 1, "row")?,
+    })
+}
+
+fn improvement_from_key(key: &str) -> Option<Improvement> {
+    [Improvement::Fort, Improvement::Watchtower]
+        .into_iter()
+        .find(|improvement| improvement_name(*improvement) == key)
+}
+
+fn action_to_string(action: PlayerAction) -> String {
+    match action {
+        PlayerAction::Click(coord) => format!("click {} {}", coord.column, coord.row),
+        PlayerAction::Deselect => "deselect".to_string(),
+        PlayerAction::Build(improvement) => format!("build {}", improvement_name(improvement)),
+        PlayerAction::Speech => "speech".to_string(),
+        PlayerAction::RelocateCapital(coord) => {
+            format!("relocate {} {}", coord.column, coord.row)
+        }
+        PlayerAction::SelectStacked(coord, index) => {
+            format!("select {} {} {}", coord.column, coord.row, index)
+        }
+        PlayerAction::Pillage => "pillage".to_string(),
+        PlayerAction::RecruitScout => "scout".to_string(),
+    }
+}
+
+fn action_from_fields(fields: &[&str]) -> Result<PlayerAction, NetError> {
+    match fields.first().copied() {
+        Some("click") => Ok(PlayerAction::Click(parse_coord(fields, 1)?)),
+        Some("deselect") => Ok(PlayerAction::Deselect),
+        Some("build") => fields
+            .get(1)
+            .and_then(|key| improvement_from_key(key))
+            .map(PlayerAction::Build)
+            .ok_or_else(|| NetError::Parse("unknown improvement".to_string())),
+        Some("speech") => Ok(PlayerAction::Speech),
+        Some("relocate") => Ok(PlayerAction::RelocateCapital(parse_coord(fields, 1)?)),
+        Some("pillage") => Ok(PlayerAction::Pillage),
+        Some("scout") => Ok(PlayerAction::RecruitScout),
+        Some("select") => Ok(PlayerAction::SelectStacked(
+            parse_coord(fields, 1)?,
+            parse_field(fields, 3, "stack index")?,
+        )),
+        _ => Err(NetError::Parse("unknown action".to_string())),
+    }
+}
+
+pub fn net_message_to_line(message: &NetMessage) -> String {
+    match message {
+        NetMessage::Hello { version } => format!("hello {}", version),
+        NetMessage::Reject { version } => format!("reject {}", version),
+        NetMessage::Welcome { peer_id } => format!("welcome {}", peer_id),
+        NetMessage::Claim { peer_id, faction } => {
+            format!("claim {} {}", peer_id, faction_name(*faction))
+        }
+        NetMessage::Release { peer_id } => format!("release {}", peer_id),
+        NetMessage::Lobby { slots } => {
+            let slots: Vec<String> = slots
+                .iter()
+                .map(|slot| slot.map_or_else(|| "-".to_string(), |peer| peer.to_string()))
+                .collect();
+            format!("lobby {}", slots.join(" "))
+        }
+        NetMessage::Start(setup) => format!(
+            "start {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}",
+            setup.seed,
+            setup.map_width,
+            setup.map_height,
+            setup.difficulties.map(difficulty_key).join(","),
+            setup.rules.actions_per_turn,
+            setup.rules.city_reinforcement,
+            setup.rules.initial_soldiers,
+            setup.rules.max_soldiers,
+            team_layout_key(setup.team_layout),
+            setup.turn_seconds,
+            setup.rules.movement_points as u8,
+            setup.rules.defection as u8,
+            setup.random_capitals as u8,
+            setup.wrap_columns as u8,
+            hex_orientation_key(setup.orientation),
+            setup.rules.supply_wagons as u8,
+        ),
+        NetMessage::Action { faction, action } => {
+            format!(
+                "action {} {}",
+                faction_name(*faction),
+                action_to_string(*action)
+            )
+        }
+        NetMessage::EndTurn {
+            faction,
+            turn,
+            hash,
+        } => format!("end_turn {} {} {:016x}", faction_name(*faction), turn, hash),
+        NetMessage::Chat { faction, text } => {
+            let words: Vec<&str> = text.split_whitespace().collect();
+            format!("chat {} {}", faction_name(*faction), words.join(" "))
+        }
+        NetMessage::Ping { faction, coord } => format!(
+            "ping {} {} {}",
+            faction_name(*faction),
+            coord.column,
+            coord.row
+        ),
+        NetMessage::Leave { peer_id } => format!("leave {}", peer_id),
+    }
+}
+
+fn parse_difficulties(field: Option<&str>) -> Result<[Difficulty; 4], NetError> {
+    let keys: Vec<&str> = field
+        .ok_or_else(|| NetError::Parse("missing difficulties".to_string()))?
+        .split(',')
+        .collect();
+    let mut difficulties = [Difficulty::default(); 4];
+    if keys.len() != difficulties.len() {
+        return Err(NetError::Parse("invalid difficulties".to_string()));
+    }
+    for (difficulty, key) in difficulties.iter_mut().zip(keys) {
+        *difficulty = difficulty_from_key(key)
+            .ok_or_else(|| NetError::Parse("invalid difficulty".to_string()))?;
+    }
+    Ok(difficulties)
+}
+
+pub fn net_message_from_line(line: &str) -> Result<NetMessage, NetError> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    match fields.first().copied() {
+        Some("hello") => Ok(NetMessage::Hello {
+            version: parse_field(&fields, 1, "version")?,
+        }),
+        Some("reject") => Ok(NetMessage::Reject {
+            version: parse_field(&fields, 1, "version")?,
+        }),
+        Some("welcome") => Ok(NetMessage::Welcome {
+            peer_id: parse_field(&fields, 1, "peer id")?,
+        }),
+        Some("claim") => Ok(NetMessage::Claim {
+            peer_id: parse_field(&fields, 1, "peer id")?,
+            faction: parse_faction(&fields, 2)?,
+        }),
+        Some("release") => Ok(NetMessage::Release {
+            peer_id: parse_field(&fields, 1, "peer id")?,
+        }),
+        Some("lobby") => {
+            let mut slots = [None; 4];
+            for (index, slot) in slots.iter_mut().enumerate() {
+                let field = fields
+                    .get(index + 1)
+                    .ok_or_else(|| NetError::Parse("missing lobby slot".to_string()))?;
+                if *field != "-" {
+                    *slot = Some(parse_field(&fields, index + 1, "lobby slot")?);
+                }
+            }
+            Ok(NetMessage::Lobby { slots })
+        }
+        Some("start") => Ok(NetMessage::Start(NetGameSetup {
+            seed: parse_field(&fields, 1, "seed")?,
+            map_width: parse_field(&fields, 2, "map width")?,
+            map_height: parse_field(&fields, 3, "map height")?,
+            random_capitals: parse_field::<u8>(&fields, 13, "random capitals")? == 1,
+            wrap_columns: parse_field::<u8>(&fields, 14, "wrap columns")? == 1,
+            orientation: fields
+                .get(15)
+                .copied()
+                .and_then(hex_orientation_from_key)
+                .ok_or_else(|| NetError::Parse("invalid orientation".to_string()))?,
+            difficulties: parse_difficulties(fields.get(4).copied())?,
+            rules: GameRules {
+                actions_per_turn: parse_field(&fields, 5, "actions per turn")?,
+                city_reinforcement: parse_field(&fields, 6, "city reinforcement")?,
+                initial_soldiers: parse_field(&fields, 7, "initial soldiers")?,
+                max_soldiers: parse_field(&fields, 8, "max soldiers")?,
+                movement_points: parse_field::<u8>(&fields, 11, "movement points")? == 1,
+                defection: parse_field::<u8>(&fields, 12, "defection")? == 1,
+                supply_wagons: parse_field::<u8>(&fields, 16, "supply wagons")? == 1,
+            },
+            team_layout: fields
+                .get(9)
+                .and_then(|key| team_layout_from_key(key))
+                .ok_or_else(|| NetError::Parse("invalid team layout".to_string()))?,
+            turn_seconds: parse_field(&fields, 10, "turn seconds")?,
+        })),
+        Some("action") => Ok(NetMessage::Action {
+            faction: parse_faction(&fields, 1)?,
+            action: action_from_fields(&fields[2..])?,
+        }),
+        Some("end_turn") => Ok(NetMessage::EndTurn {
+            faction: parse_faction(&fields, 1)?,
+            turn: parse_field(&fields, 2, "turn")?,
+            hash: fields
+                .get(3)
+                .and_then(|field| u64::from_str_radix(field, 16).ok())
+                .ok_or_else(|| NetError::Parse("invalid hash".to_string()))?,
+        }),
+        Some("chat") => Ok(NetMessage::Chat {
+            faction: parse_faction(&fields, 1)?,
+            text: fields.get(2..).unwrap_or_default().join(" "),
+        }),
+        Some("ping") => Ok(NetMessage::Ping {
+            faction: parse_faction(&fields, 1)?,
+            coord: parse_coord(&fields, 2)?,
+        }),
+        Some("leave") => Ok(NetMessage::Leave {
+            peer_id: parse_field(&fields, 1, "peer id")?,
+        }),
+        _ => Err(NetError::Parse(line.to_string())),
+    }
+}
+
+fn peer_new(peer_id: u8, stream: TcpStream) -> Result<NetPeer, NetError> {
+    stream.set_nonblocking(true)?;
+    stream.set_nodelay(true)?;
+    Ok(NetPeer {
+        peer_id,
+        stream,
+        buffer: Vec::new(),
+        outgoing: Vec::new(),
+    })
+}
+
+fn peer_send(peer: &mut NetPeer, message: &NetMessage) -> std::io::Result<()> {
+    let mut line = net_message_to_line(message);
+    line.push('\n');
+    peer.outgoing.extend_from_slice(line.as_bytes());
+    peer_flush(peer)
+}
+
+fn peer_flush(peer: &mut NetPeer) -> std::io::Result<()> {
+    while !peer.outgoing.is_empty() {
+        match peer.stream.write(&peer.outgoing) {
+            Ok(0) => return Err(ErrorKind::WriteZero.into()),
+            Ok(written) => {
+                peer.outgoing.drain(..written);
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
+
+fn peer_read_lines(peer: &mut NetPeer) -> std::io::Result<Vec<String>> {
+    let mut chunk = [0u8; NET_READ_CHUNK];
+    loop {
+        match peer.stream.read(&mut chunk) {
+            Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+            Ok(read) => {
+                peer.buffer.extend_from_slice(&chunk[..read]);
+                let line_start = peer
+                    .buffer
+                    .iter()
+                    .rposition(|byte| *byte == b'\n')
+                    .map_or(0, |newline| newline + 1);
+                if peer.buffer.len() - line_start > NET_MAX_LINE_BYTES {
+                    return Err(std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        "message line too long",
+                    ));
+                }
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    let mut lines = Vec::new();
+    while let Some(newline) = peer.buffer.iter().position(|byte| *byte == b'\n') {
+        let line: Vec<u8> = peer.buffer.drain(..=newline).collect();
+        lines.push(String::from_utf8_lossy(&line).trim().to_string());
+    }
+    Ok(lines)
+}
+
+pub fn net_host(port: u16) -> Result<NetSession, NetError> {
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    listener.set_nonblocking(true)?;
+    let mut slots = [None; 4];
+    slots[0] = Some(HOST_PEER_ID);
+    Ok(NetSession {
+        role: NetRole::Host,
+        local_peer: Some(HOST_PEER_ID),
+        listener: Some(listener),
+        peers: Vec::new(),
+        next_peer_id: HOST_PEER_ID + 1,
+        slots,
+        connected: true,
+    })
+}
+
+pub fn net_join(address: &str) -> Result<NetSession, NetError> {
+    let stream = TcpStream::connect(address)?;
+    let mut host = peer_new(HOST_PEER_ID, stream)?;
+    peer_send(
+        &mut host,
+        &NetMessage::Hello {
+            version: NET_PROTOCOL_VERSION,
+        },
+    )?;
+    Ok(NetSession {
+        role: NetRole::Client,
+        local_peer: None,
+        listener: None,
+        peers: vec![host],
+        next_peer_id: 0,
+        slots: [None; 4],
+        connected: true,
+    })
+}
+
+pub fn net_send(session: &mut NetSession, message: &NetMessage) {
+    for peer in &mut session.peers {
+        if let Err(error) = peer_send(peer, message) {
+            log::warn!("Failed to send to peer {}: {}", peer.peer_id, error);
+        }
+    }
+}
+
+fn net_relay(session: &mut NetSession, message: &NetMessage, sender: u8) {
+    for peer in session
+        .peers
+        .iter_mut()
+        .filter(|peer| peer.peer_id != sender)
+    {
+        if let Err(error) = peer_send(peer, message) {
+            log::warn!("Failed to relay to peer {}: {}", peer.peer_id, error);
+        }
+    }
+}
+
+fn assign_slot(slots: &mut [Option<u8>; 4], peer_id: u8, faction: Faction) -> bool {
+    let index = faction_index(faction);
+    if slots[index].is_some_and(|owner| owner != peer_id) {
+        return false;
+    }
+    for slot in slots.iter_mut() {
+        if *slot == Some(peer_id) {
+            *slot = None;
+        }
+    }
+    slots[index] = Some(peer_id);
+    true
+}
+
+fn free_slots(slots: &mut [Option<u8>; 4], peer_id: u8) {
+    for slot in slots.iter_mut() {
+        if *slot == Some(peer_id) {
+            *slot = None;
+        }
+    }
+}
+
+pub fn net_claim_faction(session: &mut NetSession, faction: Faction) {
+    let Some(local_peer) = session.local_peer else {
+        return;
+    };
+    match session.role {
+        NetRole::Host => {
+            if assign_slot(&mut session.slots, local_peer, faction) {
+                let slots = session.slots;
+                net_send(session, &NetMessage::Lobby { slots });
+            }
+        }
+        NetRole::Client => net_send(
+            session,
+            &NetMessage::Claim {
+                peer_id: local_peer,
+                faction,
+            },
+        ),
+    }
+}
+
+pub fn net_release_faction(session: &mut NetSession) {
+    let Some(local_peer) = session.local_peer else {
+        return;
+    };
+    match session.role {
+        NetRole::Host => {
+            free_slots(&mut session.slots, local_peer);
+            let slots = session.slots;
+            net_send(session, &NetMessage::Lobby { slots });
+        }
+        NetRole::Client => net_send(
+            session,
+            &NetMessage::Release {
+                peer_id: local_peer,
+            },
+        ),
+    }
+}
+
+pub fn net_faction_owner(session: &NetSession, faction: Faction) -> Option<u8> {
+    session.slots[faction_index(faction)]
+}
+
+pub fn net_local_faction(session: &NetSession) -> Option<Faction> {
+    let local_peer = session.local_peer?;
+    ALL_FACTIONS
+        .iter()
+        .find(|faction| net_faction_owner(session, **faction) == Some(local_peer))
+        .copied()
+}
+
+pub fn net_is_remote_faction(session: &NetSession, faction: Faction) -> bool {
+    net_faction_owner(session, faction).is_some_and(|owner| Some(owner) != session.local_peer)
+}
+
+fn net_accept_peers(session: &mut NetSession) {
+    let Some(listener) = session.listener.as_ref() else {
+        return;
+    };
+    let mut accepted = Vec::new();
+    loop {
+        match listener.accept() {
+            Ok((stream, address)) => {
+                log::info!("Peer connected from {}", address);
+                accepted.push(stream);
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+            Err(error) => {
+                log::warn!("Failed to accept peer: {}", error);
+                break;
+            }
+        }
+    }
+
+    for stream in accepted {
+        let peer_id = session.next_peer_id;
+        let Ok(mut peer) = peer_new(peer_id, stream) else {
+            continue;
+        };
+        session.next_peer_id = session.next_peer_id.wrapping_add(1).max(1);
+        let slots = session.slots;
+        if peer_send(&mut peer, &NetMessage::Welcome { peer_id }).is_ok()
+            && peer_send(&mut peer, &NetMessage::Lobby { slots }).is_ok()
+        {
+            session.peers.push(peer);
+        }
+    }
+}
+
+fn host_handle_message(
+    session: &mut NetSession,
+    sender: u8,
+    message: NetMessage,
+    received: &mut Vec<NetMessage>,
+) {
+    match message {
+        NetMessage::Claim { faction, .. } => {
+            if assign_slot(&mut session.slots, sender, faction) {
+                let slots = session.slots;
+                let lobby = NetMessage::Lobby { slots };
+                net_send(session, &lobby);
+                received.push(lobby);
+            }
+        }
+        NetMessage::Release { .. } => {
+            free_slots(&mut session.slots, sender);
+            let slots = session.slots;
+            let lobby = NetMessage::Lobby { slots };
+            net_send(session, &lobby);
+            received.push(lobby);
+        }
+        NetMessage::Action { faction, .. }
+        | NetMessage::EndTurn { faction, .. }
+        | NetMessage::Chat { faction, .. }
+        | NetMessage::Ping { faction, .. } => {
+            if net_faction_owner(session, faction) == Some(sender) {
+                net_relay(session, &message, sender);
+                received.push(message);
+            }
+        }
+        NetMessage::Hello { version } if version != NET_PROTOCOL_VERSION => {
+            log::warn!(
+                "Rejecting peer {}: protocol {} (expected {})",
+                sender,
+                version,
+                NET_PROTOCOL_VERSION
+            );
+            if let Some(peer) = session.peers.iter_mut().find(|peer| peer.peer_id == sender) {
+                let _ = peer_send(
+                    peer,
+                    &NetMessage::Reject {
+                        version: NET_PROTOCOL_VERSION,
+                    },
+                );
+            }
+            session.peers.retain(|peer| peer.peer_id != sender);
+            free_slots(&mut session.slots, sender);
+        }
+        _ => {}
+    }
+}
+
+fn client_handle_message(
+    session: &mut NetSession,
+    message: NetMessage,
+    received: &mut Vec<NetMessage>,
+) {
+    match message {
+        NetMessage::Welcome { peer_id } => session.local_peer = Some(peer_id),
+        NetMessage::Reject { version } => {
+            log::error!(
+                "Host rejected this build: protocol {} required, {} in use",
+                version,
+                NET_PROTOCOL_VERSION
+            );
+            session.connected = false;
+            received.push(message);
+        }
+        NetMessage::Lobby { slots } => {
+            session.slots = slots;
+            received.push(message);
+        }
+        NetMessage::Leave { peer_id } => {
+            free_slots(&mut session.slots, peer_id);
+            received.push(message);
+        }
+        _ => received.push(message),
+    }
+}
+
+pub fn net_poll(session: &mut NetSession) -> Vec<NetMessage> {
+    if session.role == NetRole::Host {
+        net_accept_peers(session);
+    }
+
+    let mut incoming = Vec::new();
+    let mut disconnected = Vec::new();
+    for peer in &mut session.peers {
+        match peer_flush(peer).and_then(|()| peer_read_lines(peer)) {
+            Ok(lines) => {
+                for line in lines.iter().filter(|line| !line.is_empty()) {
+                    match net_message_from_line(line) {
+                        Ok(message) => incoming.push((peer.peer_id, message)),
+                        Err(error) => log::warn!("Peer {}: {}", peer.peer_id, error),
+                    }
+                }
+            }
+            Err(error) => {
+                log::info!("Peer {} disconnected: {}", peer.peer_id, error);
+                disconnected.push(peer.peer_id);
+            }
+        }
+    }
+
+    let mut received = Vec::new();
+    for (sender, message) in incoming {
+        if !session.peers.iter().any(|peer| peer.peer_id == sender) {
+            continue;
+        }
+        match session.role {
+            NetRole::Host => host_handle_message(session, sender, message, &mut received),
+            NetRole::Client => client_handle_message(session, message, &mut received),
+        }
+    }
+
+    for peer_id in disconnected {
+        session.peers.retain(|peer| peer.peer_id != peer_id);
+        match session.role {
+            NetRole::Host => {
+                free_slots(&mut session.slots, peer_id);
+                let slots = session.slots;
+                net_send(session, &NetMessage::Leave { peer_id });
+                net_send(session, &NetMessage::Lobby { slots });
+            }
+            NetRole::Client => {
+                session.connected = false;
+                session.slots = [None; 4];
+            }
+        }
+        received.push(NetMessage::Leave { peer_id });
+    }
+
+    received
+}
@@ -0,0 +1,237 @@
+use crate::ecs::{
+    Faction, GameWorld, HEX_POSITION, TILE, TileType, UNIT, faction_capital, faction_name,
+    map_distance,
+};
+use crate::hex::HexCoord;
+use crate::hud_text::{
+    HudText, hud_text_despawn, hud_text_set, hud_text_set_color, hud_text_spawn,
+};
+use crate::teams::factions_hostile;
+use nightshade::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+const MAX_VISIBLE_TOASTS: usize = 3;
+const MAX_QUEUED_TOASTS: usize = 16;
+const TOAST_DURATION: f32 = 3.5;
+const TOAST_FADE_TIME: f32 = 0.75;
+const TOAST_TOP_OFFSET: f32 = 100.0;
+const TOAST_LINE_HEIGHT: f32 = 34.0;
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub color: [f32; 4],
+    pub age: f32,
+}
+
+#[derive(Default)]
+pub struct Notifications {
+    pub queue: VecDeque<Toast>,
+    pub active: Vec<Toast>,
+    pub texts: Vec<HudText>,
+    pub owned_key_tiles: HashMap<HexCoord, TileType>,
+    pub capital_threatened: bool,
+}
+
+pub fn notification_push(notifications: &mut Notifications, message: String, color: [f32; 4]) {
+    let duplicate = notifications
+        .active
+        .iter()
+        .chain(notifications.queue.iter())
+        .any(|toast| toast.message == message);
+    if duplicate {
+        return;
+    }
+
+    notifications.queue.push_back(Toast {
+        message,
+        color,
+        age: 0.0,
+    });
+    if notifications.queue.len() > MAX_QUEUED_TOASTS {
+        notifications.queue.pop_front();
+    }
+}
+
+pub fn notification_faction_eliminated(
+    notifications: &mut Notifications,
+    faction: Faction,
+    player_faction: Option<Faction>,
+) {
+    let message = if Some(faction) == player_faction {
+        "You have been eliminated!".to_string()
+    } else {
+        format!("{} has been eliminated!", faction_name(faction))
+    };
+    notification_push(notifications, message, [1.0, 0.85, 0.3, 1.0]);
+}
+
+fn tile_type_name(tile_type: TileType) -> &'static str {
+    match tile_type {
+        TileType::Capital => "capital",
+        TileType::City => "city",
+        TileType::Port => "port",
+        TileType::Sea => "sea",
+        TileType::Land => "land",
+        TileType::Forest => "forest",
+        TileType::Hills => "hills",
+        TileType::Swamp => "swamp",
+    }
+}
+
+fn player_key_tiles(
+    game_world: &GameWorld,
+    player_faction: Faction,
+) -> HashMap<HexCoord, TileType> {
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            let is_key_tile = matches!(
+                tile.tile_type,
+                TileType::City | TileType::Port | TileType::Capital
+            );
+            (is_key_tile && tile.faction == Some(player_faction)).then_some((coord, tile.tile_type))
+        })
+        .collect()
+}
+
+fn tile_owner(game_world: &GameWorld, coord: HexCoord) -> Option<Faction> {
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| {
+            game_world
+                .get_hex_position(entity)
+                .map(|hex| hex.0 == coord)
+                .unwrap_or(false)
+        })
+        .and_then(|entity| game_world.get_tile(entity))
+        .and_then(|tile| tile.faction)
+}
+
+pub fn capital_threatened(game_world: &GameWorld, faction: Faction) -> bool {
+    let capital = faction_capital(&game_world.resources, faction);
+    if tile_owner(game_world, capital) != Some(faction) {
+        return false;
+    }
+
+    game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .any(|entity| {
+            let Some(hex) = game_world.get_hex_position(entity) else {
+                return false;
+            };
+            let Some(unit) = game_world.get_unit(entity) else {
+                return false;
+            };
+            factions_hostile(game_world.resources.team_layout, faction, unit.faction)
+                && map_distance(&game_world.resources, hex.0, capital) <= 1
+        })
+}
+
+pub fn notification_watch_system(
+    notifications: &mut Notifications,
+    game_world: &GameWorld,
+    player_faction: Faction,
+) {
+    let owned = player_key_tiles(game_world, player_faction);
+    let mut lost: Vec<(HexCoord, TileType)> = notifications
+        .owned_key_tiles
+        .iter()
+        .filter(|(coord, _)| !owned.contains_key(coord))
+        .map(|(coord, tile_type)| (*coord, *tile_type))
+        .collect();
+    lost.sort_by_key(|(coord, _)| (coord.row, coord.column));
+
+    for (coord, tile_type) in lost {
+        let message = match tile_owner(game_world, coord) {
+            Some(captor) => format!(
+                "Your {} at ({},{}) was captured by {}!",
+                tile_type_name(tile_type),
+                coord.column,
+                coord.row,
+                faction_name(captor)
+            ),
+            None => format!(
+                "Your {} at ({},{}) was lost!",
+                tile_type_name(tile_type),
+                coord.column,
+                coord.row
+            ),
+        };
+        notification_push(notifications, message, [1.0, 0.35, 0.3, 1.0]);
+    }
+    notifications.owned_key_tiles = owned;
+
+    let threatened = capital_threatened(game_world, player_faction);
+    if threatened && !notifications.capital_threatened {
+        notification_push(
+            notifications,
+            "Your capital is under threat!".to_string(),
+            [1.0, 0.5, 0.1, 1.0],
+        );
+    }
+    notifications.capital_threatened = threatened;
+}
+
+pub fn spawn_notification_ui(notifications: &mut Notifications, world: &mut World, ui_scale: f32) {
+    let props = TextProperties {
+        font_size: 26.0,
+        color: nalgebra_glm::vec4(1.0, 1.0, 1.0, 1.0),
+        alignment: TextAlignment::Center,
+        outline_width: 0.06,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    for slot in 0..MAX_VISIBLE_TOASTS {
+        notifications.texts.push(hud_text_spawn(
+            world,
+            "",
+            HudAnchor::TopCenter,
+            nalgebra_glm::vec2(0.0, TOAST_TOP_OFFSET + slot as f32 * TOAST_LINE_HEIGHT),
+            props.clone(),
+            ui_scale,
+        ));
+    }
+}
+
+pub fn despawn_notification_ui(notifications: &mut Notifications, world: &mut World) {
+    for mut hud_text in notifications.texts.drain(..) {
+        hud_text_despawn(world, &mut hud_text);
+    }
+}
+
+pub fn notification_system(notifications: &mut Notifications, world: &mut World, delta_time: f32) {
+    for toast in &mut notifications.active {
+        toast.age += delta_time;
+    }
+    notifications
+        .active
+        .retain(|toast| toast.age < TOAST_DURATION);
+
+    while notifications.active.len() < MAX_VISIBLE_TOASTS
+        && let Some(toast) = notifications.queue.pop_front()
+    {
+        log::info!("Notification: {}", toast.message);
+        notifications.active.push(toast);
+    }
+
+    for (slot, hud_text) in notifications.texts.iter_mut().enumerate() {
+        match notifications.active.get(slot) {
+            Some(toast) => {
+                let remaining = TOAST_DURATION - toast.age;
+                let alpha = (remaining / TOAST_FADE_TIME).clamp(0.0, 1.0);
+                let alpha = (alpha * 20.0).round() / 20.0;
+                hud_text_set(world, hud_text, &toast.message);
+                hud_text_set_color(
+                    world,
+                    hud_text,
+                    [toast.color[0], toast.color[1], toast.color[2], alpha],
+                );
+            }
+            None => hud_text_set(world, hud_text, ""),
+        }
+    }
+}
@@ -1,63 +1,143 @@
+use crate::asset_manifest::{
+    AssetEntry, AssetManifest, asset_bytes, asset_manifest_tile, embedded_asset_manifest,
+};
 use crate::ecs::TileType;
+use crate::hex::HexOrientation;
+use crate::map_file::tile_type_key;
 use nightshade::ecs::prefab::{
     GltfLoadResult, MeshCache, Prefab, PrefabNode, import_gltf_from_bytes,
 };
 use nightshade::prelude::*;
 use std::collections::HashMap;
 
-const HEXAGON_TILES_GLB: &[u8] = include_bytes!("../assets/hexagon_tiles.glb");
-const GRASS_GLB: &[u8] = include_bytes!("../assets/grass.glb");
+pub const FALLBACK_UNIT_MESH: &str = "Sphere";
 
 pub struct LoadedPrefabs {
     pub tile_prefabs: HashMap<TileType, Prefab>,
     pub hex_width: f32,
     pub hex_depth: f32,
+    pub hex_orientation: HexOrientation,
 }
 
-pub fn load_tile_prefabs(world: &mut World) -> Option<LoadedPrefabs> {
-    let tiles_result = import_gltf_from_bytes(HEXAGON_TILES_GLB);
-    let grass_result = import_gltf_from_bytes(GRASS_GLB);
+pub fn load_tile_prefabs(world: &mut World, manifest: &AssetManifest) -> Option<LoadedPrefabs> {
+    let mut gltf_files = HashMap::new();
+    let manifest = resolve_manifest_entries(world, manifest, &mut gltf_files);
 
-    match (tiles_result, grass_result) {
-        (Ok(tiles), Ok(grass)) => {
-            load_textures_and_meshes(world, &tiles);
-            load_textures_and_meshes(world, &grass);
+    let tile_prefabs = extract_tile_prefabs(&manifest, &gltf_files, &world.resources.mesh_cache);
 
-            let tile_prefabs = extract_tile_prefabs(&tiles, &grass, &world.resources.mesh_cache);
+    if tile_prefabs.is_empty() {
+        log::error!("No tile prefabs found!");
+        return None;
+    }
 
-            if tile_prefabs.is_empty() {
-                log::error!("No tile prefabs found!");
-                return None;
-            }
+    let hex_width = tile_prefabs
+        .values()
+        .next()
+        .and_then(|prefab| calculate_prefab_bounds(prefab, &world.resources.mesh_cache))
+        .map(|(min_x, max_x, _, _)| max_x - min_x)
+        .unwrap_or(173.205);
+    let hex_depth = tile_prefabs
+        .values()
+        .next()
+        .and_then(|prefab| calculate_prefab_bounds(prefab, &world.resources.mesh_cache))
+        .map(|(_, _, min_z, max_z)| max_z - min_z)
+        .unwrap_or(200.0);
+    let hex_orientation = manifest.orientation.unwrap_or(if hex_width > hex_depth {
+        HexOrientation::FlatTop
+    } else {
+        HexOrientation::PointyTop
+    });
 
-            let hex_width = tile_prefabs
-                .values()
-                .next()
-                .and_then(|prefab| calculate_prefab_bounds(prefab, &world.resources.mesh_cache))
-                .map(|(min_x, max_x, _, _)| max_x - min_x)
-                .unwrap_or(173.205);
-            let hex_depth = tile_prefabs
-                .values()
-                .next()
-                .and_then(|prefab| calculate_prefab_bounds(prefab, &world.resources.mesh_cache))
-                .map(|(_, _, min_z, max_z)| max_z - min_z)
-                .unwrap_or(200.0);
-
-            Some(LoadedPrefabs {
-                tile_prefabs,
-                hex_width,
-                hex_depth,
-            })
-        }
-        (Err(error), _) => {
-            log::error!("Failed to load GLTF: {}", error);
+    Some(LoadedPrefabs {
+        tile_prefabs,
+        hex_width,
+        hex_depth,
+        hex_orientation,
+    })
+}
+
+pub fn load_unit_mesh(world: &mut World, manifest: &AssetManifest) -> Option<String> {
+    let embedded = embedded_asset_manifest();
+    let (entry, army) = [manifest.unit.as_ref(), embedded.unit.as_ref()]
+        .into_iter()
+        .flatten()
+        .find_map(|entry| Some((entry.clone(), load_gltf_file(&entry.file)?)))?;
+    load_textures_and_meshes(world, &army);
+    let mesh_name = entry
+        .node
+        .as_deref()
+        .and_then(|node_name| {
+            army.prefabs
+                .iter()
+                .find_map(|prefab| find_node_by_name(&prefab.root_nodes, node_name))
+        })
+        .and_then(|node| node.components.render_mesh.as_ref())
+        .map(|render_mesh| render_mesh.name.clone())
+        .or_else(|| army.meshes.keys().next().cloned());
+    if mesh_name.is_none() {
+        log::error!("No unit mesh found in {}!", entry.file);
+    }
+    mesh_name
+}
+
+fn load_gltf_file(file: &str) -> Option<GltfLoadResult> {
+    let Some(bytes) = asset_bytes(file) else {
+        log::error!("Missing asset file {}", file);
+        return None;
+    };
+    match import_gltf_from_bytes(&bytes) {
+        Ok(result) => Some(result),
+        Err(error) => {
+            log::error!("Failed to load GLTF {}: {}", file, error);
             None
         }
-        (_, Err(error)) => {
-            log::error!("Failed to load grass GLTF: {}", error);
-            None
+    }
+}
+
+fn load_entry_file(
+    world: &mut World,
+    entry: &AssetEntry,
+    files: &mut HashMap<String, GltfLoadResult>,
+) -> bool {
+    if !files.contains_key(&entry.file) {
+        let Some(result) = load_gltf_file(&entry.file) else {
+            return false;
+        };
+        load_textures_and_meshes(world, &result);
+        files.insert(entry.file.clone(), result);
+    }
+    entry.node.is_none() || find_entry_node(entry, files).is_some()
+}
+
+fn resolve_manifest_entries(
+    world: &mut World,
+    manifest: &AssetManifest,
+    files: &mut HashMap<String, GltfLoadResult>,
+) -> AssetManifest {
+    let embedded = embedded_asset_manifest();
+    let mut resolved = AssetManifest {
+        tiles: Vec::new(),
+        ..manifest.clone()
+    };
+    for (tile_type, entry) in &manifest.tiles {
+        if load_entry_file(world, entry, files) {
+            resolved.tiles.push((*tile_type, entry.clone()));
+            continue;
+        }
+        let Some(fallback) =
+            asset_manifest_tile(&embedded, *tile_type).filter(|fallback| *fallback != entry)
+        else {
+            continue;
+        };
+        log::warn!(
+            "Falling back to the built-in asset for {} tiles",
+            tile_type_key(*tile_type)
+        );
+        if load_entry_file(world, fallback, files) {
+            resolved.tiles.push((*tile_type, fallback.clone()));
         }
     }
+    resolved
 }
 
 fn load_textures_and_meshes(world: &mut World, result: &GltfLoadResult) {
@@ -153,25 +233,32 @@ fn calculate_prefab_bounds(
     }
 }
 
-const TILE_TYPE_PREFAB_NAMES: [(TileType, &str); 5] = [
-    (TileType::Sea, "sea"),
-    (TileType::Land, "grass"),
-    (TileType::Forest, "normal forest"),
-    (TileType::City, "startingTile"),
-    (TileType::Port, "tile animalFarm"),
-];
+fn find_entry_node<'a>(
+    entry: &AssetEntry,
+    gltf_files: &'a HashMap<String, GltfLoadResult>,
+) -> Option<&'a PrefabNode> {
+    let node_name = entry.node.as_deref()?;
+    gltf_files
+        .get(&entry.file)?
+        .prefabs
+        .iter()
+        .find_map(|prefab| find_node_by_name(&prefab.root_nodes, node_name))
+}
 
 fn extract_tile_prefabs(
-    tiles_result: &GltfLoadResult,
-    grass_result: &GltfLoadResult,
+    manifest: &AssetManifest,
+    gltf_files: &HashMap<String, GltfLoadResult>,
     mesh_cache: &MeshCache,
 ) -> HashMap<TileType, Prefab> {
     let mut tile_prefabs: HashMap<TileType, Prefab> = HashMap::new();
 
-    let reference_prefab = tiles_result
-        .prefabs
-        .iter()
-        .find_map(|prefab| find_node_by_name(&prefab.root_nodes, "normal forest"));
+    let reference_prefab = manifest.reference_tile.and_then(|reference_tile| {
+        manifest
+            .tiles
+            .iter()
+            .find(|(tile_type, _)| *tile_type == reference_tile)
+            .and_then(|(_, entry)| find_entry_node(entry, gltf_files))
+    });
 
     let (hex_width, hex_depth) = if let Some(node) = reference_prefab {
         let mut zeroed_node = node.clone();
@@ -185,31 +272,38 @@ fn extract_tile_prefabs(
         (173.205, 200.0)
     };
 
-    for (tile_type, prefab_name) in TILE_TYPE_PREFAB_NAMES {
-        if prefab_name == "grass" {
-            if let Some(grass_prefab) = grass_result.prefabs.first() {
-                let scaled_grass =
-                    scale_grass_prefab(grass_prefab, hex_width, hex_depth, mesh_cache);
-                tile_prefabs.insert(tile_type, scaled_grass);
+    for (tile_type, entry) in &manifest.tiles {
+        match entry.node.as_deref() {
+            None => {
+                if let Some(prefab) = gltf_files
+                    .get(&entry.file)
+                    .and_then(|result| result.prefabs.first())
+                {
+                    let scaled = scale_prefab_to_hex(prefab, hex_width, hex_depth, mesh_cache);
+                    tile_prefabs.insert(*tile_type, scaled);
+                }
+            }
+            Some(prefab_name) => {
+                if let Some(node) = find_entry_node(entry, gltf_files) {
+                    let mut zeroed_node = node.clone();
+                    zeroed_node.local_transform.translation = nalgebra_glm::vec3(0.0, 0.0, 0.0);
+                    tile_prefabs.insert(
+                        *tile_type,
+                        Prefab {
+                            name: prefab_name.to_string(),
+                            root_nodes: vec![zeroed_node],
+                        },
+                    );
+                } else {
+                    log::error!("Node '{}' not found in {}", prefab_name, entry.file);
+                }
             }
-        } else if let Some(node) = tiles_result
-            .prefabs
-            .iter()
-            .find_map(|prefab| find_node_by_name(&prefab.root_nodes, prefab_name))
-        {
-            let mut zeroed_node = node.clone();
-            zeroed_node.local_transform.translation = nalgebra_glm::vec3(0.0, 0.0, 0.0);
-            tile_prefabs.insert(
-                tile_type,
-                Prefab {
-                    name: prefab_name.to_string(),
-                    root_nodes: vec![zeroed_node],
-                },
-            );
         }
     }
 
-    if let Some(city_prefab) = tile_prefabs.get(&TileType::City).cloned() {
+    if !tile_prefabs.contains_key(&TileType::Capital)
+        && let Some(city_prefab) = tile_prefabs.get(&TileType::City).cloned()
+    {
         tile_prefabs.insert(TileType::Capital, city_prefab);
     }
 
@@ -221,7 +315,7 @@ fn calculate_hex_dimensions_from_prefab(prefab: &Prefab, mesh_cache: &MeshCache)
         let width = max_x - min_x;
         let depth = max_z - min_z;
         log::info!(
-            "Tile dimensions from hexagon_tiles: width={}, depth={}",
+            "Tile dimensions from reference tile: width={}, depth={}",
             width,
             depth
         );
@@ -231,32 +325,32 @@ fn calculate_hex_dimensions_from_prefab(prefab: &Prefab, mesh_cache: &MeshCache)
     }
 }
 
-fn scale_grass_prefab(
-    grass_prefab: &Prefab,
+fn scale_prefab_to_hex(
+    prefab: &Prefab,
     hex_width: f32,
     hex_depth: f32,
     mesh_cache: &MeshCache,
 ) -> Prefab {
-    let mut grass_clone = grass_prefab.clone();
-    grass_clone.name = "grass".to_string();
+    let mut scaled = prefab.clone();
 
-    if let Some((min_x, max_x, min_z, max_z)) = calculate_prefab_bounds(&grass_clone, mesh_cache) {
-        let grass_width = max_x - min_x;
-        let grass_depth = max_z - min_z;
+    if let Some((min_x, max_x, min_z, max_z)) = calculate_prefab_bounds(&scaled, mesh_cache) {
+        let prefab_width = max_x - min_x;
+        let prefab_depth = max_z - min_z;
 
-        if grass_width > 0.001 && grass_depth > 0.001 {
-            let scale_x = hex_width / grass_width;
-            let scale_z = hex_depth / grass_depth;
+        if prefab_width > 0.001 && prefab_depth > 0.001 {
+            let scale_x = hex_width / prefab_width;
+            let scale_z = hex_depth / prefab_depth;
             let scale = scale_x.min(scale_z);
 
             log::info!(
-                "Grass bounds: {}x{}, scaling by {} to match hex tiles",
-                grass_width,
-                grass_depth,
+                "Prefab {} bounds: {}x{}, scaling by {} to match hex tiles",
+                scaled.name,
+                prefab_width,
+                prefab_depth,
                 scale
             );
 
-            for root_node in &mut grass_clone.root_nodes {
+            for root_node in &mut scaled.root_nodes {
                 root_node.local_transform.translation = nalgebra_glm::vec3(0.0, 0.0, 0.0);
                 root_node.local_transform.scale = nalgebra_glm::vec3(
                     root_node.local_transform.scale.x * scale,
@@ -266,10 +360,10 @@ fn scale_grass_prefab(
             }
         }
     } else {
-        for root_node in &mut grass_clone.root_nodes {
+        for root_node in &mut scaled.root_nodes {
             root_node.local_transform.translation = nalgebra_glm::vec3(0.0, 0.0, 0.0);
         }
     }
 
-    grass_clone
+    scaled
 }
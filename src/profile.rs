@@ -0,0 +1,279 @@
+use crate::ecs::{
+    ALL_DIFFICULTIES, ALL_FACTIONS, CombatEvent, Difficulty, Faction, difficulty_from_key,
+    difficulty_key, faction_name,
+};
+use crate::text_file::{TextFileError, check_file_header, line_error, parse_faction};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub const PROFILE_FILE_PATH: &str = "profile.hexprofile";
+const PROFILE_FILE_HEADER: &str = "hexwar-profile 1";
+const FAVORITE_SEED_COUNT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WinRecord {
+    pub games: u32,
+    pub wins: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Profile {
+    pub games_played: u32,
+    pub units_destroyed: u32,
+    pub units_lost: u32,
+    pub faction_records: HashMap<Faction, WinRecord>,
+    pub difficulty_records: HashMap<Difficulty, WinRecord>,
+    pub seed_plays: HashMap<u32, u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchTally {
+    pub units_destroyed: u32,
+    pub units_lost: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchRecord {
+    pub faction: Faction,
+    pub difficulty: Difficulty,
+    pub seed: u32,
+    pub won: bool,
+    pub tally: MatchTally,
+}
+
+pub type ProfileError = TextFileError;
+
+pub fn profile_tally_combat(tally: &mut MatchTally, event: &CombatEvent, faction: Faction) {
+    if event.attacker_faction == faction {
+        if !event.defender_survived {
+            tally.units_destroyed += 1;
+        }
+        if !event.attacker_survived {
+            tally.units_lost += 1;
+        }
+    } else if event.defender_faction == faction {
+        if !event.attacker_survived {
+            tally.units_destroyed += 1;
+        }
+        if !event.defender_survived {
+            tally.units_lost += 1;
+        }
+    }
+}
+
+pub fn profile_record_match(profile: &mut Profile, record: MatchRecord) {
+    profile.games_played += 1;
+    profile.units_destroyed += record.tally.units_destroyed;
+    profile.units_lost += record.tally.units_lost;
+
+    let faction_record = profile.faction_records.entry(record.faction).or_default();
+    faction_record.games += 1;
+    faction_record.wins += record.won as u32;
+
+    let difficulty_record = profile
+        .difficulty_records
+        .entry(record.difficulty)
+        .or_default();
+    difficulty_record.games += 1;
+    difficulty_record.wins += record.won as u32;
+
+    *profile.seed_plays.entry(record.seed).or_default() += 1;
+}
+
+pub fn profile_total_wins(profile: &Profile) -> u32 {
+    profile
+        .faction_records
+        .values()
+        .map(|record| record.wins)
+        .sum()
+}
+
+pub fn profile_favorite_seeds(profile: &Profile) -> Vec<(u32, u32)> {
+    let mut seeds: Vec<(u32, u32)> = profile
+        .seed_plays
+        .iter()
+        .map(|(seed, plays)| (*seed, *plays))
+        .collect();
+    seeds.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    seeds.truncate(FAVORITE_SEED_COUNT);
+    seeds
+}
+
+fn win_record_text(record: WinRecord) -> String {
+    format!("{} / {} won", record.wins, record.games)
+}
+
+pub fn profile_summary_lines(profile: &Profile) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "Games played: {}   Wins: {}",
+            profile.games_played,
+            profile_total_wins(profile)
+        ),
+        format!(
+            "Units destroyed: {}   Units lost: {}",
+            profile.units_destroyed, profile.units_lost
+        ),
+    ];
+
+    for faction in ALL_FACTIONS {
+        let record = profile
+            .faction_records
+            .get(&faction)
+            .copied()
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}: {}",
+            faction_name(faction),
+            win_record_text(record)
+        ));
+    }
+
+    for difficulty in ALL_DIFFICULTIES {
+        let record = profile
+            .difficulty_records
+            .get(&difficulty)
+            .copied()
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}: {}",
+            difficulty_key(difficulty).to_uppercase(),
+            win_record_text(record)
+        ));
+    }
+
+    let favorites = profile_favorite_seeds(profile);
+    if favorites.is_empty() {
+        lines.push("Favorite seeds: none yet".to_string());
+    } else {
+        let seeds: Vec<String> = favorites
+            .iter()
+            .map(|(seed, plays)| format!("{} (x{})", seed, plays))
+            .collect();
+        lines.push(format!("Favorite seeds: {}", seeds.join(", ")));
+    }
+
+    lines
+}
+
+pub fn profile_to_string(profile: &Profile) -> String {
+    let mut output = String::new();
+    output.push_str(PROFILE_FILE_HEADER);
+    output.push('\n');
+    output.push_str(&format!("games_played {}\n", profile.games_played));
+    output.push_str(&format!("units_destroyed {}\n", profile.units_destroyed));
+    output.push_str(&format!("units_lost {}\n", profile.units_lost));
+
+    for faction in ALL_FACTIONS {
+        if let Some(record) = profile.faction_records.get(&faction) {
+            output.push_str(&format!(
+                "faction {} {} {}\n",
+                faction_name(faction),
+                record.games,
+                record.wins
+            ));
+        }
+    }
+
+    for difficulty in ALL_DIFFICULTIES {
+        if let Some(record) = profile.difficulty_records.get(&difficulty) {
+            output.push_str(&format!(
+                "difficulty {} {} {}\n",
+                difficulty_key(difficulty),
+                record.games,
+                record.wins
+            ));
+        }
+    }
+
+    let mut seeds: Vec<(&u32, &u32)> = profile.seed_plays.iter().collect();
+    seeds.sort();
+    for (seed, plays) in seeds {
+        output.push_str(&format!("seed {} {}\n", seed, plays));
+    }
+
+    output
+}
+
+fn parse_count(line: usize, value: &str) -> Result<u32, ProfileError> {
+    value
+        .parse::<u32>()
+        .map_err(|_| line_error(line, format!("invalid count '{}'", value)))
+}
+
+fn parse_win_record(line: usize, games: &str, wins: &str) -> Result<WinRecord, ProfileError> {
+    let record = WinRecord {
+        games: parse_count(line, games)?,
+        wins: parse_count(line, wins)?,
+    };
+    if record.wins > record.games {
+        return Err(line_error(line, "more wins than games"));
+    }
+    Ok(record)
+}
+
+pub fn profile_from_string(contents: &str) -> Result<Profile, ProfileError> {
+    let mut lines = contents.lines().enumerate();
+
+    check_file_header(
+        lines.next().map(|(_, line)| line),
+        PROFILE_FILE_HEADER,
+        "profile",
+    )?;
+
+    let mut profile = Profile::default();
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [] => {}
+            ["games_played", value] => profile.games_played = parse_count(line_number, value)?,
+            ["units_destroyed", value] => {
+                profile.units_destroyed = parse_count(line_number, value)?
+            }
+            ["units_lost", value] => profile.units_lost = parse_count(line_number, value)?,
+            ["faction", name, games, wins] => {
+                let faction = parse_faction(line_number, name)?;
+                let record = parse_win_record(line_number, games, wins)?;
+                profile.faction_records.insert(faction, record);
+            }
+            ["difficulty", key, games, wins] => {
+                let difficulty = difficulty_from_key(key).ok_or_else(|| {
+                    line_error(line_number, format!("unknown difficulty '{}'", key))
+                })?;
+                let record = parse_win_record(line_number, games, wins)?;
+                profile.difficulty_records.insert(difficulty, record);
+            }
+            ["seed", seed, plays] => {
+                let seed = seed
+                    .parse::<u32>()
+                    .map_err(|_| line_error(line_number, format!("invalid seed '{}'", seed)))?;
+                let plays = parse_count(line_number, plays)?;
+                profile.seed_plays.insert(seed, plays);
+            }
+            _ => return Err(line_error(line_number, "unrecognized entry")),
+        }
+    }
+
+    Ok(profile)
+}
+
+pub fn load_profile(path: &Path) -> Result<Profile, ProfileError> {
+    let contents = std::fs::read_to_string(path)?;
+    profile_from_string(&contents)
+}
+
+pub fn save_profile(path: &Path, profile: &Profile) -> Result<(), ProfileError> {
+    std::fs::write(path, profile_to_string(profile))?;
+    Ok(())
+}
+
+pub fn profile_for_launch() -> Result<Profile, ProfileError> {
+    let path = Path::new(PROFILE_FILE_PATH);
+    if path.exists() {
+        load_profile(path)
+    } else {
+        Ok(Profile::default())
+    }
+}
@@ -0,0 +1,44 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GraphicsQuality {
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualitySettings {
+    pub particle_density: f32,
+    pub max_firework_shells: u32,
+    pub bloom_intensity: f32,
+    pub bloom_resolution_divisor: u32,
+}
+
+impl Default for QualitySettings {
+    fn default() -> Self {
+        quality_settings(GraphicsQuality::default())
+    }
+}
+
+pub fn quality_settings(quality: GraphicsQuality) -> QualitySettings {
+    match quality {
+        GraphicsQuality::Low => QualitySettings {
+            particle_density: 0.25,
+            max_firework_shells: 2,
+            bloom_intensity: 0.002,
+            bloom_resolution_divisor: 8,
+        },
+        GraphicsQuality::Medium => QualitySettings {
+            particle_density: 0.5,
+            max_firework_shells: 4,
+            bloom_intensity: 0.004,
+            bloom_resolution_divisor: 4,
+        },
+        GraphicsQuality::High => QualitySettings {
+            particle_density: 1.0,
+            max_firework_shells: 8,
+            bloom_intensity: 0.005,
+            bloom_resolution_divisor: 2,
+        },
+    }
+}
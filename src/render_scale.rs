@@ -0,0 +1,67 @@
+use nightshade::prelude::*;
+
+pub const DEFAULT_SURFACE_SIZE: (u32, u32) = (1920, 1080);
+pub const MIN_RENDER_SCALE: f32 = 0.25;
+pub const MAX_RENDER_SCALE: f32 = 1.0;
+pub const RENDER_SCALE_STEPS: [f32; 3] = [1.0, 0.75, 0.5];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderScale {
+    pub scale: f32,
+    pub surface_size: (u32, u32),
+    pub configured_size: (u32, u32),
+}
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self {
+            scale: MAX_RENDER_SCALE,
+            surface_size: DEFAULT_SURFACE_SIZE,
+            configured_size: DEFAULT_SURFACE_SIZE,
+        }
+    }
+}
+
+pub fn render_scale_target_size(render_scale: &RenderScale) -> (u32, u32) {
+    let (width, height) = render_scale.surface_size;
+    (
+        ((width as f32 * render_scale.scale) as u32).max(1),
+        ((height as f32 * render_scale.scale) as u32).max(1),
+    )
+}
+
+pub fn render_scale_set(render_scale: &mut RenderScale, scale: f32) {
+    render_scale.scale = scale.clamp(MIN_RENDER_SCALE, MAX_RENDER_SCALE);
+}
+
+pub fn render_scale_cycle(render_scale: &mut RenderScale) -> f32 {
+    let next = RENDER_SCALE_STEPS
+        .iter()
+        .position(|step| (*step - render_scale.scale).abs() < f32::EPSILON)
+        .map(|index| RENDER_SCALE_STEPS[(index + 1) % RENDER_SCALE_STEPS.len()])
+        .unwrap_or(MAX_RENDER_SCALE);
+    render_scale_set(render_scale, next);
+    render_scale.scale
+}
+
+pub fn render_scale_system(render_scale: &mut RenderScale, world: &mut World) {
+    if let Some((width, height)) = world.resources.window.cached_viewport_size
+        && width > 0
+        && height > 0
+    {
+        render_scale.surface_size = (width, height);
+    }
+
+    let target_size = render_scale_target_size(render_scale);
+    if target_size == render_scale.configured_size {
+        return;
+    }
+
+    log::info!(
+        "Rebuilding render graph at {}x{} (scale {:.2})",
+        target_size.0,
+        target_size.1,
+        render_scale.scale
+    );
+    world.resources.graphics.render_graph_dirty = true;
+}
@@ -1,4 +1,4 @@
-use crate::hex::{HexCoord, hex_to_world_position};
+use crate::hex::{HexCoord, HexOrientation, hex_to_world_position};
 use nightshade::ecs::world::components::Line;
 use nightshade::prelude::*;
 
@@ -6,12 +6,14 @@ pub fn generate_hex_outline(
     center: Vec3,
     hex_width: f32,
     hex_height: f32,
+    orientation: HexOrientation,
     y_offset: f32,
 ) -> Vec<Line> {
     generate_hex_outline_with_color(
         center,
         hex_width,
         hex_height,
+        orientation,
         y_offset,
         nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
     )
@@ -21,13 +23,13 @@ pub fn generate_hex_outline_with_color(
     center: Vec3,
     hex_width: f32,
     hex_height: f32,
+    orientation: HexOrientation,
     y_offset: f32,
     color: Vec4,
 ) -> Vec<Line> {
     let mut lines = Vec::new();
-    let is_flat_top = hex_width > hex_height;
 
-    let vertices: Vec<Vec3> = if is_flat_top {
+    let vertices: Vec<Vec3> = if orientation == HexOrientation::FlatTop {
         let half_width = hex_width / 2.0;
         let quarter_width = hex_width / 4.0;
         let half_height = hex_height / 2.0;
@@ -66,14 +68,17 @@ pub fn generate_range_circle_lines(
     tiles_in_range: &[HexCoord],
     hex_width: f32,
     hex_depth: f32,
+    orientation: HexOrientation,
     color: Vec4,
 ) -> Vec<Line> {
     let mut lines = Vec::new();
     let y_offset = 10.0;
 
     for coord in tiles_in_range {
-        let tile_center = hex_to_world_position(coord.column, coord.row, hex_width, hex_depth);
-        let hex_lines = generate_hex_outline(tile_center, hex_width, hex_depth, y_offset);
+        let tile_center =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+        let hex_lines =
+            generate_hex_outline(tile_center, hex_width, hex_depth, orientation, y_offset);
         for mut line in hex_lines {
             line.color = color;
             lines.push(line);
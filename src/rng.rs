@@ -0,0 +1,60 @@
+//! Seedable PCG32 generator shared by every gameplay system that needs
+//! randomness. Map generation, reinforcements, and AI decisions all draw from
+//! a `GameRng`, so a game is fully reproducible from its starting seed.
+//! Purely cosmetic effects such as fireworks may keep using `rand::rng()`.
+
+const PCG_MULTIPLIER: u64 = 6364136223846793005;
+const PCG_INCREMENT: u64 = 1442695040888963407;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameRng {
+    pub state: u64,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        game_rng_new(0)
+    }
+}
+
+pub fn game_rng_new(seed: u32) -> GameRng {
+    let mut rng = GameRng { state: 0 };
+    game_rng_next_u32(&mut rng);
+    rng.state = rng.state.wrapping_add(seed as u64);
+    game_rng_next_u32(&mut rng);
+    rng
+}
+
+pub fn game_rng_next_u32(rng: &mut GameRng) -> u32 {
+    let previous = rng.state;
+    rng.state = previous
+        .wrapping_mul(PCG_MULTIPLIER)
+        .wrapping_add(PCG_INCREMENT);
+    let xorshifted = (((previous >> 18) ^ previous) >> 27) as u32;
+    let rotation = (previous >> 59) as u32;
+    xorshifted.rotate_right(rotation)
+}
+
+pub fn game_rng_range(rng: &mut GameRng, max: u32) -> u32 {
+    if max == 0 {
+        return 0;
+    }
+    let threshold = max.wrapping_neg() % max;
+    loop {
+        let value = game_rng_next_u32(rng);
+        if value >= threshold {
+            return value % max;
+        }
+    }
+}
+
+pub fn game_rng_percent(rng: &mut GameRng, chance: u32) -> bool {
+    game_rng_range(rng, 100) < chance
+}
+
+pub fn game_rng_shuffle<T>(rng: &mut GameRng, slice: &mut [T]) {
+    for index in (1..slice.len()).rev() {
+        let swap_index = game_rng_range(rng, (index + 1) as u32) as usize;
+        slice.swap(index, swap_index);
+    }
+}
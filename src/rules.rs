@@ -0,0 +1,208 @@
+use crate::constants::{ACTIONS_PER_TURN, CITY_REINFORCEMENT, INITIAL_SOLDIERS, MAX_SOLDIERS};
+use crate::text_file::{TextFileError, check_file_header, line_error};
+use std::path::Path;
+
+pub const RULES_FILE_PATH: &str = "rules.hexrules";
+const RULES_FILE_HEADER: &str = "hexwar-rules 1";
+
+pub const ACTIONS_PER_TURN_RANGE: (u8, u8) = (1, 10);
+pub const CITY_REINFORCEMENT_RANGE: (i32, i32) = (0, 30);
+pub const INITIAL_SOLDIERS_RANGE: (i32, i32) = (1, 99);
+pub const MAX_SOLDIERS_RANGE: (i32, i32) = (10, 999);
+pub const TOGGLE_RANGE: (i32, i32) = (0, 1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameRules {
+    pub actions_per_turn: u8,
+    pub city_reinforcement: i32,
+    pub initial_soldiers: i32,
+    pub max_soldiers: i32,
+    pub movement_points: bool,
+    pub defection: bool,
+    pub supply_wagons: bool,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            actions_per_turn: ACTIONS_PER_TURN,
+            city_reinforcement: CITY_REINFORCEMENT,
+            initial_soldiers: INITIAL_SOLDIERS,
+            max_soldiers: MAX_SOLDIERS,
+            movement_points: false,
+            defection: false,
+            supply_wagons: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    ActionsPerTurn,
+    CityReinforcement,
+    InitialSoldiers,
+    MaxSoldiers,
+    MovementPoints,
+    Defection,
+    SupplyWagons,
+}
+
+pub const ALL_RULES: [RuleKind; 7] = [
+    RuleKind::ActionsPerTurn,
+    RuleKind::CityReinforcement,
+    RuleKind::InitialSoldiers,
+    RuleKind::MaxSoldiers,
+    RuleKind::MovementPoints,
+    RuleKind::Defection,
+    RuleKind::SupplyWagons,
+];
+
+pub type RulesFileError = TextFileError;
+
+pub fn rule_key(rule: RuleKind) -> &'static str {
+    match rule {
+        RuleKind::ActionsPerTurn => "actions_per_turn",
+        RuleKind::CityReinforcement => "city_reinforcement",
+        RuleKind::InitialSoldiers => "initial_soldiers",
+        RuleKind::MaxSoldiers => "max_soldiers",
+        RuleKind::MovementPoints => "movement_points",
+        RuleKind::Defection => "defection",
+        RuleKind::SupplyWagons => "supply_wagons",
+    }
+}
+
+pub fn rule_label(rule: RuleKind) -> &'static str {
+    match rule {
+        RuleKind::ActionsPerTurn => "ACTIONS PER TURN",
+        RuleKind::CityReinforcement => "CITY REINFORCEMENT",
+        RuleKind::InitialSoldiers => "STARTING SOLDIERS",
+        RuleKind::MaxSoldiers => "MAX SOLDIERS",
+        RuleKind::MovementPoints => "MOVEMENT POINTS",
+        RuleKind::Defection => "DEFECTION",
+        RuleKind::SupplyWagons => "SUPPLY WAGONS",
+    }
+}
+
+pub fn rule_value(rules: &GameRules, rule: RuleKind) -> i32 {
+    match rule {
+        RuleKind::ActionsPerTurn => rules.actions_per_turn as i32,
+        RuleKind::CityReinforcement => rules.city_reinforcement,
+        RuleKind::InitialSoldiers => rules.initial_soldiers,
+        RuleKind::MaxSoldiers => rules.max_soldiers,
+        RuleKind::MovementPoints => rules.movement_points as i32,
+        RuleKind::Defection => rules.defection as i32,
+        RuleKind::SupplyWagons => rules.supply_wagons as i32,
+    }
+}
+
+pub fn rule_value_label(rules: &GameRules, rule: RuleKind) -> String {
+    match rule {
+        RuleKind::MovementPoints if rules.movement_points => "ON".to_string(),
+        RuleKind::MovementPoints => "OFF".to_string(),
+        RuleKind::Defection if rules.defection => "ON".to_string(),
+        RuleKind::Defection => "OFF".to_string(),
+        RuleKind::SupplyWagons if rules.supply_wagons => "ON".to_string(),
+        RuleKind::SupplyWagons => "OFF".to_string(),
+        _ => rule_value(rules, rule).to_string(),
+    }
+}
+
+fn rule_range(rules: &GameRules, rule: RuleKind) -> (i32, i32) {
+    match rule {
+        RuleKind::ActionsPerTurn => (
+            ACTIONS_PER_TURN_RANGE.0 as i32,
+            ACTIONS_PER_TURN_RANGE.1 as i32,
+        ),
+        RuleKind::CityReinforcement => CITY_REINFORCEMENT_RANGE,
+        RuleKind::InitialSoldiers => (
+            INITIAL_SOLDIERS_RANGE.0,
+            INITIAL_SOLDIERS_RANGE.1.min(rules.max_soldiers),
+        ),
+        RuleKind::MaxSoldiers => MAX_SOLDIERS_RANGE,
+        RuleKind::MovementPoints | RuleKind::Defection | RuleKind::SupplyWagons => TOGGLE_RANGE,
+    }
+}
+
+pub fn rules_set(rules: &mut GameRules, rule: RuleKind, value: i32) -> bool {
+    let (min, max) = rule_range(rules, rule);
+    if !(min..=max).contains(&value) {
+        return false;
+    }
+    match rule {
+        RuleKind::ActionsPerTurn => rules.actions_per_turn = value as u8,
+        RuleKind::CityReinforcement => rules.city_reinforcement = value,
+        RuleKind::InitialSoldiers => rules.initial_soldiers = value,
+        RuleKind::MaxSoldiers => {
+            rules.max_soldiers = value;
+            rules.initial_soldiers = rules.initial_soldiers.min(value);
+        }
+        RuleKind::MovementPoints => rules.movement_points = value == 1,
+        RuleKind::Defection => rules.defection = value == 1,
+        RuleKind::SupplyWagons => rules.supply_wagons = value == 1,
+    }
+    true
+}
+
+pub fn rules_adjust(rules: &mut GameRules, rule: RuleKind, delta: i32) {
+    let (min, max) = rule_range(rules, rule);
+    let value = (rule_value(rules, rule) + delta).clamp(min, max);
+    rules_set(rules, rule, value);
+}
+
+pub fn rules_from_string(contents: &str) -> Result<GameRules, RulesFileError> {
+    let mut lines = contents.lines().enumerate();
+
+    check_file_header(
+        lines.next().map(|(_, line)| line),
+        RULES_FILE_HEADER,
+        "rules file",
+    )?;
+
+    let mut rules = GameRules::default();
+    let mut entries: Vec<(usize, RuleKind, i32)> = Vec::new();
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [] => {}
+            [key, value] => {
+                let rule = ALL_RULES
+                    .iter()
+                    .find(|rule| rule_key(**rule) == *key)
+                    .copied()
+                    .ok_or_else(|| line_error(line_number, format!("unknown rule '{}'", key)))?;
+                let value = value
+                    .parse::<i32>()
+                    .map_err(|_| line_error(line_number, format!("invalid value '{}'", value)))?;
+                entries.push((line_number, rule, value));
+            }
+            _ => return Err(line_error(line_number, "unrecognized entry")),
+        }
+    }
+
+    entries.sort_by_key(|(_, rule, _)| *rule != RuleKind::MaxSoldiers);
+    for (line_number, rule, value) in entries {
+        if !rules_set(&mut rules, rule, value) {
+            return Err(line_error(
+                line_number,
+                format!("{} {} is out of range", rule_key(rule), value),
+            ));
+        }
+    }
+
+    Ok(rules)
+}
+
+pub fn load_rules_file(path: &Path) -> Result<GameRules, RulesFileError> {
+    let contents = std::fs::read_to_string(path)?;
+    rules_from_string(&contents)
+}
+
+pub fn rules_for_launch(path: Option<&Path>) -> Result<GameRules, RulesFileError> {
+    match path {
+        Some(path) => load_rules_file(path),
+        None if Path::new(RULES_FILE_PATH).exists() => load_rules_file(Path::new(RULES_FILE_PATH)),
+        None => Ok(GameRules::default()),
+    }
+}
@@ -0,0 +1,222 @@
+use crate::autosave::{AutosaveSlot, list_autosaves};
+use crate::ecs::{difficulty_key, faction_color, faction_name};
+use crate::hall_of_fame::timestamp_label;
+use crate::hud_text::spawn_scaled_hud_text;
+use crate::menu::{MenuAction, MenuButton, create_button, update_buttons_hover};
+use crate::savegame::{SaveMetadata, SaveThumbnail, ThumbnailCell};
+use nightshade::prelude::*;
+
+const SLOT_LIST_X: f32 = -220.0;
+const SLOT_LIST_TOP: f32 = -150.0;
+const SLOT_SPACING: f32 = 50.0;
+const THUMBNAIL_LEFT: f32 = 40.0;
+const THUMBNAIL_TOP: f32 = -150.0;
+const THUMBNAIL_CELL_SIZE: f32 = 12.0;
+const NEUTRAL_LAND_COLOR: [f32; 4] = [0.55, 0.55, 0.5, 1.0];
+
+#[derive(Default)]
+pub struct SaveBrowser {
+    pub slots: Vec<AutosaveSlot>,
+    pub buttons: Vec<MenuButton>,
+    pub text_entities: Vec<Entity>,
+    pub preview_entities: Vec<Entity>,
+    pub hovered_index: Option<usize>,
+    pub previewed_index: Option<usize>,
+}
+
+fn text_properties(font_size: f32, color: Vec4) -> TextProperties {
+    TextProperties {
+        font_size,
+        color,
+        alignment: TextAlignment::Center,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    }
+}
+
+fn slot_label(slot: &AutosaveSlot) -> String {
+    match &slot.metadata {
+        Some(metadata) => format!(
+            "TURN {}  {}  {}",
+            metadata.turn_number,
+            difficulty_key(metadata.difficulty).to_uppercase(),
+            timestamp_label(metadata.saved_at)
+        ),
+        None => format!("SLOT {}  EMPTY", slot.slot + 1),
+    }
+}
+
+pub fn despawn_save_browser(browser: &mut SaveBrowser, world: &mut World) {
+    despawn_save_preview(browser, world);
+    for button in browser.buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+    for entity in browser.text_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
+    browser.hovered_index = None;
+}
+
+fn despawn_save_preview(browser: &mut SaveBrowser, world: &mut World) {
+    for entity in browser.preview_entities.drain(..) {
+        world.despawn_entities(&[entity]);
+    }
+    browser.previewed_index = None;
+}
+
+pub fn save_browser_open(browser: &mut SaveBrowser, world: &mut World, ui_scale: f32) {
+    despawn_save_browser(browser, world);
+    browser.slots = list_autosaves();
+
+    browser.text_entities.push(spawn_scaled_hud_text(
+        world,
+        "LOAD AUTOSAVE",
+        HudAnchor::Center,
+        nalgebra_glm::vec2(0.0, -230.0),
+        text_properties(56.0, nalgebra_glm::vec4(1.0, 0.5, 0.2, 1.0)),
+        ui_scale,
+    ));
+
+    for (index, slot) in browser.slots.iter().enumerate() {
+        browser.buttons.push(create_button(
+            world,
+            &slot_label(slot),
+            nalgebra_glm::vec2(SLOT_LIST_X, SLOT_LIST_TOP + index as f32 * SLOT_SPACING),
+            HudAnchor::Center,
+            24.0,
+            ui_scale,
+        ));
+    }
+    browser.buttons.push(create_button(
+        world,
+        "BACK",
+        nalgebra_glm::vec2(0.0, 180.0),
+        HudAnchor::Center,
+        40.0,
+        ui_scale,
+    ));
+}
+
+fn thumbnail_cell_color(cell: ThumbnailCell) -> Option<Vec4> {
+    let color = match cell {
+        ThumbnailCell::Sea => return None,
+        ThumbnailCell::Land(None) => NEUTRAL_LAND_COLOR,
+        ThumbnailCell::Land(Some(faction)) => faction_color(faction),
+    };
+    Some(nalgebra_glm::vec4(color[0], color[1], color[2], 1.0))
+}
+
+fn spawn_thumbnail(
+    entities: &mut Vec<Entity>,
+    world: &mut World,
+    thumbnail: &SaveThumbnail,
+    ui_scale: f32,
+) {
+    for (index, cell) in thumbnail.cells.iter().enumerate() {
+        let Some(color) = thumbnail_cell_color(*cell) else {
+            continue;
+        };
+        let column = index as i32 % thumbnail.width.max(1);
+        let row = index as i32 / thumbnail.width.max(1);
+        let stagger = if column % 2 == 1 {
+            THUMBNAIL_CELL_SIZE / 2.0
+        } else {
+            0.0
+        };
+        entities.push(spawn_scaled_hud_text(
+            world,
+            "#",
+            HudAnchor::Center,
+            nalgebra_glm::vec2(
+                THUMBNAIL_LEFT + column as f32 * THUMBNAIL_CELL_SIZE,
+                THUMBNAIL_TOP + row as f32 * THUMBNAIL_CELL_SIZE + stagger,
+            ),
+            text_properties(THUMBNAIL_CELL_SIZE * 1.4, color),
+            ui_scale,
+        ));
+    }
+}
+
+fn spawn_save_preview(
+    entities: &mut Vec<Entity>,
+    world: &mut World,
+    metadata: &SaveMetadata,
+    ui_scale: f32,
+) {
+    spawn_thumbnail(entities, world, &metadata.thumbnail, ui_scale);
+
+    let center_x = THUMBNAIL_LEFT + metadata.thumbnail.width as f32 * THUMBNAIL_CELL_SIZE / 2.0;
+    let below = THUMBNAIL_TOP + (metadata.thumbnail.height as f32 + 1.5) * THUMBNAIL_CELL_SIZE;
+    entities.push(spawn_scaled_hud_text(
+        world,
+        &format!("{} factions alive", metadata.factions_alive.len()),
+        HudAnchor::Center,
+        nalgebra_glm::vec2(center_x, below),
+        text_properties(20.0, nalgebra_glm::vec4(0.9, 0.9, 0.9, 1.0)),
+        ui_scale,
+    ));
+    for (index, faction) in metadata.factions_alive.iter().enumerate() {
+        let color = faction_color(*faction);
+        entities.push(spawn_scaled_hud_text(
+            world,
+            faction_name(*faction),
+            HudAnchor::Center,
+            nalgebra_glm::vec2(center_x, below + 24.0 * (index as f32 + 1.0)),
+            text_properties(18.0, nalgebra_glm::vec4(color[0], color[1], color[2], 1.0)),
+            ui_scale,
+        ));
+    }
+}
+
+pub fn save_browser_system(
+    browser: &mut SaveBrowser,
+    world: &mut World,
+    screen_width: f32,
+    screen_height: f32,
+    ui_scale: f32,
+) -> MenuAction {
+    let mouse_x = world.resources.input.mouse.position.x;
+    let mouse_y = world.resources.input.mouse.position.y;
+    let clicked = world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::LEFT_JUST_RELEASED);
+
+    browser.hovered_index = update_buttons_hover(
+        &browser.buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        browser.hovered_index,
+    );
+
+    let hovered_slot = browser
+        .hovered_index
+        .filter(|index| *index < browser.slots.len());
+    if hovered_slot.is_some() && hovered_slot != browser.previewed_index {
+        despawn_save_preview(browser, world);
+        if let Some(metadata) =
+            hovered_slot.and_then(|index| browser.slots[index].metadata.as_ref())
+        {
+            spawn_save_preview(&mut browser.preview_entities, world, metadata, ui_scale);
+        }
+        browser.previewed_index = hovered_slot;
+    }
+
+    if !clicked {
+        return MenuAction::None;
+    }
+    match browser.hovered_index {
+        Some(index) if index == browser.slots.len() => MenuAction::ReturnToMainMenu,
+        Some(index) => match &browser.slots[index].metadata {
+            Some(_) => MenuAction::LoadAutosave(browser.slots[index].slot),
+            None => MenuAction::None,
+        },
+        None => MenuAction::None,
+    }
+}
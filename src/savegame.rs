@@ -0,0 +1,858 @@
+use crate::ecs::{
+    ALL_FACTIONS, CasualtyStats, Construction, Difficulty, Faction, GameWorld, General,
+    HEX_POSITION, Improvement, TILE, TileType, UNIT, UnitKind, difficulty_from_key, difficulty_key,
+    faction_index, faction_name, unit_kind_name,
+};
+use crate::hex::HexCoord;
+use crate::map::GeneratedMap;
+use crate::map_file::{map_file_from_string, map_file_to_string};
+use crate::rng::GameRng;
+use crate::rules::GameRules;
+use crate::state_hash::{StateHashMismatch, StateHashRecord, hash_game_state};
+use crate::systems::{build_turn_order, despawn_unit, improvement_name, make_general, spawn_unit};
+use crate::teams::{TeamLayout, team_layout_from_key, team_layout_key};
+use crate::text_file::{TextFileError, check_file_header, line_error, parse_faction};
+use crate::turn_rotation::TurnRotation;
+use crate::turn_timer::turn_timer_new;
+use std::path::Path;
+use std::str::FromStr;
+
+pub const IRONMAN_SAVE_PATH: &str = "ironman.hexsave";
+const SAVE_FILE_HEADER: &str = "hexwar-save 12";
+const MAP_SECTION_MARKER: &str = "[map]";
+pub const THUMBNAIL_MAX_WIDTH: i32 = 24;
+pub const THUMBNAIL_MAX_HEIGHT: i32 = 16;
+const THUMBNAIL_SEA: char = '~';
+const THUMBNAIL_LAND: char = '.';
+const THUMBNAIL_FACTIONS: [char; 4] = ['r', 'v', 'b', 'g'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailCell {
+    Sea,
+    Land(Option<Faction>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SaveThumbnail {
+    pub width: i32,
+    pub height: i32,
+    pub cells: Vec<ThumbnailCell>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SaveMetadata {
+    pub turn_number: u32,
+    pub difficulty: Difficulty,
+    pub factions_alive: Vec<Faction>,
+    pub saved_at: u64,
+    pub thumbnail: SaveThumbnail,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SavedFaction {
+    pub eliminated: bool,
+    pub morale: i32,
+    pub capital_owner: Option<Faction>,
+    pub capital_coord: HexCoord,
+    pub capital_relocated: bool,
+    pub ability_cooldown: u32,
+    pub ability_active: bool,
+    pub difficulty: Difficulty,
+    pub casualties: CasualtyStats,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SavedTile {
+    pub coord: HexCoord,
+    pub owner: Option<Faction>,
+    pub growth: u32,
+    pub improvement: Option<Improvement>,
+    pub construction: Option<Construction>,
+    pub pillaged_until: Option<u32>,
+    pub battles: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SavedUnit {
+    pub coord: HexCoord,
+    pub faction: Faction,
+    pub kind: UnitKind,
+    pub soldiers: i32,
+    pub morale: i32,
+    pub movement_range: i32,
+    pub movement_left: i32,
+    pub has_moved: bool,
+    pub general: Option<General>,
+}
+
+#[derive(Clone)]
+pub struct SaveGame {
+    pub player_faction: Faction,
+    pub ironman: bool,
+    pub difficulty: Difficulty,
+    pub team_layout: TeamLayout,
+    pub rules: GameRules,
+    pub rng_state: u64,
+    pub turn_seconds: u32,
+    pub turn_number: u32,
+    pub current_faction: Faction,
+    pub actions_remaining: u8,
+    pub speech_used: bool,
+    pub rotation: TurnRotation,
+    pub factions: [SavedFaction; 4],
+    pub tiles: Vec<SavedTile>,
+    pub units: Vec<SavedUnit>,
+    pub seed: u32,
+    pub map: GeneratedMap,
+    pub saved_at: u64,
+    pub state_hash: u64,
+    pub state_hashes: Vec<StateHashRecord>,
+}
+
+pub type SaveGameError = TextFileError;
+
+pub fn savegame_capture(
+    game_world: &GameWorld,
+    player_faction: Faction,
+    ironman: bool,
+    saved_at: u64,
+) -> SaveGame {
+    let resources = &game_world.resources;
+
+    let mut factions = [SavedFaction::default(); 4];
+    for faction in ALL_FACTIONS {
+        let index = faction_index(faction);
+        factions[index] = SavedFaction {
+            eliminated: resources.faction_eliminated[index],
+            morale: resources.faction_morale[index],
+            capital_owner: resources.capital_owners[index],
+            capital_coord: resources.capital_coords[index],
+            capital_relocated: resources.capital_relocated[index],
+            ability_cooldown: resources.ability_cooldowns[index],
+            ability_active: resources.active_abilities[index],
+            difficulty: resources.faction_difficulties[index],
+            casualties: resources.casualty_stats[index],
+        };
+    }
+
+    let mut tiles: Vec<SavedTile> = game_world
+        .query_entities(TILE | HEX_POSITION)
+        .filter_map(|entity| {
+            let tile = game_world.get_tile(entity)?;
+            let coord = game_world.get_hex_position(entity)?.0;
+            Some(SavedTile {
+                coord,
+                owner: tile.faction,
+                growth: tile.growth,
+                improvement: tile.improvement,
+                construction: tile.construction,
+                pillaged_until: tile.pillaged_until,
+                battles: tile.battles,
+            })
+        })
+        .collect();
+    tiles.sort_by_key(|tile| (tile.coord.row, tile.coord.column));
+
+    let mut units: Vec<SavedUnit> = game_world
+        .query_entities(UNIT | HEX_POSITION)
+        .filter_map(|entity| {
+            let unit = game_world.get_unit(entity)?;
+            let coord = game_world.get_hex_position(entity)?.0;
+            Some(SavedUnit {
+                coord,
+                faction: unit.faction,
+                kind: unit.kind,
+                soldiers: unit.soldiers,
+                morale: unit.morale,
+                movement_range: unit.movement_range,
+                movement_left: unit.movement_left,
+                has_moved: unit.has_moved,
+                general: game_world.get_general(entity).copied(),
+            })
+        })
+        .collect();
+    units.sort_by_key(|unit| {
+        (
+            unit.coord.row,
+            unit.coord.column,
+            faction_index(unit.faction),
+            unit.kind as u8,
+            unit.soldiers,
+            unit.morale,
+            unit.movement_range,
+            unit.movement_left,
+            unit.has_moved,
+            unit.general
+                .map(|general| (general.level, general.experience)),
+        )
+    });
+
+    SaveGame {
+        player_faction,
+        ironman,
+        difficulty: resources.difficulty,
+        team_layout: resources.team_layout,
+        rules: resources.rules,
+        rng_state: resources.game_rng.state,
+        turn_seconds: resources.turn_timer.seconds_per_turn,
+        turn_number: resources.turn_number,
+        current_faction: resources.current_faction,
+        actions_remaining: resources.actions_remaining,
+        speech_used: resources.speech_used,
+        rotation: resources.turn_rotation.clone(),
+        factions,
+        tiles,
+        units,
+        seed: resources.rng_seed,
+        map: resources.generated_map.clone(),
+        saved_at,
+        state_hash: hash_game_state(game_world),
+        state_hashes: resources.state_hashes.clone(),
+    }
+}
+
+pub fn savegame_thumbnail(save: &SaveGame) -> SaveThumbnail {
+    let width = save.map.width.clamp(1, THUMBNAIL_MAX_WIDTH);
+    let height = save.map.height.clamp(1, THUMBNAIL_MAX_HEIGHT);
+    let mut cells = Vec::with_capacity((width * height) as usize);
+    for row in 0..height {
+        for column in 0..width {
+            let coord = HexCoord {
+                column: column * save.map.width / width,
+                row: row * save.map.height / height,
+            };
+            let cell = match save.map.tiles.get(&coord) {
+                None | Some(TileType::Sea) => ThumbnailCell::Sea,
+                Some(_) => ThumbnailCell::Land(
+                    save.tiles
+                        .iter()
+                        .find(|tile| tile.coord == coord)
+                        .and_then(|tile| tile.owner),
+                ),
+            };
+            cells.push(cell);
+        }
+    }
+    SaveThumbnail {
+        width,
+        height,
+        cells,
+    }
+}
+
+fn thumbnail_cell_key(cell: ThumbnailCell) -> char {
+    match cell {
+        ThumbnailCell::Sea => THUMBNAIL_SEA,
+        ThumbnailCell::Land(None) => THUMBNAIL_LAND,
+        ThumbnailCell::Land(Some(faction)) => THUMBNAIL_FACTIONS[faction_index(faction)],
+    }
+}
+
+fn parse_thumbnail(
+    line: usize,
+    width: &str,
+    height: &str,
+    cells: &str,
+) -> Result<SaveThumbnail, SaveGameError> {
+    let width: i32 = parse_value(line, "thumbnail width", width)?;
+    let height: i32 = parse_value(line, "thumbnail height", height)?;
+    let cells = cells
+        .chars()
+        .map(|key| match key {
+            THUMBNAIL_SEA => Ok(ThumbnailCell::Sea),
+            THUMBNAIL_LAND => Ok(ThumbnailCell::Land(None)),
+            _ => THUMBNAIL_FACTIONS
+                .iter()
+                .position(|faction_key| *faction_key == key)
+                .map(|index| ThumbnailCell::Land(Some(ALL_FACTIONS[index])))
+                .ok_or_else(|| line_error(line, format!("invalid thumbnail cell '{}'", key))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if cells.len() != (width.max(0) * height.max(0)) as usize {
+        return Err(line_error(line, "thumbnail size mismatch"));
+    }
+    Ok(SaveThumbnail {
+        width,
+        height,
+        cells,
+    })
+}
+
+pub fn savegame_prepare_map(game_world: &mut GameWorld, save: &SaveGame) {
+    let resources = &mut game_world.resources;
+    resources.rng_seed = save.seed;
+    resources.map_params.map_width = save.map.width;
+    resources.map_params.map_height = save.map.height;
+    resources.map_params.wrap_columns = save.map.wrap_columns;
+    resources.map_params.orientation = save.map.orientation;
+    resources.generated_map = save.map.clone();
+    resources.rules = save.rules;
+}
+
+pub fn savegame_restore(
+    game_world: &mut GameWorld,
+    save: &SaveGame,
+) -> Result<(), StateHashMismatch> {
+    let resources = &mut game_world.resources;
+    resources.difficulty = save.difficulty;
+    resources.team_layout = save.team_layout;
+    resources.game_rng = GameRng {
+        state: save.rng_state,
+    };
+    resources.turn_timer = turn_timer_new(save.turn_seconds);
+    resources.turn_number = save.turn_number;
+    resources.current_faction = save.current_faction;
+    resources.actions_remaining = save.actions_remaining;
+    resources.speech_used = save.speech_used;
+    resources.turn_rotation = save.rotation.clone();
+    resources.game_speed = 1.0;
+    for (index, faction) in save.factions.iter().enumerate() {
+        resources.faction_eliminated[index] = faction.eliminated;
+        resources.faction_morale[index] = faction.morale;
+        resources.capital_owners[index] = faction.capital_owner;
+        resources.capital_coords[index] = faction.capital_coord;
+        resources.capital_relocated[index] = faction.capital_relocated;
+        resources.ability_cooldowns[index] = faction.ability_cooldown;
+        resources.active_abilities[index] = faction.ability_active;
+        resources.faction_difficulties[index] = faction.difficulty;
+        resources.casualty_stats[index] = faction.casualties;
+    }
+
+    for entity in game_world
+        .query_entities(TILE | HEX_POSITION)
+        .collect::<Vec<_>>()
+    {
+        let Some(coord) = game_world
+            .get_hex_position(entity)
+            .map(|position| position.0)
+        else {
+            continue;
+        };
+        let Some(saved) = save.tiles.iter().find(|tile| tile.coord == coord) else {
+            continue;
+        };
+        if let Some(tile) = game_world.get_tile_mut(entity) {
+            tile.faction = saved.owner;
+            tile.growth = saved.growth;
+            tile.improvement = saved.improvement;
+            tile.construction = saved.construction;
+            tile.pillaged_until = saved.pillaged_until;
+            tile.battles = saved.battles;
+        }
+    }
+
+    for entity in game_world.query_entities(UNIT).collect::<Vec<_>>() {
+        despawn_unit(game_world, entity);
+    }
+    for saved in &save.units {
+        let entity = spawn_unit(game_world, saved.coord, saved.faction, saved.soldiers);
+        if let Some(unit) = game_world.get_unit_mut(entity) {
+            unit.kind = saved.kind;
+            unit.morale = saved.morale;
+            unit.movement_range = saved.movement_range;
+            unit.movement_left = saved.movement_left;
+            unit.has_moved = saved.has_moved;
+        }
+        if let Some(general) = saved.general {
+            make_general(game_world, entity, general);
+        }
+    }
+
+    build_turn_order(game_world);
+    game_world.resources.state_hashes = save.state_hashes.clone();
+    let actual = hash_game_state(game_world);
+    if actual != save.state_hash {
+        return Err(StateHashMismatch {
+            expected: save.state_hash,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn bool_key(value: bool) -> u8 {
+    value as u8
+}
+
+fn faction_key(faction: Option<Faction>) -> &'static str {
+    faction.map(faction_name).unwrap_or("none")
+}
+
+fn improvement_key(improvement: Option<Improvement>) -> &'static str {
+    improvement.map(improvement_name).unwrap_or("none")
+}
+
+pub fn savegame_to_string(save: &SaveGame) -> String {
+    let mut output = String::new();
+    output.push_str(SAVE_FILE_HEADER);
+    output.push('\n');
+    output.push_str(&format!("saved {}\n", save.saved_at));
+    let thumbnail = savegame_thumbnail(save);
+    let cells: String = thumbnail
+        .cells
+        .iter()
+        .map(|cell| thumbnail_cell_key(*cell))
+        .collect();
+    output.push_str(&format!(
+        "thumbnail {} {} {}\n",
+        thumbnail.width, thumbnail.height, cells
+    ));
+    output.push_str(&format!("player {}\n", faction_name(save.player_faction)));
+    output.push_str(&format!("ironman {}\n", bool_key(save.ironman)));
+    output.push_str(&format!("difficulty {}\n", difficulty_key(save.difficulty)));
+    output.push_str(&format!("teams {}\n", team_layout_key(save.team_layout)));
+    output.push_str(&format!(
+        "rules {} {} {} {} {} {} {}\n",
+        save.rules.actions_per_turn,
+        save.rules.city_reinforcement,
+        save.rules.initial_soldiers,
+        save.rules.max_soldiers,
+        bool_key(save.rules.movement_points),
+        bool_key(save.rules.defection),
+        bool_key(save.rules.supply_wagons)
+    ));
+    output.push_str(&format!("rng {}\n", save.rng_state));
+    output.push_str(&format!("timer {}\n", save.turn_seconds));
+    output.push_str(&format!(
+        "turn {} {} {} {}\n",
+        save.turn_number,
+        faction_name(save.current_faction),
+        save.actions_remaining,
+        bool_key(save.speech_used)
+    ));
+    let rotation: Vec<&str> = save
+        .rotation
+        .factions
+        .iter()
+        .map(|faction| faction_name(*faction))
+        .collect();
+    output.push_str(&format!(
+        "rotation {} {} {}\n",
+        save.rotation.current_index,
+        bool_key(save.rotation.current_removed),
+        rotation.join(" ")
+    ));
+    output.push_str(&format!("state_hash {:016x}\n", save.state_hash));
+    for record in &save.state_hashes {
+        output.push_str(&format!(
+            "hash_history {} {} {:016x}\n",
+            record.turn,
+            faction_name(record.faction),
+            record.hash
+        ));
+    }
+
+    for faction in ALL_FACTIONS {
+        let saved = save.factions[faction_index(faction)];
+        output.push_str(&format!(
+            "faction {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}\n",
+            faction_name(faction),
+            bool_key(saved.eliminated),
+            saved.morale,
+            faction_key(saved.capital_owner),
+            saved.capital_coord.column,
+            saved.capital_coord.row,
+            bool_key(saved.capital_relocated),
+            saved.ability_cooldown,
+            bool_key(saved.ability_active),
+            difficulty_key(saved.difficulty),
+            saved.casualties.soldiers_lost,
+            saved.casualties.soldiers_killed,
+            saved.casualties.units_lost,
+            saved.casualties.units_destroyed,
+            saved.casualties.cities_captured
+        ));
+    }
+
+    for tile in &save.tiles {
+        let (construction, progress) = match tile.construction {
+            Some(construction) => (Some(construction.improvement), construction.progress),
+            None => (None, 0),
+        };
+        output.push_str(&format!(
+            "tile {} {} {} {} {} {} {} {} {}\n",
+            tile.coord.column,
+            tile.coord.row,
+            faction_key(tile.owner),
+            tile.growth,
+            improvement_key(tile.improvement),
+            improvement_key(construction),
+            progress,
+            tile.pillaged_until.unwrap_or(0),
+            tile.battles
+        ));
+    }
+
+    for unit in &save.units {
+        let (level, experience) = unit
+            .general
+            .map(|general| (general.level, general.experience))
+            .unwrap_or((0, 0));
+        output.push_str(&format!(
+            "unit {} {} {} {} {} {} {} {} {} {} {}\n",
+            unit.coord.column,
+            unit.coord.row,
+            faction_name(unit.faction),
+            unit_kind_name(unit.kind),
+            unit.soldiers,
+            unit.morale,
+            unit.movement_range,
+            unit.movement_left,
+            bool_key(unit.has_moved),
+            level,
+            experience
+        ));
+    }
+
+    output.push_str(MAP_SECTION_MARKER);
+    output.push('\n');
+    output.push_str(&map_file_to_string(save.seed, &save.map));
+    output
+}
+
+fn parse_value<T: FromStr>(line: usize, name: &str, value: &str) -> Result<T, SaveGameError> {
+    value
+        .parse()
+        .map_err(|_| line_error(line, format!("invalid {} '{}'", name, value)))
+}
+
+fn parse_bool(line: usize, name: &str, value: &str) -> Result<bool, SaveGameError> {
+    match value {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(line_error(line, format!("invalid {} '{}'", name, value))),
+    }
+}
+
+fn parse_optional_faction(line: usize, name: &str) -> Result<Option<Faction>, SaveGameError> {
+    if name == "none" {
+        return Ok(None);
+    }
+    parse_faction(line, name).map(Some)
+}
+
+fn parse_improvement(line: usize, key: &str) -> Result<Option<Improvement>, SaveGameError> {
+    if key == "none" {
+        return Ok(None);
+    }
+    [Improvement::Fort, Improvement::Watchtower]
+        .into_iter()
+        .find(|improvement| improvement_name(*improvement) == key)
+        .map(Some)
+        .ok_or_else(|| line_error(line, format!("unknown improvement '{}'", key)))
+}
+
+fn parse_unit_kind(line: usize, key: &str) -> Result<UnitKind, SaveGameError> {
+    [UnitKind::Infantry, UnitKind::Scout, UnitKind::Supply]
+        .into_iter()
+        .find(|kind| unit_kind_name(*kind) == key)
+        .ok_or_else(|| line_error(line, format!("unknown unit kind '{}'", key)))
+}
+
+fn parse_hash(line: usize, value: &str) -> Result<u64, SaveGameError> {
+    u64::from_str_radix(value, 16)
+        .map_err(|_| line_error(line, format!("invalid state hash '{}'", value)))
+}
+
+fn parse_coord(line: usize, column: &str, row: &str) -> Result<HexCoord, SaveGameError> {
+    Ok(HexCoord {
+        column: parse_value(line, "column", column)?,
+        row: parse_value(line, "row", row)?,
+    })
+}
+
+pub fn savegame_from_string(contents: &str) -> Result<SaveGame, SaveGameError> {
+    check_file_header(contents.lines().next(), SAVE_FILE_HEADER, "save")?;
+    let (state, map) = contents
+        .split_once(&format!("{}\n", MAP_SECTION_MARKER))
+        .ok_or_else(|| line_error(0, "missing map section"))?;
+    let map_line_offset = state.lines().count() + 1;
+    let loaded = map_file_from_string(map)
+        .map_err(|error| line_error(map_line_offset, format!("map section: {}", error)))?;
+
+    let lines = state.lines().enumerate().skip(1);
+
+    let mut save = SaveGame {
+        player_faction: Faction::default(),
+        ironman: false,
+        difficulty: Difficulty::default(),
+        team_layout: TeamLayout::default(),
+        rules: GameRules::default(),
+        rng_state: 0,
+        turn_seconds: 0,
+        turn_number: 1,
+        current_faction: Faction::default(),
+        actions_remaining: 0,
+        speech_used: false,
+        rotation: TurnRotation::default(),
+        factions: [SavedFaction::default(); 4],
+        tiles: Vec::new(),
+        units: Vec::new(),
+        seed: loaded.seed,
+        map: loaded.generated,
+        saved_at: 0,
+        state_hash: 0,
+        state_hashes: Vec::new(),
+    };
+
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [] => {}
+            ["saved", seconds] => {
+                save.saved_at = parse_value(line_number, "save timestamp", seconds)?
+            }
+            ["thumbnail", width, height, cells] => {
+                parse_thumbnail(line_number, width, height, cells)?;
+            }
+            ["player", name] => save.player_faction = parse_faction(line_number, name)?,
+            ["ironman", value] => save.ironman = parse_bool(line_number, "ironman flag", value)?,
+            ["difficulty", key] => {
+                save.difficulty = difficulty_from_key(key).ok_or_else(|| {
+                    line_error(line_number, format!("unknown difficulty '{}'", key))
+                })?;
+            }
+            ["teams", key] => {
+                save.team_layout = team_layout_from_key(key).ok_or_else(|| {
+                    line_error(line_number, format!("unknown team layout '{}'", key))
+                })?;
+            }
+            [
+                "rules",
+                actions,
+                reinforcement,
+                initial,
+                max,
+                movement_points,
+                defection,
+                supply_wagons,
+            ] => {
+                save.rules = GameRules {
+                    actions_per_turn: parse_value(line_number, "actions per turn", actions)?,
+                    city_reinforcement: parse_value(
+                        line_number,
+                        "city reinforcement",
+                        reinforcement,
+                    )?,
+                    initial_soldiers: parse_value(line_number, "initial soldiers", initial)?,
+                    max_soldiers: parse_value(line_number, "max soldiers", max)?,
+                    movement_points: parse_bool(
+                        line_number,
+                        "movement points flag",
+                        movement_points,
+                    )?,
+                    defection: parse_bool(line_number, "defection flag", defection)?,
+                    supply_wagons: parse_bool(line_number, "supply wagons flag", supply_wagons)?,
+                };
+            }
+            ["rng", state] => save.rng_state = parse_value(line_number, "rng state", state)?,
+            ["timer", seconds] => {
+                save.turn_seconds = parse_value(line_number, "turn timer", seconds)?
+            }
+            ["turn", number, faction, actions, speech] => {
+                save.turn_number = parse_value(line_number, "turn number", number)?;
+                save.current_faction = parse_faction(line_number, faction)?;
+                save.actions_remaining = parse_value(line_number, "actions", actions)?;
+                save.speech_used = parse_bool(line_number, "speech flag", speech)?;
+            }
+            ["rotation", current_index, current_removed, factions @ ..] => {
+                let factions = factions
+                    .iter()
+                    .map(|name| parse_faction(line_number, name))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let current_index = parse_value(line_number, "rotation index", current_index)?;
+                if current_index >= factions.len().max(1) {
+                    return Err(line_error(line_number, "rotation index out of range"));
+                }
+                let current_removed =
+                    parse_bool(line_number, "rotation removal flag", current_removed)?;
+                save.rotation = TurnRotation {
+                    factions,
+                    current_index,
+                    current_removed,
+                };
+            }
+            ["state_hash", hash] => save.state_hash = parse_hash(line_number, hash)?,
+            ["hash_history", turn, faction, hash] => {
+                save.state_hashes.push(StateHashRecord {
+                    turn: parse_value(line_number, "hash turn", turn)?,
+                    faction: parse_faction(line_number, faction)?,
+                    hash: parse_hash(line_number, hash)?,
+                });
+            }
+            [
+                "faction",
+                name,
+                eliminated,
+                morale,
+                capital_owner,
+                column,
+                row,
+                relocated,
+                cooldown,
+                active,
+                difficulty,
+                soldiers_lost,
+                soldiers_killed,
+                units_lost,
+                units_destroyed,
+                cities_captured,
+            ] => {
+                let faction = parse_faction(line_number, name)?;
+                save.factions[faction_index(faction)] = SavedFaction {
+                    eliminated: parse_bool(line_number, "eliminated flag", eliminated)?,
+                    morale: parse_value(line_number, "morale", morale)?,
+                    capital_owner: parse_optional_faction(line_number, capital_owner)?,
+                    capital_coord: parse_coord(line_number, column, row)?,
+                    capital_relocated: parse_bool(line_number, "relocated flag", relocated)?,
+                    ability_cooldown: parse_value(line_number, "cooldown", cooldown)?,
+                    ability_active: parse_bool(line_number, "ability flag", active)?,
+                    difficulty: difficulty_from_key(difficulty).ok_or_else(|| {
+                        line_error(line_number, format!("unknown difficulty '{}'", difficulty))
+                    })?,
+                    casualties: CasualtyStats {
+                        soldiers_lost: parse_value(line_number, "soldiers lost", soldiers_lost)?,
+                        soldiers_killed: parse_value(
+                            line_number,
+                            "soldiers killed",
+                            soldiers_killed,
+                        )?,
+                        units_lost: parse_value(line_number, "units lost", units_lost)?,
+                        units_destroyed: parse_value(
+                            line_number,
+                            "units destroyed",
+                            units_destroyed,
+                        )?,
+                        cities_captured: parse_value(
+                            line_number,
+                            "cities captured",
+                            cities_captured,
+                        )?,
+                    },
+                };
+            }
+            [
+                "tile",
+                column,
+                row,
+                owner,
+                growth,
+                improvement,
+                construction,
+                progress,
+                pillaged_until,
+                battles,
+            ] => {
+                let construction = parse_improvement(line_number, construction)?;
+                let progress = parse_value(line_number, "construction progress", progress)?;
+                let pillaged_until: u32 =
+                    parse_value(line_number, "pillaged turn", pillaged_until)?;
+                save.tiles.push(SavedTile {
+                    coord: parse_coord(line_number, column, row)?,
+                    owner: parse_optional_faction(line_number, owner)?,
+                    growth: parse_value(line_number, "growth", growth)?,
+                    improvement: parse_improvement(line_number, improvement)?,
+                    construction: construction.map(|improvement| Construction {
+                        improvement,
+                        progress,
+                    }),
+                    pillaged_until: (pillaged_until > 0).then_some(pillaged_until),
+                    battles: parse_value(line_number, "battle count", battles)?,
+                });
+            }
+            [
+                "unit",
+                column,
+                row,
+                faction,
+                kind,
+                soldiers,
+                morale,
+                movement_range,
+                movement_left,
+                has_moved,
+                level,
+                experience,
+            ] => {
+                let level: u32 = parse_value(line_number, "general level", level)?;
+                let experience = parse_value(line_number, "general experience", experience)?;
+                save.units.push(SavedUnit {
+                    coord: parse_coord(line_number, column, row)?,
+                    faction: parse_faction(line_number, faction)?,
+                    kind: parse_unit_kind(line_number, kind)?,
+                    soldiers: parse_value(line_number, "soldiers", soldiers)?,
+                    morale: parse_value(line_number, "morale", morale)?,
+                    movement_range: parse_value(line_number, "movement range", movement_range)?,
+                    movement_left: parse_value(line_number, "movement left", movement_left)?,
+                    has_moved: parse_bool(line_number, "moved flag", has_moved)?,
+                    general: (level > 0).then_some(General { level, experience }),
+                });
+            }
+            _ => return Err(line_error(line_number, "unrecognized entry")),
+        }
+    }
+
+    Ok(save)
+}
+
+pub fn savegame_metadata_from_string(contents: &str) -> Result<SaveMetadata, SaveGameError> {
+    let mut lines = contents.lines().enumerate();
+    check_file_header(lines.next().map(|(_, line)| line), SAVE_FILE_HEADER, "save")?;
+
+    let mut metadata = SaveMetadata {
+        turn_number: 1,
+        difficulty: Difficulty::default(),
+        factions_alive: Vec::new(),
+        saved_at: 0,
+        thumbnail: SaveThumbnail::default(),
+    };
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [marker] if *marker == MAP_SECTION_MARKER => break,
+            ["saved", seconds] => {
+                metadata.saved_at = parse_value(line_number, "save timestamp", seconds)?
+            }
+            ["thumbnail", width, height, cells] => {
+                metadata.thumbnail = parse_thumbnail(line_number, width, height, cells)?;
+            }
+            ["difficulty", key] => {
+                metadata.difficulty = difficulty_from_key(key).ok_or_else(|| {
+                    line_error(line_number, format!("unknown difficulty '{}'", key))
+                })?;
+            }
+            ["turn", number, ..] => {
+                metadata.turn_number = parse_value(line_number, "turn number", number)?;
+            }
+            ["faction", name, eliminated, ..] => {
+                if !parse_bool(line_number, "eliminated flag", eliminated)? {
+                    metadata
+                        .factions_alive
+                        .push(parse_faction(line_number, name)?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(metadata)
+}
+
+pub fn load_save_metadata(path: &Path) -> Result<SaveMetadata, SaveGameError> {
+    let contents = std::fs::read_to_string(path)?;
+    savegame_metadata_from_string(&contents)
+}
+
+pub fn save_game_file(path: &Path, save: &SaveGame) -> Result<(), SaveGameError> {
+    std::fs::write(path, savegame_to_string(save))?;
+    Ok(())
+}
+
+pub fn load_game_file(path: &Path) -> Result<SaveGame, SaveGameError> {
+    let contents = std::fs::read_to_string(path)?;
+    savegame_from_string(&contents)
+}
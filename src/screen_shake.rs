@@ -0,0 +1,44 @@
+use nightshade::prelude::*;
+
+pub const CAPITAL_CAPTURE_SHAKE: f32 = 60.0;
+pub const ELIMINATION_SHAKE: f32 = 120.0;
+const SHAKE_DECAY: f32 = 4.0;
+const SHAKE_CUTOFF: f32 = 1.0;
+const SHAKE_FREQUENCY_X: f32 = 37.0;
+const SHAKE_FREQUENCY_Z: f32 = 29.0;
+
+#[derive(Default)]
+pub struct ScreenShake {
+    pub amplitude: f32,
+    pub elapsed: f32,
+    pub applied_offset: Vec3,
+}
+
+pub fn screen_shake_trigger(shake: &mut ScreenShake, amplitude: f32) {
+    shake.amplitude = shake.amplitude.max(amplitude);
+}
+
+pub fn screen_shake_system(shake: &mut ScreenShake, world: &mut World, delta_time: f32) {
+    if shake.amplitude <= 0.0 && shake.applied_offset == Vec3::zeros() {
+        return;
+    }
+
+    shake.elapsed += delta_time;
+    shake.amplitude *= (-SHAKE_DECAY * delta_time).exp();
+    if shake.amplitude < SHAKE_CUTOFF {
+        shake.amplitude = 0.0;
+    }
+
+    let offset = nalgebra_glm::vec3(
+        (shake.elapsed * SHAKE_FREQUENCY_X).sin() * shake.amplitude,
+        0.0,
+        (shake.elapsed * SHAKE_FREQUENCY_Z).cos() * shake.amplitude,
+    );
+
+    if let Some(camera_entity) = world.resources.active_camera
+        && let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity)
+    {
+        pan_orbit.target_focus += offset - shake.applied_offset;
+    }
+    shake.applied_offset = offset;
+}
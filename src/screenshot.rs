@@ -0,0 +1,17 @@
+use crate::wall_clock::unix_millis_now;
+use nightshade::prelude::*;
+use std::path::PathBuf;
+
+pub const SCREENSHOT_DIRECTORY: &str = "screenshots";
+
+pub fn screenshot_path() -> PathBuf {
+    PathBuf::from(SCREENSHOT_DIRECTORY).join(format!("hex_war_{}.png", unix_millis_now()))
+}
+
+pub fn request_screenshot(world: &mut World) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(SCREENSHOT_DIRECTORY)
+        .map_err(|error| format!("Failed to create {}: {}", SCREENSHOT_DIRECTORY, error))?;
+    let path = screenshot_path();
+    world.resources.screenshot_path = Some(path.clone());
+    Ok(path)
+}
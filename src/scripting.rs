@@ -0,0 +1,375 @@
+use crate::ecs::{
+    ALL_FACTIONS, Faction, GameWorld, HEX_POSITION, TILE, TileType, UNIT, faction_index,
+    faction_name, get_faction_morale, modify_faction_morale,
+};
+use crate::hex::HexCoord;
+use crate::map_file::tile_type_key;
+use crate::text_file::faction_from_name;
+use nightshade::prelude::*;
+use std::cell::RefCell;
+use std::fmt;
+use std::path::Path;
+use std::rc::Rc;
+
+pub const SCRIPT_FILE_PATH: &str = "scripts/rules.rhai";
+const SCRIPT_MAX_OPERATIONS: u64 = 1_000_000;
+const SCRIPT_MAX_CALL_LEVELS: usize = 32;
+const SCRIPT_MAX_EXPR_DEPTH: usize = 64;
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Io(std::io::Error),
+    Compile(String),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::Io(error) => write!(formatter, "{}", error),
+            ScriptError::Compile(message) => write!(formatter, "script error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+impl From<std::io::Error> for ScriptError {
+    fn from(error: std::io::Error) -> Self {
+        ScriptError::Io(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ScriptHook {
+    TurnStart {
+        faction: Faction,
+        turn: u32,
+    },
+    Capture {
+        faction: Faction,
+        coord: HexCoord,
+        tile_type: TileType,
+    },
+    CombatResolved {
+        attacker: Faction,
+        defender: Faction,
+        attacker_survived: bool,
+        defender_survived: bool,
+    },
+    VictoryCheck,
+}
+
+#[derive(Debug, Clone)]
+pub enum ScriptCommand {
+    AddMorale { faction: Faction, amount: i32 },
+    AddSoldiers { coord: HexCoord, amount: i32 },
+    Message(String),
+    Victory(Faction),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScriptSnapshot {
+    pub turn: u32,
+    pub current_faction: Faction,
+    pub cities: [i64; 4],
+    pub units: [i64; 4],
+    pub soldiers: [i64; 4],
+    pub morale: [i64; 4],
+    pub eliminated: [bool; 4],
+}
+
+#[derive(Debug, Default)]
+pub struct ScriptContext {
+    pub snapshot: ScriptSnapshot,
+    pub commands: Vec<ScriptCommand>,
+}
+
+#[derive(Debug, Default)]
+pub struct ScriptOutcome {
+    pub messages: Vec<String>,
+    pub victory: Option<Faction>,
+}
+
+pub struct ScriptHost {
+    pub engine: rhai::Engine,
+    pub ast: rhai::AST,
+    pub context: Rc<RefCell<ScriptContext>>,
+}
+
+pub fn script_hook_name(hook: &ScriptHook) -> &'static str {
+    match hook {
+        ScriptHook::TurnStart { .. } => "on_turn_start",
+        ScriptHook::Capture { .. } => "on_capture",
+        ScriptHook::CombatResolved { .. } => "on_combat_resolved",
+        ScriptHook::VictoryCheck => "check_victory",
+    }
+}
+
+pub fn script_snapshot(game_world: &GameWorld) -> ScriptSnapshot {
+    let resources = &game_world.resources;
+    let mut snapshot = ScriptSnapshot {
+        turn: resources.turn_number,
+        current_faction: resources.current_faction,
+        eliminated: resources.faction_eliminated,
+        ..ScriptSnapshot::default()
+    };
+
+    for faction in ALL_FACTIONS {
+        snapshot.morale[faction_index(faction)] = get_faction_morale(resources, faction) as i64;
+    }
+
+    for entity in game_world.query_entities(TILE) {
+        if let Some(tile) = game_world.get_tile(entity)
+            && matches!(tile.tile_type, TileType::City | TileType::Capital)
+            && let Some(owner) = tile.faction
+        {
+            snapshot.cities[faction_index(owner)] += 1;
+        }
+    }
+
+    for entity in game_world.query_entities(UNIT) {
+        if let Some(unit) = game_world.get_unit(entity) {
+            let index = faction_index(unit.faction);
+            snapshot.units[index] += 1;
+            snapshot.soldiers[index] += unit.soldiers as i64;
+        }
+    }
+
+    snapshot
+}
+
+fn apply_script_commands(
+    game_world: &mut GameWorld,
+    commands: Vec<ScriptCommand>,
+) -> ScriptOutcome {
+    let mut outcome = ScriptOutcome::default();
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+
+    for command in commands {
+        match command {
+            ScriptCommand::AddMorale { faction, amount } => {
+                modify_faction_morale(&mut game_world.resources, faction, amount);
+            }
+            ScriptCommand::AddSoldiers { coord, amount } => {
+                let unit_entity = game_world
+                    .query_entities(UNIT | HEX_POSITION)
+                    .find(|&entity| {
+                        game_world
+                            .get_hex_position(entity)
+                            .is_some_and(|hex| hex.0 == coord)
+                    });
+                if let Some(entity) = unit_entity
+                    && let Some(unit) = game_world.get_unit(entity)
+                {
+                    let mut unit = *unit;
+                    unit.soldiers = (unit.soldiers + amount).clamp(1, max_soldiers);
+                    game_world.set_unit(entity, unit);
+                }
+            }
+            ScriptCommand::Message(message) => outcome.messages.push(message),
+            ScriptCommand::Victory(faction) => {
+                if !game_world.resources.faction_eliminated[faction_index(faction)] {
+                    outcome.victory = Some(faction);
+                }
+            }
+        }
+    }
+
+    outcome
+}
+
+fn snapshot_faction_value(
+    context: &Rc<RefCell<ScriptContext>>,
+    name: &str,
+    field: fn(&ScriptSnapshot) -> [i64; 4],
+) -> i64 {
+    faction_from_name(name)
+        .map(|faction| field(&context.borrow().snapshot)[faction_index(faction)])
+        .unwrap_or(0)
+}
+
+fn register_script_api(engine: &mut rhai::Engine, context: &Rc<RefCell<ScriptContext>>) {
+    let ctx = context.clone();
+    engine.register_fn("turn", move || ctx.borrow().snapshot.turn as i64);
+    let ctx = context.clone();
+    engine.register_fn("current_faction", move || {
+        faction_name(ctx.borrow().snapshot.current_faction).to_string()
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("cities", move |name: &str| {
+        snapshot_faction_value(&ctx, name, |snapshot| snapshot.cities)
+    });
+    let ctx = context.clone();
+    engine.register_fn("units", move |name: &str| {
+        snapshot_faction_value(&ctx, name, |snapshot| snapshot.units)
+    });
+    let ctx = context.clone();
+    engine.register_fn("soldiers", move |name: &str| {
+        snapshot_faction_value(&ctx, name, |snapshot| snapshot.soldiers)
+    });
+    let ctx = context.clone();
+    engine.register_fn("morale", move |name: &str| {
+        snapshot_faction_value(&ctx, name, |snapshot| snapshot.morale)
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("is_eliminated", move |name: &str| {
+        faction_from_name(name)
+            .map(|faction| ctx.borrow().snapshot.eliminated[faction_index(faction)])
+            .unwrap_or(true)
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("add_morale", move |name: &str, amount: i64| {
+        if let Some(faction) = faction_from_name(name) {
+            ctx.borrow_mut().commands.push(ScriptCommand::AddMorale {
+                faction,
+                amount: amount as i32,
+            });
+        }
+    });
+    let ctx = context.clone();
+    engine.register_fn("add_soldiers", move |column: i64, row: i64, amount: i64| {
+        ctx.borrow_mut().commands.push(ScriptCommand::AddSoldiers {
+            coord: HexCoord {
+                column: column as i32,
+                row: row as i32,
+            },
+            amount: amount as i32,
+        });
+    });
+    let ctx = context.clone();
+    engine.register_fn("message", move |text: &str| {
+        ctx.borrow_mut()
+            .commands
+            .push(ScriptCommand::Message(text.to_string()));
+    });
+    let ctx = context.clone();
+    engine.register_fn("declare_victory", move |name: &str| {
+        if let Some(faction) = faction_from_name(name) {
+            ctx.borrow_mut()
+                .commands
+                .push(ScriptCommand::Victory(faction));
+        }
+    });
+}
+
+pub fn script_host_from_string(source: &str) -> Result<ScriptHost, ScriptError> {
+    let context = Rc::new(RefCell::new(ScriptContext::default()));
+    let mut engine = rhai::Engine::new();
+    engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+    engine.set_max_call_levels(SCRIPT_MAX_CALL_LEVELS);
+    engine.set_max_expr_depths(SCRIPT_MAX_EXPR_DEPTH, SCRIPT_MAX_EXPR_DEPTH);
+    register_script_api(&mut engine, &context);
+    let ast = engine
+        .compile(source)
+        .map_err(|error| ScriptError::Compile(error.to_string()))?;
+    Ok(ScriptHost {
+        engine,
+        ast,
+        context,
+    })
+}
+
+pub fn load_script_file(path: &Path) -> Result<ScriptHost, ScriptError> {
+    let source = std::fs::read_to_string(path)?;
+    script_host_from_string(&source)
+}
+
+pub fn scripts_for_launch(path: Option<&Path>) -> Result<Option<ScriptHost>, ScriptError> {
+    match path {
+        Some(path) => load_script_file(path).map(Some),
+        None if Path::new(SCRIPT_FILE_PATH).exists() => {
+            load_script_file(Path::new(SCRIPT_FILE_PATH)).map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+fn call_script_hook(host: &ScriptHost, hook: &ScriptHook) -> bool {
+    let name = script_hook_name(hook);
+    let defined = host
+        .ast
+        .iter_functions()
+        .any(|function| function.name == name);
+    if !defined {
+        return true;
+    }
+
+    let mut scope = rhai::Scope::new();
+    let result = match *hook {
+        ScriptHook::TurnStart { faction, turn } => host.engine.call_fn::<rhai::Dynamic>(
+            &mut scope,
+            &host.ast,
+            name,
+            (faction_name(faction).to_string(), turn as i64),
+        ),
+        ScriptHook::Capture {
+            faction,
+            coord,
+            tile_type,
+        } => host.engine.call_fn::<rhai::Dynamic>(
+            &mut scope,
+            &host.ast,
+            name,
+            (
+                faction_name(faction).to_string(),
+                coord.column as i64,
+                coord.row as i64,
+                tile_type_key(tile_type).to_string(),
+            ),
+        ),
+        ScriptHook::CombatResolved {
+            attacker,
+            defender,
+            attacker_survived,
+            defender_survived,
+        } => host.engine.call_fn::<rhai::Dynamic>(
+            &mut scope,
+            &host.ast,
+            name,
+            (
+                faction_name(attacker).to_string(),
+                faction_name(defender).to_string(),
+                attacker_survived,
+                defender_survived,
+            ),
+        ),
+        ScriptHook::VictoryCheck => {
+            host.engine
+                .call_fn::<rhai::Dynamic>(&mut scope, &host.ast, name, ())
+        }
+    };
+
+    match result {
+        Ok(_) => true,
+        Err(error) => {
+            log::error!("Script hook {} failed: {}", name, error);
+            false
+        }
+    }
+}
+
+pub fn script_run_hook(
+    host: &mut ScriptHost,
+    game_world: &mut GameWorld,
+    hook: ScriptHook,
+) -> ScriptOutcome {
+    {
+        let mut context = host.context.borrow_mut();
+        context.snapshot = script_snapshot(game_world);
+        context.commands.clear();
+    }
+
+    let succeeded = call_script_hook(host, &hook);
+
+    let commands = std::mem::take(&mut host.context.borrow_mut().commands);
+    if !succeeded {
+        return ScriptOutcome {
+            messages: vec![format!("Script hook {} failed", script_hook_name(&hook))],
+            victory: None,
+        };
+    }
+    apply_script_commands(game_world, commands)
+}
@@ -0,0 +1,47 @@
+use crate::constants::{COLD_LATITUDE_ROWS_FRACTION, SEASON_LENGTH_TURNS};
+use crate::hex::HexCoord;
+use crate::map::MapGenParams;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Season {
+    #[default]
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+pub const SEASON_CYCLE: [Season; 4] = [
+    Season::Spring,
+    Season::Summer,
+    Season::Autumn,
+    Season::Winter,
+];
+
+pub fn season_for_turn(turn_number: u32) -> Season {
+    let index = (turn_number.saturating_sub(1) / SEASON_LENGTH_TURNS) as usize;
+    SEASON_CYCLE[index % SEASON_CYCLE.len()]
+}
+
+pub fn turns_until_next_season(turn_number: u32) -> u32 {
+    SEASON_LENGTH_TURNS - turn_number.saturating_sub(1) % SEASON_LENGTH_TURNS
+}
+
+pub fn season_name(season: Season) -> &'static str {
+    match season {
+        Season::Spring => "Spring",
+        Season::Summer => "Summer",
+        Season::Autumn => "Autumn",
+        Season::Winter => "Winter",
+    }
+}
+
+pub fn is_cold_latitude(map_params: &MapGenParams, coord: HexCoord) -> bool {
+    let cold_rows =
+        ((map_params.map_height as f32 * COLD_LATITUDE_ROWS_FRACTION).round() as i32).max(1);
+    coord.row < cold_rows || coord.row >= map_params.map_height - cold_rows
+}
+
+pub fn sea_travel_frozen(turn_number: u32, map_params: &MapGenParams, coord: HexCoord) -> bool {
+    season_for_turn(turn_number) == Season::Winter && is_cold_latitude(map_params, coord)
+}
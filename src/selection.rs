@@ -5,15 +5,23 @@ pub fn get_selected_unit(game_world: &GameWorld) -> Option<Entity> {
     game_world.query_selected().next()
 }
 
-pub fn get_unit_at_tile(game_world: &GameWorld, coord: HexCoord) -> Option<Entity> {
-    game_world
+pub fn get_units_at_tile(game_world: &GameWorld, coord: HexCoord) -> Vec<Entity> {
+    let mut units: Vec<(Entity, i32)> = game_world
         .query_entities(HEX_POSITION | UNIT)
-        .find(|&entity| {
+        .filter(|&entity| {
             game_world
                 .get_hex_position(entity)
                 .map(|hex| hex.0 == coord)
                 .unwrap_or(false)
         })
+        .filter_map(|entity| Some((entity, game_world.get_unit(entity)?.soldiers)))
+        .collect();
+    units.sort_by_key(|(_, soldiers)| std::cmp::Reverse(*soldiers));
+    units.into_iter().map(|(entity, _)| entity).collect()
+}
+
+pub fn get_unit_at_tile(game_world: &GameWorld, coord: HexCoord) -> Option<Entity> {
+    get_units_at_tile(game_world, coord).first().copied()
 }
 
 pub fn select_unit(game_world: &mut GameWorld, unit_entity: Entity) {
@@ -27,4 +35,7 @@ pub fn clear_selection(game_world: &mut GameWorld) {
         game_world.remove_selected(selected);
     }
     game_world.resources.valid_move_tiles.clear();
+    game_world.resources.port_route_tiles.clear();
+    game_world.resources.port_route_origin = None;
+    game_world.resources.airlift_tiles.clear();
 }
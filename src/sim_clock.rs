@@ -0,0 +1,21 @@
+use crate::ecs::GameResources;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimulationClock {
+    pub paused: bool,
+    pub delta_time: f32,
+    pub scaled_delta_time: f32,
+}
+
+pub fn scaled_delta_time(resources: &GameResources, delta_time: f32) -> f32 {
+    delta_time * resources.game_speed
+}
+
+pub fn sim_clock_tick(resources: &mut GameResources, frame_delta: f32, paused: bool) {
+    let delta_time = if paused { 0.0 } else { frame_delta };
+    let scaled = scaled_delta_time(resources, delta_time);
+    let clock = &mut resources.sim_clock;
+    clock.paused = paused;
+    clock.delta_time = delta_time;
+    clock.scaled_delta_time = scaled;
+}
@@ -0,0 +1,29 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationStage {
+    PendingSpawns,
+    Movement,
+    AiTurn,
+    PlayerInput,
+    TileOwnership,
+    Victory,
+}
+
+pub const SIMULATION_STAGES: [SimulationStage; 6] = [
+    SimulationStage::PendingSpawns,
+    SimulationStage::Movement,
+    SimulationStage::AiTurn,
+    SimulationStage::PlayerInput,
+    SimulationStage::TileOwnership,
+    SimulationStage::Victory,
+];
+
+pub fn simulation_stage_name(stage: SimulationStage) -> &'static str {
+    match stage {
+        SimulationStage::PendingSpawns => "Pending spawns",
+        SimulationStage::Movement => "Movement tick",
+        SimulationStage::AiTurn => "AI step",
+        SimulationStage::PlayerInput => "Player input",
+        SimulationStage::TileOwnership => "Ownership pass",
+        SimulationStage::Victory => "Victory check",
+    }
+}
@@ -0,0 +1,134 @@
+use crate::ecs::{Faction, GameWorld};
+use crate::hex::HexCoord;
+use crate::menu::{MenuButton, create_button, update_buttons_hover};
+use crate::selection::{get_selected_unit, get_units_at_tile};
+use nightshade::prelude::*;
+
+const STACK_BUTTON_SPACING: f32 = 150.0;
+const STACK_BUTTON_Y: f32 = -40.0;
+const SELECTED_COLOR: [f32; 4] = [0.4, 1.0, 0.5, 1.0];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackListing {
+    pub coord: HexCoord,
+    pub soldiers: Vec<i32>,
+    pub selected_index: usize,
+}
+
+#[derive(Default)]
+pub struct StackPopup {
+    pub listing: Option<StackListing>,
+    pub buttons: Vec<MenuButton>,
+    pub hovered_index: Option<usize>,
+}
+
+pub fn stack_popup_hovered(popup: &StackPopup) -> bool {
+    popup.hovered_index.is_some()
+}
+
+pub fn despawn_stack_popup(popup: &mut StackPopup, world: &mut World) {
+    popup.listing = None;
+    popup.hovered_index = None;
+    for button in popup.buttons.drain(..) {
+        world.despawn_entities(&[button.entity]);
+    }
+}
+
+fn stack_listing(game_world: &GameWorld, local_faction: Option<Faction>) -> Option<StackListing> {
+    let selected = get_selected_unit(game_world)?;
+    let unit = game_world.get_unit(selected)?;
+    if Some(unit.faction) != local_faction {
+        return None;
+    }
+    let coord = game_world.get_hex_position(selected)?.0;
+    let stack = get_units_at_tile(game_world, coord);
+    if stack.len() < 2 {
+        return None;
+    }
+    Some(StackListing {
+        coord,
+        soldiers: stack
+            .iter()
+            .filter_map(|entity| game_world.get_unit(*entity).map(|unit| unit.soldiers))
+            .collect(),
+        selected_index: stack.iter().position(|entity| *entity == selected)?,
+    })
+}
+
+fn spawn_stack_popup_ui(
+    popup: &mut StackPopup,
+    world: &mut World,
+    listing: &StackListing,
+    ui_scale: f32,
+) {
+    let left = -(listing.soldiers.len() as f32 - 1.0) * STACK_BUTTON_SPACING / 2.0;
+    for (index, soldiers) in listing.soldiers.iter().enumerate() {
+        let mut button = create_button(
+            world,
+            &format!("ARMY {} ({})", index + 1, soldiers),
+            nalgebra_glm::vec2(left + index as f32 * STACK_BUTTON_SPACING, STACK_BUTTON_Y),
+            HudAnchor::BottomCenter,
+            22.0,
+            ui_scale,
+        );
+        if index == listing.selected_index {
+            button.base_color = nalgebra_glm::vec4(
+                SELECTED_COLOR[0],
+                SELECTED_COLOR[1],
+                SELECTED_COLOR[2],
+                SELECTED_COLOR[3],
+            );
+            if let Some(hud_text) = world.get_hud_text_mut(button.entity) {
+                hud_text.properties.color = button.base_color;
+                hud_text.dirty = true;
+            }
+        }
+        popup.buttons.push(button);
+    }
+}
+
+pub fn stack_popup_system(
+    popup: &mut StackPopup,
+    game_world: &GameWorld,
+    world: &mut World,
+    local_faction: Option<Faction>,
+    screen_width: f32,
+    screen_height: f32,
+    ui_scale: f32,
+) -> Option<(HexCoord, u8)> {
+    let listing = stack_listing(game_world, local_faction);
+    if listing != popup.listing {
+        despawn_stack_popup(popup, world);
+        if let Some(listing) = &listing {
+            spawn_stack_popup_ui(popup, world, listing, ui_scale);
+        }
+        popup.listing = listing;
+    }
+    let listing = popup.listing.as_ref()?;
+
+    let mouse_x = world.resources.input.mouse.position.x;
+    let mouse_y = world.resources.input.mouse.position.y;
+    let clicked = world
+        .resources
+        .input
+        .mouse
+        .state
+        .contains(MouseState::LEFT_JUST_RELEASED);
+
+    popup.hovered_index = update_buttons_hover(
+        &popup.buttons,
+        world,
+        mouse_x,
+        mouse_y,
+        screen_width,
+        screen_height,
+        popup.hovered_index,
+    );
+
+    match (clicked, popup.hovered_index) {
+        (true, Some(index)) if index != listing.selected_index => {
+            Some((listing.coord, index as u8))
+        }
+        _ => None,
+    }
+}
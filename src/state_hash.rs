@@ -0,0 +1,191 @@
+use crate::ecs::{Faction, GameWorld, HEX_POSITION, TILE, UNIT, faction_index};
+use nightshade::prelude::*;
+use std::fmt;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateHashRecord {
+    pub turn: u32,
+    pub faction: Faction,
+    pub hash: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateHashMismatch {
+    pub expected: u64,
+    pub actual: u64,
+}
+
+impl fmt::Display for StateHashMismatch {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "saved state hash {:016x} does not match restored state {:016x}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for StateHashMismatch {}
+
+struct StateHasher {
+    state: u64,
+}
+
+fn hasher_write(hasher: &mut StateHasher, bytes: &[u8]) {
+    for byte in bytes {
+        hasher.state ^= *byte as u64;
+        hasher.state = hasher.state.wrapping_mul(FNV_PRIME);
+    }
+}
+
+fn hasher_write_i32(hasher: &mut StateHasher, value: i32) {
+    hasher_write(hasher, &value.to_le_bytes());
+}
+
+fn hasher_write_u32(hasher: &mut StateHasher, value: u32) {
+    hasher_write(hasher, &value.to_le_bytes());
+}
+
+fn hasher_write_owner(hasher: &mut StateHasher, owner: Option<Faction>) {
+    let value = owner
+        .map(|faction| faction_index(faction) as u8 + 1)
+        .unwrap_or(0);
+    hasher_write(hasher, &[value]);
+}
+
+pub fn hash_game_state(game_world: &GameWorld) -> u64 {
+    let mut hasher = StateHasher {
+        state: FNV_OFFSET_BASIS,
+    };
+
+    let mut units: Vec<_> = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter_map(|entity| {
+            let hex = game_world.get_hex_position(entity)?.0;
+            let unit = game_world.get_unit(entity)?;
+            Some((hex.row, hex.column, *unit))
+        })
+        .collect();
+    units.sort_by_key(|(row, column, unit)| {
+        (
+            *row,
+            *column,
+            faction_index(unit.faction),
+            unit.kind as u8,
+            unit.soldiers,
+            unit.morale,
+            unit.movement_range,
+            unit.movement_left,
+            unit.has_moved,
+        )
+    });
+
+    hasher_write_u32(&mut hasher, units.len() as u32);
+    for (row, column, unit) in &units {
+        hasher_write_i32(&mut hasher, *column);
+        hasher_write_i32(&mut hasher, *row);
+        hasher_write_owner(&mut hasher, Some(unit.faction));
+        hasher_write(&mut hasher, &[unit.kind as u8]);
+        hasher_write_i32(&mut hasher, unit.soldiers);
+        hasher_write_i32(&mut hasher, unit.morale);
+        hasher_write_i32(&mut hasher, unit.movement_range);
+        hasher_write_i32(&mut hasher, unit.movement_left);
+        hasher_write(&mut hasher, &[unit.has_moved as u8]);
+    }
+
+    let mut tiles: Vec<_> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let hex = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            Some((hex.row, hex.column, *tile))
+        })
+        .collect();
+    tiles.sort_by_key(|(row, column, _)| (*row, *column));
+
+    hasher_write_u32(&mut hasher, tiles.len() as u32);
+    for (row, column, tile) in &tiles {
+        hasher_write_i32(&mut hasher, *column);
+        hasher_write_i32(&mut hasher, *row);
+        hasher_write(&mut hasher, &[tile.tile_type as u8]);
+        hasher_write_owner(&mut hasher, tile.faction);
+        hasher_write_u32(&mut hasher, tile.growth);
+        hasher_write(
+            &mut hasher,
+            &[tile.improvement.map(|i| i as u8 + 1).unwrap_or(0)],
+        );
+        hasher_write(
+            &mut hasher,
+            &[tile
+                .construction
+                .map(|c| c.improvement as u8 + 1)
+                .unwrap_or(0)],
+        );
+        hasher_write(&mut hasher, &[tile.ruin.map(|r| r as u8 + 1).unwrap_or(0)]);
+        hasher_write_u32(&mut hasher, tile.pillaged_until.unwrap_or(0));
+        hasher_write_u32(&mut hasher, tile.battles);
+    }
+
+    let resources = &game_world.resources;
+    hasher_write_owner(&mut hasher, Some(resources.current_faction));
+    hasher_write(&mut hasher, &[resources.actions_remaining]);
+    hasher_write_u32(&mut hasher, resources.turn_number);
+    hasher_write_u32(&mut hasher, resources.rng_seed);
+    hasher_write(&mut hasher, &resources.game_rng.state.to_le_bytes());
+    hasher_write(&mut hasher, &[resources.speech_used as u8]);
+    hasher_write(&mut hasher, &[resources.rules.actions_per_turn]);
+    hasher_write_i32(&mut hasher, resources.rules.city_reinforcement);
+    hasher_write_i32(&mut hasher, resources.rules.initial_soldiers);
+    hasher_write_i32(&mut hasher, resources.rules.max_soldiers);
+    hasher_write(&mut hasher, &[resources.rules.movement_points as u8]);
+    hasher_write(&mut hasher, &[resources.rules.defection as u8]);
+    hasher_write(&mut hasher, &[resources.rules.supply_wagons as u8]);
+    for cooldown in resources.ability_cooldowns {
+        hasher_write_u32(&mut hasher, cooldown);
+    }
+    for active in resources.active_abilities {
+        hasher_write(&mut hasher, &[active as u8]);
+    }
+    for eliminated in resources.faction_eliminated {
+        hasher_write(&mut hasher, &[eliminated as u8]);
+    }
+    for morale in resources.faction_morale {
+        hasher_write_i32(&mut hasher, morale);
+    }
+    for coord in resources.capital_coords {
+        hasher_write_i32(&mut hasher, coord.column);
+        hasher_write_i32(&mut hasher, coord.row);
+    }
+    for relocated in resources.capital_relocated {
+        hasher_write(&mut hasher, &[relocated as u8]);
+    }
+    for owner in resources.capital_owners {
+        hasher_write_owner(&mut hasher, owner);
+    }
+    for faction in &resources.turn_rotation.factions {
+        hasher_write_owner(&mut hasher, Some(*faction));
+    }
+    hasher_write_u32(&mut hasher, resources.turn_rotation.current_index as u32);
+    hasher_write_u32(&mut hasher, resources.turn_rotation.current_removed as u32);
+
+    hasher.state
+}
+
+pub fn record_state_hash(game_world: &mut GameWorld) -> StateHashRecord {
+    let record = StateHashRecord {
+        turn: game_world.resources.turn_number,
+        faction: game_world.resources.current_faction,
+        hash: hash_game_state(game_world),
+    };
+    game_world.resources.state_hashes.push(record);
+    log::info!(
+        "State hash turn {} ({:?}): {:016x}",
+        record.turn,
+        record.faction,
+        record.hash
+    );
+    record
+}
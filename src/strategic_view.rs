@@ -0,0 +1,265 @@
+use crate::ecs::{ENGINE_ENTITY, GameWorld, HEX_POSITION, TILE, TileType, UNIT, faction_color};
+use crate::hex::hex_to_world_position;
+use crate::map_generation::{MapEntities, spawn_hidden_lines_entity};
+use crate::rendering::generate_hex_outline_with_color;
+use crate::state_hash::hash_game_state;
+use nightshade::ecs::world::components::Line;
+use nightshade::prelude::*;
+
+const STRATEGIC_LINE_HEIGHT: f32 = 2.0;
+const STRATEGIC_ICON_HEIGHT: f32 = 4.0;
+const OWNERSHIP_RING_SCALES: [f32; 3] = [0.9, 0.78, 0.66];
+const UNIT_RING_SCALES: [f32; 2] = [0.42, 0.36];
+const NEUTRAL_LAND_COLOR: [f32; 4] = [0.45, 0.45, 0.45, 1.0];
+const NEUTRAL_FOREST_COLOR: [f32; 4] = [0.3, 0.45, 0.3, 1.0];
+const NEUTRAL_HILLS_COLOR: [f32; 4] = [0.55, 0.48, 0.38, 1.0];
+const NEUTRAL_SWAMP_COLOR: [f32; 4] = [0.3, 0.38, 0.32, 1.0];
+const ICON_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const STRATEGIC_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+pub struct SavedCamera {
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+#[derive(Default)]
+pub struct StrategicView {
+    pub enabled: bool,
+    pub lines_entity: Option<Entity>,
+    pub saved_camera: Option<SavedCamera>,
+    pub last_state_hash: Option<u64>,
+}
+
+fn vec4_from(color: [f32; 4]) -> Vec4 {
+    nalgebra_glm::vec4(color[0], color[1], color[2], color[3])
+}
+
+fn set_visible(world: &mut World, entity: Entity, visible: bool) {
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = visible;
+    }
+}
+
+fn set_unit_meshes_visible(game_world: &GameWorld, world: &mut World, visible: bool) {
+    for entity in game_world.query_entities(UNIT | ENGINE_ENTITY) {
+        if let Some(engine_entity) = game_world.get_engine_entity(entity) {
+            set_visible(world, engine_entity.0, visible);
+        }
+    }
+}
+
+fn set_map_visuals_visible(map_entities: &MapEntities, world: &mut World, visible: bool) {
+    for group in &map_entities.instanced_tiles.groups {
+        set_visible(world, group.entity, visible);
+    }
+    for entity in &map_entities.port_label_entities {
+        set_visible(world, *entity, visible);
+    }
+    for entity in &map_entities.airfield_label_entities {
+        set_visible(world, *entity, visible);
+    }
+    for (_, entity) in &map_entities.ruin_label_entities {
+        set_visible(world, *entity, visible);
+    }
+}
+
+pub fn strategic_view_enable(
+    view: &mut StrategicView,
+    game_world: &GameWorld,
+    world: &mut World,
+    map_entities: &MapEntities,
+) {
+    if view.enabled {
+        return;
+    }
+    view.enabled = true;
+    view.last_state_hash = None;
+
+    if let Some(camera_entity) = world.resources.active_camera
+        && let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity)
+    {
+        view.saved_camera = Some(SavedCamera {
+            yaw: pan_orbit.target_yaw,
+            pitch: pan_orbit.target_pitch,
+        });
+        pan_orbit.target_yaw = 0.0;
+        pan_orbit.target_pitch = STRATEGIC_PITCH;
+    }
+
+    set_map_visuals_visible(map_entities, world, false);
+    set_unit_meshes_visible(game_world, world, false);
+    if view.lines_entity.is_none() {
+        view.lines_entity = Some(spawn_hidden_lines_entity(world));
+    }
+}
+
+pub fn strategic_view_disable(
+    view: &mut StrategicView,
+    game_world: &GameWorld,
+    world: &mut World,
+    map_entities: Option<&MapEntities>,
+) {
+    if !view.enabled {
+        return;
+    }
+    view.enabled = false;
+
+    if let Some(saved) = view.saved_camera.take()
+        && let Some(camera_entity) = world.resources.active_camera
+        && let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity)
+    {
+        pan_orbit.target_yaw = saved.yaw;
+        pan_orbit.target_pitch = saved.pitch;
+    }
+
+    if let Some(map_entities) = map_entities {
+        set_map_visuals_visible(map_entities, world, true);
+    }
+    set_unit_meshes_visible(game_world, world, true);
+    if let Some(entity) = view.lines_entity {
+        set_visible(world, entity, false);
+    }
+}
+
+pub fn despawn_strategic_view(view: &mut StrategicView, world: &mut World) {
+    if let Some(entity) = view.lines_entity.take() {
+        world.queue_command(WorldCommand::DespawnRecursive { entity });
+    }
+    *view = StrategicView::default();
+}
+
+fn icon_lines(tile_type: TileType, center: Vec3, hex_width: f32, hex_depth: f32) -> Vec<Line> {
+    let color = vec4_from(ICON_COLOR);
+    let size = hex_width.min(hex_depth) * 0.22;
+    let point = |angle: f32, radius: f32| {
+        nalgebra_glm::vec3(
+            center.x + angle.cos() * radius,
+            STRATEGIC_ICON_HEIGHT,
+            center.z + angle.sin() * radius,
+        )
+    };
+
+    let vertices: Vec<Vec3> = match tile_type {
+        TileType::Capital => (0..10)
+            .map(|index| {
+                let angle =
+                    -std::f32::consts::FRAC_PI_2 + index as f32 * std::f32::consts::PI / 5.0;
+                let radius = if index % 2 == 0 { size } else { size * 0.45 };
+                point(angle, radius)
+            })
+            .collect(),
+        TileType::City => (0..4)
+            .map(|index| {
+                point(
+                    std::f32::consts::FRAC_PI_4 + index as f32 * std::f32::consts::FRAC_PI_2,
+                    size,
+                )
+            })
+            .collect(),
+        TileType::Port => (0..3)
+            .map(|index| {
+                point(
+                    std::f32::consts::FRAC_PI_2 + index as f32 * std::f32::consts::TAU / 3.0,
+                    size,
+                )
+            })
+            .collect(),
+        TileType::Sea | TileType::Land | TileType::Forest | TileType::Hills | TileType::Swamp => {
+            Vec::new()
+        }
+    };
+
+    (0..vertices.len())
+        .map(|index| Line {
+            start: vertices[index],
+            end: vertices[(index + 1) % vertices.len()],
+            color,
+        })
+        .collect()
+}
+
+fn strategic_view_lines(game_world: &GameWorld) -> Vec<Line> {
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let mut lines = Vec::new();
+
+    for entity in game_world.query_entities(HEX_POSITION | TILE) {
+        let (Some(hex), Some(tile)) = (
+            game_world.get_hex_position(entity),
+            game_world.get_tile(entity),
+        ) else {
+            continue;
+        };
+        if tile.tile_type == TileType::Sea {
+            continue;
+        }
+
+        let color = match (tile.faction, tile.tile_type) {
+            (Some(owner), _) => faction_color(owner),
+            (None, TileType::Forest) => NEUTRAL_FOREST_COLOR,
+            (None, TileType::Hills) => NEUTRAL_HILLS_COLOR,
+            (None, TileType::Swamp) => NEUTRAL_SWAMP_COLOR,
+            (None, _) => NEUTRAL_LAND_COLOR,
+        };
+        let center =
+            hex_to_world_position(hex.0.column, hex.0.row, hex_width, hex_depth, orientation);
+        for scale in OWNERSHIP_RING_SCALES {
+            lines.extend(generate_hex_outline_with_color(
+                center,
+                hex_width * scale,
+                hex_depth * scale,
+                orientation,
+                STRATEGIC_LINE_HEIGHT,
+                vec4_from(color),
+            ));
+        }
+        lines.extend(icon_lines(tile.tile_type, center, hex_width, hex_depth));
+    }
+
+    for entity in game_world.query_entities(HEX_POSITION | UNIT) {
+        let (Some(hex), Some(unit)) = (
+            game_world.get_hex_position(entity),
+            game_world.get_unit(entity),
+        ) else {
+            continue;
+        };
+        let center =
+            hex_to_world_position(hex.0.column, hex.0.row, hex_width, hex_depth, orientation);
+        for scale in UNIT_RING_SCALES {
+            lines.extend(generate_hex_outline_with_color(
+                center,
+                hex_width * scale,
+                hex_depth * scale,
+                orientation,
+                STRATEGIC_ICON_HEIGHT,
+                vec4_from(faction_color(unit.faction)),
+            ));
+        }
+    }
+
+    lines
+}
+
+pub fn strategic_view_system(view: &mut StrategicView, game_world: &GameWorld, world: &mut World) {
+    if !view.enabled {
+        return;
+    }
+    let Some(entity) = view.lines_entity else {
+        return;
+    };
+
+    set_unit_meshes_visible(game_world, world, false);
+
+    let state_hash = hash_game_state(game_world);
+    if view.last_state_hash == Some(state_hash) {
+        return;
+    }
+    view.last_state_hash = Some(state_hash);
+
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = strategic_view_lines(game_world);
+        lines_component.mark_dirty();
+    }
+    set_visible(world, entity, true);
+}
@@ -1,40 +1,93 @@
 mod ai;
+mod capital;
+mod capital_threat;
+mod casualties;
+mod city_growth;
 mod combat;
+mod damage_flash;
+mod death_effect;
 mod fireworks;
+mod general;
 mod highlight;
 mod hover;
+mod improvements;
 mod input;
 mod merge_popup;
 mod movement;
+mod movement_trail;
+mod pillage;
+mod port_route;
 mod range_lines;
 mod reinforcement;
+mod ruins;
+mod scouts;
 mod selection_visual;
 mod speech;
+mod supply;
+mod surrender;
 mod tile_ownership;
 mod turn;
 mod unit;
 mod unit_text;
 mod valid_moves;
 mod victory;
+mod world_events;
 
 pub use ai::{ai_turn_system, build_turn_order};
-pub use combat::resolve_combat;
-pub use fireworks::{FireworkShell, spawn_capture_firework, update_firework_shells};
+pub use capital::{ai_capital_relocation, capital_marker_system, relocate_capital};
+pub use capital_threat::{CapitalThreat, capital_threat_system};
+pub use casualties::{record_casualties, record_city_capture};
+pub use city_growth::{
+    city_growth_system, city_tier_defense_bonus, city_tier_label_system, city_tier_reinforcement,
+};
+pub use combat::{
+    CombatResult, combat_modifiers, combat_win_chance, encirclement_multiplier, resolve_combat,
+};
+pub use damage_flash::{damage_flash_system, start_damage_flash};
+pub use death_effect::death_effect_system;
+pub use fireworks::{
+    FireworkShell, spawn_capture_firework, spawn_clash_burst, update_firework_shells,
+};
+pub use general::{
+    general_aura_multiplier, general_gain_experience, general_marker_system, make_general,
+};
 pub use highlight::{hover_outline_system, tile_highlight_system};
 pub use hover::hover_system;
-pub use input::input_system;
-pub use merge_popup::{floating_popup_system, spawn_capture_popup, spawn_merge_popup};
+pub use improvements::{
+    ImprovementVisuals, construction_system, despawn_improvement_visuals,
+    improvement_defense_bonus, improvement_name, improvement_visual_system, start_construction,
+};
+pub use input::{PlayerAction, apply_player_action, attack_target, input_system};
+pub use merge_popup::{
+    floating_popup_system, spawn_capture_popup, spawn_casualty_popups, spawn_merge_popup,
+    spawn_morale_popup,
+};
 pub use movement::movement_system;
+pub use movement_trail::movement_trail_system;
+pub use pillage::{pillage_tile, tile_is_pillaged};
+pub use port_route::port_route_system;
 pub use range_lines::range_lines_system;
-pub use reinforcement::{PendingSpawn, reinforcement_system};
-pub use selection_visual::selection_visual_system;
-pub use speech::speech_system;
+pub use reinforcement::{PendingSpawn, apply_starting_bonus, reinforcement_system, spawn_pending};
+pub use ruins::{ruin_bonus_name, ruin_label_system, ruin_system};
+pub use scouts::{recruit_scout, scout_count};
+pub use selection_visual::selection_outline_system;
+pub use speech::{
+    leader_ability, leader_ability_active, leader_ability_cooldown, leader_ability_description,
+    leader_ability_expire, leader_ability_name, leader_ability_turn_start, speech_system,
+};
+pub use supply::{spawn_supply_wagon, supply_delivery_system, supply_wagon_at};
+pub use surrender::{ai_offer_surrender, dominant_rival, surrender_faction};
 pub use tile_ownership::tile_ownership_system;
-pub use turn::{can_end_turn, end_turn};
+pub use turn::{TurnTransition, begin_game, can_end_turn, end_turn};
 pub use unit::{
-    UNIT_SELECTED_COLOR, UNIT_TEXT_HEIGHT_OFFSET, despawn_unit, move_unit_to, spawn_unit,
-    unit_radius_for_soldiers, unit_visual_update_system,
+    UNIT_TEXT_HEIGHT_OFFSET, airlift_unit_to, despawn_unit, despawned_visuals_system, move_unit_to,
+    sailing_heading, spawn_unit, unit_radius_for_soldiers, unit_visual_spawn_system,
+    unit_visual_update_system,
 };
 pub use unit_text::unit_text_system;
-pub use valid_moves::{calculate_valid_moves, find_path, valid_moves_system};
-pub use victory::{GameResult, victory_system};
+pub use valid_moves::{
+    calculate_valid_moves, find_path, find_sea_path, friendly_airfield_destinations,
+    friendly_port_destinations, path_movement_cost, valid_moves_system,
+};
+pub use victory::{GameResult, eliminate_faction, victory_system};
+pub use world_events::{world_event_message, world_event_system};
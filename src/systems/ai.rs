@@ -1,50 +1,45 @@
+use crate::constants::{AI_MAX_DECISIONS_PER_FRAME, AI_MAX_SCOUTS, AI_THREAT_RADIUS};
 use crate::ecs::{
-    CombatEvent, Difficulty, Faction, GameEvents, GameWorld, HEX_POSITION, MOVEMENT, TILE,
-    TileType, UNIT, faction_index, tile_defense_bonus,
+    AiDecisionTrace, CombatEvent, Difficulty, Faction, GameEvents, GameWorld, HEX_POSITION,
+    MOVEMENT, TILE, TileType, UNIT, UnitKind, faction_capital, faction_difficulty, faction_index,
+    map_distance, tile_defense_bonus,
 };
-use crate::hex::{HexCoord, hex_distance};
-use crate::map::CAPITAL_POSITIONS;
-use crate::systems::{calculate_valid_moves, move_unit_to, resolve_combat};
+use crate::hex::HexCoord;
+use crate::rng::{GameRng, game_rng_percent};
+use crate::systems::{
+    ai_capital_relocation, ai_offer_surrender, airlift_unit_to, calculate_valid_moves,
+    encirclement_multiplier, friendly_airfield_destinations, move_unit_to, pillage_tile,
+    recruit_scout, resolve_combat, scout_count, unit_movement_budget,
+};
+use crate::teams::{faction_allies, factions_hostile};
 use nightshade::prelude::*;
 
-fn get_capital_coord(faction: Faction) -> HexCoord {
-    let index = faction_index(faction);
-    let (col, row, _) = CAPITAL_POSITIONS[index];
-    HexCoord { column: col, row }
-}
-
 fn get_attack_threshold(difficulty: Difficulty, is_capital: bool) -> f32 {
     let base = match difficulty {
         Difficulty::Easy => 0.8,
         Difficulty::Normal => 0.7,
         Difficulty::Hard => 0.6,
+        Difficulty::Brutal => 0.55,
     };
     if is_capital { base - 0.2 } else { base }
 }
 
-fn should_make_suboptimal_move(
-    difficulty: Difficulty,
-    rng_seed: u32,
-    turn: u32,
-    unit_index: usize,
-) -> bool {
+fn should_make_suboptimal_move(difficulty: Difficulty, rng: &mut GameRng) -> bool {
     if difficulty != Difficulty::Easy {
         return false;
     }
-    let hash = rng_seed
-        .wrapping_mul(31)
-        .wrapping_add(turn)
-        .wrapping_mul(17)
-        .wrapping_add(unit_index as u32);
-    hash.is_multiple_of(5)
+    game_rng_percent(rng, 20)
 }
 
 fn should_prefer_human_target(difficulty: Difficulty) -> bool {
-    matches!(difficulty, Difficulty::Normal | Difficulty::Hard)
+    matches!(
+        difficulty,
+        Difficulty::Normal | Difficulty::Hard | Difficulty::Brutal
+    )
 }
 
 fn should_avoid_ai_vs_ai(difficulty: Difficulty) -> bool {
-    difficulty == Difficulty::Hard
+    matches!(difficulty, Difficulty::Hard | Difficulty::Brutal)
 }
 
 fn calculate_win_chance(
@@ -53,8 +48,10 @@ fn calculate_win_chance(
     defender_soldiers: i32,
     defender_morale: i32,
     defense_bonus: f32,
+    encirclement_bonus: f32,
 ) -> f32 {
-    let attacker_strength = attacker_soldiers as f32 * (1.0 + attacker_morale as f32 / 100.0);
+    let attacker_strength =
+        attacker_soldiers as f32 * (1.0 + attacker_morale as f32 / 100.0) * encirclement_bonus;
     let defender_strength =
         defender_soldiers as f32 * (1.0 + defender_morale as f32 / 100.0) * defense_bonus;
 
@@ -107,23 +104,182 @@ pub fn build_turn_order(game_world: &mut GameWorld) {
     game_world.resources.current_unit_index = 0;
 }
 
+fn ai_recruit_scout(game_world: &mut GameWorld) {
+    let faction = game_world.resources.current_faction;
+    let difficulty = faction_difficulty(&game_world.resources, faction);
+    if !matches!(difficulty, Difficulty::Hard | Difficulty::Brutal)
+        || game_world.resources.actions_remaining == 0
+        || scout_count(game_world, faction) >= AI_MAX_SCOUTS
+    {
+        return;
+    }
+
+    let mut candidates: Vec<(freecs::Entity, i32)> = game_world
+        .resources
+        .turn_order
+        .iter()
+        .filter_map(|entity| Some((*entity, game_world.get_unit(*entity)?.soldiers)))
+        .collect();
+    candidates.sort_by_key(|(_, soldiers)| std::cmp::Reverse(*soldiers));
+
+    for (entity, _) in candidates {
+        if recruit_scout(game_world, entity) {
+            game_world.resources.actions_remaining -= 1;
+            return;
+        }
+    }
+}
+
+fn ai_scout_decision(
+    game_world: &mut GameWorld,
+    unit_entity: freecs::Entity,
+    unit_hex: HexCoord,
+    mut trace: AiDecisionTrace,
+) {
+    let current_faction = game_world.resources.current_faction;
+    let allies = faction_allies(game_world.resources.team_layout, current_faction);
+    let my_capital = faction_capital(&game_world.resources, current_faction);
+    let budget = game_world
+        .get_unit(unit_entity)
+        .map(|unit| unit_movement_budget(game_world, unit))
+        .unwrap_or(0);
+    let valid_moves = calculate_valid_moves(game_world, unit_entity, unit_hex, budget);
+    trace.considered_moves = valid_moves.clone();
+
+    let unclaimed: Vec<HexCoord> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let hex = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            let claimable = tile.tile_type != TileType::Sea
+                && tile.tile_type != TileType::Capital
+                && tile
+                    .faction
+                    .is_none_or(|owner| owner != current_faction && !allies.contains(&owner));
+            claimable.then_some(hex)
+        })
+        .collect();
+
+    let destination = valid_moves
+        .iter()
+        .filter(|coord| unclaimed.contains(coord))
+        .max_by_key(|coord| map_distance(&game_world.resources, **coord, my_capital))
+        .or_else(|| {
+            valid_moves
+                .iter()
+                .max_by_key(|coord| map_distance(&game_world.resources, **coord, my_capital))
+        })
+        .copied();
+
+    trace.target = destination;
+    trace.reason = "scout";
+    game_world.resources.ai_trace = trace;
+    if let Some(destination) = destination {
+        move_unit_to(game_world, unit_entity, destination);
+        game_world.resources.actions_remaining -= 1;
+    }
+    if let Some(unit_data) = game_world.get_unit_mut(unit_entity) {
+        unit_data.has_moved = true;
+    }
+    game_world.resources.current_unit_index += 1;
+}
+
+fn ai_supply_decision(
+    game_world: &mut GameWorld,
+    unit_entity: freecs::Entity,
+    unit_hex: HexCoord,
+    mut trace: AiDecisionTrace,
+) {
+    let current_faction = game_world.resources.current_faction;
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    let budget = game_world
+        .get_unit(unit_entity)
+        .map(|unit| unit_movement_budget(game_world, unit))
+        .unwrap_or(0);
+    let valid_moves = calculate_valid_moves(game_world, unit_entity, unit_hex, budget);
+    trace.considered_moves = valid_moves.clone();
+
+    let recipient = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter_map(|entity| {
+            let hex = game_world.get_hex_position(entity)?.0;
+            let unit = game_world.get_unit(entity)?;
+            (unit.faction == current_faction
+                && unit.kind == UnitKind::Infantry
+                && unit.soldiers < max_soldiers)
+                .then_some((hex, unit.soldiers))
+        })
+        .min_by_key(|(hex, soldiers)| {
+            (
+                *soldiers,
+                map_distance(&game_world.resources, unit_hex, *hex),
+            )
+        })
+        .map(|(hex, _)| hex);
+
+    let destination = recipient
+        .filter(|target| map_distance(&game_world.resources, unit_hex, *target) > 1)
+        .and_then(|target| {
+            valid_moves
+                .iter()
+                .min_by_key(|coord| map_distance(&game_world.resources, **coord, target))
+                .copied()
+        });
+
+    trace.target = recipient;
+    trace.reason = "supply";
+    game_world.resources.ai_trace = trace;
+    if let Some(destination) = destination {
+        move_unit_to(game_world, unit_entity, destination);
+        game_world.resources.actions_remaining -= 1;
+    }
+    if let Some(unit_data) = game_world.get_unit_mut(unit_entity) {
+        unit_data.has_moved = true;
+    }
+    game_world.resources.current_unit_index += 1;
+}
+
+fn movement_in_progress(game_world: &GameWorld) -> bool {
+    game_world.query_entities(MOVEMENT).next().is_some()
+}
+
 pub fn ai_turn_system(
     game_world: &mut GameWorld,
-    world: &mut World,
-    player_faction: Faction,
+    player_faction: Option<Faction>,
     events: &mut GameEvents,
 ) -> bool {
-    let current_faction = game_world.resources.current_faction;
-
-    if current_faction == player_faction {
+    if Some(game_world.resources.current_faction) == player_faction {
         return false;
     }
 
-    let has_active_movement = game_world.query_entities(MOVEMENT).next().is_some();
-    if has_active_movement {
-        return false;
+    if game_world.resources.current_unit_index == 0 {
+        if ai_offer_surrender(game_world, events) {
+            return true;
+        }
+        ai_capital_relocation(game_world, events);
+        ai_recruit_scout(game_world);
+    }
+
+    for _ in 0..AI_MAX_DECISIONS_PER_FRAME {
+        if movement_in_progress(game_world) {
+            return false;
+        }
+
+        if ai_unit_decision(game_world, player_faction, events) {
+            return true;
+        }
     }
 
+    false
+}
+
+fn ai_unit_decision(
+    game_world: &mut GameWorld,
+    player_faction: Option<Faction>,
+    events: &mut GameEvents,
+) -> bool {
+    let current_faction = game_world.resources.current_faction;
+
     if game_world.resources.actions_remaining == 0 {
         return true;
     }
@@ -154,13 +310,14 @@ pub fn ai_turn_system(
         return false;
     }
 
-    let my_capital = get_capital_coord(current_faction);
+    let my_capital = faction_capital(&game_world.resources, current_faction);
+    let team_layout = game_world.resources.team_layout;
 
     let enemy_units: Vec<(freecs::Entity, HexCoord, i32, i32)> = game_world
         .query_entities(HEX_POSITION | UNIT)
         .filter_map(|entity| {
             let enemy_unit = game_world.get_unit(entity)?;
-            if enemy_unit.faction == current_faction {
+            if !factions_hostile(team_layout, current_faction, enemy_unit.faction) {
                 return None;
             }
             let hex = game_world.get_hex_position(entity)?.0;
@@ -170,20 +327,35 @@ pub fn ai_turn_system(
 
     let adjacent_enemies: Vec<_> = enemy_units
         .iter()
-        .filter(|(_, hex, _, _)| hex_distance(unit_hex, *hex) == 1)
+        .filter(|(_, hex, _, _)| map_distance(&game_world.resources, unit_hex, *hex) == 1)
         .copied()
         .collect();
 
-    let difficulty = game_world.resources.difficulty;
-    let rng_seed = game_world.resources.rng_seed;
-    let turn_number = game_world.resources.turn_number;
+    let difficulty = faction_difficulty(&game_world.resources, current_faction);
+    let mut trace = AiDecisionTrace {
+        faction: current_faction,
+        unit: Some(unit_hex),
+        ..AiDecisionTrace::default()
+    };
+
+    if unit.kind == UnitKind::Scout {
+        ai_scout_decision(game_world, unit_entity, unit_hex, trace);
+        return false;
+    }
+
+    if unit.kind == UnitKind::Supply {
+        ai_supply_decision(game_world, unit_entity, unit_hex, trace);
+        return false;
+    }
 
-    if should_make_suboptimal_move(difficulty, rng_seed, turn_number, current_index) {
+    if should_make_suboptimal_move(difficulty, &mut game_world.resources.game_rng) {
         if let Some(unit_data) = game_world.get_unit(unit_entity) {
             let mut unit_data = *unit_data;
             unit_data.has_moved = true;
             game_world.set_unit(unit_entity, unit_data);
         }
+        trace.reason = "skipped turn (easy blunder)";
+        game_world.resources.ai_trace = trace;
         game_world.resources.current_unit_index += 1;
         return false;
     }
@@ -193,19 +365,19 @@ pub fn ai_turn_system(
         sorted_enemies.sort_by_key(|(entity, _, _, _)| {
             let is_human = game_world
                 .get_unit(*entity)
-                .map(|u| u.faction == player_faction)
+                .map(|u| Some(u.faction) == player_faction)
                 .unwrap_or(false);
             if is_human { 0 } else { 1 }
         });
     }
 
     for (enemy_entity, enemy_hex, enemy_soldiers, enemy_morale) in &sorted_enemies {
-        let enemy_faction = game_world
-            .get_unit(*enemy_entity)
-            .map(|u| u.faction)
-            .unwrap_or(player_faction);
+        let enemy_faction = game_world.get_unit(*enemy_entity).map(|u| u.faction);
 
-        if should_avoid_ai_vs_ai(difficulty) && enemy_faction != player_faction {
+        if should_avoid_ai_vs_ai(difficulty)
+            && player_faction.is_some()
+            && enemy_faction != player_faction
+        {
             continue;
         }
 
@@ -216,7 +388,9 @@ pub fn ai_turn_system(
             *enemy_soldiers,
             *enemy_morale,
             defense_bonus,
+            encirclement_multiplier(game_world, unit_entity, *enemy_entity),
         );
+        trace.attack_odds.push((*enemy_hex, win_chance));
 
         let tile_type = get_tile_type_at(game_world, *enemy_hex);
         let is_capital = tile_type == Some(TileType::Capital);
@@ -224,12 +398,22 @@ pub fn ai_turn_system(
         let attack_threshold = get_attack_threshold(difficulty, is_capital);
 
         if win_chance > attack_threshold {
-            if let Some(result) = resolve_combat(game_world, world, unit_entity, *enemy_entity) {
+            trace.target = Some(*enemy_hex);
+            trace.reason = "attack";
+            game_world.resources.ai_trace = trace;
+            if let Some(result) = resolve_combat(game_world, unit_entity, *enemy_entity) {
                 events.combat_events.push(CombatEvent {
                     attacker_faction: result.attacker_faction,
                     defender_faction: result.defender_faction,
                     attacker_survived: result.attacker_survived,
                     defender_survived: result.defender_survived,
+                    location: result.location,
+                    attacker_location: result.attacker_location,
+                    attacker_losses: result.attacker_losses,
+                    defender_losses: result.defender_losses,
+                    wounded_unit: result.wounded_unit,
+                    general_killed: result.general_killed,
+                    modifiers: result.modifiers,
                 });
             }
             if let Some(unit_data) = game_world.get_unit(unit_entity) {
@@ -243,7 +427,9 @@ pub fn ai_turn_system(
         }
     }
 
-    let valid_moves = calculate_valid_moves(game_world, unit_entity, unit_hex, unit.movement_range);
+    let budget = unit_movement_budget(game_world, &unit);
+    let valid_moves = calculate_valid_moves(game_world, unit_entity, unit_hex, budget);
+    trace.considered_moves = valid_moves.clone();
 
     if valid_moves.is_empty() {
         if let Some(unit_data) = game_world.get_unit(unit_entity) {
@@ -251,21 +437,61 @@ pub fn ai_turn_system(
             unit_data.has_moved = true;
             game_world.set_unit(unit_entity, unit_data);
         }
+        trace.reason = "no valid moves";
+        game_world.resources.ai_trace = trace;
         game_world.resources.current_unit_index += 1;
         return false;
     }
 
-    let threat_to_capital = enemy_units
-        .iter()
-        .any(|(_, hex, _, _)| hex_distance(*hex, my_capital) <= 3);
+    let mut defended_capitals = vec![my_capital];
+    defended_capitals.extend(
+        faction_allies(team_layout, current_faction)
+            .into_iter()
+            .filter(|ally| !game_world.resources.faction_eliminated[faction_index(*ally)])
+            .map(|ally| faction_capital(&game_world.resources, ally)),
+    );
+    let threatened_capital = defended_capitals
+        .into_iter()
+        .filter(|capital| {
+            enemy_units.iter().any(|(_, hex, _, _)| {
+                map_distance(&game_world.resources, *hex, *capital) <= AI_THREAT_RADIUS
+            })
+        })
+        .min_by_key(|capital| map_distance(&game_world.resources, unit_hex, *capital));
+
+    if let Some(capital) = threatened_capital
+        && map_distance(&game_world.resources, unit_hex, capital) > 2
+    {
+        let closest_move_distance = valid_moves
+            .iter()
+            .map(|coord| map_distance(&game_world.resources, *coord, capital))
+            .min()
+            .unwrap_or(i32::MAX);
+        let airlift = friendly_airfield_destinations(game_world, unit_entity)
+            .into_iter()
+            .min_by_key(|coord| map_distance(&game_world.resources, *coord, capital))
+            .filter(|coord| {
+                map_distance(&game_world.resources, *coord, capital) < closest_move_distance
+            });
+        if let Some(destination) = airlift {
+            trace.target = Some(capital);
+            trace.reason = "airlift to capital";
+            game_world.resources.ai_trace = trace;
+            airlift_unit_to(game_world, unit_entity, destination);
+            game_world.resources.actions_remaining -= 1;
+            game_world.resources.current_unit_index += 1;
+            return false;
+        }
 
-    if threat_to_capital && hex_distance(unit_hex, my_capital) > 2 {
         let best_move = valid_moves
             .iter()
-            .min_by_key(|coord| hex_distance(**coord, my_capital))
+            .min_by_key(|coord| map_distance(&game_world.resources, **coord, capital))
             .copied();
 
         if let Some(destination) = best_move {
+            trace.target = Some(capital);
+            trace.reason = "defend capital";
+            game_world.resources.ai_trace = trace;
             move_unit_to(game_world, unit_entity, destination);
             if let Some(unit_data) = game_world.get_unit(unit_entity) {
                 let mut unit_data = *unit_data;
@@ -284,7 +510,9 @@ pub fn ai_turn_system(
             let hex = game_world.get_hex_position(entity)?.0;
             let tile = game_world.get_tile(entity)?;
             if (tile.tile_type == TileType::City || tile.tile_type == TileType::Capital)
-                && tile.faction != Some(current_faction)
+                && tile
+                    .faction
+                    .is_none_or(|owner| factions_hostile(team_layout, current_faction, owner))
             {
                 let has_enemy = enemy_units.iter().any(|(_, eh, _, _)| *eh == hex);
                 if !has_enemy {
@@ -297,6 +525,9 @@ pub fn ai_turn_system(
 
     for city in &undefended_cities {
         if valid_moves.contains(city) {
+            trace.target = Some(*city);
+            trace.reason = "capture city";
+            game_world.resources.ai_trace = trace;
             move_unit_to(game_world, unit_entity, *city);
             if let Some(unit_data) = game_world.get_unit(unit_entity) {
                 let mut unit_data = *unit_data;
@@ -309,6 +540,15 @@ pub fn ai_turn_system(
         }
     }
 
+    if pillage_tile(game_world, unit_entity, events) {
+        trace.target = Some(unit_hex);
+        trace.reason = "pillage";
+        game_world.resources.ai_trace = trace;
+        game_world.resources.actions_remaining -= 1;
+        game_world.resources.current_unit_index += 1;
+        return false;
+    }
+
     let enemy_capitals: Vec<HexCoord> = [
         Faction::Redosia,
         Faction::Violetnam,
@@ -317,20 +557,21 @@ pub fn ai_turn_system(
     ]
     .iter()
     .filter(|&&f| {
-        f != current_faction && !game_world.resources.faction_eliminated[faction_index(f)]
+        factions_hostile(team_layout, current_faction, f)
+            && !game_world.resources.faction_eliminated[faction_index(f)]
     })
-    .map(|&f| get_capital_coord(f))
+    .map(|&f| faction_capital(&game_world.resources, f))
     .collect();
 
     let closest_enemy_capital = enemy_capitals
         .iter()
-        .min_by_key(|coord| hex_distance(unit_hex, **coord));
+        .min_by_key(|coord| map_distance(&game_world.resources, unit_hex, **coord));
 
     let target = if let Some(&capital) = closest_enemy_capital {
         capital
     } else if let Some((_, closest_hex, _, _)) = enemy_units
         .iter()
-        .min_by_key(|(_, hex, _, _)| hex_distance(unit_hex, *hex))
+        .min_by_key(|(_, hex, _, _)| map_distance(&game_world.resources, unit_hex, *hex))
     {
         *closest_hex
     } else {
@@ -349,18 +590,23 @@ pub fn ai_turn_system(
 
         if let Some(&closest) = unclaimed
             .iter()
-            .min_by_key(|hex| hex_distance(unit_hex, **hex))
+            .min_by_key(|hex| map_distance(&game_world.resources, unit_hex, **hex))
         {
             closest
         } else {
+            trace.reason = "no target";
+            game_world.resources.ai_trace = trace;
             game_world.resources.current_unit_index += 1;
             return false;
         }
     };
+    trace.target = Some(target);
+    trace.reason = "advance";
+    game_world.resources.ai_trace = trace;
 
     let best_move = valid_moves
         .iter()
-        .min_by_key(|coord| hex_distance(**coord, target))
+        .min_by_key(|coord| map_distance(&game_world.resources, **coord, target))
         .copied();
 
     if let Some(destination) = best_move {
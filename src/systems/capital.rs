@@ -0,0 +1,163 @@
+use crate::constants::CAPITAL_RELOCATION_MORALE_COST;
+use crate::ecs::{
+    ALL_FACTIONS, CapitalRelocatedEvent, Faction, GameEvents, GameWorld, HEX_POSITION, TILE,
+    TileType, UNIT, faction_capital, faction_color, faction_index, map_distance,
+    modify_faction_morale,
+};
+use crate::hex::{HexCoord, hex_to_world_position};
+use crate::notifications::capital_threatened;
+use crate::rendering::generate_hex_outline_with_color;
+use crate::teams::factions_hostile;
+use nightshade::prelude::*;
+
+const CAPITAL_MARKER_HEIGHT: f32 = 3.5;
+const CAPITAL_MARKER_SCALES: [f32; 2] = [0.92, 0.84];
+
+fn tile_entity_at(game_world: &GameWorld, coord: HexCoord) -> Option<freecs::Entity> {
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| game_world.get_hex_position(entity).map(|h| h.0) == Some(coord))
+}
+
+fn set_tile_type(game_world: &mut GameWorld, coord: HexCoord, tile_type: TileType) {
+    if let Some(entity) = tile_entity_at(game_world, coord)
+        && let Some(tile) = game_world.get_tile_mut(entity)
+    {
+        tile.tile_type = tile_type;
+    }
+}
+
+pub fn capital_relocation_allowed(game_world: &GameWorld, faction: Faction) -> bool {
+    !game_world.resources.capital_relocated[faction_index(faction)]
+        && capital_threatened(game_world, faction)
+}
+
+pub fn relocate_capital(
+    game_world: &mut GameWorld,
+    faction: Faction,
+    target: HexCoord,
+    events: &mut GameEvents,
+) -> bool {
+    if !capital_relocation_allowed(game_world, faction) {
+        return false;
+    }
+
+    let target_is_owned_city = tile_entity_at(game_world, target)
+        .and_then(|entity| game_world.get_tile(entity))
+        .is_some_and(|tile| tile.tile_type == TileType::City && tile.faction == Some(faction));
+    if !target_is_owned_city {
+        return false;
+    }
+
+    let from = faction_capital(&game_world.resources, faction);
+    set_tile_type(game_world, from, TileType::City);
+    set_tile_type(game_world, target, TileType::Capital);
+
+    let index = faction_index(faction);
+    game_world.resources.capital_coords[index] = target;
+    game_world.resources.capital_relocated[index] = true;
+    modify_faction_morale(
+        &mut game_world.resources,
+        faction,
+        -CAPITAL_RELOCATION_MORALE_COST,
+    );
+    events.capital_relocated_events.push(CapitalRelocatedEvent {
+        faction,
+        from,
+        to: target,
+    });
+    true
+}
+
+pub fn ai_capital_relocation(game_world: &mut GameWorld, events: &mut GameEvents) {
+    let faction = game_world.resources.current_faction;
+    if !capital_relocation_allowed(game_world, faction) {
+        return;
+    }
+
+    let capital = faction_capital(&game_world.resources, faction);
+    let mut garrison = 0;
+    let mut besiegers = 0;
+    for entity in game_world.query_entities(HEX_POSITION | UNIT) {
+        let (Some(hex), Some(unit)) = (
+            game_world.get_hex_position(entity),
+            game_world.get_unit(entity),
+        ) else {
+            continue;
+        };
+        if hex.0 == capital && unit.faction == faction {
+            garrison += unit.soldiers;
+        } else if factions_hostile(game_world.resources.team_layout, faction, unit.faction)
+            && map_distance(&game_world.resources, hex.0, capital) <= 1
+        {
+            besiegers += unit.soldiers;
+        }
+    }
+    if besiegers <= garrison {
+        return;
+    }
+
+    let refuge = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            (tile.tile_type == TileType::City && tile.faction == Some(faction)).then_some(coord)
+        })
+        .max_by_key(|coord| {
+            (
+                map_distance(&game_world.resources, *coord, capital),
+                coord.column,
+                coord.row,
+            )
+        });
+
+    if let Some(target) = refuge {
+        relocate_capital(game_world, faction, target, events);
+    }
+}
+
+pub fn capital_marker_system(
+    game_world: &GameWorld,
+    world: &mut World,
+    marker_entity: Option<Entity>,
+) {
+    let Some(entity) = marker_entity else {
+        return;
+    };
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+
+    let mut lines = Vec::new();
+    for faction in ALL_FACTIONS {
+        let index = faction_index(faction);
+        if !game_world.resources.capital_relocated[index]
+            || game_world.resources.faction_eliminated[index]
+        {
+            continue;
+        }
+        let coord = faction_capital(&game_world.resources, faction);
+        let center =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+        let [r, g, b, a] = faction_color(faction);
+        for scale in CAPITAL_MARKER_SCALES {
+            lines.extend(generate_hex_outline_with_color(
+                center,
+                hex_width * scale,
+                hex_depth * scale,
+                orientation,
+                CAPITAL_MARKER_HEIGHT,
+                nalgebra_glm::vec4(r, g, b, a),
+            ));
+        }
+    }
+
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = lines;
+        lines_component.mark_dirty();
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = true;
+    }
+}
@@ -0,0 +1,38 @@
+use crate::constants::CAPITAL_THREAT_RADIUS;
+use crate::ecs::{Faction, GameWorld, HEX_POSITION, UNIT, faction_capital, map_distance};
+use crate::hex::HexCoord;
+use crate::teams::factions_hostile;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapitalThreat {
+    pub capital: HexCoord,
+    pub enemy_units: usize,
+    pub enemy_soldiers: i32,
+}
+
+pub fn capital_threat_system(game_world: &GameWorld, faction: Faction) -> Option<CapitalThreat> {
+    let capital = faction_capital(&game_world.resources, faction);
+    let team_layout = game_world.resources.team_layout;
+    let mut threat = CapitalThreat {
+        capital,
+        enemy_units: 0,
+        enemy_soldiers: 0,
+    };
+
+    for entity in game_world.query_entities(HEX_POSITION | UNIT) {
+        let (Some(hex), Some(unit)) = (
+            game_world.get_hex_position(entity),
+            game_world.get_unit(entity),
+        ) else {
+            continue;
+        };
+        if factions_hostile(team_layout, faction, unit.faction)
+            && map_distance(&game_world.resources, hex.0, capital) <= CAPITAL_THREAT_RADIUS
+        {
+            threat.enemy_units += 1;
+            threat.enemy_soldiers += unit.soldiers;
+        }
+    }
+
+    (threat.enemy_units > 0).then_some(threat)
+}
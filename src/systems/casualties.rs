@@ -0,0 +1,22 @@
+use crate::ecs::{Faction, GameResources, TileType, faction_index};
+use crate::systems::CombatResult;
+
+pub fn record_casualties(resources: &mut GameResources, result: &CombatResult) {
+    let attacker = &mut resources.casualty_stats[faction_index(result.attacker_faction)];
+    attacker.soldiers_lost += result.attacker_losses;
+    attacker.soldiers_killed += result.defender_losses;
+    attacker.units_lost += u32::from(!result.attacker_survived);
+    attacker.units_destroyed += u32::from(!result.defender_survived);
+
+    let defender = &mut resources.casualty_stats[faction_index(result.defender_faction)];
+    defender.soldiers_lost += result.defender_losses;
+    defender.soldiers_killed += result.attacker_losses;
+    defender.units_lost += u32::from(!result.defender_survived);
+    defender.units_destroyed += u32::from(!result.attacker_survived);
+}
+
+pub fn record_city_capture(resources: &mut GameResources, faction: Faction, tile_type: TileType) {
+    if matches!(tile_type, TileType::City | TileType::Capital) {
+        resources.casualty_stats[faction_index(faction)].cities_captured += 1;
+    }
+}
@@ -0,0 +1,76 @@
+use crate::constants::{
+    CITY_GROWTH_PER_TIER, CITY_MAX_TIER, CITY_TIER_DEFENSE_BONUS, CITY_TIER_REINFORCEMENT,
+};
+use crate::ecs::{GameWorld, HEX_POSITION, TILE, Tile, TileType};
+use crate::hex::HexCoord;
+use crate::labels::label_set_text;
+use crate::systems::tile_is_pillaged;
+use nightshade::prelude::*;
+use std::collections::HashMap;
+
+pub fn city_tier(tile: &Tile) -> u32 {
+    match tile.tile_type {
+        TileType::City | TileType::Capital => {
+            (tile.growth / CITY_GROWTH_PER_TIER).min(CITY_MAX_TIER)
+        }
+        _ => 0,
+    }
+}
+
+pub fn city_tier_reinforcement(tile: &Tile) -> i32 {
+    city_tier(tile) as i32 * CITY_TIER_REINFORCEMENT
+}
+
+pub fn city_tier_defense_bonus(tile: &Tile) -> f32 {
+    city_tier(tile) as f32 * CITY_TIER_DEFENSE_BONUS
+}
+
+fn city_tier_numeral(tier: u32) -> &'static str {
+    match tier {
+        1 => "I",
+        2 => "II",
+        3 => "III",
+        _ => "",
+    }
+}
+
+pub fn city_growth_system(game_world: &mut GameWorld) {
+    let current_faction = game_world.resources.current_faction;
+    let turn_number = game_world.resources.turn_number;
+    for entity in game_world.query_entities(TILE).collect::<Vec<_>>() {
+        let Some(tile) = game_world.get_tile_mut(entity) else {
+            continue;
+        };
+        if matches!(tile.tile_type, TileType::City | TileType::Capital)
+            && tile.faction == Some(current_faction)
+            && !tile_is_pillaged(tile, turn_number)
+        {
+            tile.growth += 1;
+        }
+    }
+}
+
+pub fn city_tier_label_system(
+    game_world: &mut GameWorld,
+    world: &mut World,
+    label_entities: &[(HexCoord, Entity)],
+) {
+    let tiers: HashMap<HexCoord, u32> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            Some((coord, city_tier(tile)))
+        })
+        .collect();
+
+    for (coord, text_entity) in label_entities {
+        let tier = tiers.get(coord).copied().unwrap_or(0);
+        label_set_text(
+            &mut game_world.resources.labels,
+            world,
+            *text_entity,
+            city_tier_numeral(tier),
+        );
+    }
+}
@@ -1,8 +1,19 @@
+use crate::constants::{
+    BLOOD_AND_IRON_ATTACK_BONUS, ENCIRCLEMENT_BONUS, ENCIRCLEMENT_MIN_UNITS, FOREST_AMBUSH_BONUS,
+    GENERAL_DEATH_MORALE_PENALTY,
+};
 use crate::ecs::{
-    Faction, GameWorld, HEX_POSITION, TILE, modify_faction_morale, tile_defense_bonus,
+    CombatModifier, CombatModifierKind, CombatSide, Faction, GameWorld, HEX_POSITION,
+    LeaderAbility, TILE, TileType, UNIT, map_distance, modify_faction_morale,
+    record_ownership_change, tile_defense_bonus, unit_kind_is_combatant,
 };
 use crate::hex::HexCoord;
-use crate::systems::{despawn_unit, move_unit_to};
+use crate::selection::get_unit_at_tile;
+use crate::systems::{
+    city_tier_defense_bonus, general_aura_multiplier, general_gain_experience,
+    improvement_defense_bonus, kill_unit, leader_ability_active, move_unit_to, record_casualties,
+    record_city_capture,
+};
 use nightshade::prelude::*;
 
 pub struct CombatResult {
@@ -10,26 +21,36 @@ pub struct CombatResult {
     pub defender_faction: Faction,
     pub attacker_survived: bool,
     pub defender_survived: bool,
+    pub location: HexCoord,
+    pub attacker_location: HexCoord,
+    pub attacker_losses: i32,
+    pub defender_losses: i32,
+    pub wounded_unit: Option<freecs::Entity>,
+    pub general_killed: Option<Faction>,
+    pub modifiers: Vec<CombatModifier>,
 }
 
 pub fn resolve_combat(
     game_world: &mut GameWorld,
-    world: &mut World,
     attacker_entity: freecs::Entity,
     defender_entity: freecs::Entity,
 ) -> Option<CombatResult> {
     let attacker = game_world.get_unit(attacker_entity).copied()?;
     let defender = game_world.get_unit(defender_entity).copied()?;
     let defender_hex = game_world.get_hex_position(defender_entity)?.0;
+    let attacker_hex = game_world.get_hex_position(attacker_entity)?.0;
 
     let attacker_faction = attacker.faction;
     let defender_faction = defender.faction;
 
-    let defense_bonus = get_defense_bonus_at(game_world, defender_hex);
+    let modifiers = combat_modifiers(game_world, attacker_entity, defender_entity)?;
+    let attacker_strength = side_strength(attacker.soldiers, &modifiers, CombatSide::Attacker);
+    let defender_strength = side_strength(defender.soldiers, &modifiers, CombatSide::Defender);
 
-    let attacker_strength = attacker.soldiers as f32 * (1.0 + attacker.morale as f32 / 100.0);
-    let defender_strength =
-        defender.soldiers as f32 * (1.0 + defender.morale as f32 / 100.0) * defense_bonus;
+    record_battle(game_world, defender_hex);
+
+    let attacker_is_general = game_world.get_general(attacker_entity).is_some();
+    let defender_is_general = game_world.get_general(defender_entity).is_some();
 
     let attacker_wins = attacker_strength > defender_strength;
 
@@ -37,7 +58,7 @@ pub fn resolve_combat(
         let attacker_casualties = (defender.soldiers as f32 * 0.7).floor() as i32;
         let attacker_new_soldiers = attacker.soldiers - attacker_casualties;
 
-        despawn_unit(game_world, world, defender_entity);
+        kill_unit(game_world, defender_entity);
 
         let attacker_survived = attacker_new_soldiers > 0;
         if attacker_survived {
@@ -45,61 +66,316 @@ pub fn resolve_combat(
                 unit.soldiers = attacker_new_soldiers;
                 unit.has_moved = true;
             }
-            move_unit_to(game_world, attacker_entity, defender_hex);
-            update_tile_ownership(game_world, defender_hex, attacker_faction);
+            general_gain_experience(game_world, attacker_entity);
+            if get_unit_at_tile(game_world, defender_hex).is_none() {
+                move_unit_to(game_world, attacker_entity, defender_hex);
+                update_tile_ownership(game_world, defender_hex, attacker_faction);
+            }
         } else {
-            despawn_unit(game_world, world, attacker_entity);
+            kill_unit(game_world, attacker_entity);
         }
 
         modify_faction_morale(&mut game_world.resources, attacker_faction, 2);
         modify_faction_morale(&mut game_world.resources, defender_faction, -2);
 
-        Some(CombatResult {
+        let general_killed = if defender_is_general {
+            Some(defender_faction)
+        } else if attacker_is_general && !attacker_survived {
+            Some(attacker_faction)
+        } else {
+            None
+        };
+        if let Some(faction) = general_killed {
+            modify_faction_morale(
+                &mut game_world.resources,
+                faction,
+                -GENERAL_DEATH_MORALE_PENALTY,
+            );
+        }
+
+        let result = CombatResult {
             attacker_faction,
             defender_faction,
             attacker_survived,
             defender_survived: false,
-        })
+            location: defender_hex,
+            attacker_location: attacker_hex,
+            attacker_losses: attacker_casualties.min(attacker.soldiers),
+            defender_losses: defender.soldiers,
+            wounded_unit: (attacker_survived && attacker_casualties > 0).then_some(attacker_entity),
+            general_killed,
+            modifiers,
+        };
+        record_casualties(&mut game_world.resources, &result);
+        Some(result)
     } else {
         let defender_casualties = (attacker.soldiers as f32 * 0.5).floor() as i32;
         let defender_new_soldiers = defender.soldiers - defender_casualties;
 
-        despawn_unit(game_world, world, attacker_entity);
+        kill_unit(game_world, attacker_entity);
 
         let defender_survived = defender_new_soldiers > 0;
         if defender_survived {
             if let Some(unit) = game_world.get_unit_mut(defender_entity) {
                 unit.soldiers = defender_new_soldiers;
             }
+            general_gain_experience(game_world, defender_entity);
         } else {
-            despawn_unit(game_world, world, defender_entity);
+            kill_unit(game_world, defender_entity);
         }
 
         modify_faction_morale(&mut game_world.resources, defender_faction, 2);
         modify_faction_morale(&mut game_world.resources, attacker_faction, -2);
 
-        Some(CombatResult {
+        let general_killed = if attacker_is_general {
+            Some(attacker_faction)
+        } else if defender_is_general && !defender_survived {
+            Some(defender_faction)
+        } else {
+            None
+        };
+        if let Some(faction) = general_killed {
+            modify_faction_morale(
+                &mut game_world.resources,
+                faction,
+                -GENERAL_DEATH_MORALE_PENALTY,
+            );
+        }
+
+        let result = CombatResult {
             attacker_faction,
             defender_faction,
             attacker_survived: false,
             defender_survived,
+            location: defender_hex,
+            attacker_location: attacker_hex,
+            attacker_losses: attacker.soldiers,
+            defender_losses: defender_casualties.min(defender.soldiers),
+            wounded_unit: (defender_survived && defender_casualties > 0).then_some(defender_entity),
+            general_killed,
+            modifiers,
+        };
+        record_casualties(&mut game_world.resources, &result);
+        Some(result)
+    }
+}
+
+pub fn combat_modifiers(
+    game_world: &GameWorld,
+    attacker_entity: freecs::Entity,
+    defender_entity: freecs::Entity,
+) -> Option<Vec<CombatModifier>> {
+    let attacker = game_world.get_unit(attacker_entity).copied()?;
+    let defender = game_world.get_unit(defender_entity).copied()?;
+    let defender_hex = game_world.get_hex_position(defender_entity)?.0;
+    let attacker_hex = game_world.get_hex_position(attacker_entity)?.0;
+
+    let (terrain_bonus, fortification_bonus) = get_defense_bonus_at(game_world, defender_hex);
+
+    let leader_bonus = if leader_ability_active(
+        &game_world.resources,
+        attacker.faction,
+        LeaderAbility::BloodAndIron,
+    ) {
+        1.0 + BLOOD_AND_IRON_ATTACK_BONUS
+    } else {
+        1.0
+    };
+    let ambush_bonus = if forest_ambush(game_world, attacker_entity, defender_entity) {
+        1.0 + FOREST_AMBUSH_BONUS
+    } else {
+        1.0
+    };
+    let encirclement_bonus = encirclement_multiplier(game_world, attacker_entity, defender_entity);
+
+    let attacker_aura = general_aura_multiplier(game_world, attacker.faction, attacker_hex);
+    let defender_aura = general_aura_multiplier(game_world, defender.faction, defender_hex);
+
+    let defender_presence = if !unit_kind_is_combatant(defender.kind) {
+        0.0
+    } else {
+        1.0
+    };
+
+    let modifiers = [
+        (
+            CombatSide::Attacker,
+            CombatModifierKind::Morale,
+            1.0 + attacker.morale as f32 / 100.0,
+        ),
+        (
+            CombatSide::Attacker,
+            CombatModifierKind::Leader,
+            leader_bonus,
+        ),
+        (
+            CombatSide::Attacker,
+            CombatModifierKind::ForestAmbush,
+            ambush_bonus,
+        ),
+        (
+            CombatSide::Attacker,
+            CombatModifierKind::Flanking,
+            encirclement_bonus,
+        ),
+        (
+            CombatSide::Attacker,
+            CombatModifierKind::Veterancy,
+            attacker_aura,
+        ),
+        (
+            CombatSide::Defender,
+            CombatModifierKind::Morale,
+            1.0 + defender.morale as f32 / 100.0,
+        ),
+        (
+            CombatSide::Defender,
+            CombatModifierKind::Terrain,
+            terrain_bonus,
+        ),
+        (
+            CombatSide::Defender,
+            CombatModifierKind::Fortification,
+            (terrain_bonus + fortification_bonus) / terrain_bonus,
+        ),
+        (
+            CombatSide::Defender,
+            CombatModifierKind::Veterancy,
+            defender_aura,
+        ),
+        (
+            CombatSide::Defender,
+            CombatModifierKind::Unarmed,
+            defender_presence,
+        ),
+    ];
+
+    Some(
+        modifiers
+            .into_iter()
+            .filter(|(_, _, multiplier)| *multiplier != 1.0)
+            .map(|(side, kind, multiplier)| CombatModifier {
+                side,
+                kind,
+                multiplier,
+            })
+            .collect(),
+    )
+}
+
+fn side_strength(soldiers: i32, modifiers: &[CombatModifier], side: CombatSide) -> f32 {
+    modifiers
+        .iter()
+        .filter(|modifier| modifier.side == side)
+        .fold(soldiers as f32, |strength, modifier| {
+            strength * modifier.multiplier
+        })
+}
+
+pub fn combat_win_chance(
+    game_world: &GameWorld,
+    attacker_entity: freecs::Entity,
+    defender_entity: freecs::Entity,
+) -> Option<f32> {
+    let attacker = game_world.get_unit(attacker_entity)?;
+    let defender = game_world.get_unit(defender_entity)?;
+    let modifiers = combat_modifiers(game_world, attacker_entity, defender_entity)?;
+    let attacker_strength = side_strength(attacker.soldiers, &modifiers, CombatSide::Attacker);
+    let defender_strength = side_strength(defender.soldiers, &modifiers, CombatSide::Defender);
+    Some(attacker_strength / (attacker_strength + defender_strength))
+}
+
+fn tile_type_at(game_world: &GameWorld, coord: HexCoord) -> Option<TileType> {
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|entity| game_world.get_hex_position(*entity).map(|hex| hex.0) == Some(coord))
+        .and_then(|entity| game_world.get_tile(entity))
+        .map(|tile| tile.tile_type)
+}
+
+pub fn forest_ambush(
+    game_world: &GameWorld,
+    attacker_entity: freecs::Entity,
+    defender_entity: freecs::Entity,
+) -> bool {
+    let (Some(attacker_hex), Some(defender_hex)) = (
+        game_world
+            .get_hex_position(attacker_entity)
+            .map(|hex| hex.0),
+        game_world
+            .get_hex_position(defender_entity)
+            .map(|hex| hex.0),
+    ) else {
+        return false;
+    };
+    map_distance(&game_world.resources, attacker_hex, defender_hex) == 1
+        && tile_type_at(game_world, attacker_hex) == Some(TileType::Forest)
+        && tile_type_at(game_world, defender_hex) == Some(TileType::Land)
+}
+
+pub fn encircled(
+    game_world: &GameWorld,
+    attacker_entity: freecs::Entity,
+    defender_entity: freecs::Entity,
+) -> bool {
+    let (Some(attacker), Some(defender_hex)) = (
+        game_world.get_unit(attacker_entity),
+        game_world
+            .get_hex_position(defender_entity)
+            .map(|hex| hex.0),
+    ) else {
+        return false;
+    };
+    let flankers = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter(|entity| {
+            game_world.get_unit(*entity).is_some_and(|unit| {
+                unit.faction == attacker.faction && unit_kind_is_combatant(unit.kind)
+            }) && game_world
+                .get_hex_position(*entity)
+                .is_some_and(|hex| map_distance(&game_world.resources, hex.0, defender_hex) == 1)
         })
+        .count();
+    flankers >= ENCIRCLEMENT_MIN_UNITS
+}
+
+pub fn encirclement_multiplier(
+    game_world: &GameWorld,
+    attacker_entity: freecs::Entity,
+    defender_entity: freecs::Entity,
+) -> f32 {
+    if encircled(game_world, attacker_entity, defender_entity) {
+        1.0 + ENCIRCLEMENT_BONUS
+    } else {
+        1.0
     }
 }
 
-fn get_defense_bonus_at(game_world: &GameWorld, coord: HexCoord) -> f32 {
+fn get_defense_bonus_at(game_world: &GameWorld, coord: HexCoord) -> (f32, f32) {
     game_world
         .query_entities(HEX_POSITION | TILE)
         .find_map(|entity| {
             let hex = game_world.get_hex_position(entity)?;
             if hex.0 == coord {
                 let tile = game_world.get_tile(entity)?;
-                Some(tile_defense_bonus(tile.tile_type))
+                Some((
+                    tile_defense_bonus(tile.tile_type),
+                    city_tier_defense_bonus(tile) + improvement_defense_bonus(tile),
+                ))
             } else {
                 None
             }
         })
-        .unwrap_or(1.0)
+        .unwrap_or((1.0, 0.0))
+}
+
+fn record_battle(game_world: &mut GameWorld, coord: HexCoord) {
+    let tile_entity = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| game_world.get_hex_position(entity).map(|hex| hex.0) == Some(coord));
+    if let Some(tile) = tile_entity.and_then(|entity| game_world.get_tile_mut(entity)) {
+        tile.battles += 1;
+    }
 }
 
 fn update_tile_ownership(game_world: &mut GameWorld, coord: HexCoord, faction: Faction) {
@@ -114,7 +390,11 @@ fn update_tile_ownership(game_world: &mut GameWorld, coord: HexCoord, faction: F
 
     if let Some(entity) = tile_entity
         && let Some(tile) = game_world.get_tile_mut(entity)
+        && tile.faction != Some(faction)
     {
         tile.faction = Some(faction);
+        let tile_type = tile.tile_type;
+        record_ownership_change(&mut game_world.resources, coord, Some(faction));
+        record_city_capture(&mut game_world.resources, faction, tile_type);
     }
 }
@@ -0,0 +1,52 @@
+use crate::ecs::{GameWorld, faction_color};
+use nightshade::prelude::*;
+
+const DAMAGE_FLASH_DURATION: f32 = 0.5;
+const DAMAGE_FLASH_COLOR: [f32; 4] = [1.0, 0.1, 0.1, 1.0];
+
+pub fn start_damage_flash(game_world: &mut GameWorld, unit_entity: freecs::Entity) {
+    game_world
+        .resources
+        .damage_flashes
+        .insert(unit_entity, DAMAGE_FLASH_DURATION);
+}
+
+pub fn damage_flash_system(game_world: &mut GameWorld, world: &mut World, delta_time: f32) {
+    let flashes: Vec<(freecs::Entity, f32)> = game_world
+        .resources
+        .damage_flashes
+        .iter()
+        .map(|(entity, remaining)| (*entity, *remaining))
+        .collect();
+
+    for (unit_entity, remaining) in flashes {
+        let remaining = remaining - delta_time;
+        let (Some(unit), Some(engine_entity)) = (
+            game_world.get_unit(unit_entity),
+            game_world.get_engine_entity(unit_entity),
+        ) else {
+            game_world.resources.damage_flashes.remove(&unit_entity);
+            continue;
+        };
+
+        let base = faction_color(unit.faction);
+        let intensity = (remaining / DAMAGE_FLASH_DURATION).clamp(0.0, 1.0);
+        if let Some(material) = world.get_material_mut(engine_entity.0) {
+            material.base_color = [
+                base[0] + (DAMAGE_FLASH_COLOR[0] - base[0]) * intensity,
+                base[1] + (DAMAGE_FLASH_COLOR[1] - base[1]) * intensity,
+                base[2] + (DAMAGE_FLASH_COLOR[2] - base[2]) * intensity,
+                base[3],
+            ];
+        }
+
+        if remaining <= 0.0 {
+            game_world.resources.damage_flashes.remove(&unit_entity);
+        } else {
+            game_world
+                .resources
+                .damage_flashes
+                .insert(unit_entity, remaining);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::ecs::{GameWorld, faction_color};
+use crate::labels::label_release;
+use crate::quality::QualitySettings;
+use nightshade::prelude::*;
+
+const DEATH_EFFECT_DURATION: f32 = 0.8;
+const DEATH_SINK_SPEED: f32 = 60.0;
+const SMOKE_SCALE: f32 = 30.0;
+const SMOKE_PARTICLES: f32 = 120.0;
+
+fn spawn_death_smoke(world: &mut World, position: Vec3, color: Vec3, quality: &QualitySettings) {
+    let particle_count = ((SMOKE_PARTICLES * quality.particle_density) as u32).max(1);
+    let mut emitter = ParticleEmitter::firework_explosion(position, color, particle_count);
+    emitter.initial_velocity_min = 0.5 * SMOKE_SCALE;
+    emitter.initial_velocity_max = 2.0 * SMOKE_SCALE;
+    emitter.gravity = nalgebra_glm::vec3(0.0, 1.5 * SMOKE_SCALE, 0.0);
+    emitter.size_start = 0.6 * SMOKE_SCALE;
+    emitter.size_end = 1.5 * SMOKE_SCALE;
+    emitter.particle_lifetime_min = 0.6;
+    emitter.particle_lifetime_max = 1.2;
+    emitter.emissive_strength = 1.0;
+
+    let entity = world.spawn_entities(nightshade::ecs::PARTICLE_EMITTER, 1)[0];
+    world.set_particle_emitter(entity, emitter);
+}
+
+pub fn death_effect_system(
+    game_world: &mut GameWorld,
+    world: &mut World,
+    quality: &QualitySettings,
+    delta_time: f32,
+) {
+    if delta_time <= 0.0 {
+        return;
+    }
+
+    let mut finished = Vec::new();
+    let mut released_labels = Vec::new();
+
+    for death in &mut game_world.resources.unit_deaths {
+        if death.elapsed == 0.0 {
+            let color = faction_color(death.faction);
+            spawn_death_smoke(
+                world,
+                death.position,
+                nalgebra_glm::vec3(color[0], color[1], color[2]),
+                quality,
+            );
+        }
+        death.elapsed += delta_time;
+        let fade = (1.0 - death.elapsed / DEATH_EFFECT_DURATION).max(0.0);
+
+        if let Some(engine_entity) = death.engine_entity {
+            if let Some(transform) = world.get_local_transform_mut(engine_entity) {
+                let radius = death.radius * fade;
+                transform.scale = nalgebra_glm::vec3(radius, radius, radius);
+                transform.translation = nalgebra_glm::vec3(
+                    death.position.x,
+                    death.position.y - death.elapsed * DEATH_SINK_SPEED,
+                    death.position.z,
+                );
+            }
+            mark_local_transform_dirty(world, engine_entity);
+        }
+
+        if let Some(text_entity) = death.text_entity
+            && let Some(text) = world.get_text_mut(text_entity)
+        {
+            text.properties.color.w = fade;
+            text.dirty = true;
+        }
+
+        if fade <= 0.0 {
+            finished.extend(death.engine_entity);
+            released_labels.extend(death.text_entity);
+        }
+    }
+
+    game_world
+        .resources
+        .unit_deaths
+        .retain(|death| death.elapsed < DEATH_EFFECT_DURATION);
+    game_world.resources.despawned_visuals.extend(finished);
+    for text_entity in released_labels {
+        label_release(&mut game_world.resources.labels, text_entity);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::ecs::{Faction, TileType, faction_color};
+use crate::quality::QualitySettings;
 use nightshade::ecs::particles::components::{ColorGradient, EmitterShape, EmitterType};
 use nightshade::prelude::*;
 
@@ -25,13 +26,13 @@ fn scale_emitter(emitter: &mut ParticleEmitter) {
     }
 }
 
-fn create_shell_trail(position: Vec3) -> ParticleEmitter {
+fn create_shell_trail(position: Vec3, particle_density: f32) -> ParticleEmitter {
     ParticleEmitter {
         emitter_type: EmitterType::Firework,
         shape: EmitterShape::Point,
         position,
         direction: nalgebra_glm::vec3(0.0, -1.0, 0.0),
-        spawn_rate: 150.0,
+        spawn_rate: 150.0 * particle_density,
         burst_count: 0,
         particle_lifetime_min: 0.3,
         particle_lifetime_max: 0.6,
@@ -59,25 +60,28 @@ pub fn spawn_capture_firework(
     position: Vec3,
     tile_type: TileType,
     faction: Faction,
+    quality: &QualitySettings,
 ) {
     let color = faction_color(faction);
     let color_vec = nalgebra_glm::vec3(color[0], color[1], color[2]);
 
     let launch_pos = nalgebra_glm::vec3(position.x, position.y, position.z);
 
-    let particle_count = match tile_type {
+    let base_particle_count: u32 = match tile_type {
         TileType::Capital => 1200,
         TileType::City => 800,
         TileType::Port => 500,
         _ => 300,
     };
+    let particle_count = ((base_particle_count as f32 * quality.particle_density) as u32).max(1);
 
     let shell_count = match tile_type {
         TileType::Capital => 8,
         TileType::City => 1,
         TileType::Port => 1,
         _ => 1,
-    };
+    }
+    .min(quality.max_firework_shells.max(1));
 
     for shell_index in 0..shell_count {
         let mut rng = rand::rng();
@@ -99,7 +103,7 @@ pub fn spawn_capture_firework(
         let fuse_time = target_height / velocity.y + (shell_index as f32) * 0.2;
 
         let entity = world.spawn_entities(nightshade::ecs::PARTICLE_EMITTER, 1)[0];
-        let trail_emitter = create_shell_trail(shell_launch_pos);
+        let trail_emitter = create_shell_trail(shell_launch_pos, quality.particle_density);
         world.set_particle_emitter(entity, trail_emitter);
 
         shells.push(FireworkShell {
@@ -158,7 +162,8 @@ pub fn update_firework_shells(shells: &mut Vec<FireworkShell>, world: &mut World
             for ring_index in 0..6 {
                 let angle = (ring_index as f32) * std::f32::consts::TAU / 6.0;
                 let ring_entity = world.spawn_entities(nightshade::ecs::PARTICLE_EMITTER, 1)[0];
-                let mut ring_emitter = ParticleEmitter::firework_ring(pos, color, 400);
+                let mut ring_emitter =
+                    ParticleEmitter::firework_ring(pos, color, particle_count / 3);
                 ring_emitter.direction = nalgebra_glm::vec3(angle.sin(), 0.0, angle.cos());
                 scale_emitter(&mut ring_emitter);
                 world.set_particle_emitter(ring_entity, ring_emitter);
@@ -172,3 +177,16 @@ pub fn update_firework_shells(shells: &mut Vec<FireworkShell>, world: &mut World
 
     shells.retain(|shell| shell.fuse_time > 0.0);
 }
+
+pub fn spawn_clash_burst(world: &mut World, position: Vec3, quality: &QualitySettings) {
+    let flash_entity = world.spawn_entities(nightshade::ecs::PARTICLE_EMITTER, 1)[0];
+    let mut flash_emitter = ParticleEmitter::flash_burst(position);
+    scale_emitter(&mut flash_emitter);
+    world.set_particle_emitter(flash_entity, flash_emitter);
+
+    let spark_count = ((200.0 * quality.particle_density) as u32).max(1);
+    let spark_entity = world.spawn_entities(nightshade::ecs::PARTICLE_EMITTER, 1)[0];
+    let mut spark_emitter = ParticleEmitter::firework_glitter(position, spark_count);
+    scale_emitter(&mut spark_emitter);
+    world.set_particle_emitter(spark_entity, spark_emitter);
+}
@@ -0,0 +1,89 @@
+use crate::constants::{
+    GENERAL_AURA_BONUS, GENERAL_EXPERIENCE_PER_LEVEL, GENERAL_LEVEL_AURA_BONUS, GENERAL_MAX_LEVEL,
+};
+use crate::ecs::{Faction, GENERAL, GameWorld, General, HEX_POSITION, UNIT, map_distance};
+use crate::hex::{HexCoord, hex_to_world_position};
+use crate::rendering::generate_hex_outline_with_color;
+use nightshade::prelude::*;
+
+const GENERAL_MARKER_COLOR: [f32; 4] = [1.0, 0.8, 0.2, 1.0];
+const GENERAL_MARKER_HEIGHT: f32 = 3.0;
+const GENERAL_MARKER_BASE_SCALE: f32 = 0.72;
+const GENERAL_MARKER_SCALE_STEP: f32 = 0.05;
+
+pub fn make_general(game_world: &mut GameWorld, unit_entity: freecs::Entity, general: General) {
+    game_world.add_components(unit_entity, GENERAL);
+    game_world.set_general(unit_entity, general);
+}
+
+pub fn general_aura_multiplier(game_world: &GameWorld, faction: Faction, coord: HexCoord) -> f32 {
+    let best_bonus = game_world
+        .query_entities(GENERAL | UNIT | HEX_POSITION)
+        .filter_map(|entity| {
+            let unit = game_world.get_unit(entity)?;
+            let hex = game_world.get_hex_position(entity)?.0;
+            let general = game_world.get_general(entity)?;
+            (unit.faction == faction && map_distance(&game_world.resources, hex, coord) <= 1)
+                .then(|| GENERAL_AURA_BONUS + (general.level - 1) as f32 * GENERAL_LEVEL_AURA_BONUS)
+        })
+        .fold(0.0, f32::max);
+    1.0 + best_bonus
+}
+
+pub fn general_gain_experience(game_world: &mut GameWorld, unit_entity: freecs::Entity) {
+    let Some(general) = game_world.get_general_mut(unit_entity) else {
+        return;
+    };
+    general.experience += 1;
+    general.level = (1 + general.experience / GENERAL_EXPERIENCE_PER_LEVEL).min(GENERAL_MAX_LEVEL);
+}
+
+pub fn general_marker_system(
+    game_world: &GameWorld,
+    world: &mut World,
+    marker_entity: Option<Entity>,
+) {
+    let Some(entity) = marker_entity else {
+        return;
+    };
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let color = nalgebra_glm::vec4(
+        GENERAL_MARKER_COLOR[0],
+        GENERAL_MARKER_COLOR[1],
+        GENERAL_MARKER_COLOR[2],
+        GENERAL_MARKER_COLOR[3],
+    );
+
+    let mut lines = Vec::new();
+    for general_entity in game_world.query_entities(GENERAL | UNIT | HEX_POSITION) {
+        let (Some(hex), Some(general)) = (
+            game_world.get_hex_position(general_entity),
+            game_world.get_general(general_entity),
+        ) else {
+            continue;
+        };
+        let center =
+            hex_to_world_position(hex.0.column, hex.0.row, hex_width, hex_depth, orientation);
+        for ring in 0..general.level {
+            let scale = GENERAL_MARKER_BASE_SCALE - ring as f32 * GENERAL_MARKER_SCALE_STEP;
+            lines.extend(generate_hex_outline_with_color(
+                center,
+                hex_width * scale,
+                hex_depth * scale,
+                orientation,
+                GENERAL_MARKER_HEIGHT,
+                color,
+            ));
+        }
+    }
+
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = lines;
+        lines_component.mark_dirty();
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = true;
+    }
+}
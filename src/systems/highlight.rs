@@ -1,97 +1,174 @@
-use crate::ecs::GameWorld;
+use crate::constants::BATTLE_SCAR_THRESHOLD;
+use crate::ecs::{Faction, GameWorld, HEX_POSITION, TILE, faction_color};
 use crate::hex::{HexCoord, hex_to_world_position};
-use crate::instancing::InstancedTileGroup;
+use crate::instancing::InstancedTiles;
 use crate::rendering::generate_hex_outline;
 use nightshade::ecs::world::components::Line;
 use nightshade::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 const DEFAULT_TINT: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
 const HOVER_TINT: [f32; 4] = [1.3, 1.3, 1.0, 1.0];
 const VALID_MOVE_TINT: [f32; 4] = [0.8, 1.2, 0.8, 1.0];
 const HOVER_VALID_TINT: [f32; 4] = [1.0, 1.5, 0.7, 1.0];
+const PORT_ROUTE_TINT: [f32; 4] = [0.7, 1.0, 1.5, 1.0];
+const HOVER_PORT_ROUTE_TINT: [f32; 4] = [0.9, 1.3, 1.9, 1.0];
+const SCORCHED_TINT: [f32; 4] = [0.6, 0.52, 0.45, 1.0];
+const OWNERSHIP_TINT_STRENGTH: f32 = 0.25;
+
+fn ownership_tint(owner: Option<Faction>) -> [f32; 4] {
+    let Some(owner) = owner else {
+        return DEFAULT_TINT;
+    };
+    let color = faction_color(owner);
+    let blend = |channel: f32| 1.0 + (channel - 0.5) * OWNERSHIP_TINT_STRENGTH * 2.0;
+    [blend(color[0]), blend(color[1]), blend(color[2]), 1.0]
+}
+
+fn highlight_tint(is_hovered: bool, is_valid_move: bool, is_port_route: bool) -> [f32; 4] {
+    if is_port_route {
+        return if is_hovered {
+            HOVER_PORT_ROUTE_TINT
+        } else {
+            PORT_ROUTE_TINT
+        };
+    }
+    match (is_hovered, is_valid_move) {
+        (true, true) => HOVER_VALID_TINT,
+        (true, false) => HOVER_TINT,
+        (false, true) => VALID_MOVE_TINT,
+        (false, false) => DEFAULT_TINT,
+    }
+}
+
+fn combined_tint(
+    owner: Option<Faction>,
+    is_hovered: bool,
+    is_valid_move: bool,
+    is_port_route: bool,
+    is_scorched: bool,
+) -> [f32; 4] {
+    let ownership = ownership_tint(owner);
+    let highlight = highlight_tint(is_hovered, is_valid_move, is_port_route);
+    let scorched = if is_scorched {
+        SCORCHED_TINT
+    } else {
+        DEFAULT_TINT
+    };
+    [
+        ownership[0] * highlight[0] * scorched[0],
+        ownership[1] * highlight[1] * scorched[1],
+        ownership[2] * highlight[2] * scorched[2],
+        ownership[3] * highlight[3] * scorched[3],
+    ]
+}
+
+fn scorched_tiles(game_world: &GameWorld) -> HashSet<HexCoord> {
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            (tile.battles >= BATTLE_SCAR_THRESHOLD).then_some(coord)
+        })
+        .collect()
+}
+
+fn tile_owners(game_world: &GameWorld) -> HashMap<HexCoord, Faction> {
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let owner = game_world.get_tile(entity)?.faction?;
+            Some((coord, owner))
+        })
+        .collect()
+}
 
 pub fn tile_highlight_system(
     game_world: &mut GameWorld,
     world: &mut World,
-    instanced_tile_groups: &[InstancedTileGroup],
+    instanced_tiles: &InstancedTiles,
+    battle_scars: bool,
 ) {
     let hovered_tile = game_world.resources.hovered_tile;
     let valid_move_tiles = &game_world.resources.valid_move_tiles;
+    let port_route_tiles = &game_world.resources.port_route_tiles;
+    let airlift_tiles = &game_world.resources.airlift_tiles;
 
     let mut currently_highlighted: HashSet<HexCoord> = valid_move_tiles.clone();
+    currently_highlighted.extend(port_route_tiles.iter().copied());
+    currently_highlighted.extend(airlift_tiles.iter().copied());
     if let Some(coord) = hovered_tile {
         currently_highlighted.insert(coord);
     }
 
-    let tiles_to_reset: Vec<HexCoord> = game_world
-        .resources
-        .previously_highlighted
-        .difference(&currently_highlighted)
-        .copied()
-        .collect();
-
-    let tiles_to_highlight: Vec<HexCoord> = currently_highlighted
-        .difference(&game_world.resources.previously_highlighted)
-        .copied()
-        .collect();
+    let owners = tile_owners(game_world);
+    let tinted_owners = &game_world.resources.tinted_owners;
+    let ownership_changed = owners
+        .iter()
+        .filter(|(coord, owner)| tinted_owners.get(coord) != Some(owner))
+        .map(|(coord, _)| *coord)
+        .chain(
+            tinted_owners
+                .keys()
+                .filter(|coord| !owners.contains_key(coord))
+                .copied(),
+        );
+
+    let scorched = if battle_scars {
+        scorched_tiles(game_world)
+    } else {
+        HashSet::new()
+    };
 
-    let tiles_needing_tint_update: Vec<HexCoord> = currently_highlighted
-        .intersection(&game_world.resources.previously_highlighted)
-        .copied()
-        .collect();
+    let mut tiles_to_update: HashSet<HexCoord> = ownership_changed.collect();
+    tiles_to_update.extend(
+        scorched
+            .symmetric_difference(&game_world.resources.scorched_tiles)
+            .copied(),
+    );
+    tiles_to_update.extend(
+        game_world
+            .resources
+            .previously_highlighted
+            .symmetric_difference(&currently_highlighted)
+            .copied(),
+    );
 
     let hover_changed = hovered_tile != game_world.resources.previous_hovered_tile;
-
-    if tiles_to_reset.is_empty()
-        && tiles_to_highlight.is_empty()
-        && (!hover_changed || tiles_needing_tint_update.is_empty())
-    {
-        return;
+    if hover_changed {
+        tiles_to_update.extend(
+            currently_highlighted
+                .intersection(&game_world.resources.previously_highlighted)
+                .copied(),
+        );
     }
 
-    for group in instanced_tile_groups {
-        let Some(instanced_mesh) = world.get_instanced_mesh_mut(group.entity) else {
+    for coord in &tiles_to_update {
+        let Some(group_indices) = instanced_tiles.coord_groups.get(coord) else {
             continue;
         };
-
-        for coord in &tiles_to_reset {
-            if let Some(&instance_index) = group.coord_to_instance.get(coord) {
-                instanced_mesh.set_instance_tint(instance_index, DEFAULT_TINT);
-            }
-        }
-
-        for coord in &tiles_to_highlight {
-            if let Some(&instance_index) = group.coord_to_instance.get(coord) {
-                let is_hovered = hovered_tile == Some(*coord);
-                let is_valid_move = valid_move_tiles.contains(coord);
-                let tint = match (is_hovered, is_valid_move) {
-                    (true, true) => HOVER_VALID_TINT,
-                    (true, false) => HOVER_TINT,
-                    (false, true) => VALID_MOVE_TINT,
-                    (false, false) => DEFAULT_TINT,
-                };
+        let tint = combined_tint(
+            owners.get(coord).copied(),
+            hovered_tile == Some(*coord),
+            valid_move_tiles.contains(coord),
+            port_route_tiles.contains(coord) || airlift_tiles.contains(coord),
+            scorched.contains(coord),
+        );
+        for &group_index in group_indices {
+            let group = &instanced_tiles.groups[group_index];
+            let Some(instanced_mesh) = world.get_instanced_mesh_mut(group.entity) else {
+                continue;
+            };
+            for &instance_index in group.coord_to_instance.get(coord).into_iter().flatten() {
                 instanced_mesh.set_instance_tint(instance_index, tint);
             }
         }
-
-        if hover_changed {
-            for coord in &tiles_needing_tint_update {
-                if let Some(&instance_index) = group.coord_to_instance.get(coord) {
-                    let is_hovered = hovered_tile == Some(*coord);
-                    let is_valid_move = valid_move_tiles.contains(coord);
-                    let tint = match (is_hovered, is_valid_move) {
-                        (true, true) => HOVER_VALID_TINT,
-                        (true, false) => HOVER_TINT,
-                        (false, true) => VALID_MOVE_TINT,
-                        (false, false) => DEFAULT_TINT,
-                    };
-                    instanced_mesh.set_instance_tint(instance_index, tint);
-                }
-            }
-        }
     }
 
+    game_world.resources.tinted_owners = owners;
+    game_world.resources.scorched_tiles = scorched;
     game_world.resources.previously_highlighted = currently_highlighted;
     game_world.resources.previous_hovered_tile = hovered_tile;
 }
@@ -111,8 +188,11 @@ pub fn hover_outline_system(
         Some(coord) => {
             let hex_width = game_world.resources.hex_width;
             let hex_depth = game_world.resources.hex_depth;
-            let tile_center = hex_to_world_position(coord.column, coord.row, hex_width, hex_depth);
-            let outline_lines = generate_hex_outline(tile_center, hex_width, hex_depth, 6.0);
+            let orientation = game_world.resources.hex_orientation;
+            let tile_center =
+                hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+            let outline_lines =
+                generate_hex_outline(tile_center, hex_width, hex_depth, orientation, 6.0);
             let yellow_lines: Vec<Line> = outline_lines
                 .into_iter()
                 .map(|mut line| {
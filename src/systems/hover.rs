@@ -1,6 +1,8 @@
 use crate::camera::world_to_screen;
-use crate::ecs::{GameWorld, HEX_POSITION, TILE};
-use crate::hex::{HexCoord, hex_tiles_at_distance, hex_to_world_position, world_to_hex};
+use crate::ecs::{GameWorld, HEX_POSITION, TILE, map_wrap};
+use crate::hex::{
+    HexCoord, HexOrientation, hex_tiles_at_distance, hex_to_world_position, world_to_hex,
+};
 use nightshade::ecs::picking::queries::PickingRay;
 use nightshade::prelude::*;
 use std::collections::HashSet;
@@ -11,9 +13,16 @@ pub fn hover_system(game_world: &mut GameWorld, world: &World) {
 
     let hex_width = game_world.resources.hex_width;
     let hex_depth = game_world.resources.hex_depth;
-
-    let hovered_tile_coord =
-        find_tile_under_cursor(game_world, world, mouse_pos, hex_width, hex_depth);
+    let orientation = game_world.resources.hex_orientation;
+
+    let hovered_tile_coord = find_tile_under_cursor(
+        game_world,
+        world,
+        mouse_pos,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
     game_world.resources.hovered_tile = hovered_tile_coord;
 }
 
@@ -23,10 +32,11 @@ fn find_tile_under_cursor(
     mouse_pos: Vec2,
     hex_width: f32,
     hex_depth: f32,
+    orientation: HexOrientation,
 ) -> Option<HexCoord> {
     let ray = PickingRay::from_screen_position(world, mouse_pos)?;
     let rough_hit = ray.intersect_ground_plane(0.0)?;
-    let rough_coord = world_to_hex(rough_hit.x, rough_hit.z, hex_width, hex_depth);
+    let rough_coord = world_to_hex(rough_hit.x, rough_hit.z, hex_width, hex_depth, orientation);
 
     let tile_surface_y = 5.0;
 
@@ -54,18 +64,19 @@ fn find_tile_under_cursor(
     let mut candidates: Vec<HexCoord> = Vec::new();
     candidates.push(rough_coord);
     for distance in 1..=search_tiles_radius {
-        candidates.extend(hex_tiles_at_distance(rough_coord, distance));
+        candidates.extend(hex_tiles_at_distance(rough_coord, distance, orientation));
     }
 
     let mut best_tile: Option<HexCoord> = None;
     let mut best_distance_sq = f32::MAX;
 
     for coord in candidates {
-        if !existing_tiles.contains(&coord) {
+        if !existing_tiles.contains(&map_wrap(&game_world.resources, coord)) {
             continue;
         }
 
-        let tile_world_pos = hex_to_world_position(coord.column, coord.row, hex_width, hex_depth);
+        let tile_world_pos =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
         let tile_top_pos = Vec3::new(tile_world_pos.x, tile_surface_y, tile_world_pos.z);
 
         if let Some(screen_pos) = world_to_screen(world, tile_top_pos) {
@@ -75,7 +86,7 @@ fn find_tile_under_cursor(
 
             if distance_sq < best_distance_sq {
                 best_distance_sq = distance_sq;
-                best_tile = Some(coord);
+                best_tile = Some(map_wrap(&game_world.resources, coord));
             }
         }
     }
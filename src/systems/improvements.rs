@@ -0,0 +1,211 @@
+use crate::constants::{FORT_DEFENSE_BONUS, IMPROVEMENT_BUILD_TURNS};
+use crate::ecs::{
+    Construction, Faction, GameEvents, GameWorld, HEX_POSITION, Improvement, ImprovementEvent,
+    TILE, Tile, TileType, UNIT,
+};
+use crate::hex::{HexCoord, HexOrientation, hex_to_world_position};
+use nightshade::ecs::world::spawn_instanced_mesh_with_material;
+use nightshade::prelude::*;
+use std::collections::HashMap;
+
+const IMPROVEMENT_MESH: &str = "Cube";
+const FORT_COLOR: [f32; 4] = [0.45, 0.4, 0.35, 1.0];
+const WATCHTOWER_COLOR: [f32; 4] = [0.55, 0.4, 0.25, 1.0];
+const FORT_HEIGHT: f32 = 8.0;
+const WATCHTOWER_HEIGHT: f32 = 60.0;
+const WATCHTOWER_WIDTH: f32 = 10.0;
+
+#[derive(Default)]
+pub struct ImprovementVisuals {
+    pub entities: Vec<Entity>,
+    pub built: HashMap<HexCoord, Improvement>,
+}
+
+pub fn improvement_name(improvement: Improvement) -> &'static str {
+    match improvement {
+        Improvement::Fort => "fort",
+        Improvement::Watchtower => "watchtower",
+    }
+}
+
+pub fn improvement_defense_bonus(tile: &Tile) -> f32 {
+    match tile.improvement {
+        Some(Improvement::Fort) if tile.tile_type != TileType::Swamp => FORT_DEFENSE_BONUS,
+        _ => 0.0,
+    }
+}
+
+pub fn start_construction(
+    game_world: &mut GameWorld,
+    unit_entity: freecs::Entity,
+    improvement: Improvement,
+) -> bool {
+    let Some(unit) = game_world.get_unit(unit_entity).copied() else {
+        return false;
+    };
+    if unit.has_moved || unit.faction != game_world.resources.current_faction {
+        return false;
+    }
+    let Some(coord) = game_world.get_hex_position(unit_entity).map(|h| h.0) else {
+        return false;
+    };
+
+    let tile_entity = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| game_world.get_hex_position(entity).map(|h| h.0) == Some(coord));
+    let Some(tile_entity) = tile_entity else {
+        return false;
+    };
+    let Some(tile) = game_world.get_tile_mut(tile_entity) else {
+        return false;
+    };
+    if tile.tile_type == TileType::Sea
+        || (tile.tile_type == TileType::Swamp && improvement == Improvement::Fort)
+        || tile.faction != Some(unit.faction)
+        || tile.improvement.is_some()
+        || tile.construction.is_some()
+    {
+        return false;
+    }
+    tile.construction = Some(Construction {
+        improvement,
+        progress: 0,
+    });
+
+    if let Some(unit) = game_world.get_unit_mut(unit_entity) {
+        unit.has_moved = true;
+    }
+    true
+}
+
+pub fn construction_system(game_world: &mut GameWorld, events: &mut GameEvents) {
+    let current_faction = game_world.resources.current_faction;
+    let builders: HashMap<HexCoord, Faction> = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let unit = game_world.get_unit(entity)?;
+            Some((coord, unit.faction))
+        })
+        .collect();
+
+    for entity in game_world
+        .query_entities(HEX_POSITION | TILE)
+        .collect::<Vec<_>>()
+    {
+        let Some(coord) = game_world.get_hex_position(entity).map(|h| h.0) else {
+            continue;
+        };
+        let Some(tile) = game_world.get_tile_mut(entity) else {
+            continue;
+        };
+        let Some(mut construction) = tile.construction else {
+            continue;
+        };
+        if tile.faction != Some(current_faction) {
+            continue;
+        }
+        if builders.get(&coord) != Some(&current_faction) {
+            tile.construction = None;
+            continue;
+        }
+
+        construction.progress += 1;
+        if construction.progress < IMPROVEMENT_BUILD_TURNS {
+            tile.construction = Some(construction);
+            continue;
+        }
+
+        tile.construction = None;
+        tile.improvement = Some(construction.improvement);
+        events.improvement_events.push(ImprovementEvent {
+            faction: current_faction,
+            location: coord,
+            improvement: construction.improvement,
+        });
+    }
+}
+
+fn improvement_instance(
+    improvement: Improvement,
+    coord: HexCoord,
+    hex_width: f32,
+    hex_depth: f32,
+    orientation: HexOrientation,
+) -> InstanceTransform {
+    let position =
+        hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+    match improvement {
+        Improvement::Fort => InstanceTransform::new(
+            nalgebra_glm::vec3(position.x, position.y + FORT_HEIGHT * 0.5, position.z),
+            nalgebra_glm::Quat::identity(),
+            nalgebra_glm::vec3(hex_width * 0.55, FORT_HEIGHT, hex_depth * 0.55),
+        ),
+        Improvement::Watchtower => InstanceTransform::new(
+            nalgebra_glm::vec3(
+                position.x + hex_width * 0.3,
+                position.y + WATCHTOWER_HEIGHT * 0.5,
+                position.z,
+            ),
+            nalgebra_glm::Quat::identity(),
+            nalgebra_glm::vec3(WATCHTOWER_WIDTH, WATCHTOWER_HEIGHT, WATCHTOWER_WIDTH),
+        ),
+    }
+}
+
+pub fn despawn_improvement_visuals(world: &mut World, visuals: &mut ImprovementVisuals) {
+    for entity in visuals.entities.drain(..) {
+        world.queue_command(WorldCommand::DespawnRecursive { entity });
+    }
+    visuals.built.clear();
+}
+
+pub fn improvement_visual_system(
+    game_world: &GameWorld,
+    world: &mut World,
+    visuals: &mut ImprovementVisuals,
+) {
+    let built: HashMap<HexCoord, Improvement> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let improvement = game_world.get_tile(entity)?.improvement?;
+            Some((coord, improvement))
+        })
+        .collect();
+
+    if built == visuals.built {
+        return;
+    }
+
+    despawn_improvement_visuals(world, visuals);
+
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    for (improvement, color) in [
+        (Improvement::Fort, FORT_COLOR),
+        (Improvement::Watchtower, WATCHTOWER_COLOR),
+    ] {
+        let instances: Vec<InstanceTransform> = built
+            .iter()
+            .filter(|(_, kind)| **kind == improvement)
+            .map(|(coord, _)| {
+                improvement_instance(improvement, *coord, hex_width, hex_depth, orientation)
+            })
+            .collect();
+        if instances.is_empty() {
+            continue;
+        }
+        let material = Material {
+            base_color: color,
+            roughness: 0.9,
+            ..Default::default()
+        };
+        let entity =
+            spawn_instanced_mesh_with_material(world, IMPROVEMENT_MESH, instances, material);
+        visuals.entities.push(entity);
+    }
+
+    visuals.built = built;
+}
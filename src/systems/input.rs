@@ -1,39 +1,30 @@
-use crate::constants::MAX_SOLDIERS;
-use crate::ecs::{CombatEvent, Faction, GameEvents, GameWorld, HEX_POSITION, TILE, TileType, UNIT};
-use crate::hex::{HexCoord, hex_distance};
-use crate::selection::{clear_selection, get_selected_unit, get_unit_at_tile, select_unit};
+use crate::ecs::{
+    CombatEvent, GameEvents, GameWorld, HEX_POSITION, Improvement, UNIT, map_distance,
+    unit_kind_is_combatant,
+};
+use crate::hex::HexCoord;
+use crate::selection::{
+    clear_selection, get_selected_unit, get_unit_at_tile, get_units_at_tile, select_unit,
+};
 use crate::systems::{
-    calculate_valid_moves, despawn_unit, move_unit_to, resolve_combat, spawn_merge_popup,
+    airlift_unit_to, calculate_valid_moves, despawn_unit, friendly_airfield_destinations,
+    friendly_port_destinations, make_general, move_action_cost, move_unit_to, pillage_tile,
+    recruit_scout, relocate_capital, resolve_combat, spawn_merge_popup, speech_system,
+    start_construction, unit_movement_budget,
 };
+use crate::teams::factions_hostile;
 use nightshade::prelude::*;
 
-fn get_friendly_ports(game_world: &GameWorld, faction: Faction) -> Vec<HexCoord> {
-    game_world
-        .query_entities(HEX_POSITION | TILE)
-        .filter_map(|entity| {
-            let hex = game_world.get_hex_position(entity)?.0;
-            let tile = game_world.get_tile(entity)?;
-            if tile.tile_type == TileType::Port && tile.faction == Some(faction) {
-                Some(hex)
-            } else {
-                None
-            }
-        })
-        .collect()
-}
-
-fn is_unit_on_friendly_port(game_world: &GameWorld, unit_hex: HexCoord, faction: Faction) -> bool {
-    game_world
-        .query_entities(HEX_POSITION | TILE)
-        .any(|entity| {
-            let Some(hex) = game_world.get_hex_position(entity) else {
-                return false;
-            };
-            let Some(tile) = game_world.get_tile(entity) else {
-                return false;
-            };
-            hex.0 == unit_hex && tile.tile_type == TileType::Port && tile.faction == Some(faction)
-        })
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerAction {
+    Click(HexCoord),
+    Deselect,
+    Build(Improvement),
+    Speech,
+    RelocateCapital(HexCoord),
+    SelectStacked(HexCoord, u8),
+    Pillage,
+    RecruitScout,
 }
 
 pub struct MergeResult {
@@ -43,14 +34,13 @@ pub struct MergeResult {
 
 fn merge_units(
     game_world: &mut GameWorld,
-    world: &mut World,
     source_entity: freecs::Entity,
     target_entity: freecs::Entity,
 ) -> Option<MergeResult> {
     let source_unit = game_world.get_unit(source_entity).copied()?;
     let target_unit = game_world.get_unit(target_entity).copied()?;
 
-    if source_unit.faction != target_unit.faction {
+    if source_unit.faction != target_unit.faction || source_unit.kind != target_unit.kind {
         return None;
     }
 
@@ -60,7 +50,7 @@ fn merge_units(
         .unwrap_or_default();
 
     let total_soldiers = source_unit.soldiers + target_unit.soldiers;
-    let new_soldiers = total_soldiers.min(MAX_SOLDIERS);
+    let new_soldiers = total_soldiers.min(game_world.resources.rules.max_soldiers);
     let soldiers_gained = new_soldiers - target_unit.soldiers;
 
     let weighted_morale = (source_unit.soldiers * source_unit.morale
@@ -72,7 +62,16 @@ fn merge_units(
         unit.morale = weighted_morale;
     }
 
-    despawn_unit(game_world, world, source_entity);
+    if let Some(source_general) = game_world.get_general(source_entity).copied() {
+        let target_general = game_world.get_general(target_entity).copied();
+        let general = match target_general {
+            Some(target_general) if target_general.level >= source_general.level => target_general,
+            _ => source_general,
+        };
+        make_general(game_world, target_entity, general);
+    }
+
+    despawn_unit(game_world, source_entity);
     Some(MergeResult {
         soldiers_gained,
         position,
@@ -102,8 +101,8 @@ fn is_valid_merge_target(
 
         let adjacent_to_reachable = reachable_tiles
             .iter()
-            .any(|&tile| hex_distance(tile, target_hex) <= 1);
-        let directly_adjacent = hex_distance(source_hex, target_hex) == 1;
+            .any(|&tile| map_distance(&game_world.resources, tile, target_hex) <= 1);
+        let directly_adjacent = map_distance(&game_world.resources, source_hex, target_hex) == 1;
 
         if adjacent_to_reachable || directly_adjacent {
             return true;
@@ -112,24 +111,119 @@ fn is_valid_merge_target(
     false
 }
 
-pub fn input_system(game_world: &mut GameWorld, world: &mut World, events: &mut GameEvents) {
+pub fn input_system(
+    game_world: &GameWorld,
+    world: &World,
+    build_request: Option<Improvement>,
+) -> Option<PlayerAction> {
+    if let Some(improvement) = build_request {
+        return Some(PlayerAction::Build(improvement));
+    }
+
     let mouse = &world.resources.input.mouse;
-    let left_clicked = mouse.state.contains(MouseState::LEFT_JUST_PRESSED);
-    let right_clicked = mouse.state.contains(MouseState::RIGHT_JUST_PRESSED);
+    if mouse.state.contains(MouseState::RIGHT_JUST_PRESSED) {
+        return Some(PlayerAction::Deselect);
+    }
+    if !mouse.state.contains(MouseState::LEFT_JUST_PRESSED) {
+        return None;
+    }
+
+    game_world.resources.hovered_tile.map(PlayerAction::Click)
+}
+
+pub fn apply_player_action(
+    game_world: &mut GameWorld,
+    world: &mut World,
+    action: PlayerAction,
+    events: &mut GameEvents,
+) {
+    match action {
+        PlayerAction::Click(tile) => player_click(game_world, world, tile, events),
+        PlayerAction::Deselect => clear_selection(game_world),
+        PlayerAction::Build(improvement) => {
+            if game_world.resources.actions_remaining > 0
+                && let Some(selected) = get_selected_unit(game_world)
+                && start_construction(game_world, selected, improvement)
+            {
+                game_world.resources.actions_remaining -= 1;
+                clear_selection(game_world);
+            }
+        }
+        PlayerAction::Speech => speech_system(game_world, true, events),
+        PlayerAction::RelocateCapital(target) => {
+            let faction = game_world.resources.current_faction;
+            relocate_capital(game_world, faction, target, events);
+        }
+        PlayerAction::SelectStacked(tile, index) => {
+            if let Some(&entity) = get_units_at_tile(game_world, tile).get(index as usize)
+                && game_world
+                    .get_unit(entity)
+                    .is_some_and(|unit| unit.faction == game_world.resources.current_faction)
+            {
+                select_unit(game_world, entity);
+            }
+        }
+        PlayerAction::RecruitScout => {
+            if game_world.resources.actions_remaining > 0
+                && let Some(selected) = get_selected_unit(game_world)
+                && recruit_scout(game_world, selected)
+            {
+                game_world.resources.actions_remaining -= 1;
+                clear_selection(game_world);
+            }
+        }
+        PlayerAction::Pillage => {
+            if game_world.resources.actions_remaining > 0
+                && let Some(selected) = get_selected_unit(game_world)
+                && pillage_tile(game_world, selected, events)
+            {
+                game_world.resources.actions_remaining -= 1;
+                clear_selection(game_world);
+            }
+        }
+    }
+}
 
-    if right_clicked {
-        clear_selection(game_world);
-        return;
+fn unit_is_combatant(game_world: &GameWorld, entity: freecs::Entity) -> bool {
+    game_world
+        .get_unit(entity)
+        .is_some_and(|unit| unit_kind_is_combatant(unit.kind))
+}
+
+pub fn attack_target(
+    game_world: &GameWorld,
+    hovered_tile: HexCoord,
+) -> Option<(freecs::Entity, freecs::Entity)> {
+    let resources = &game_world.resources;
+    if resources.actions_remaining == 0 || resources.valid_move_tiles.contains(&hovered_tile) {
+        return None;
     }
 
-    if !left_clicked {
-        return;
+    let selected = get_selected_unit(game_world)?;
+    if !unit_is_combatant(game_world, selected) {
+        return None;
+    }
+    let clicked_unit = get_unit_at_tile(game_world, hovered_tile)?;
+    let clicked_faction = game_world.get_unit(clicked_unit)?.faction;
+    if !factions_hostile(
+        resources.team_layout,
+        resources.current_faction,
+        clicked_faction,
+    ) {
+        return None;
     }
 
-    let Some(hovered_tile) = game_world.resources.hovered_tile else {
-        return;
-    };
+    let selected_hex = game_world.get_hex_position(selected)?.0;
+    (map_distance(&game_world.resources, selected_hex, hovered_tile) == 1)
+        .then_some((selected, clicked_unit))
+}
 
+fn player_click(
+    game_world: &mut GameWorld,
+    world: &mut World,
+    hovered_tile: HexCoord,
+    events: &mut GameEvents,
+) {
     let current_faction = game_world.resources.current_faction;
     let actions_remaining = game_world.resources.actions_remaining;
 
@@ -137,64 +231,74 @@ pub fn input_system(game_world: &mut GameWorld, world: &mut World, events: &mut
     let unit_at_tile = get_unit_at_tile(game_world, hovered_tile);
 
     if let Some(selected) = selected_unit {
-        if game_world
+        if game_world.get_hex_position(selected).map(|hex| hex.0) == Some(hovered_tile) {
+            let stack = get_units_at_tile(game_world, hovered_tile);
+            if let Some(position) = stack.iter().position(|&entity| entity == selected)
+                && stack.len() > 1
+            {
+                select_unit(game_world, stack[(position + 1) % stack.len()]);
+                return;
+            }
+        }
+
+        if actions_remaining > 0
+            && friendly_airfield_destinations(game_world, selected).contains(&hovered_tile)
+        {
+            airlift_unit_to(game_world, selected, hovered_tile);
+            game_world.resources.actions_remaining -= 1;
+            clear_selection(game_world);
+            return;
+        }
+
+        let move_cost = game_world
+            .get_unit(selected)
+            .map(|unit| move_action_cost(game_world, unit))
+            .unwrap_or(1);
+        let can_move_to = game_world
             .resources
             .valid_move_tiles
             .contains(&hovered_tile)
-            && actions_remaining > 0
-        {
+            || friendly_port_destinations(game_world, selected).contains(&hovered_tile);
+        if can_move_to && actions_remaining >= move_cost {
             move_unit_to(game_world, selected, hovered_tile);
+            let movement_points = game_world.resources.rules.movement_points;
             if let Some(unit) = game_world.get_unit(selected) {
                 let mut unit = *unit;
-                unit.has_moved = true;
+                unit.has_moved = !movement_points || unit.movement_left == 0;
                 game_world.set_unit(selected, unit);
             }
-            game_world.resources.actions_remaining -= 1;
+            game_world.resources.actions_remaining -= move_cost;
             clear_selection(game_world);
             return;
         }
 
-        if let Some(selected_unit_data) = game_world.get_unit(selected).copied()
-            && !selected_unit_data.has_moved
-            && actions_remaining > 0
-            && let Some(source_hex) = game_world.get_hex_position(selected).map(|h| h.0)
-            && is_unit_on_friendly_port(game_world, source_hex, current_faction)
-        {
-            let friendly_ports = get_friendly_ports(game_world, current_faction);
-            let is_dest_port = friendly_ports.contains(&hovered_tile);
-            let is_dest_unoccupied = unit_at_tile.is_none();
-            let is_different_port = source_hex != hovered_tile;
-
-            if is_dest_port && is_dest_unoccupied && is_different_port {
-                move_unit_to(game_world, selected, hovered_tile);
-                if let Some(unit) = game_world.get_unit(selected) {
-                    let mut unit = *unit;
-                    unit.has_moved = true;
-                    game_world.set_unit(selected, unit);
-                }
-                game_world.resources.actions_remaining -= 1;
-                clear_selection(game_world);
-                return;
-            }
-        }
-
         if let Some(clicked_unit) = unit_at_tile
             && let Some(clicked_unit_data) = game_world.get_unit(clicked_unit).copied()
         {
-            if clicked_unit_data.faction != current_faction && actions_remaining > 0 {
+            let team_layout = game_world.resources.team_layout;
+            if factions_hostile(team_layout, current_faction, clicked_unit_data.faction)
+                && actions_remaining > 0
+                && unit_is_combatant(game_world, selected)
+            {
                 let selected_hex = game_world.get_hex_position(selected).map(|h| h.0);
                 let is_adjacent = selected_hex
-                    .map(|hex| hex_distance(hex, hovered_tile) == 1)
+                    .map(|hex| map_distance(&game_world.resources, hex, hovered_tile) == 1)
                     .unwrap_or(false);
 
                 if is_adjacent {
-                    if let Some(result) = resolve_combat(game_world, world, selected, clicked_unit)
-                    {
+                    if let Some(result) = resolve_combat(game_world, selected, clicked_unit) {
                         events.combat_events.push(CombatEvent {
                             attacker_faction: result.attacker_faction,
                             defender_faction: result.defender_faction,
                             attacker_survived: result.attacker_survived,
                             defender_survived: result.defender_survived,
+                            location: result.location,
+                            attacker_location: result.attacker_location,
+                            attacker_losses: result.attacker_losses,
+                            defender_losses: result.defender_losses,
+                            wounded_unit: result.wounded_unit,
+                            general_killed: result.general_killed,
+                            modifiers: result.modifiers,
                         });
                         game_world.resources.actions_remaining -= 1;
                     }
@@ -213,9 +317,9 @@ pub fn input_system(game_world: &mut GameWorld, world: &mut World, events: &mut
                         selected,
                         source_hex,
                         hovered_tile,
-                        selected_unit_data.movement_range,
+                        unit_movement_budget(game_world, &selected_unit_data),
                     )
-                    && let Some(result) = merge_units(game_world, world, selected, clicked_unit)
+                    && let Some(result) = merge_units(game_world, selected, clicked_unit)
                 {
                     if result.soldiers_gained > 0 {
                         spawn_merge_popup(
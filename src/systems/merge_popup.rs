@@ -1,4 +1,5 @@
-use crate::ecs::{FLOATING_POPUP, FloatingPopup, GameWorld, TileType};
+use crate::ecs::{CombatEvent, FLOATING_POPUP, FloatingPopup, GameWorld, TileType};
+use crate::hex::{HexCoord, hex_to_world_position};
 use nightshade::prelude::*;
 
 const POPUP_LIFETIME: f32 = 1.5;
@@ -65,17 +66,56 @@ pub fn spawn_capture_popup(
     spawn_floating_popup(game_world, world, position, text, color, font_size);
 }
 
+pub fn spawn_morale_popup(
+    game_world: &mut GameWorld,
+    world: &mut World,
+    position: Vec3,
+    amount: i32,
+) {
+    let (text, color) = if amount > 0 {
+        (
+            format!("+{} morale", amount),
+            nalgebra_glm::vec4(0.4, 1.0, 0.5, 1.0),
+        )
+    } else {
+        (
+            format!("{} morale", amount),
+            nalgebra_glm::vec4(1.0, 0.45, 0.3, 1.0),
+        )
+    };
+    spawn_floating_popup(game_world, world, position, &text, color, 12000.0);
+}
+
+pub fn spawn_casualty_popups(game_world: &mut GameWorld, world: &mut World, event: &CombatEvent) {
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let color = nalgebra_glm::vec4(1.0, 0.25, 0.2, 1.0);
+    let casualties: [(HexCoord, i32); 2] = [
+        (event.attacker_location, event.attacker_losses),
+        (event.location, event.defender_losses),
+    ];
+    for (coord, losses) in casualties {
+        if losses <= 0 {
+            continue;
+        }
+        let position =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+        let text = format!("-{}", losses);
+        spawn_floating_popup(game_world, world, position, &text, color, 15000.0);
+    }
+}
+
 pub fn floating_popup_system(game_world: &mut GameWorld, world: &mut World, delta_time: f32) {
     let entities: Vec<_> = game_world.query_entities(FLOATING_POPUP).collect();
     let mut popups_to_remove = Vec::new();
-    let game_speed = game_world.resources.game_speed;
 
     for entity in entities {
         let Some(mut popup) = game_world.get_floating_popup(entity).copied() else {
             continue;
         };
 
-        popup.lifetime += delta_time * game_speed;
+        popup.lifetime += delta_time;
 
         if popup.lifetime > POPUP_LIFETIME {
             popups_to_remove.push((entity, popup.text_entity));
@@ -85,7 +125,7 @@ pub fn floating_popup_system(game_world: &mut GameWorld, world: &mut World, delt
         game_world.set_floating_popup(entity, popup);
 
         if let Some(transform) = world.get_local_transform_mut(popup.text_entity) {
-            transform.translation.y += delta_time * POPUP_FLOAT_SPEED * game_speed;
+            transform.translation.y += delta_time * POPUP_FLOAT_SPEED;
         }
         mark_local_transform_dirty(world, popup.text_entity);
 
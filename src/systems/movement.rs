@@ -1,26 +1,49 @@
 use crate::constants::UNIT_HEIGHT_OFFSET;
-use crate::ecs::{ENGINE_ENTITY, GameWorld, HEX_POSITION, MOVEMENT, UNIT, WORLD_POSITION};
-use crate::hex::{HexCoord, hex_to_world_position};
-use crate::systems::{UNIT_TEXT_HEIGHT_OFFSET, unit_radius_for_soldiers};
+use crate::ecs::{GameWorld, HEX_POSITION, MOVEMENT, TrailPoint, UNIT, WORLD_POSITION};
+use crate::hex::{HexCoord, hex_to_world_position, hex_unwrap_near};
+use crate::map::{map_tile_elevation, map_wrap_width};
+use crate::systems::unit_radius_for_soldiers;
 use nightshade::prelude::*;
+use std::collections::HashMap;
 
-pub fn movement_system(game_world: &mut GameWorld, world: &mut World, delta_time: f32) {
+pub const TRAIL_LIFETIME: f32 = 0.6;
+const TRAIL_POINT_SPACING: f32 = 4.0;
+
+pub fn movement_system(game_world: &mut GameWorld, delta_time: f32) {
     let hex_width = game_world.resources.hex_width;
     let hex_depth = game_world.resources.hex_depth;
-    let game_speed = game_world.resources.game_speed;
+    let orientation = game_world.resources.hex_orientation;
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    let wrap_width = map_wrap_width(&game_world.resources.generated_map);
+
+    let generated = &game_world.resources.generated_map;
+    let segment_elevations: HashMap<freecs::Entity, (f32, f32)> = game_world
+        .query_entities(MOVEMENT)
+        .filter_map(|entity| {
+            let movement = game_world.get_movement(entity)?;
+            let from_hex = movement.path.get(movement.current_segment)?;
+            let to_hex = movement.path.get(movement.current_segment + 1)?;
+            Some((
+                entity,
+                (
+                    map_tile_elevation(generated, *from_hex),
+                    map_tile_elevation(generated, *to_hex),
+                ),
+            ))
+        })
+        .collect();
 
     let mut completed_entities: Vec<(freecs::Entity, HexCoord)> = Vec::new();
     let mut segment_completed: Vec<(freecs::Entity, HexCoord, f32)> = Vec::new();
-    let mut transform_updates = Vec::new();
 
     game_world
         .query_mut()
-        .with(MOVEMENT | WORLD_POSITION | ENGINE_ENTITY | UNIT | HEX_POSITION)
+        .with(MOVEMENT | WORLD_POSITION | UNIT | HEX_POSITION)
         .iter(|entity, table, index| {
             let movement = &mut table.movement[index];
             let current_hex = table.hex_position[index].0;
             let soldiers = table.unit[index].soldiers;
-            let radius = unit_radius_for_soldiers(soldiers);
+            let radius = unit_radius_for_soldiers(soldiers, max_soldiers);
 
             if movement.path.is_empty() || movement.current_segment >= movement.path.len() - 1 {
                 let final_hex = movement.path.last().copied().unwrap_or(current_hex);
@@ -30,23 +53,37 @@ pub fn movement_system(game_world: &mut GameWorld, world: &mut World, delta_time
 
             let from_hex = movement.path[movement.current_segment];
             let to_hex = movement.path[movement.current_segment + 1];
+            let to_visual = hex_unwrap_near(to_hex, from_hex, wrap_width);
+
+            let from_world = hex_to_world_position(
+                from_hex.column,
+                from_hex.row,
+                hex_width,
+                hex_depth,
+                orientation,
+            );
+            let to_world = hex_to_world_position(
+                to_visual.column,
+                to_visual.row,
+                hex_width,
+                hex_depth,
+                orientation,
+            );
 
-            let from_world =
-                hex_to_world_position(from_hex.column, from_hex.row, hex_width, hex_depth);
-            let to_world = hex_to_world_position(to_hex.column, to_hex.row, hex_width, hex_depth);
-
+            let (from_elevation, to_elevation) =
+                segment_elevations.get(&entity).copied().unwrap_or_default();
             let from_position = nalgebra_glm::vec3(
                 from_world.x,
-                from_world.y + radius + UNIT_HEIGHT_OFFSET,
+                from_world.y + from_elevation + radius + UNIT_HEIGHT_OFFSET,
                 from_world.z,
             );
             let to_position = nalgebra_glm::vec3(
                 to_world.x,
-                to_world.y + radius + UNIT_HEIGHT_OFFSET,
+                to_world.y + to_elevation + radius + UNIT_HEIGHT_OFFSET,
                 to_world.z,
             );
 
-            let new_progress = movement.segment_progress + delta_time * movement.speed * game_speed;
+            let new_progress = movement.segment_progress + delta_time * movement.speed;
             let t = new_progress.clamp(0.0, 1.0);
             let smooth_t = t * t * (3.0 - 2.0 * t);
 
@@ -55,38 +92,30 @@ pub fn movement_system(game_world: &mut GameWorld, world: &mut World, delta_time
             movement.segment_progress = new_progress;
             table.world_position[index].0 = current_position;
 
-            let text_entity = table.unit[index].text_entity;
-            transform_updates.push((
-                entity,
-                table.engine_entity[index],
-                current_position,
-                text_entity,
-                radius,
-            ));
+            for point in &mut movement.trail {
+                point.age += delta_time;
+            }
+            movement.trail.retain(|point| point.age < TRAIL_LIFETIME);
+            let needs_point = movement
+                .trail
+                .last()
+                .map(|point| {
+                    nalgebra_glm::distance(&point.position, &current_position)
+                        >= TRAIL_POINT_SPACING
+                })
+                .unwrap_or(true);
+            if needs_point {
+                movement.trail.push(TrailPoint {
+                    position: current_position,
+                    age: 0.0,
+                });
+            }
 
             if new_progress >= 1.0 {
                 segment_completed.push((entity, to_hex, new_progress - 1.0));
             }
         });
 
-    for (_, engine_entity, position, text_entity, radius) in transform_updates {
-        if let Some(transform) = world.get_local_transform_mut(engine_entity.0) {
-            transform.translation = position;
-        }
-        mark_local_transform_dirty(world, engine_entity.0);
-
-        if let Some(text_ent) = text_entity {
-            if let Some(text_transform) = world.get_local_transform_mut(text_ent) {
-                text_transform.translation = nalgebra_glm::vec3(
-                    position.x,
-                    position.y + radius + UNIT_TEXT_HEIGHT_OFFSET,
-                    position.z,
-                );
-            }
-            mark_local_transform_dirty(world, text_ent);
-        }
-    }
-
     for (entity, reached_hex, excess_progress) in segment_completed {
         let (final_hex, is_complete) = {
             if let Some(movement) = game_world.get_movement_mut(entity) {
@@ -0,0 +1,119 @@
+use crate::ecs::{GameWorld, MOVEMENT, UNIT, faction_color};
+use crate::hex::hex_to_world_position;
+use crate::rendering::generate_hex_outline_with_color;
+use crate::systems::movement::TRAIL_LIFETIME;
+use crate::systems::{sailing_heading, unit_radius_for_soldiers};
+use nightshade::ecs::world::components::Line;
+use nightshade::prelude::*;
+
+const GHOST_OUTLINE_HEIGHT: f32 = 6.0;
+const GHOST_NODE_SCALE: f32 = 0.5;
+const GHOST_BASE_ALPHA: f32 = 0.8;
+const GHOST_ALPHA_FALLOFF: f32 = 0.2;
+const WAKE_COLOR: [f32; 3] = [0.75, 0.9, 1.0];
+const MAST_HEIGHT_SCALE: f32 = 1.8;
+const SAIL_WIDTH_SCALE: f32 = 0.9;
+
+fn sail_lines(hull: Vec3, radius: f32, heading: f32, color: [f32; 4]) -> Vec<Line> {
+    let forward = nalgebra_glm::vec3(heading.cos(), 0.0, -heading.sin());
+    let mast_base = hull;
+    let mast_top = hull + nalgebra_glm::vec3(0.0, radius * MAST_HEIGHT_SCALE, 0.0);
+    let boom =
+        hull + nalgebra_glm::vec3(0.0, radius * 0.4, 0.0) + forward * (radius * SAIL_WIDTH_SCALE);
+    let boom_base = hull + nalgebra_glm::vec3(0.0, radius * 0.4, 0.0);
+    let sail_color = nalgebra_glm::vec4(color[0], color[1], color[2], 1.0);
+    [(mast_base, mast_top), (mast_top, boom), (boom, boom_base)]
+        .into_iter()
+        .map(|(start, end)| Line {
+            start,
+            end,
+            color: sail_color,
+        })
+        .collect()
+}
+
+pub fn movement_trail_system(
+    game_world: &GameWorld,
+    world: &mut World,
+    movement_trail_entity: Option<Entity>,
+) {
+    let Some(entity) = movement_trail_entity else {
+        return;
+    };
+
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    let mut lines: Vec<Line> = Vec::new();
+
+    for unit_entity in game_world.query_entities(MOVEMENT | UNIT) {
+        let Some(movement) = game_world.get_movement(unit_entity) else {
+            continue;
+        };
+        let Some(unit) = game_world.get_unit(unit_entity) else {
+            continue;
+        };
+        let color = faction_color(unit.faction);
+        let heading = sailing_heading(game_world, movement);
+        let trail_color = if heading.is_some() {
+            WAKE_COLOR
+        } else {
+            [color[0], color[1], color[2]]
+        };
+
+        for pair in movement.trail.windows(2) {
+            let fade = 1.0 - pair[0].age / TRAIL_LIFETIME;
+            lines.push(Line {
+                start: pair[0].position,
+                end: pair[1].position,
+                color: nalgebra_glm::vec4(
+                    trail_color[0],
+                    trail_color[1],
+                    trail_color[2],
+                    fade.clamp(0.0, 1.0),
+                ),
+            });
+        }
+
+        if let Some(heading) = heading
+            && let Some(position) = game_world.get_world_position(unit_entity)
+        {
+            let radius = unit_radius_for_soldiers(unit.soldiers, max_soldiers);
+            lines.extend(sail_lines(position.0, radius, heading, color));
+        }
+
+        let upcoming_nodes = movement.path.iter().skip(movement.current_segment + 1);
+        for (step, coord) in upcoming_nodes.enumerate() {
+            let alpha = (GHOST_BASE_ALPHA - step as f32 * GHOST_ALPHA_FALLOFF).max(0.2);
+            let center =
+                hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+            lines.extend(generate_hex_outline_with_color(
+                center,
+                hex_width * GHOST_NODE_SCALE,
+                hex_depth * GHOST_NODE_SCALE,
+                orientation,
+                GHOST_OUTLINE_HEIGHT,
+                nalgebra_glm::vec4(color[0], color[1], color[2], alpha),
+            ));
+        }
+    }
+
+    let has_lines = !lines.is_empty();
+    let was_visible = world
+        .get_visibility(entity)
+        .map(|visibility| visibility.visible)
+        .unwrap_or(false);
+
+    if !has_lines && !was_visible {
+        return;
+    }
+
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = lines;
+        lines_component.mark_dirty();
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = has_lines;
+    }
+}
@@ -0,0 +1,69 @@
+use crate::constants::{PILLAGE_MORALE, PILLAGE_TURNS};
+use crate::ecs::{
+    Faction, GameEvents, GameWorld, HEX_POSITION, PillageEvent, TILE, Tile, TileType,
+    modify_faction_morale,
+};
+use crate::teams::factions_hostile;
+
+pub fn tile_is_pillaged(tile: &Tile, turn_number: u32) -> bool {
+    tile.pillaged_until.is_some_and(|until| turn_number < until)
+}
+
+fn pillage_target_owner(game_world: &GameWorld, unit_entity: freecs::Entity) -> Option<Faction> {
+    let unit = game_world.get_unit(unit_entity)?;
+    if unit.has_moved || unit.faction != game_world.resources.current_faction {
+        return None;
+    }
+    let target = game_world.get_hex_position(unit_entity)?.0;
+
+    let tile = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| game_world.get_hex_position(entity).map(|h| h.0) == Some(target))
+        .and_then(|entity| game_world.get_tile(entity))?;
+    let owner = tile.faction?;
+    let pillageable = matches!(tile.tile_type, TileType::City | TileType::Port)
+        && !tile_is_pillaged(tile, game_world.resources.turn_number)
+        && factions_hostile(game_world.resources.team_layout, unit.faction, owner);
+    pillageable.then_some(owner)
+}
+
+pub fn pillage_tile(
+    game_world: &mut GameWorld,
+    unit_entity: freecs::Entity,
+    events: &mut GameEvents,
+) -> bool {
+    let Some(victim) = pillage_target_owner(game_world, unit_entity) else {
+        return false;
+    };
+    let Some(target) = game_world.get_hex_position(unit_entity).map(|h| h.0) else {
+        return false;
+    };
+    let Some(faction) = game_world.get_unit(unit_entity).map(|unit| unit.faction) else {
+        return false;
+    };
+
+    let turn_number = game_world.resources.turn_number;
+    let tile_entity = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| game_world.get_hex_position(entity).map(|h| h.0) == Some(target));
+    let Some(tile) = tile_entity.and_then(|entity| game_world.get_tile_mut(entity)) else {
+        return false;
+    };
+    tile.pillaged_until = Some(turn_number + PILLAGE_TURNS);
+    tile.growth = 0;
+    let tile_type = tile.tile_type;
+
+    if let Some(unit) = game_world.get_unit_mut(unit_entity) {
+        unit.has_moved = true;
+        unit.movement_left = 0;
+    }
+    modify_faction_morale(&mut game_world.resources, faction, PILLAGE_MORALE);
+    modify_faction_morale(&mut game_world.resources, victim, -PILLAGE_MORALE);
+    events.pillage_events.push(PillageEvent {
+        faction,
+        victim,
+        location: target,
+        tile_type,
+    });
+    true
+}
@@ -0,0 +1,116 @@
+use crate::ecs::GameWorld;
+use crate::hex::hex_to_world_position;
+use crate::systems::find_sea_path;
+use nightshade::ecs::world::components::Line;
+use nightshade::prelude::*;
+
+const SHIP_ICON_HEIGHT: f32 = 40.0;
+const SHIP_ICON_SCALE: f32 = 0.2;
+const SEA_ROUTE_HEIGHT: f32 = 8.0;
+const SHIP_ICON_COLOR: [f32; 4] = [0.4, 0.8, 1.0, 1.0];
+const SEA_ROUTE_COLOR: [f32; 4] = [0.3, 0.9, 1.0, 1.0];
+
+fn color(rgba: [f32; 4]) -> Vec4 {
+    nalgebra_glm::vec4(rgba[0], rgba[1], rgba[2], rgba[3])
+}
+
+fn ship_icon_lines(center: Vec3, size: f32) -> Vec<Line> {
+    let point = |x: f32, y: f32| nalgebra_glm::vec3(center.x + x * size, y, center.z);
+    let deck = SHIP_ICON_HEIGHT;
+    let keel = deck - size * 0.4;
+    let mast_top = deck + size * 1.2;
+    let boom = deck + size * 0.3;
+    let segments = [
+        (point(-1.0, deck), point(1.0, deck)),
+        (point(-0.6, keel), point(0.6, keel)),
+        (point(-1.0, deck), point(-0.6, keel)),
+        (point(1.0, deck), point(0.6, keel)),
+        (point(0.0, deck), point(0.0, mast_top)),
+        (point(0.0, mast_top), point(0.8, boom)),
+        (point(0.8, boom), point(0.0, boom)),
+    ];
+    segments
+        .into_iter()
+        .map(|(start, end)| Line {
+            start,
+            end,
+            color: color(SHIP_ICON_COLOR),
+        })
+        .collect()
+}
+
+pub fn port_route_system(
+    game_world: &mut GameWorld,
+    world: &mut World,
+    port_route_entity: Option<Entity>,
+) {
+    let Some(entity) = port_route_entity else {
+        return;
+    };
+
+    let hovered_tile = game_world
+        .resources
+        .hovered_tile
+        .filter(|coord| game_world.resources.port_route_tiles.contains(coord));
+    let route = game_world
+        .resources
+        .port_route_origin
+        .map(|origin| (origin, hovered_tile));
+
+    if route == game_world.resources.previous_port_route {
+        return;
+    }
+    game_world.resources.previous_port_route = route;
+
+    let Some((origin, hovered_tile)) = route else {
+        if let Some(visibility) = world.get_visibility_mut(entity) {
+            visibility.visible = false;
+        }
+        return;
+    };
+
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let mut lines = Vec::new();
+
+    for coord in &game_world.resources.port_route_tiles {
+        let center =
+            hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+        lines.extend(ship_icon_lines(center, hex_width * SHIP_ICON_SCALE));
+    }
+
+    if let Some(destination) = hovered_tile
+        && let Some(path) = find_sea_path(game_world, origin, destination)
+    {
+        for pair in path.windows(2) {
+            let start = hex_to_world_position(
+                pair[0].column,
+                pair[0].row,
+                hex_width,
+                hex_depth,
+                orientation,
+            );
+            let end = hex_to_world_position(
+                pair[1].column,
+                pair[1].row,
+                hex_width,
+                hex_depth,
+                orientation,
+            );
+            lines.push(Line {
+                start: nalgebra_glm::vec3(start.x, SEA_ROUTE_HEIGHT, start.z),
+                end: nalgebra_glm::vec3(end.x, SEA_ROUTE_HEIGHT, end.z),
+                color: color(SEA_ROUTE_COLOR),
+            });
+        }
+    }
+
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = lines;
+        lines_component.mark_dirty();
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = true;
+    }
+}
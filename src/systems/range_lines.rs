@@ -35,6 +35,7 @@ pub fn range_lines_system(
             &valid_coords,
             game_world.resources.hex_width,
             game_world.resources.hex_depth,
+            game_world.resources.hex_orientation,
             nalgebra_glm::vec4(1.0, 1.0, 0.0, 1.0),
         );
 
@@ -1,16 +1,38 @@
-use crate::constants::{CITY_REINFORCEMENT, MAX_SOLDIERS};
+use crate::constants::{
+    BRUTAL_REINFORCEMENT_PERCENT, BRUTAL_STARTING_SOLDIERS, MAX_UNITS_PER_HEX, SWAMP_ATTRITION,
+    WINTER_ATTRITION,
+};
 use crate::ecs::{
-    Entity, Faction, GameEvents, GameWorld, HEX_POSITION, ReinforcementEvent, TILE, TileType, UNIT,
-    faction_index,
+    AttritionCause, AttritionEvent, Difficulty, Entity, Faction, GameEvents, GameResources,
+    GameWorld, HEX_POSITION, LeaderAbility, ReinforcementEvent, TILE, TileType, UNIT, UnitKind,
+    faction_capital, faction_difficulty, faction_is_ai, map_distance,
+};
+use crate::hex::HexCoord;
+use crate::rng::game_rng_range;
+use crate::season::{Season, season_for_turn};
+use crate::selection::get_units_at_tile;
+use crate::systems::{
+    city_tier_reinforcement, leader_ability_active, spawn_supply_wagon, spawn_unit,
+    supply_wagon_at, tile_is_pillaged,
 };
-use crate::hex::{HexCoord, hex_distance};
-use crate::map::CAPITAL_POSITIONS;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub struct PendingSpawn {
     pub coord: HexCoord,
     pub faction: Faction,
     pub soldiers: i32,
+    pub kind: UnitKind,
+}
+
+pub fn spawn_pending(game_world: &mut GameWorld, pending: PendingSpawn) {
+    match pending.kind {
+        UnitKind::Supply => {
+            spawn_supply_wagon(game_world, pending.coord, pending.faction, pending.soldiers);
+        }
+        _ => {
+            spawn_unit(game_world, pending.coord, pending.faction, pending.soldiers);
+        }
+    }
 }
 
 fn tile_type_name(tile_type: TileType) -> &'static str {
@@ -22,10 +44,116 @@ fn tile_type_name(tile_type: TileType) -> &'static str {
     }
 }
 
-fn get_capital_coord(faction: Faction) -> HexCoord {
-    let index = faction_index(faction);
-    let (col, row, _) = CAPITAL_POSITIONS[index];
-    HexCoord { column: col, row }
+fn difficulty_reinforcement(resources: &GameResources, faction: Faction, soldiers: i32) -> i32 {
+    if faction_difficulty(resources, faction) == Difficulty::Brutal
+        && faction_is_ai(resources, faction)
+    {
+        soldiers * BRUTAL_REINFORCEMENT_PERCENT / 100
+    } else {
+        soldiers
+    }
+}
+
+pub fn apply_starting_bonus(game_world: &mut GameWorld) {
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    let units: Vec<Entity> = game_world.query_entities(UNIT).collect();
+    for entity in units {
+        let Some(unit) = game_world.get_unit(entity) else {
+            continue;
+        };
+        if faction_difficulty(&game_world.resources, unit.faction) != Difficulty::Brutal
+            || !faction_is_ai(&game_world.resources, unit.faction)
+        {
+            continue;
+        }
+        let mut unit = *unit;
+        unit.soldiers = (unit.soldiers + BRUTAL_STARTING_SOLDIERS).min(max_soldiers);
+        game_world.set_unit(entity, unit);
+    }
+}
+
+fn winter_attrition(
+    game_world: &mut GameWorld,
+    events: &mut GameEvents,
+    tile_info: &HashMap<HexCoord, (TileType, Option<Faction>)>,
+) {
+    if season_for_turn(game_world.resources.turn_number) != Season::Winter {
+        return;
+    }
+
+    let current_faction = game_world.resources.current_faction;
+    let mut total_lost = 0;
+    let units: Vec<(HexCoord, Entity)> = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter_map(|entity| Some((game_world.get_hex_position(entity)?.0, entity)))
+        .collect();
+    for (coord, unit_entity) in units {
+        let sheltered = matches!(
+            tile_info.get(&coord),
+            Some((TileType::City | TileType::Capital, Some(owner))) if *owner == current_faction
+        );
+        if sheltered {
+            continue;
+        }
+        if let Some(unit) = game_world.get_unit(unit_entity)
+            && unit.faction == current_faction
+        {
+            let mut unit = *unit;
+            let lost = WINTER_ATTRITION.min(unit.soldiers - 1);
+            if lost <= 0 {
+                continue;
+            }
+            unit.soldiers -= lost;
+            game_world.set_unit(unit_entity, unit);
+            total_lost += lost;
+        }
+    }
+
+    if total_lost > 0 {
+        events.attrition_events.push(AttritionEvent {
+            faction: current_faction,
+            soldiers: total_lost,
+            cause: AttritionCause::Winter,
+        });
+    }
+}
+
+fn swamp_attrition(
+    game_world: &mut GameWorld,
+    events: &mut GameEvents,
+    tile_info: &HashMap<HexCoord, (TileType, Option<Faction>)>,
+) {
+    let current_faction = game_world.resources.current_faction;
+    let mut total_lost = 0;
+    let units: Vec<(HexCoord, Entity)> = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter_map(|entity| Some((game_world.get_hex_position(entity)?.0, entity)))
+        .collect();
+    for (coord, unit_entity) in units {
+        if !matches!(tile_info.get(&coord), Some((TileType::Swamp, _))) {
+            continue;
+        }
+        if let Some(unit) = game_world.get_unit(unit_entity)
+            && unit.faction == current_faction
+        {
+            let mut unit = *unit;
+            let lost = SWAMP_ATTRITION.min(unit.soldiers - 1);
+            if lost <= 0 {
+                continue;
+            }
+            unit.soldiers -= lost;
+            game_world.set_unit(unit_entity, unit);
+            total_lost += lost;
+        }
+    }
+
+    if total_lost > 0 {
+        events.attrition_events.push(AttritionEvent {
+            faction: current_faction,
+            soldiers: total_lost,
+            cause: AttritionCause::Swamp,
+        });
+    }
 }
 
 pub fn reinforcement_system(
@@ -33,6 +161,7 @@ pub fn reinforcement_system(
     events: &mut GameEvents,
 ) -> Vec<PendingSpawn> {
     let current_faction = game_world.resources.current_faction;
+    let rules = game_world.resources.rules;
     let mut pending_spawns = Vec::new();
 
     let tile_info: HashMap<HexCoord, (TileType, Option<Faction>)> = game_world
@@ -44,6 +173,25 @@ pub fn reinforcement_system(
         })
         .collect();
 
+    let city_tiers: HashMap<HexCoord, i32> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            Some((coord, city_tier_reinforcement(tile)))
+        })
+        .collect();
+
+    let turn_number = game_world.resources.turn_number;
+    let pillaged: HashSet<HexCoord> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            tile_is_pillaged(tile, turn_number).then_some(coord)
+        })
+        .collect();
+
     let unit_positions: HashMap<HexCoord, Entity> = game_world
         .query_entities(HEX_POSITION | UNIT)
         .filter_map(|entity| {
@@ -52,22 +200,47 @@ pub fn reinforcement_system(
         })
         .collect();
 
+    winter_attrition(game_world, events, &tile_info);
+    swamp_attrition(game_world, events, &tile_info);
+
+    let capital_coord = faction_capital(&game_world.resources, current_faction);
+    let capital_wagon = supply_wagon_at(game_world, capital_coord, current_faction);
+    let supply_wagons = rules.supply_wagons
+        && tile_info
+            .get(&capital_coord)
+            .is_some_and(|(_, owner)| *owner == Some(current_faction))
+        && (capital_wagon.is_some()
+            || get_units_at_tile(game_world, capital_coord).len() < MAX_UNITS_PER_HEX);
+    let mut wagon_soldiers = 0;
+
     for (&coord, &(tile_type, tile_faction)) in &tile_info {
-        if tile_faction != Some(current_faction) {
+        if tile_faction != Some(current_faction) || pillaged.contains(&coord) {
             continue;
         }
 
         let reinforcement = match tile_type {
-            TileType::City | TileType::Capital => CITY_REINFORCEMENT,
+            TileType::City | TileType::Capital => rules.city_reinforcement + city_tiers[&coord],
             _ => continue,
         };
+        let reinforcement = if game_world.resources.bumper_harvest == Some(coord) {
+            reinforcement * 2
+        } else {
+            reinforcement
+        };
+        let reinforcement =
+            difficulty_reinforcement(&game_world.resources, current_faction, reinforcement);
+
+        if supply_wagons {
+            wagon_soldiers += reinforcement;
+            continue;
+        }
 
         if let Some(&unit_entity) = unit_positions.get(&coord) {
             if let Some(unit) = game_world.get_unit(unit_entity)
                 && unit.faction == current_faction
             {
                 let mut unit = *unit;
-                unit.soldiers = (unit.soldiers + reinforcement).min(MAX_SOLDIERS);
+                unit.soldiers = (unit.soldiers + reinforcement).min(rules.max_soldiers);
                 game_world.set_unit(unit_entity, unit);
                 events.reinforcement_events.push(ReinforcementEvent {
                     faction: current_faction,
@@ -80,6 +253,7 @@ pub fn reinforcement_system(
                 coord,
                 faction: current_faction,
                 soldiers: reinforcement,
+                kind: UnitKind::Infantry,
             });
             events.reinforcement_events.push(ReinforcementEvent {
                 faction: current_faction,
@@ -89,21 +263,47 @@ pub fn reinforcement_system(
         }
     }
 
+    if wagon_soldiers > 0 {
+        if let Some(wagon_entity) = capital_wagon
+            && let Some(unit) = game_world.get_unit_mut(wagon_entity)
+        {
+            unit.soldiers = (unit.soldiers + wagon_soldiers).min(rules.max_soldiers);
+        } else {
+            pending_spawns.push(PendingSpawn {
+                coord: capital_coord,
+                faction: current_faction,
+                soldiers: wagon_soldiers.min(rules.max_soldiers),
+                kind: UnitKind::Supply,
+            });
+        }
+        events.reinforcement_events.push(ReinforcementEvent {
+            faction: current_faction,
+            soldiers: wagon_soldiers,
+            location_name: "supply wagon".to_string(),
+        });
+    }
+
     for (&coord, &(tile_type, tile_faction)) in &tile_info {
         if tile_type != TileType::Port {
             continue;
         }
 
-        if tile_faction != Some(current_faction) {
+        if tile_faction != Some(current_faction) || pillaged.contains(&coord) {
             continue;
         }
 
-        let port_reinforcement = 1 + (game_world.resources.rng_seed as i32 % 3);
-        game_world.resources.rng_seed = game_world
-            .resources
-            .rng_seed
-            .wrapping_mul(1103515245)
-            .wrapping_add(12345);
+        let port_reinforcement = 1 + game_rng_range(&mut game_world.resources.game_rng, 3) as i32;
+        let port_reinforcement = if leader_ability_active(
+            &game_world.resources,
+            current_faction,
+            LeaderAbility::CallOfTheTides,
+        ) {
+            port_reinforcement * 2
+        } else {
+            port_reinforcement
+        };
+        let port_reinforcement =
+            difficulty_reinforcement(&game_world.resources, current_faction, port_reinforcement);
 
         let mut closest_unit: Option<(Entity, i32)> = None;
         for (&unit_coord, &unit_entity) in &unit_positions {
@@ -113,7 +313,7 @@ pub fn reinforcement_system(
                 continue;
             }
 
-            let distance = hex_distance(coord, unit_coord);
+            let distance = map_distance(&game_world.resources, coord, unit_coord);
             if distance <= 3 && (closest_unit.is_none() || distance < closest_unit.unwrap().1) {
                 closest_unit = Some((unit_entity, distance));
             }
@@ -123,7 +323,7 @@ pub fn reinforcement_system(
             && let Some(unit) = game_world.get_unit(unit_entity)
         {
             let mut unit = *unit;
-            unit.soldiers = (unit.soldiers + port_reinforcement).min(MAX_SOLDIERS);
+            unit.soldiers = (unit.soldiers + port_reinforcement).min(rules.max_soldiers);
             game_world.set_unit(unit_entity, unit);
             events.reinforcement_events.push(ReinforcementEvent {
                 faction: current_faction,
@@ -139,17 +339,19 @@ pub fn reinforcement_system(
             *faction == Some(current_faction) && *tile_type != TileType::Sea
         })
         .count();
-    let territory_bonus = (territory_count / 10) as i32;
+    let territory_bonus = difficulty_reinforcement(
+        &game_world.resources,
+        current_faction,
+        (territory_count / 10) as i32,
+    );
 
     if territory_bonus > 0 {
-        let capital_coord = get_capital_coord(current_faction);
-
         if let Some(&unit_entity) = unit_positions.get(&capital_coord) {
             if let Some(unit) = game_world.get_unit(unit_entity)
                 && unit.faction == current_faction
             {
                 let mut unit = *unit;
-                unit.soldiers = (unit.soldiers + territory_bonus).min(MAX_SOLDIERS);
+                unit.soldiers = (unit.soldiers + territory_bonus).min(rules.max_soldiers);
                 game_world.set_unit(unit_entity, unit);
                 events.reinforcement_events.push(ReinforcementEvent {
                     faction: current_faction,
@@ -166,6 +368,7 @@ pub fn reinforcement_system(
                 coord: capital_coord,
                 faction: current_faction,
                 soldiers: territory_bonus.max(1),
+                kind: UnitKind::Infantry,
             });
             events.reinforcement_events.push(ReinforcementEvent {
                 faction: current_faction,
@@ -0,0 +1,98 @@
+use crate::constants::{RUIN_MORALE, RUIN_SOLDIERS};
+use crate::ecs::{
+    Faction, GameEvents, GameWorld, HEX_POSITION, RuinBonus, RuinClaimedEvent, TILE, UNIT,
+    modify_faction_morale,
+};
+use crate::hex::HexCoord;
+use crate::labels::{label_release, label_release_system};
+use crate::map::TileFeature;
+use nightshade::prelude::*;
+use std::collections::HashMap;
+
+pub fn ruin_bonus_name(bonus: RuinBonus) -> &'static str {
+    match bonus {
+        RuinBonus::Soldiers => "recruits",
+        RuinBonus::Morale => "relics",
+    }
+}
+
+pub fn ruin_system(game_world: &mut GameWorld, events: &mut GameEvents) {
+    let mut unit_positions: HashMap<HexCoord, (freecs::Entity, Faction)> = HashMap::new();
+    for entity in game_world.query_entities(HEX_POSITION | UNIT) {
+        if let (Some(position), Some(unit)) = (
+            game_world.get_hex_position(entity),
+            game_world.get_unit(entity),
+        ) {
+            unit_positions
+                .entry(position.0)
+                .or_insert((entity, unit.faction));
+        }
+    }
+
+    let claims: Vec<(freecs::Entity, HexCoord, RuinBonus)> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let bonus = game_world.get_tile(entity)?.ruin?;
+            unit_positions
+                .contains_key(&coord)
+                .then_some((entity, coord, bonus))
+        })
+        .collect();
+
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    for (tile_entity, coord, bonus) in claims {
+        let (unit_entity, faction) = unit_positions[&coord];
+        let amount = match bonus {
+            RuinBonus::Soldiers => {
+                let Some(unit) = game_world.get_unit_mut(unit_entity) else {
+                    continue;
+                };
+                let gained = RUIN_SOLDIERS.min(max_soldiers - unit.soldiers).max(0);
+                unit.soldiers += gained;
+                gained
+            }
+            RuinBonus::Morale => {
+                modify_faction_morale(&mut game_world.resources, faction, RUIN_MORALE);
+                RUIN_MORALE
+            }
+        };
+
+        if let Some(tile) = game_world.get_tile_mut(tile_entity) {
+            tile.ruin = None;
+        }
+        if matches!(
+            game_world.resources.generated_map.features.get(&coord),
+            Some(TileFeature::Ruin(_))
+        ) {
+            game_world.resources.generated_map.features.remove(&coord);
+        }
+        events.ruin_events.push(RuinClaimedEvent {
+            faction,
+            location: coord,
+            bonus,
+            amount,
+        });
+    }
+}
+
+pub fn ruin_label_system(
+    game_world: &mut GameWorld,
+    world: &mut World,
+    label_entities: &mut Vec<(HexCoord, Entity)>,
+) {
+    let resources = &mut game_world.resources;
+    let features = &resources.generated_map.features;
+    let labels = &mut resources.labels;
+    let count = label_entities.len();
+    label_entities.retain(|(coord, entity)| {
+        let standing = matches!(features.get(coord), Some(TileFeature::Ruin(_)));
+        if !standing {
+            label_release(labels, *entity);
+        }
+        standing
+    });
+    if label_entities.len() != count {
+        label_release_system(labels, world);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::constants::{MAX_UNITS_PER_HEX, SCOUT_MOVEMENT_RANGE, SCOUT_RECRUIT_COST};
+use crate::ecs::{Faction, GameWorld, HEX_POSITION, TILE, TileType, UNIT, UnitKind};
+use crate::hex::HexCoord;
+use crate::selection::get_units_at_tile;
+use crate::systems::spawn_unit;
+
+pub fn scout_count(game_world: &GameWorld, faction: Faction) -> usize {
+    game_world
+        .query_entities(UNIT)
+        .filter_map(|entity| game_world.get_unit(entity))
+        .filter(|unit| unit.faction == faction && unit.kind == UnitKind::Scout)
+        .count()
+}
+
+pub fn spawn_scout(
+    game_world: &mut GameWorld,
+    coord: HexCoord,
+    faction: Faction,
+) -> freecs::Entity {
+    let entity = spawn_unit(game_world, coord, faction, SCOUT_RECRUIT_COST);
+    if let Some(unit) = game_world.get_unit_mut(entity) {
+        unit.kind = UnitKind::Scout;
+        unit.movement_range = SCOUT_MOVEMENT_RANGE;
+        unit.movement_left = SCOUT_MOVEMENT_RANGE;
+        unit.has_moved = true;
+    }
+    entity
+}
+
+pub fn can_recruit_scout(game_world: &GameWorld, unit_entity: freecs::Entity) -> bool {
+    let Some(unit) = game_world.get_unit(unit_entity) else {
+        return false;
+    };
+    if unit.has_moved
+        || unit.kind != UnitKind::Infantry
+        || unit.faction != game_world.resources.current_faction
+        || unit.soldiers <= SCOUT_RECRUIT_COST
+    {
+        return false;
+    }
+    let Some(coord) = game_world.get_hex_position(unit_entity).map(|hex| hex.0) else {
+        return false;
+    };
+    if get_units_at_tile(game_world, coord).len() >= MAX_UNITS_PER_HEX {
+        return false;
+    }
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| game_world.get_hex_position(entity).map(|hex| hex.0) == Some(coord))
+        .and_then(|entity| game_world.get_tile(entity))
+        .is_some_and(|tile| {
+            matches!(tile.tile_type, TileType::City | TileType::Capital)
+                && tile.faction == Some(unit.faction)
+        })
+}
+
+pub fn recruit_scout(game_world: &mut GameWorld, unit_entity: freecs::Entity) -> bool {
+    if !can_recruit_scout(game_world, unit_entity) {
+        return false;
+    }
+    let Some(coord) = game_world.get_hex_position(unit_entity).map(|hex| hex.0) else {
+        return false;
+    };
+    let Some(unit) = game_world.get_unit_mut(unit_entity) else {
+        return false;
+    };
+    unit.soldiers -= SCOUT_RECRUIT_COST;
+    unit.has_moved = true;
+    let faction = unit.faction;
+    spawn_scout(game_world, coord, faction);
+    true
+}
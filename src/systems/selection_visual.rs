@@ -1,27 +1,94 @@
-use crate::ecs::{GameWorld, faction_color};
-use crate::systems::UNIT_SELECTED_COLOR;
+use crate::ecs::GameWorld;
+use crate::systems::unit_radius_for_soldiers;
+use nightshade::ecs::world::components::Line;
 use nightshade::prelude::*;
 
-pub fn selection_visual_system(game_world: &GameWorld, world: &mut World) {
-    let current_selected: Option<freecs::Entity> = game_world.query_selected().next();
-    let previous_selected = game_world.resources.previous_selected_unit;
+const SELECTION_OUTLINE_COLOR: [f32; 4] = [4.0, 4.0, 4.0, 1.0];
+const SELECTION_OUTLINE_SCALES: [f32; 3] = [1.06, 1.1, 1.14];
+const SELECTION_OUTLINE_SEGMENTS: usize = 48;
 
-    if current_selected == previous_selected {
-        return;
-    }
+fn camera_position(world: &World) -> Option<Vec3> {
+    let camera_entity = world.resources.active_camera?;
+    let global_transform = world.get_global_transform(camera_entity)?;
+    Some(global_transform.0.column(3).xyz())
+}
+
+fn silhouette_lines(center: Vec3, radius: f32, eye: Vec3) -> Vec<Line> {
+    let view_direction = nalgebra_glm::normalize(&(eye - center));
+    let reference = if view_direction.y.abs() > 0.99 {
+        nalgebra_glm::vec3(1.0, 0.0, 0.0)
+    } else {
+        nalgebra_glm::vec3(0.0, 1.0, 0.0)
+    };
+    let tangent = nalgebra_glm::normalize(&nalgebra_glm::cross(&view_direction, &reference));
+    let bitangent = nalgebra_glm::cross(&view_direction, &tangent);
+    let color = nalgebra_glm::vec4(
+        SELECTION_OUTLINE_COLOR[0],
+        SELECTION_OUTLINE_COLOR[1],
+        SELECTION_OUTLINE_COLOR[2],
+        SELECTION_OUTLINE_COLOR[3],
+    );
 
-    if let Some(prev_entity) = previous_selected
-        && let Some(unit) = game_world.get_unit(prev_entity)
-        && let Some(engine_entity) = game_world.get_engine_entity(prev_entity)
-        && let Some(material) = world.get_material_mut(engine_entity.0)
-    {
-        material.base_color = faction_color(unit.faction);
+    let mut lines = Vec::new();
+    for scale in SELECTION_OUTLINE_SCALES {
+        let ring_radius = radius * scale;
+        let point = |index: usize| {
+            let angle = index as f32 * std::f32::consts::TAU / SELECTION_OUTLINE_SEGMENTS as f32;
+            center + (tangent * angle.cos() + bitangent * angle.sin()) * ring_radius
+        };
+        for index in 0..SELECTION_OUTLINE_SEGMENTS {
+            lines.push(Line {
+                start: point(index),
+                end: point(index + 1),
+                color,
+            });
+        }
     }
+    lines
+}
+
+pub fn selection_outline_system(
+    game_world: &GameWorld,
+    world: &mut World,
+    outline_entity: Option<Entity>,
+) {
+    let Some(entity) = outline_entity else {
+        return;
+    };
+
+    let outline = game_world.query_selected().next().and_then(|selected| {
+        let unit = game_world.get_unit(selected)?;
+        let engine_entity = game_world.get_engine_entity(selected)?;
+        let mesh_visible = world
+            .get_visibility(engine_entity.0)
+            .map(|visibility| visibility.visible)
+            .unwrap_or(true);
+        if !mesh_visible {
+            return None;
+        }
+        let center = game_world.get_world_position(selected)?.0;
+        let eye = camera_position(world)?;
+        Some(silhouette_lines(
+            center,
+            unit_radius_for_soldiers(unit.soldiers, game_world.resources.rules.max_soldiers),
+            eye,
+        ))
+    });
 
-    if let Some(curr_entity) = current_selected
-        && let Some(engine_entity) = game_world.get_engine_entity(curr_entity)
-        && let Some(material) = world.get_material_mut(engine_entity.0)
-    {
-        material.base_color = UNIT_SELECTED_COLOR;
+    match outline {
+        Some(lines) => {
+            if let Some(lines_component) = world.get_lines_mut(entity) {
+                lines_component.lines = lines;
+                lines_component.mark_dirty();
+            }
+            if let Some(visibility) = world.get_visibility_mut(entity) {
+                visibility.visible = true;
+            }
+        }
+        None => {
+            if let Some(visibility) = world.get_visibility_mut(entity) {
+                visibility.visible = false;
+            }
+        }
     }
 }
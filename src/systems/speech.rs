@@ -1,37 +1,137 @@
-use crate::constants::{MAX_MORALE, SPEECH_MORALE_BOOST};
-use crate::ecs::{GameEvents, GameWorld, SpeechEvent, UNIT};
+use crate::constants::{
+    BOUNTIFUL_LANDS_SOLDIERS, LEADER_ABILITY_COOLDOWN, MAX_MORALE, RALLYING_HYMN_MORALE_BOOST,
+};
+use crate::ecs::{
+    Faction, GameEvents, GameResources, GameWorld, HEX_POSITION, LeaderAbility, SpeechEvent, TILE,
+    UNIT, faction_index,
+};
+use crate::hex::HexCoord;
+use std::collections::HashSet;
 
-pub fn speech_system(game_world: &mut GameWorld, speech_requested: bool, events: &mut GameEvents) {
-    if !speech_requested {
-        return;
+pub fn leader_ability(faction: Faction) -> LeaderAbility {
+    match faction {
+        Faction::Redosia => LeaderAbility::BloodAndIron,
+        Faction::Violetnam => LeaderAbility::RallyingHymn,
+        Faction::Bluegaria => LeaderAbility::CallOfTheTides,
+        Faction::Greenland => LeaderAbility::BountifulLands,
     }
+}
 
-    if game_world.resources.speech_used {
-        return;
+pub fn leader_ability_name(ability: LeaderAbility) -> &'static str {
+    match ability {
+        LeaderAbility::BloodAndIron => "Blood and Iron",
+        LeaderAbility::RallyingHymn => "Rallying Hymn",
+        LeaderAbility::CallOfTheTides => "Call of the Tides",
+        LeaderAbility::BountifulLands => "Bountiful Lands",
     }
+}
 
-    let current_faction = game_world.resources.current_faction;
+pub fn leader_ability_description(ability: LeaderAbility) -> &'static str {
+    match ability {
+        LeaderAbility::BloodAndIron => "+25% attack strength this turn",
+        LeaderAbility::RallyingHymn => "+10 morale for every army",
+        LeaderAbility::CallOfTheTides => "double port reinforcements next turn",
+        LeaderAbility::BountifulLands => "+3 soldiers for armies on friendly land",
+    }
+}
+
+pub fn leader_ability_cooldown(resources: &GameResources, faction: Faction) -> u32 {
+    resources.ability_cooldowns[faction_index(faction)]
+}
 
-    let faction_units: Vec<_> = game_world
+pub fn leader_ability_active(
+    resources: &GameResources,
+    faction: Faction,
+    ability: LeaderAbility,
+) -> bool {
+    resources.active_abilities[faction_index(faction)] && leader_ability(faction) == ability
+}
+
+pub fn leader_ability_turn_start(resources: &mut GameResources, faction: Faction) {
+    let index = faction_index(faction);
+    resources.ability_cooldowns[index] = resources.ability_cooldowns[index].saturating_sub(1);
+}
+
+pub fn leader_ability_expire(resources: &mut GameResources, faction: Faction) {
+    resources.active_abilities[faction_index(faction)] = false;
+}
+
+fn faction_units(game_world: &GameWorld, faction: Faction) -> Vec<freecs::Entity> {
+    game_world
         .query_entities(UNIT)
         .filter(|entity| {
             game_world
                 .get_unit(*entity)
-                .map(|unit| unit.faction == current_faction)
+                .map(|unit| unit.faction == faction)
                 .unwrap_or(false)
         })
+        .collect()
+}
+
+fn rallying_hymn(game_world: &mut GameWorld, faction: Faction) {
+    for entity in faction_units(game_world, faction) {
+        if let Some(unit) = game_world.get_unit(entity) {
+            let mut unit = *unit;
+            unit.morale = (unit.morale + RALLYING_HYMN_MORALE_BOOST).min(MAX_MORALE);
+            game_world.set_unit(entity, unit);
+        }
+    }
+}
+
+fn bountiful_lands(game_world: &mut GameWorld, faction: Faction) {
+    let friendly_tiles: HashSet<HexCoord> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            (tile.faction == Some(faction)).then_some(coord)
+        })
         .collect();
 
-    for entity in faction_units {
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    for entity in faction_units(game_world, faction) {
+        let on_friendly_land = game_world
+            .get_hex_position(entity)
+            .map(|hex| friendly_tiles.contains(&hex.0))
+            .unwrap_or(false);
+        if !on_friendly_land {
+            continue;
+        }
         if let Some(unit) = game_world.get_unit(entity) {
             let mut unit = *unit;
-            unit.morale = (unit.morale + SPEECH_MORALE_BOOST).min(MAX_MORALE);
+            unit.soldiers = (unit.soldiers + BOUNTIFUL_LANDS_SOLDIERS).min(max_soldiers);
             game_world.set_unit(entity, unit);
         }
     }
+}
+
+pub fn speech_system(game_world: &mut GameWorld, speech_requested: bool, events: &mut GameEvents) {
+    if !speech_requested {
+        return;
+    }
+
+    if game_world.resources.speech_used {
+        return;
+    }
+
+    let current_faction = game_world.resources.current_faction;
+    if leader_ability_cooldown(&game_world.resources, current_faction) > 0 {
+        return;
+    }
+
+    let ability = leader_ability(current_faction);
+    match ability {
+        LeaderAbility::BloodAndIron | LeaderAbility::CallOfTheTides => {}
+        LeaderAbility::RallyingHymn => rallying_hymn(game_world, current_faction),
+        LeaderAbility::BountifulLands => bountiful_lands(game_world, current_faction),
+    }
 
+    let index = faction_index(current_faction);
+    game_world.resources.active_abilities[index] = true;
+    game_world.resources.ability_cooldowns[index] = LEADER_ABILITY_COOLDOWN;
     game_world.resources.speech_used = true;
     events.speech_events.push(SpeechEvent {
         faction: current_faction,
+        ability,
     });
 }
@@ -0,0 +1,86 @@
+use crate::constants::SUPPLY_WAGON_MOVEMENT_RANGE;
+use crate::ecs::{
+    Faction, GameEvents, GameWorld, HEX_POSITION, ReinforcementEvent, UNIT, UnitKind,
+    faction_capital, map_distance,
+};
+use crate::hex::HexCoord;
+use crate::selection::get_units_at_tile;
+use crate::systems::{despawn_unit, spawn_unit};
+
+pub fn spawn_supply_wagon(
+    game_world: &mut GameWorld,
+    coord: HexCoord,
+    faction: Faction,
+    soldiers: i32,
+) -> freecs::Entity {
+    let entity = spawn_unit(game_world, coord, faction, soldiers);
+    if let Some(unit) = game_world.get_unit_mut(entity) {
+        unit.kind = UnitKind::Supply;
+        unit.movement_range = SUPPLY_WAGON_MOVEMENT_RANGE;
+        unit.movement_left = SUPPLY_WAGON_MOVEMENT_RANGE;
+    }
+    entity
+}
+
+pub fn supply_wagon_at(
+    game_world: &GameWorld,
+    coord: HexCoord,
+    faction: Faction,
+) -> Option<freecs::Entity> {
+    get_units_at_tile(game_world, coord)
+        .into_iter()
+        .find(|entity| {
+            game_world
+                .get_unit(*entity)
+                .is_some_and(|unit| unit.faction == faction && unit.kind == UnitKind::Supply)
+        })
+}
+
+pub fn supply_delivery_system(game_world: &mut GameWorld, events: &mut GameEvents) {
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    let positioned: Vec<(freecs::Entity, HexCoord)> = game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter(|entity| game_world.get_movement(*entity).is_none())
+        .filter_map(|entity| Some((entity, game_world.get_hex_position(entity)?.0)))
+        .collect();
+
+    for &(wagon_entity, wagon_hex) in &positioned {
+        let Some(wagon) = game_world.get_unit(wagon_entity).copied() else {
+            continue;
+        };
+        if wagon.kind != UnitKind::Supply
+            || wagon_hex == faction_capital(&game_world.resources, wagon.faction)
+        {
+            continue;
+        }
+
+        let recipient = positioned
+            .iter()
+            .filter(|(_, hex)| map_distance(&game_world.resources, wagon_hex, *hex) <= 1)
+            .filter_map(|(entity, _)| Some((*entity, *game_world.get_unit(*entity)?)))
+            .filter(|(_, unit)| {
+                unit.faction == wagon.faction
+                    && unit.kind == UnitKind::Infantry
+                    && unit.soldiers < max_soldiers
+            })
+            .min_by_key(|(_, unit)| unit.soldiers);
+        let Some((recipient_entity, recipient)) = recipient else {
+            continue;
+        };
+
+        let delivered = wagon.soldiers.min(max_soldiers - recipient.soldiers);
+        if let Some(unit) = game_world.get_unit_mut(recipient_entity) {
+            unit.soldiers += delivered;
+        }
+        if delivered == wagon.soldiers {
+            despawn_unit(game_world, wagon_entity);
+        } else if let Some(unit) = game_world.get_unit_mut(wagon_entity) {
+            unit.soldiers -= delivered;
+        }
+        events.reinforcement_events.push(ReinforcementEvent {
+            faction: wagon.faction,
+            soldiers: delivered,
+            location_name: "from supply wagon".to_string(),
+        });
+    }
+}
@@ -0,0 +1,86 @@
+use crate::constants::AI_SURRENDER_STRENGTH_RATIO;
+use crate::ecs::{
+    ALL_FACTIONS, Faction, GameEvents, GameWorld, HEX_POSITION, SurrenderEvent, TILE, TileType,
+    UNIT, faction_index, record_ownership_change,
+};
+use crate::systems::eliminate_faction;
+use crate::teams::factions_hostile;
+
+fn faction_strength(game_world: &GameWorld, faction: Faction) -> i32 {
+    game_world
+        .query_entities(UNIT)
+        .filter_map(|entity| game_world.get_unit(entity))
+        .filter(|unit| unit.faction == faction)
+        .map(|unit| unit.soldiers)
+        .sum()
+}
+
+fn holds_only_capital(game_world: &GameWorld, faction: Faction) -> bool {
+    let held: Vec<TileType> = game_world
+        .query_entities(TILE)
+        .filter_map(|entity| game_world.get_tile(entity))
+        .filter(|tile| {
+            tile.faction == Some(faction)
+                && matches!(tile.tile_type, TileType::City | TileType::Capital)
+        })
+        .map(|tile| tile.tile_type)
+        .collect();
+    held == [TileType::Capital]
+}
+
+pub fn dominant_rival(game_world: &GameWorld, faction: Faction) -> Option<Faction> {
+    let team_layout = game_world.resources.team_layout;
+    ALL_FACTIONS
+        .into_iter()
+        .filter(|rival| {
+            factions_hostile(team_layout, faction, *rival)
+                && !game_world.resources.faction_eliminated[faction_index(*rival)]
+        })
+        .max_by_key(|rival| (faction_strength(game_world, *rival), faction_index(*rival)))
+}
+
+pub fn surrender_faction(
+    game_world: &mut GameWorld,
+    faction: Faction,
+    conqueror: Faction,
+    events: &mut GameEvents,
+) {
+    for entity in game_world
+        .query_entities(HEX_POSITION | TILE)
+        .collect::<Vec<_>>()
+    {
+        if let Some(tile) = game_world.get_tile(entity)
+            && tile.faction == Some(faction)
+        {
+            let mut tile = *tile;
+            tile.faction = Some(conqueror);
+            game_world.set_tile(entity, tile);
+            if let Some(coord) = game_world.get_hex_position(entity).map(|h| h.0) {
+                record_ownership_change(&mut game_world.resources, coord, Some(conqueror));
+            }
+        }
+    }
+
+    eliminate_faction(game_world, faction, conqueror, events);
+    events
+        .surrender_events
+        .push(SurrenderEvent { faction, conqueror });
+}
+
+pub fn ai_offer_surrender(game_world: &mut GameWorld, events: &mut GameEvents) -> bool {
+    let faction = game_world.resources.current_faction;
+    if !holds_only_capital(game_world, faction) {
+        return false;
+    }
+    let Some(rival) = dominant_rival(game_world, faction) else {
+        return false;
+    };
+    if faction_strength(game_world, rival)
+        < faction_strength(game_world, faction) * AI_SURRENDER_STRENGTH_RATIO
+    {
+        return false;
+    }
+
+    surrender_faction(game_world, faction, rival, events);
+    true
+}
@@ -1,5 +1,10 @@
-use crate::ecs::{Faction, GameWorld, HEX_POSITION, TILE, TileType, UNIT, modify_faction_morale};
+use crate::ecs::{
+    Faction, GameWorld, HEX_POSITION, TILE, TileType, UNIT, modify_faction_morale,
+    record_ownership_change, unit_kind_is_combatant,
+};
 use crate::hex::HexCoord;
+use crate::systems::record_city_capture;
+use crate::teams::factions_allied;
 use std::collections::HashMap;
 
 pub struct TileCapture {
@@ -29,15 +34,19 @@ fn morale_change_for_loss(tile_type: TileType) -> i32 {
 }
 
 pub fn tile_ownership_system(game_world: &mut GameWorld) -> Vec<TileCapture> {
-    let unit_positions: HashMap<HexCoord, Faction> = game_world
-        .query_entities(HEX_POSITION | UNIT)
-        .filter_map(|entity| {
-            let coord = game_world.get_hex_position(entity)?.0;
-            let unit = game_world.get_unit(entity)?;
-            Some((coord, unit.faction))
-        })
-        .collect();
+    let mut unit_positions: HashMap<HexCoord, (Faction, bool)> = HashMap::new();
+    for entity in game_world.query_entities(HEX_POSITION | UNIT) {
+        let (Some(coord), Some(unit)) = (
+            game_world.get_hex_position(entity).map(|hex| hex.0),
+            game_world.get_unit(entity),
+        ) else {
+            continue;
+        };
+        let position = unit_positions.entry(coord).or_insert((unit.faction, false));
+        position.1 |= unit_kind_is_combatant(unit.kind);
+    }
 
+    let team_layout = game_world.resources.team_layout;
     let mut morale_changes: Vec<(Faction, i32)> = Vec::new();
     let mut captures: Vec<TileCapture> = Vec::new();
 
@@ -49,15 +58,19 @@ pub fn tile_ownership_system(game_world: &mut GameWorld) -> Vec<TileCapture> {
             continue;
         };
 
-        if let Some(&unit_faction) = unit_positions.get(&coord)
+        if let Some(&(unit_faction, can_take_capital)) = unit_positions.get(&coord)
             && let Some(tile) = game_world.get_tile(entity)
         {
-            if tile.tile_type == TileType::Sea {
+            if tile.tile_type == TileType::Sea
+                || (tile.tile_type == TileType::Capital && !can_take_capital)
+            {
                 continue;
             }
 
             let old_owner = tile.faction;
-            if old_owner == Some(unit_faction) {
+            if old_owner.is_some_and(|owner| {
+                owner == unit_faction || factions_allied(team_layout, owner, unit_faction)
+            }) {
                 continue;
             }
 
@@ -85,7 +98,11 @@ pub fn tile_ownership_system(game_world: &mut GameWorld) -> Vec<TileCapture> {
 
             let mut tile = *tile;
             tile.faction = Some(unit_faction);
+            tile.growth = 0;
+            tile.construction = None;
             game_world.set_tile(entity, tile);
+            record_ownership_change(&mut game_world.resources, coord, Some(unit_faction));
+            record_city_capture(&mut game_world.resources, unit_faction, tile.tile_type);
         }
     }
 
@@ -1,7 +1,15 @@
-use crate::constants::ACTIONS_PER_TURN;
-use crate::ecs::{Faction, GameEvents, GameWorld, MOVEMENT, UNIT, faction_index, next_faction};
+use crate::ecs::{
+    ALL_FACTIONS, CasualtyStats, Difficulty, Faction, GameEvents, GameWorld, MOVEMENT, UNIT,
+    hardest_ai_difficulty,
+};
+use crate::rng::game_rng_new;
 use crate::selection::clear_selection;
-use crate::systems::{PendingSpawn, build_turn_order, reinforcement_system};
+use crate::state_hash::record_state_hash;
+use crate::systems::{
+    PendingSpawn, apply_starting_bonus, build_turn_order, city_growth_system, construction_system,
+    leader_ability_expire, leader_ability_turn_start, reinforcement_system, world_event_system,
+};
+use crate::turn_rotation::{turn_rotation_advance, turn_rotation_current, turn_rotation_new};
 
 pub struct TurnTransition {
     pub new_faction: Faction,
@@ -9,6 +17,32 @@ pub struct TurnTransition {
     pub pending_spawns: Vec<PendingSpawn>,
 }
 
+pub fn begin_game(game_world: &mut GameWorld, difficulties: [Difficulty; 4]) -> Faction {
+    game_world.resources.turn_rotation = turn_rotation_new(&ALL_FACTIONS);
+    let first_faction =
+        turn_rotation_current(&game_world.resources.turn_rotation).unwrap_or_default();
+    game_world.resources.current_faction = first_faction;
+    game_world.resources.actions_remaining = game_world.resources.rules.actions_per_turn;
+    game_world.resources.turn_number = 1;
+    game_world.resources.faction_eliminated = [false; 4];
+    game_world.resources.capital_relocated = [false; 4];
+    game_world.resources.casualty_stats = [CasualtyStats::default(); 4];
+    game_world.resources.ability_cooldowns = [0; 4];
+    game_world.resources.active_abilities = [false; 4];
+    game_world.resources.game_speed = 1.0;
+    game_world.resources.faction_difficulties = difficulties;
+    game_world.resources.difficulty = hardest_ai_difficulty(&game_world.resources);
+    apply_starting_bonus(game_world);
+    game_world.resources.game_rng = game_rng_new(game_world.resources.rng_seed);
+
+    build_turn_order(game_world);
+
+    game_world.resources.state_hashes.clear();
+    record_state_hash(game_world);
+
+    first_faction
+}
+
 pub fn end_turn(game_world: &mut GameWorld, events: &mut GameEvents) -> TurnTransition {
     clear_selection(game_world);
 
@@ -16,33 +50,37 @@ pub fn end_turn(game_world: &mut GameWorld, events: &mut GameEvents) -> TurnTran
         if let Some(unit) = game_world.get_unit(entity) {
             let mut unit = *unit;
             unit.has_moved = false;
+            unit.movement_left = unit.movement_range;
             game_world.set_unit(entity, unit);
         }
     }
 
-    let mut next = next_faction(game_world.resources.current_faction);
-    let mut attempts = 0;
-
-    while attempts < 4 {
-        if !game_world.resources.faction_eliminated[faction_index(next)] {
-            break;
-        }
-
-        next = next_faction(next);
-        attempts += 1;
-    }
+    let Some(advance) = turn_rotation_advance(&mut game_world.resources.turn_rotation) else {
+        return TurnTransition {
+            new_faction: game_world.resources.current_faction,
+            turn_number: game_world.resources.turn_number,
+            pending_spawns: Vec::new(),
+        };
+    };
+    let next = advance.faction;
 
-    if next == Faction::Redosia {
+    if advance.new_round {
         game_world.resources.turn_number += 1;
     }
 
     game_world.resources.current_faction = next;
-    game_world.resources.actions_remaining = ACTIONS_PER_TURN;
+    game_world.resources.actions_remaining = game_world.resources.rules.actions_per_turn;
     game_world.resources.speech_used = false;
 
     build_turn_order(game_world);
 
+    leader_ability_turn_start(&mut game_world.resources, next);
+    world_event_system(game_world, events);
+    city_growth_system(game_world);
+    construction_system(game_world, events);
     let pending_spawns = reinforcement_system(game_world, events);
+    leader_ability_expire(&mut game_world.resources, next);
+    record_state_hash(game_world);
 
     TurnTransition {
         new_faction: next,
@@ -1,117 +1,209 @@
 use crate::constants::{
-    MAX_SOLDIERS, UNIT_DEFAULT_MOVEMENT_RANGE, UNIT_HEIGHT_OFFSET, UNIT_MOVEMENT_SPEED,
+    AIRLIFT_MOVEMENT_SPEED, SHIP_MOVEMENT_SPEED, UNIT_DEFAULT_MOVEMENT_RANGE, UNIT_HEIGHT_OFFSET,
+    UNIT_MOVEMENT_SPEED,
 };
 use crate::ecs::{
-    ENGINE_ENTITY, EngineEntity, Faction, GameWorld, HEX_POSITION, HexPosition, MOVEMENT, Movement,
-    UNIT, Unit, WORLD_POSITION, WorldPosition, faction_color, get_faction_morale,
+    ENGINE_ENTITY, EngineEntity, Faction, GameWorld, HEX_POSITION, HealthBarMode, HexPosition,
+    MOVEMENT, Movement, TILE, TileType, UNIT, Unit, UnitDeath, UnitKind, WORLD_POSITION,
+    WorldPosition, faction_color, get_faction_morale, map_distance,
 };
-use crate::hex::{HexCoord, hex_to_world_position};
-use crate::systems::find_path;
+use crate::hex::{HexCoord, hex_to_world_position, hex_unwrap_near};
+use crate::labels::{label_acquire, label_release, label_release_system, label_set_font_size};
+use crate::map::{map_tile_elevation, map_wrap_width};
+use crate::prefabs::FALLBACK_UNIT_MESH;
+use crate::selection::get_units_at_tile;
+use crate::systems::{find_path, path_movement_cost};
+use nightshade::ecs::world::components::Line;
 use nightshade::prelude::*;
+use std::collections::{HashMap, HashSet};
 
 pub const UNIT_BASE_RADIUS: f32 = 25.0;
 pub const UNIT_MAX_RADIUS: f32 = 50.0;
 pub const UNIT_TEXT_HEIGHT_OFFSET: f32 = 200.0;
-pub const UNIT_SELECTED_COLOR: [f32; 4] = [1.0, 0.8, 0.2, 1.0];
+pub const HEALTH_BAR_HEIGHT_OFFSET: f32 = 120.0;
+pub const HEALTH_BAR_WIDTH: f32 = 90.0;
+pub const HEALTH_BAR_THICKNESS: f32 = 10.0;
+pub const HEALTH_BAR_LINES: usize = 6;
+pub const HEALTH_BAR_BACKGROUND_COLOR: [f32; 4] = [0.1, 0.1, 0.1, 1.0];
+const SHIP_HULL_SCALE: [f32; 3] = [1.6, 0.45, 0.8];
+const SHIP_BOB_AMPLITUDE: f32 = 3.0;
+const STACK_FORMATION_RADIUS: f32 = 0.22;
 
-pub fn unit_radius_for_soldiers(soldiers: i32) -> f32 {
-    let t = (soldiers as f32 / MAX_SOLDIERS as f32).clamp(0.0, 1.0);
+pub fn unit_radius_for_soldiers(soldiers: i32, max_soldiers: i32) -> f32 {
+    let t = (soldiers as f32 / max_soldiers as f32).clamp(0.0, 1.0);
     UNIT_BASE_RADIUS + (UNIT_MAX_RADIUS - UNIT_BASE_RADIUS) * t
 }
 
 pub fn spawn_unit(
     game_world: &mut GameWorld,
-    world: &mut World,
     hex_coord: HexCoord,
-    hex_width: f32,
-    hex_depth: f32,
     faction: Faction,
     soldiers: i32,
 ) -> freecs::Entity {
-    let radius = unit_radius_for_soldiers(soldiers);
-    let position = hex_to_world_position(hex_coord.column, hex_coord.row, hex_width, hex_depth);
+    let radius = unit_radius_for_soldiers(soldiers, game_world.resources.rules.max_soldiers);
+    let position = hex_to_world_position(
+        hex_coord.column,
+        hex_coord.row,
+        game_world.resources.hex_width,
+        game_world.resources.hex_depth,
+        game_world.resources.hex_orientation,
+    );
+    let elevation = map_tile_elevation(&game_world.resources.generated_map, hex_coord);
     let unit_position = nalgebra_glm::vec3(
         position.x,
-        position.y + radius + UNIT_HEIGHT_OFFSET,
+        position.y + elevation + radius + UNIT_HEIGHT_OFFSET,
         position.z,
     );
 
-    let render_entity = spawn_mesh(
-        world,
-        "Sphere",
-        unit_position,
-        nalgebra_glm::vec3(radius, radius, radius),
-    );
-
-    if let Some(material) = world.get_material_mut(render_entity) {
-        material.base_color = faction_color(faction);
-    }
-
-    let font_size = font_size_for_soldiers(soldiers);
-    let text_position = nalgebra_glm::vec3(
-        unit_position.x,
-        unit_position.y + radius + UNIT_TEXT_HEIGHT_OFFSET,
-        unit_position.z,
-    );
-    let color = faction_color(faction);
-    let text_entity = spawn_3d_billboard_text_with_properties(
-        world,
-        &soldiers.to_string(),
-        text_position,
-        TextProperties {
-            font_size,
-            color: nalgebra_glm::vec4(color[0], color[1], color[2], 1.0),
-            alignment: TextAlignment::Center,
-            outline_width: 0.15,
-            outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
-            smoothing: 0.15,
-            ..Default::default()
-        },
-    );
-
     let morale = get_faction_morale(&game_world.resources, faction);
 
-    let game_entity =
-        game_world.spawn_entities(ENGINE_ENTITY | WORLD_POSITION | HEX_POSITION | UNIT, 1)[0];
-    game_world.set_engine_entity(game_entity, EngineEntity(render_entity));
+    let game_entity = game_world.spawn_entities(WORLD_POSITION | HEX_POSITION | UNIT, 1)[0];
     game_world.set_world_position(game_entity, WorldPosition(unit_position));
     game_world.set_hex_position(game_entity, HexPosition(hex_coord));
     game_world.set_unit(
         game_entity,
         Unit {
             faction,
+            kind: UnitKind::Infantry,
             soldiers,
             morale,
             movement_range: UNIT_DEFAULT_MOVEMENT_RANGE,
+            movement_left: UNIT_DEFAULT_MOVEMENT_RANGE,
             has_moved: false,
-            text_entity: Some(text_entity),
+            text_entity: None,
         },
     );
 
     game_entity
 }
 
-pub fn font_size_for_soldiers(soldiers: i32) -> f32 {
-    let t = (soldiers as f32 / MAX_SOLDIERS as f32).clamp(0.0, 1.0);
+pub fn unit_visual_spawn_system(game_world: &mut GameWorld, world: &mut World) {
+    let unspawned: Vec<freecs::Entity> = game_world
+        .query_entities(UNIT | WORLD_POSITION)
+        .filter(|entity| game_world.get_engine_entity(*entity).is_none())
+        .collect();
+
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    for entity in unspawned {
+        let Some(unit) = game_world.get_unit(entity).copied() else {
+            continue;
+        };
+        let Some(unit_position) = game_world.get_world_position(entity).map(|p| p.0) else {
+            continue;
+        };
+
+        let radius = unit_radius_for_soldiers(unit.soldiers, max_soldiers);
+        let mesh_name = if game_world.resources.unit_mesh_name.is_empty() {
+            FALLBACK_UNIT_MESH
+        } else {
+            game_world.resources.unit_mesh_name.as_str()
+        };
+        let render_entity = spawn_mesh(
+            world,
+            mesh_name,
+            unit_position,
+            nalgebra_glm::vec3(radius, radius, radius),
+        );
+
+        if let Some(material) = world.get_material_mut(render_entity) {
+            material.base_color = faction_color(unit.faction);
+        }
+
+        let text_position = nalgebra_glm::vec3(
+            unit_position.x,
+            unit_position.y + radius + UNIT_TEXT_HEIGHT_OFFSET,
+            unit_position.z,
+        );
+        let color = faction_color(unit.faction);
+        let text_entity = label_acquire(
+            &mut game_world.resources.labels,
+            world,
+            &unit.soldiers.to_string(),
+            text_position,
+            TextProperties {
+                font_size: font_size_for_soldiers(unit.soldiers, max_soldiers),
+                color: nalgebra_glm::vec4(color[0], color[1], color[2], 1.0),
+                alignment: TextAlignment::Center,
+                outline_width: 0.15,
+                outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                smoothing: 0.15,
+                ..Default::default()
+            },
+        );
+
+        game_world.add_components(entity, ENGINE_ENTITY);
+        game_world.set_engine_entity(entity, EngineEntity(render_entity));
+        if let Some(unit) = game_world.get_unit_mut(entity) {
+            unit.text_entity = Some(text_entity);
+        }
+    }
+}
+
+pub fn font_size_for_soldiers(soldiers: i32, max_soldiers: i32) -> f32 {
+    let t = (soldiers as f32 / max_soldiers as f32).clamp(0.0, 1.0);
     15000.0 + 5000.0 * t
 }
 
-pub fn despawn_unit(game_world: &mut GameWorld, world: &mut World, entity: freecs::Entity) {
-    if let Some(unit) = game_world.get_unit(entity)
-        && let Some(text_entity) = unit.text_entity
+pub fn despawn_unit(game_world: &mut GameWorld, entity: freecs::Entity) {
+    if let Some(text_entity) = game_world
+        .get_unit(entity)
+        .and_then(|unit| unit.text_entity)
     {
-        world.queue_command(WorldCommand::DespawnRecursive {
-            entity: text_entity,
-        });
+        label_release(&mut game_world.resources.labels, text_entity);
     }
-    if let Some(engine_entity) = game_world.get_engine_entity(entity) {
-        world.queue_command(WorldCommand::DespawnRecursive {
-            entity: engine_entity.0,
-        });
+    if let Some(engine_entity) = game_world.get_engine_entity(entity).copied() {
+        game_world.resources.despawned_visuals.push(engine_entity.0);
     }
     game_world.despawn_entities(&[entity]);
 }
 
+pub fn kill_unit(game_world: &mut GameWorld, entity: freecs::Entity) {
+    let (Some(unit), Some(position)) = (
+        game_world.get_unit(entity).copied(),
+        game_world
+            .get_world_position(entity)
+            .map(|position| position.0),
+    ) else {
+        despawn_unit(game_world, entity);
+        return;
+    };
+    let engine_entity = game_world
+        .get_engine_entity(entity)
+        .map(|engine_entity| engine_entity.0);
+    game_world.resources.unit_deaths.push(UnitDeath {
+        faction: unit.faction,
+        position,
+        radius: unit_radius_for_soldiers(unit.soldiers, game_world.resources.rules.max_soldiers),
+        text_entity: unit.text_entity,
+        engine_entity,
+        elapsed: 0.0,
+    });
+    game_world.despawn_entities(&[entity]);
+}
+
+pub fn despawned_visuals_system(game_world: &mut GameWorld, world: &mut World) {
+    for entity in game_world.resources.despawned_visuals.drain(..) {
+        world.queue_command(WorldCommand::DespawnRecursive { entity });
+    }
+    label_release_system(&mut game_world.resources.labels, world);
+}
+
+pub fn unit_movement_budget(game_world: &GameWorld, unit: &Unit) -> i32 {
+    if game_world.resources.rules.movement_points {
+        unit.movement_left
+    } else {
+        unit.movement_range
+    }
+}
+
+pub fn move_action_cost(game_world: &GameWorld, unit: &Unit) -> u8 {
+    if game_world.resources.rules.movement_points && unit.movement_left < unit.movement_range {
+        0
+    } else {
+        1
+    }
+}
+
 pub fn move_unit_to(
     game_world: &mut GameWorld,
     unit_entity: freecs::Entity,
@@ -130,6 +222,16 @@ pub fn move_unit_to(
         return;
     }
 
+    let by_sea = path_crosses_sea(game_world, &path);
+    let land_cost = path_movement_cost(game_world, &path);
+    if let Some(unit) = game_world.get_unit_mut(unit_entity) {
+        let cost = if by_sea {
+            unit.movement_left
+        } else {
+            land_cost
+        };
+        unit.movement_left = (unit.movement_left - cost).max(0);
+    }
     game_world.add_components(unit_entity, MOVEMENT);
     game_world.set_movement(
         unit_entity,
@@ -137,12 +239,172 @@ pub fn move_unit_to(
             path,
             current_segment: 0,
             segment_progress: 0.0,
-            speed: UNIT_MOVEMENT_SPEED,
+            speed: if by_sea {
+                SHIP_MOVEMENT_SPEED
+            } else {
+                UNIT_MOVEMENT_SPEED
+            },
+            trail: Vec::new(),
+            by_sea,
         },
     );
 }
 
-pub fn unit_visual_update_system(game_world: &GameWorld, world: &mut World) {
+pub fn airlift_unit_to(
+    game_world: &mut GameWorld,
+    unit_entity: freecs::Entity,
+    destination: HexCoord,
+) {
+    let Some(start) = game_world.get_hex_position(unit_entity).map(|hex| hex.0) else {
+        return;
+    };
+    if let Some(unit) = game_world.get_unit_mut(unit_entity) {
+        unit.movement_left = 0;
+        unit.has_moved = true;
+    }
+    game_world.add_components(unit_entity, MOVEMENT);
+    game_world.set_movement(
+        unit_entity,
+        Movement {
+            path: vec![start, destination],
+            current_segment: 0,
+            segment_progress: 0.0,
+            speed: AIRLIFT_MOVEMENT_SPEED,
+            trail: Vec::new(),
+            by_sea: false,
+        },
+    );
+}
+
+fn path_crosses_sea(game_world: &GameWorld, path: &[HexCoord]) -> bool {
+    if path
+        .windows(2)
+        .any(|pair| map_distance(&game_world.resources, pair[0], pair[1]) > 1)
+    {
+        return true;
+    }
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .any(|entity| {
+            let on_path = game_world
+                .get_hex_position(entity)
+                .is_some_and(|hex| path.contains(&hex.0));
+            on_path
+                && game_world
+                    .get_tile(entity)
+                    .is_some_and(|tile| tile.tile_type == TileType::Sea)
+        })
+}
+
+pub fn sailing_heading(game_world: &GameWorld, movement: &Movement) -> Option<f32> {
+    if !movement.by_sea || movement.current_segment + 1 >= movement.path.len() {
+        return None;
+    }
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let from = movement.path[movement.current_segment];
+    let to = hex_unwrap_near(
+        movement.path[movement.current_segment + 1],
+        from,
+        map_wrap_width(&game_world.resources.generated_map),
+    );
+    let from_world =
+        hex_to_world_position(from.column, from.row, hex_width, hex_depth, orientation);
+    let to_world = hex_to_world_position(to.column, to.row, hex_width, hex_depth, orientation);
+    Some((from_world.z - to_world.z).atan2(to_world.x - from_world.x))
+}
+
+fn camera_right_vector(world: &World) -> Option<Vec3> {
+    let camera_entity = world.resources.active_camera?;
+    let global_transform = world.get_global_transform(camera_entity)?;
+    let right = global_transform.0.column(0).xyz();
+    let flattened = nalgebra_glm::vec3(right.x, 0.0, right.z);
+    (nalgebra_glm::length(&flattened) > f32::EPSILON).then(|| nalgebra_glm::normalize(&flattened))
+}
+
+fn health_bar_lines_for_unit(
+    unit: &Unit,
+    max_soldiers: i32,
+    center: Vec3,
+    right: Vec3,
+) -> Vec<Line> {
+    let fill = (unit.soldiers as f32 / max_soldiers as f32).clamp(0.0, 1.0);
+    let color = faction_color(unit.faction);
+    let fill_color = nalgebra_glm::vec4(color[0], color[1], color[2], 1.0);
+    let background_color = nalgebra_glm::vec4(
+        HEALTH_BAR_BACKGROUND_COLOR[0],
+        HEALTH_BAR_BACKGROUND_COLOR[1],
+        HEALTH_BAR_BACKGROUND_COLOR[2],
+        HEALTH_BAR_BACKGROUND_COLOR[3],
+    );
+    let left = center - right * (HEALTH_BAR_WIDTH / 2.0);
+    let split = left + right * (HEALTH_BAR_WIDTH * fill);
+    let end = left + right * HEALTH_BAR_WIDTH;
+
+    let mut lines = Vec::new();
+    for row in 0..HEALTH_BAR_LINES {
+        let height = row as f32 * HEALTH_BAR_THICKNESS / (HEALTH_BAR_LINES - 1) as f32
+            - HEALTH_BAR_THICKNESS / 2.0;
+        let offset = nalgebra_glm::vec3(0.0, height, 0.0);
+        if fill > 0.0 {
+            lines.push(Line {
+                start: left + offset,
+                end: split + offset,
+                color: fill_color,
+            });
+        }
+        if fill < 1.0 {
+            lines.push(Line {
+                start: split + offset,
+                end: end + offset,
+                color: background_color,
+            });
+        }
+    }
+    lines
+}
+
+fn stack_formation_offsets(game_world: &GameWorld) -> HashMap<Entity, Vec3> {
+    let stacked_tiles: HashSet<HexCoord> = game_world
+        .query_entities(UNIT | HEX_POSITION)
+        .filter(|entity| game_world.get_movement(*entity).is_none())
+        .filter_map(|entity| game_world.get_hex_position(entity).map(|hex| hex.0))
+        .collect();
+    let radius = game_world.resources.hex_width * STACK_FORMATION_RADIUS;
+
+    let mut offsets = HashMap::new();
+    for coord in stacked_tiles {
+        let stack: Vec<Entity> = get_units_at_tile(game_world, coord)
+            .into_iter()
+            .filter(|entity| game_world.get_movement(*entity).is_none())
+            .collect();
+        if stack.len() < 2 {
+            continue;
+        }
+        for (index, entity) in stack.iter().enumerate() {
+            let angle = index as f32 * std::f32::consts::TAU / stack.len() as f32;
+            offsets.insert(
+                *entity,
+                nalgebra_glm::vec3(angle.cos() * radius, 0.0, angle.sin() * radius),
+            );
+        }
+    }
+    offsets
+}
+
+pub fn unit_visual_update_system(
+    game_world: &GameWorld,
+    world: &mut World,
+    health_bar_entity: Option<Entity>,
+) {
+    let camera_right = camera_right_vector(world);
+    let selected = game_world.query_selected().next();
+    let hovered_tile = game_world.resources.hovered_tile;
+    let max_soldiers = game_world.resources.rules.max_soldiers;
+    let mut health_bar_lines = Vec::new();
+    let formation_offsets = stack_formation_offsets(game_world);
+
     for entity in game_world.query_entities(UNIT | ENGINE_ENTITY | WORLD_POSITION) {
         let Some(unit) = game_world.get_unit(entity) else {
             continue;
@@ -153,29 +415,94 @@ pub fn unit_visual_update_system(game_world: &GameWorld, world: &mut World) {
         let Some(world_position) = game_world.get_world_position(entity) else {
             continue;
         };
+        let position = world_position.0
+            + formation_offsets
+                .get(&entity)
+                .copied()
+                .unwrap_or_else(|| nalgebra_glm::vec3(0.0, 0.0, 0.0));
+
+        let radius = unit_radius_for_soldiers(unit.soldiers, max_soldiers);
 
-        let radius = unit_radius_for_soldiers(unit.soldiers);
+        let show_health_bar = match game_world.resources.health_bar_mode {
+            HealthBarMode::Always => true,
+            HealthBarMode::HoverOrSelection => {
+                selected == Some(entity)
+                    || (hovered_tile.is_some()
+                        && game_world.get_hex_position(entity).map(|hex| hex.0) == hovered_tile)
+            }
+            HealthBarMode::Hidden => false,
+        };
+        let mesh_visible = world
+            .get_visibility(engine_entity.0)
+            .map(|visibility| visibility.visible)
+            .unwrap_or(true);
+        if show_health_bar
+            && mesh_visible
+            && let Some(right) = camera_right
+        {
+            let center = nalgebra_glm::vec3(
+                position.x,
+                position.y + radius + HEALTH_BAR_HEIGHT_OFFSET,
+                position.z,
+            );
+            health_bar_lines.extend(health_bar_lines_for_unit(unit, max_soldiers, center, right));
+        }
 
+        let heading = game_world
+            .get_movement(entity)
+            .and_then(|movement| sailing_heading(game_world, movement));
+        let bob = game_world
+            .get_movement(entity)
+            .filter(|_| heading.is_some())
+            .map(|movement| {
+                (movement.segment_progress * std::f32::consts::TAU).sin() * SHIP_BOB_AMPLITUDE
+            })
+            .unwrap_or(0.0);
         if let Some(transform) = world.get_local_transform_mut(engine_entity.0) {
-            transform.scale = nalgebra_glm::vec3(radius, radius, radius);
+            transform.translation = position + nalgebra_glm::vec3(0.0, bob, 0.0);
+            match heading {
+                Some(heading) => {
+                    transform.scale = nalgebra_glm::vec3(
+                        radius * SHIP_HULL_SCALE[0],
+                        radius * SHIP_HULL_SCALE[1],
+                        radius * SHIP_HULL_SCALE[2],
+                    );
+                    transform.rotation =
+                        nalgebra_glm::quat_angle_axis(heading, &nalgebra_glm::vec3(0.0, 1.0, 0.0));
+                }
+                None => {
+                    transform.scale = nalgebra_glm::vec3(radius, radius, radius);
+                    transform.rotation = nalgebra_glm::quat_identity();
+                }
+            }
         }
         mark_local_transform_dirty(world, engine_entity.0);
 
         if let Some(text_entity) = unit.text_entity {
             if let Some(text_transform) = world.get_local_transform_mut(text_entity) {
                 text_transform.translation = nalgebra_glm::vec3(
-                    world_position.0.x,
-                    world_position.0.y + radius + UNIT_TEXT_HEIGHT_OFFSET,
-                    world_position.0.z,
+                    position.x,
+                    position.y + radius + UNIT_TEXT_HEIGHT_OFFSET,
+                    position.z,
                 );
             }
             mark_local_transform_dirty(world, text_entity);
 
-            let font_size = font_size_for_soldiers(unit.soldiers);
-            if let Some(text) = world.get_text_mut(text_entity) {
-                text.properties.font_size = font_size;
-                text.dirty = true;
-            }
+            label_set_font_size(
+                world,
+                text_entity,
+                font_size_for_soldiers(unit.soldiers, max_soldiers),
+            );
         }
     }
+    let Some(entity) = health_bar_entity else {
+        return;
+    };
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = health_bar_lines;
+        lines_component.mark_dirty();
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = true;
+    }
 }
@@ -1,27 +1,22 @@
-use crate::ecs::{GameWorld, UNIT};
+use crate::ecs::{GameWorld, UNIT, UnitKind};
+use crate::labels::label_set_text;
 use nightshade::prelude::*;
 
-pub fn unit_text_system(game_world: &GameWorld, world: &mut World) {
-    for entity in game_world.query_entities(UNIT) {
-        let Some(unit) = game_world.get_unit(entity) else {
-            continue;
-        };
+pub fn unit_text_system(game_world: &mut GameWorld, world: &mut World) {
+    let labels: Vec<(Entity, String)> = game_world
+        .query_entities(UNIT)
+        .filter_map(|entity| {
+            let unit = game_world.get_unit(entity)?;
+            let text = match unit.kind {
+                UnitKind::Infantry => unit.soldiers.to_string(),
+                UnitKind::Scout => format!("S{}", unit.soldiers),
+                UnitKind::Supply => format!("W{}", unit.soldiers),
+            };
+            Some((unit.text_entity?, text))
+        })
+        .collect();
 
-        let Some(text_entity) = unit.text_entity else {
-            continue;
-        };
-
-        let Some(text_index) = world.get_text(text_entity).map(|t| t.text_index) else {
-            continue;
-        };
-
-        world
-            .resources
-            .text_cache
-            .set_text(text_index, unit.soldiers.to_string());
-
-        if let Some(text) = world.get_text_mut(text_entity) {
-            text.dirty = true;
-        }
+    for (text_entity, text) in labels {
+        label_set_text(&mut game_world.resources.labels, world, text_entity, &text);
     }
 }
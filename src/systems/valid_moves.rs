@@ -1,8 +1,21 @@
-use crate::ecs::{Entity, GameWorld, HEX_POSITION, TILE, TileType, UNIT};
-use crate::hex::{HexCoord, hex_neighbors, hex_to_world_position};
+use crate::constants::MAX_UNITS_PER_HEX;
+use crate::ecs::{
+    Entity, Faction, GameWorld, HEX_POSITION, TILE, TileType, UNIT, map_neighbors,
+    tile_movement_cost,
+};
+use crate::hex::{HexCoord, HexOrientation, hex_to_world_position, hex_unwrap_near};
+use crate::map::map_wrap_width;
+use crate::season::sea_travel_frozen;
+use crate::selection::get_unit_at_tile;
+use crate::systems::unit_movement_budget;
+use crate::teams::factions_hostile;
 use std::collections::{HashMap, HashSet, VecDeque};
 
-fn find_sea_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option<Vec<HexCoord>> {
+pub fn find_sea_path(
+    game_world: &GameWorld,
+    from: HexCoord,
+    to: HexCoord,
+) -> Option<Vec<HexCoord>> {
     let sea_tiles: HashSet<HexCoord> = game_world
         .query_entities(HEX_POSITION | TILE)
         .filter_map(|entity| {
@@ -16,12 +29,12 @@ fn find_sea_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option
         })
         .collect();
 
-    let sea_neighbors_of_from: Vec<HexCoord> = hex_neighbors(from)
+    let sea_neighbors_of_from: Vec<HexCoord> = map_neighbors(&game_world.resources, from)
         .into_iter()
         .filter(|coord| sea_tiles.contains(coord))
         .collect();
 
-    let sea_neighbors_of_to: Vec<HexCoord> = hex_neighbors(to)
+    let sea_neighbors_of_to: Vec<HexCoord> = map_neighbors(&game_world.resources, to)
         .into_iter()
         .filter(|coord| sea_tiles.contains(coord))
         .collect();
@@ -49,7 +62,7 @@ fn find_sea_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option
 
                 let current_dist = distances[&current];
 
-                for neighbor in hex_neighbors(current) {
+                for neighbor in map_neighbors(&game_world.resources, current) {
                     if !sea_tiles.contains(&neighbor) {
                         continue;
                     }
@@ -92,10 +105,29 @@ fn direction_alignment(
     goal_coord: HexCoord,
     hex_width: f32,
     hex_depth: f32,
+    orientation: HexOrientation,
 ) -> i32 {
-    let from_world = hex_to_world_position(from_coord.column, from_coord.row, hex_width, hex_depth);
-    let to_world = hex_to_world_position(to_coord.column, to_coord.row, hex_width, hex_depth);
-    let goal_world = hex_to_world_position(goal_coord.column, goal_coord.row, hex_width, hex_depth);
+    let from_world = hex_to_world_position(
+        from_coord.column,
+        from_coord.row,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
+    let to_world = hex_to_world_position(
+        to_coord.column,
+        to_coord.row,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
+    let goal_world = hex_to_world_position(
+        goal_coord.column,
+        goal_coord.row,
+        hex_width,
+        hex_depth,
+        orientation,
+    );
 
     let step_x = to_world.x - from_world.x;
     let step_z = to_world.z - from_world.z;
@@ -113,28 +145,88 @@ fn direction_alignment(
     (dot * 10000.0) as i32
 }
 
-pub fn find_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option<Vec<HexCoord>> {
-    if from == to {
-        return Some(vec![from]);
-    }
-
-    let hex_width = game_world.resources.hex_width;
-    let hex_depth = game_world.resources.hex_depth;
+fn hostile_occupied_tiles(game_world: &GameWorld, faction: Faction) -> HashSet<HexCoord> {
+    let team_layout = game_world.resources.team_layout;
+    game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .filter_map(|entity| {
+            let unit = game_world.get_unit(entity)?;
+            if !factions_hostile(team_layout, faction, unit.faction) {
+                return None;
+            }
+            game_world.get_hex_position(entity).map(|hex| hex.0)
+        })
+        .collect()
+}
 
-    let passable_tiles: HashSet<HexCoord> = game_world
+fn passable_tile_costs(
+    game_world: &GameWorld,
+    blocked_tiles: &HashSet<HexCoord>,
+) -> HashMap<HexCoord, i32> {
+    game_world
         .query_entities(HEX_POSITION | TILE)
         .filter_map(|entity| {
             let coord = game_world.get_hex_position(entity)?.0;
             let tile = game_world.get_tile(entity)?;
-            if tile.tile_type != TileType::Sea {
-                Some(coord)
+            if tile.tile_type != TileType::Sea && !blocked_tiles.contains(&coord) {
+                Some((coord, tile_movement_cost(tile.tile_type)))
             } else {
                 None
             }
         })
-        .collect();
+        .collect()
+}
+
+pub fn path_movement_cost(game_world: &GameWorld, path: &[HexCoord]) -> i32 {
+    let costs = passable_tile_costs(game_world, &HashSet::new());
+    path.iter()
+        .skip(1)
+        .map(|coord| costs.get(coord).copied().unwrap_or(1))
+        .sum()
+}
+
+pub fn unstackable_tiles(game_world: &GameWorld, unit_entity: Entity) -> HashSet<HexCoord> {
+    let Some(faction) = game_world.get_unit(unit_entity).map(|unit| unit.faction) else {
+        return HashSet::new();
+    };
+    let mut stacks: HashMap<HexCoord, (usize, bool)> = HashMap::new();
+    for entity in game_world.query_entities(HEX_POSITION | UNIT) {
+        if entity == unit_entity {
+            continue;
+        }
+        let (Some(hex), Some(unit)) = (
+            game_world.get_hex_position(entity),
+            game_world.get_unit(entity),
+        ) else {
+            continue;
+        };
+        let stack = stacks.entry(hex.0).or_insert((0, false));
+        stack.0 += 1;
+        stack.1 |= unit.faction != faction;
+    }
+    stacks
+        .into_iter()
+        .filter(|(_, (count, foreign))| *foreign || *count >= MAX_UNITS_PER_HEX)
+        .map(|(coord, _)| coord)
+        .collect()
+}
+
+pub fn find_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option<Vec<HexCoord>> {
+    if from == to {
+        return Some(vec![from]);
+    }
+
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
 
-    if !passable_tiles.contains(&from) || !passable_tiles.contains(&to) {
+    let blocked_tiles = get_unit_at_tile(game_world, from)
+        .and_then(|entity| game_world.get_unit(entity))
+        .map(|unit| hostile_occupied_tiles(game_world, unit.faction))
+        .unwrap_or_default();
+    let passable_tiles = passable_tile_costs(game_world, &blocked_tiles);
+
+    if !passable_tiles.contains_key(&from) || !passable_tiles.contains_key(&to) {
         return None;
     }
 
@@ -162,7 +254,12 @@ pub fn find_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option
             coord == to && tile.tile_type == TileType::Port
         });
 
-    if from_is_port && to_is_port {
+    let turn_number = game_world.resources.turn_number;
+    let map_params = &game_world.resources.map_params;
+    let sea_frozen = sea_travel_frozen(turn_number, map_params, from)
+        || sea_travel_frozen(turn_number, map_params, to);
+
+    if from_is_port && to_is_port && !sea_frozen {
         return find_sea_path(game_world, from, to);
     }
 
@@ -176,23 +273,26 @@ pub fn find_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option
     while let Some(current) = queue.pop_front() {
         let current_dist = distances[&current];
 
-        for neighbor in hex_neighbors(current) {
-            if !passable_tiles.contains(&neighbor) {
+        for neighbor in map_neighbors(&game_world.resources, current) {
+            let Some(&cost) = passable_tiles.get(&neighbor) else {
                 continue;
-            }
+            };
 
-            let new_dist = current_dist + 1;
+            let new_dist = current_dist + cost;
 
             match distances.get(&neighbor) {
-                None => {
+                Some(&existing_dist) if existing_dist < new_dist => {}
+                Some(&existing_dist) if existing_dist == new_dist => {
+                    let preds = predecessors.get_mut(&neighbor).unwrap();
+                    if !preds.contains(&current) {
+                        preds.push(current);
+                    }
+                }
+                _ => {
                     distances.insert(neighbor, new_dist);
                     predecessors.insert(neighbor, vec![current]);
                     queue.push_back(neighbor);
                 }
-                Some(&existing_dist) if existing_dist == new_dist => {
-                    predecessors.get_mut(&neighbor).unwrap().push(current);
-                }
-                _ => {}
             }
         }
     }
@@ -201,6 +301,7 @@ pub fn find_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option
         return None;
     }
 
+    let wrap_width = map_wrap_width(&game_world.resources.generated_map);
     let mut path = vec![to];
     let mut current = to;
 
@@ -219,8 +320,14 @@ pub fn find_path(game_world: &GameWorld, from: HexCoord, to: HexCoord) -> Option
             *preds
                 .iter()
                 .max_by_key(|&&pred| {
-                    let alignment_to_goal =
-                        direction_alignment(pred, current, to, hex_width, hex_depth);
+                    let alignment_to_goal = direction_alignment(
+                        pred,
+                        hex_unwrap_near(current, pred, wrap_width),
+                        hex_unwrap_near(to, pred, wrap_width),
+                        hex_width,
+                        hex_depth,
+                        orientation,
+                    );
 
                     let alignment_to_next = if let Some(next) = next_in_path {
                         let prev_step_x = current.column - next.column;
@@ -255,31 +362,25 @@ pub fn calculate_valid_moves(
     unit_hex: HexCoord,
     movement_range: i32,
 ) -> Vec<HexCoord> {
-    let unit_positions: HashSet<HexCoord> = game_world
-        .query_entities(HEX_POSITION | UNIT)
-        .filter(|&entity| entity != unit_entity)
-        .filter_map(|entity| game_world.get_hex_position(entity).map(|hex| hex.0))
-        .collect();
+    let unstackable = unstackable_tiles(game_world, unit_entity);
 
-    let passable_tiles: HashSet<HexCoord> = game_world
-        .query_entities(HEX_POSITION | TILE)
-        .filter_map(|entity| {
-            let coord = game_world.get_hex_position(entity)?.0;
-            let tile = game_world.get_tile(entity)?;
-            if tile.tile_type != TileType::Sea {
-                Some(coord)
-            } else {
-                None
-            }
-        })
-        .collect();
+    let unit_faction = game_world.get_unit(unit_entity).map(|unit| unit.faction);
+
+    let blocked_tiles = unit_faction
+        .map(|faction| hostile_occupied_tiles(game_world, faction))
+        .unwrap_or_default();
+
+    let passable_tiles = passable_tile_costs(game_world, &blocked_tiles);
 
     let port_tiles: HashSet<HexCoord> = game_world
         .query_entities(HEX_POSITION | TILE)
         .filter_map(|entity| {
             let coord = game_world.get_hex_position(entity)?.0;
             let tile = game_world.get_tile(entity)?;
-            if tile.tile_type == TileType::Port {
+            if tile.tile_type == TileType::Port
+                && tile.faction.is_some()
+                && tile.faction == unit_faction
+            {
                 Some(coord)
             } else {
                 None
@@ -287,6 +388,17 @@ pub fn calculate_valid_moves(
         })
         .collect();
 
+    let turn_number = game_world.resources.turn_number;
+    let map_params = &game_world.resources.map_params;
+    let port_tiles: HashSet<HexCoord> = if sea_travel_frozen(turn_number, map_params, unit_hex) {
+        HashSet::new()
+    } else {
+        port_tiles
+            .into_iter()
+            .filter(|coord| !sea_travel_frozen(turn_number, map_params, *coord))
+            .collect()
+    };
+
     let starting_on_port = port_tiles.contains(&unit_hex);
 
     let mut distances: HashMap<HexCoord, i32> = HashMap::new();
@@ -301,14 +413,21 @@ pub fn calculate_valid_moves(
             continue;
         }
 
-        for neighbor in hex_neighbors(current) {
-            if !passable_tiles.contains(&neighbor) {
+        for neighbor in map_neighbors(&game_world.resources, current) {
+            let Some(&cost) = passable_tiles.get(&neighbor) else {
+                continue;
+            };
+            let new_distance = current_distance + cost;
+            if new_distance > movement_range {
                 continue;
             }
-            if distances.contains_key(&neighbor) {
+            if distances
+                .get(&neighbor)
+                .is_some_and(|existing| *existing <= new_distance)
+            {
                 continue;
             }
-            distances.insert(neighbor, current_distance + 1);
+            distances.insert(neighbor, new_distance);
             queue.push_back(neighbor);
         }
 
@@ -325,12 +444,80 @@ pub fn calculate_valid_moves(
         }
     }
 
-    distances
+    let mut valid_moves: Vec<HexCoord> = distances
         .into_iter()
         .filter(|(coord, distance)| {
-            *distance > 0 && *distance <= movement_range && !unit_positions.contains(coord)
+            *distance > 0 && *distance <= movement_range && !unstackable.contains(coord)
         })
         .map(|(coord, _)| coord)
+        .collect();
+    valid_moves.sort_by_key(|coord| (coord.row, coord.column));
+    valid_moves
+}
+
+pub fn friendly_port_destinations(game_world: &GameWorld, unit_entity: Entity) -> Vec<HexCoord> {
+    let Some(unit) = game_world.get_unit(unit_entity).copied() else {
+        return Vec::new();
+    };
+    let Some(unit_hex) = game_world.get_hex_position(unit_entity).map(|hex| hex.0) else {
+        return Vec::new();
+    };
+    if unit.has_moved || unit_movement_budget(game_world, &unit) <= 0 {
+        return Vec::new();
+    }
+
+    let friendly_ports: Vec<HexCoord> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            (tile.tile_type == TileType::Port && tile.faction == Some(unit.faction))
+                .then_some(coord)
+        })
+        .collect();
+    if !friendly_ports.contains(&unit_hex) {
+        return Vec::new();
+    }
+
+    let unstackable = unstackable_tiles(game_world, unit_entity);
+
+    friendly_ports
+        .into_iter()
+        .filter(|coord| *coord != unit_hex && !unstackable.contains(coord))
+        .collect()
+}
+
+pub fn friendly_airfield_destinations(
+    game_world: &GameWorld,
+    unit_entity: Entity,
+) -> Vec<HexCoord> {
+    let Some(unit) = game_world.get_unit(unit_entity).copied() else {
+        return Vec::new();
+    };
+    let Some(unit_hex) = game_world.get_hex_position(unit_entity).map(|hex| hex.0) else {
+        return Vec::new();
+    };
+    if unit.has_moved || unit.movement_left < unit.movement_range {
+        return Vec::new();
+    }
+
+    let friendly_airfields: Vec<HexCoord> = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            (tile.airfield && tile.faction == Some(unit.faction)).then_some(coord)
+        })
+        .collect();
+    if !friendly_airfields.contains(&unit_hex) {
+        return Vec::new();
+    }
+
+    let unstackable = unstackable_tiles(game_world, unit_entity);
+
+    friendly_airfields
+        .into_iter()
+        .filter(|coord| *coord != unit_hex && !unstackable.contains(coord))
         .collect()
 }
 
@@ -343,6 +530,9 @@ pub fn valid_moves_system(game_world: &mut GameWorld) {
     }
 
     game_world.resources.valid_move_tiles.clear();
+    game_world.resources.port_route_tiles.clear();
+    game_world.resources.port_route_origin = None;
+    game_world.resources.airlift_tiles.clear();
 
     if let Some(unit_entity) = current_selected
         && let (Some(hex_pos), Some(unit)) = (
@@ -350,11 +540,26 @@ pub fn valid_moves_system(game_world: &mut GameWorld) {
             game_world.get_unit(unit_entity),
         )
     {
-        let valid_moves =
-            calculate_valid_moves(game_world, unit_entity, hex_pos.0, unit.movement_range);
+        let budget = unit_movement_budget(game_world, unit);
+        let valid_moves = calculate_valid_moves(game_world, unit_entity, hex_pos.0, budget);
         for coord in valid_moves {
             game_world.resources.valid_move_tiles.insert(coord);
         }
+
+        let port_destinations = friendly_port_destinations(game_world, unit_entity);
+        if !port_destinations.is_empty() {
+            game_world.resources.port_route_origin = Some(hex_pos.0);
+            game_world
+                .resources
+                .port_route_tiles
+                .extend(port_destinations);
+        }
+
+        let airfield_destinations = friendly_airfield_destinations(game_world, unit_entity);
+        game_world
+            .resources
+            .airlift_tiles
+            .extend(airfield_destinations);
     }
 
     game_world.resources.previous_selected_unit = current_selected;
@@ -1,10 +1,11 @@
 use crate::ecs::{
-    Faction, FactionEliminatedEvent, GameEvents, GameWorld, HEX_POSITION, TILE, TileType, UNIT,
-    faction_index,
+    ALL_FACTIONS, Faction, FactionEliminatedEvent, GameEvents, GameWorld, HEX_POSITION, TILE,
+    TileType, UNIT, faction_capital, faction_index, record_ownership_change,
 };
 use crate::hex::HexCoord;
-use crate::map::CAPITAL_POSITIONS;
-use nightshade::prelude::*;
+use crate::systems::{despawn_unit, spawn_unit};
+use crate::teams::factions_allied;
+use crate::turn_rotation::turn_rotation_remove;
 
 pub enum GameResult {
     Ongoing,
@@ -20,24 +21,64 @@ fn faction_from_index(index: usize) -> Faction {
     }
 }
 
-fn get_capital_coord(faction: Faction) -> HexCoord {
-    let index = faction_index(faction);
-    let (col, row, _) = CAPITAL_POSITIONS[index];
-    HexCoord { column: col, row }
+fn defect_unit(game_world: &mut GameWorld, coord: HexCoord, conqueror: Faction, soldiers: i32) {
+    let entity = spawn_unit(game_world, coord, conqueror, soldiers);
+    if let Some(unit) = game_world.get_unit_mut(entity) {
+        unit.movement_left = 0;
+        unit.has_moved = true;
+    }
 }
 
-pub fn victory_system(
+pub fn eliminate_faction(
     game_world: &mut GameWorld,
-    world: &mut World,
+    faction: Faction,
+    conqueror: Faction,
     events: &mut GameEvents,
-) -> GameResult {
+) {
+    game_world.resources.faction_eliminated[faction_index(faction)] = true;
+    turn_rotation_remove(&mut game_world.resources.turn_rotation, faction);
+
+    let units_to_remove: Vec<_> = game_world
+        .query_entities(UNIT)
+        .filter(|&entity| {
+            game_world
+                .get_unit(entity)
+                .map(|u| u.faction == faction)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    let mut defected_units = 0;
+    for entity in units_to_remove {
+        let defector = game_world
+            .get_unit(entity)
+            .map(|unit| unit.soldiers / 2)
+            .filter(|soldiers| game_world.resources.rules.defection && *soldiers > 0)
+            .zip(game_world.get_hex_position(entity).map(|hex| hex.0));
+        despawn_unit(game_world, entity);
+        if let Some((soldiers, coord)) = defector {
+            defect_unit(game_world, coord, conqueror, soldiers);
+            defected_units += 1;
+        }
+    }
+
+    events
+        .faction_eliminated_events
+        .push(FactionEliminatedEvent {
+            faction,
+            conqueror,
+            defected_units,
+        });
+}
+
+pub fn victory_system(game_world: &mut GameWorld, events: &mut GameEvents) -> GameResult {
     for faction_idx in 0..4 {
         if game_world.resources.faction_eliminated[faction_idx] {
             continue;
         }
 
         let faction = faction_from_index(faction_idx);
-        let capital_coord = get_capital_coord(faction);
+        let capital_coord = faction_capital(&game_world.resources, faction);
 
         let capital_owner = game_world
             .query_entities(HEX_POSITION | TILE)
@@ -55,24 +96,7 @@ pub fn victory_system(
         if let Some(owner) = capital_owner
             && owner != faction
         {
-            game_world.resources.faction_eliminated[faction_idx] = true;
-            events
-                .faction_eliminated_events
-                .push(FactionEliminatedEvent { faction });
-
-            let units_to_remove: Vec<_> = game_world
-                .query_entities(UNIT)
-                .filter(|&entity| {
-                    game_world
-                        .get_unit(entity)
-                        .map(|u| u.faction == faction)
-                        .unwrap_or(false)
-                })
-                .collect();
-
-            for entity in units_to_remove {
-                crate::systems::despawn_unit(game_world, world, entity);
-            }
+            eliminate_faction(game_world, faction, owner, events);
 
             for entity in game_world
                 .query_entities(HEX_POSITION | TILE)
@@ -84,24 +108,26 @@ pub fn victory_system(
                     let mut tile = *tile;
                     tile.faction = None;
                     game_world.set_tile(entity, tile);
+                    if let Some(coord) = game_world.get_hex_position(entity).map(|h| h.0) {
+                        record_ownership_change(&mut game_world.resources, coord, None);
+                    }
                 }
             }
         }
     }
 
-    let alive_count = game_world
-        .resources
-        .faction_eliminated
-        .iter()
-        .filter(|&&eliminated| !eliminated)
-        .count();
+    let team_layout = game_world.resources.team_layout;
+    let alive: Vec<Faction> = ALL_FACTIONS
+        .into_iter()
+        .filter(|faction| !game_world.resources.faction_eliminated[faction_index(*faction)])
+        .collect();
 
-    if alive_count == 1 {
-        for (index, &eliminated) in game_world.resources.faction_eliminated.iter().enumerate() {
-            if !eliminated {
-                return GameResult::Victory(faction_from_index(index));
-            }
-        }
+    if let Some(&winner) = alive.first()
+        && alive
+            .iter()
+            .all(|&faction| faction == winner || factions_allied(team_layout, winner, faction))
+    {
+        return GameResult::Victory(winner);
     }
 
     GameResult::Ongoing
@@ -0,0 +1,159 @@
+use crate::constants::{ERUPTION_LOSS_PERCENT, PLAGUE_LOSS_PERCENT, WORLD_EVENT_CHANCE};
+use crate::ecs::{
+    Entity, GameEvents, GameWorld, HEX_POSITION, TILE, TileType, UNIT, WorldEvent, WorldEventKind,
+    record_ownership_change,
+};
+use crate::hex::HexCoord;
+use crate::rng::{game_rng_percent, game_rng_range};
+
+fn pick_coord(game_world: &mut GameWorld, mut candidates: Vec<HexCoord>) -> Option<HexCoord> {
+    if candidates.is_empty() {
+        return None;
+    }
+    candidates.sort_by_key(|coord| (coord.row, coord.column));
+    let index = game_rng_range(&mut game_world.resources.game_rng, candidates.len() as u32);
+    candidates.get(index as usize).copied()
+}
+
+fn unit_at(game_world: &GameWorld, coord: HexCoord) -> Option<Entity> {
+    game_world
+        .query_entities(HEX_POSITION | UNIT)
+        .find(|&entity| {
+            game_world
+                .get_hex_position(entity)
+                .map(|hex| hex.0 == coord)
+                .unwrap_or(false)
+        })
+}
+
+fn apply_losses(game_world: &mut GameWorld, coord: HexCoord, percent: i32) -> i32 {
+    let Some(unit_entity) = unit_at(game_world, coord) else {
+        return 0;
+    };
+    let Some(unit) = game_world.get_unit(unit_entity) else {
+        return 0;
+    };
+    let mut unit = *unit;
+    let lost = (unit.soldiers * percent / 100)
+        .min(unit.soldiers - 1)
+        .max(0);
+    unit.soldiers -= lost;
+    game_world.set_unit(unit_entity, unit);
+    lost
+}
+
+fn owned_cities(game_world: &GameWorld) -> Vec<HexCoord> {
+    let current_faction = game_world.resources.current_faction;
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            let is_city = matches!(tile.tile_type, TileType::City | TileType::Capital);
+            (is_city && tile.faction == Some(current_faction)).then_some(coord)
+        })
+        .collect()
+}
+
+fn owned_garrisoned_cities(game_world: &GameWorld) -> Vec<HexCoord> {
+    owned_cities(game_world)
+        .into_iter()
+        .filter(|coord| unit_at(game_world, *coord).is_some())
+        .collect()
+}
+
+fn eruption_sites(game_world: &GameWorld) -> Vec<HexCoord> {
+    let current_faction = game_world.resources.current_faction;
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            let is_wild = matches!(
+                tile.tile_type,
+                TileType::Land | TileType::Forest | TileType::Hills | TileType::Swamp
+            );
+            (is_wild && tile.faction == Some(current_faction)).then_some(coord)
+        })
+        .collect()
+}
+
+fn neutralize_tile(game_world: &mut GameWorld, coord: HexCoord) {
+    let tile_entity = game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| {
+            game_world
+                .get_hex_position(entity)
+                .map(|hex| hex.0 == coord)
+                .unwrap_or(false)
+        });
+
+    if let Some(entity) = tile_entity
+        && let Some(tile) = game_world.get_tile_mut(entity)
+        && tile.faction.is_some()
+    {
+        tile.faction = None;
+        record_ownership_change(&mut game_world.resources, coord, None);
+    }
+}
+
+pub fn world_event_message(event: &WorldEvent) -> String {
+    let location = format!("({},{})", event.location.column, event.location.row);
+    match event.kind {
+        WorldEventKind::Plague => format!(
+            "Plague strikes the city at {} (-{} soldiers)",
+            location, event.soldiers_lost
+        ),
+        WorldEventKind::Eruption if event.soldiers_lost > 0 => format!(
+            "A volcano erupts at {} (-{} soldiers)",
+            location, event.soldiers_lost
+        ),
+        WorldEventKind::Eruption => format!("A volcano erupts at {}", location),
+        WorldEventKind::BumperHarvest => {
+            format!("Bumper harvest at {} doubles reinforcements", location)
+        }
+    }
+}
+
+pub fn world_event_system(game_world: &mut GameWorld, events: &mut GameEvents) {
+    game_world.resources.bumper_harvest = None;
+
+    if !game_rng_percent(&mut game_world.resources.game_rng, WORLD_EVENT_CHANCE) {
+        return;
+    }
+
+    let kind = match game_rng_range(&mut game_world.resources.game_rng, 3) {
+        0 => WorldEventKind::Plague,
+        1 => WorldEventKind::Eruption,
+        _ => WorldEventKind::BumperHarvest,
+    };
+
+    let candidates = match kind {
+        WorldEventKind::Plague => owned_garrisoned_cities(game_world),
+        WorldEventKind::Eruption => eruption_sites(game_world),
+        WorldEventKind::BumperHarvest => owned_cities(game_world),
+    };
+    let Some(location) = pick_coord(game_world, candidates) else {
+        return;
+    };
+
+    let soldiers_lost = match kind {
+        WorldEventKind::Plague => apply_losses(game_world, location, PLAGUE_LOSS_PERCENT),
+        WorldEventKind::Eruption => {
+            let lost = apply_losses(game_world, location, ERUPTION_LOSS_PERCENT);
+            neutralize_tile(game_world, location);
+            lost
+        }
+        WorldEventKind::BumperHarvest => {
+            game_world.resources.bumper_harvest = Some(location);
+            0
+        }
+    };
+
+    events.world_events.push(WorldEvent {
+        kind,
+        faction: game_world.resources.current_faction,
+        location,
+        soldiers_lost,
+    });
+}
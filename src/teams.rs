@@ -0,0 +1,78 @@
+use crate::ecs::{ALL_FACTIONS, Faction, faction_index};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TeamLayout {
+    #[default]
+    FreeForAll,
+    RedosiaVioletnam,
+    RedosiaBluegaria,
+    RedosiaGreenland,
+}
+
+pub const ALL_TEAM_LAYOUTS: [TeamLayout; 4] = [
+    TeamLayout::FreeForAll,
+    TeamLayout::RedosiaVioletnam,
+    TeamLayout::RedosiaBluegaria,
+    TeamLayout::RedosiaGreenland,
+];
+
+pub fn team_layout_name(layout: TeamLayout) -> &'static str {
+    match layout {
+        TeamLayout::FreeForAll => "FREE FOR ALL",
+        TeamLayout::RedosiaVioletnam => "REDOSIA + VIOLETNAM",
+        TeamLayout::RedosiaBluegaria => "REDOSIA + BLUEGARIA",
+        TeamLayout::RedosiaGreenland => "REDOSIA + GREENLAND",
+    }
+}
+
+pub fn team_layout_key(layout: TeamLayout) -> &'static str {
+    match layout {
+        TeamLayout::FreeForAll => "ffa",
+        TeamLayout::RedosiaVioletnam => "rv",
+        TeamLayout::RedosiaBluegaria => "rb",
+        TeamLayout::RedosiaGreenland => "rg",
+    }
+}
+
+pub fn team_layout_from_key(key: &str) -> Option<TeamLayout> {
+    ALL_TEAM_LAYOUTS
+        .into_iter()
+        .find(|layout| team_layout_key(*layout) == key)
+}
+
+pub fn team_layout_next(layout: TeamLayout) -> TeamLayout {
+    let index = ALL_TEAM_LAYOUTS
+        .iter()
+        .position(|candidate| *candidate == layout)
+        .unwrap_or(0);
+    ALL_TEAM_LAYOUTS[(index + 1) % ALL_TEAM_LAYOUTS.len()]
+}
+
+fn faction_team(layout: TeamLayout, faction: Faction) -> usize {
+    let partner = match layout {
+        TeamLayout::FreeForAll => return faction_index(faction),
+        TeamLayout::RedosiaVioletnam => Faction::Violetnam,
+        TeamLayout::RedosiaBluegaria => Faction::Bluegaria,
+        TeamLayout::RedosiaGreenland => Faction::Greenland,
+    };
+    if faction == Faction::Redosia || faction == partner {
+        0
+    } else {
+        1
+    }
+}
+
+pub fn factions_allied(layout: TeamLayout, first: Faction, second: Faction) -> bool {
+    first != second && faction_team(layout, first) == faction_team(layout, second)
+}
+
+pub fn factions_hostile(layout: TeamLayout, first: Faction, second: Faction) -> bool {
+    first != second && !factions_allied(layout, first, second)
+}
+
+pub fn faction_allies(layout: TeamLayout, faction: Faction) -> Vec<Faction> {
+    ALL_FACTIONS
+        .into_iter()
+        .filter(|other| factions_allied(layout, faction, *other))
+        .collect()
+}
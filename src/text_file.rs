@@ -0,0 +1,63 @@
+use crate::ecs::{ALL_FACTIONS, Faction, faction_name};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum TextFileError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for TextFileError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextFileError::Io(error) => write!(formatter, "{}", error),
+            TextFileError::Parse { line, message } => {
+                write!(formatter, "line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TextFileError {}
+
+impl From<std::io::Error> for TextFileError {
+    fn from(error: std::io::Error) -> Self {
+        TextFileError::Io(error)
+    }
+}
+
+pub fn line_error(line: usize, message: impl Into<String>) -> TextFileError {
+    TextFileError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+pub fn check_file_header(
+    first_line: Option<&str>,
+    header: &str,
+    kind: &str,
+) -> Result<(), TextFileError> {
+    let found = first_line.unwrap_or_default().trim();
+    if found == header {
+        return Ok(());
+    }
+    let magic = header.split_whitespace().next().unwrap_or_default();
+    match found.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [found_magic, version] if *found_magic == magic => Err(line_error(
+            1,
+            format!("unsupported {} version {}", kind, version),
+        )),
+        _ => Err(line_error(1, format!("missing {} header", kind))),
+    }
+}
+
+pub fn faction_from_name(name: &str) -> Option<Faction> {
+    ALL_FACTIONS
+        .into_iter()
+        .find(|faction| faction_name(*faction).eq_ignore_ascii_case(name))
+}
+
+pub fn parse_faction(line: usize, name: &str) -> Result<Faction, TextFileError> {
+    faction_from_name(name).ok_or_else(|| line_error(line, format!("unknown faction '{}'", name)))
+}
@@ -9,6 +9,13 @@ pub fn spawn_tile(game_world: &mut GameWorld, hex_coord: HexCoord, tile_type: Ti
         Tile {
             tile_type,
             faction: None,
+            growth: 0,
+            improvement: None,
+            construction: None,
+            ruin: None,
+            pillaged_until: None,
+            airfield: false,
+            battles: 0,
         },
     );
     entity
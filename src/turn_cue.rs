@@ -0,0 +1,86 @@
+use crate::ecs::{Faction, faction_color};
+use crate::hud_text::{HudText, hud_text_despawn, hud_text_set_color, hud_text_spawn};
+use nightshade::prelude::*;
+use nightshade::winit::window::UserAttentionType;
+
+const TURN_CUE_DURATION: f32 = 1.5;
+const TURN_CUE_PULSE_SPEED: f32 = 10.0;
+
+#[derive(Default)]
+pub struct TurnCue {
+    pub local_faction: Option<Faction>,
+    pub remaining: f32,
+    pub banner: HudText,
+}
+
+fn request_window_attention(world: &World) {
+    if let Some(handle) = world.resources.window.handle.as_ref()
+        && !handle.has_focus()
+    {
+        handle.request_user_attention(Some(UserAttentionType::Informational));
+    }
+}
+
+pub fn despawn_turn_cue(cue: &mut TurnCue, world: &mut World) {
+    hud_text_despawn(world, &mut cue.banner);
+    cue.remaining = 0.0;
+}
+
+pub fn turn_cue_system(
+    cue: &mut TurnCue,
+    world: &mut World,
+    local_faction: Option<Faction>,
+    ui_scale: f32,
+    delta_time: f32,
+) {
+    if local_faction != cue.local_faction {
+        cue.local_faction = local_faction;
+        despawn_turn_cue(cue, world);
+        if let Some(faction) = local_faction {
+            request_window_attention(world);
+            let color = faction_color(faction);
+            cue.banner = hud_text_spawn(
+                world,
+                "YOUR TURN",
+                HudAnchor::TopCenter,
+                nalgebra_glm::vec2(0.0, 140.0),
+                TextProperties {
+                    font_size: 48.0,
+                    color: nalgebra_glm::vec4(color[0], color[1], color[2], 1.0),
+                    alignment: TextAlignment::Center,
+                    outline_width: 0.08,
+                    outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+                    ..Default::default()
+                },
+                ui_scale,
+            );
+            cue.remaining = TURN_CUE_DURATION;
+        }
+    }
+
+    if cue.remaining <= 0.0 {
+        return;
+    }
+    cue.remaining -= delta_time;
+    if cue.remaining <= 0.0 {
+        despawn_turn_cue(cue, world);
+        return;
+    }
+
+    let Some(faction) = cue.local_faction else {
+        return;
+    };
+    let color = faction_color(faction);
+    let pulse = 0.5 + 0.5 * (cue.remaining * TURN_CUE_PULSE_SPEED).cos();
+    let fade = (cue.remaining / TURN_CUE_DURATION).min(1.0);
+    hud_text_set_color(
+        world,
+        &mut cue.banner,
+        [
+            color[0] + (1.0 - color[0]) * pulse,
+            color[1] + (1.0 - color[1]) * pulse,
+            color[2] + (1.0 - color[2]) * pulse,
+            fade,
+        ],
+    );
+}
@@ -0,0 +1,83 @@
+use crate::ecs::Faction;
+
+#[derive(Debug, Clone, Default)]
+pub struct TurnRotation {
+    pub factions: Vec<Faction>,
+    pub current_index: usize,
+    pub current_removed: bool,
+}
+
+pub struct RotationAdvance {
+    pub faction: Faction,
+    pub new_round: bool,
+}
+
+pub fn turn_rotation_new(factions: &[Faction]) -> TurnRotation {
+    TurnRotation {
+        factions: factions.to_vec(),
+        current_index: 0,
+        current_removed: false,
+    }
+}
+
+pub fn turn_rotation_current(rotation: &TurnRotation) -> Option<Faction> {
+    rotation.factions.get(rotation.current_index).copied()
+}
+
+pub fn turn_rotation_advance(rotation: &mut TurnRotation) -> Option<RotationAdvance> {
+    if rotation.factions.is_empty() {
+        return None;
+    }
+
+    if rotation.current_removed {
+        rotation.current_removed = false;
+        return Some(RotationAdvance {
+            faction: rotation.factions[rotation.current_index],
+            new_round: false,
+        });
+    }
+
+    rotation.current_index = (rotation.current_index + 1) % rotation.factions.len();
+
+    Some(RotationAdvance {
+        faction: rotation.factions[rotation.current_index],
+        new_round: rotation.current_index == 0,
+    })
+}
+
+pub fn turn_rotation_remove(rotation: &mut TurnRotation, faction: Faction) {
+    let Some(position) = rotation.factions.iter().position(|f| *f == faction) else {
+        return;
+    };
+
+    rotation.factions.remove(position);
+
+    if rotation.factions.is_empty() {
+        rotation.current_index = 0;
+        rotation.current_removed = false;
+        return;
+    }
+
+    if position < rotation.current_index {
+        rotation.current_index -= 1;
+    } else if position == rotation.current_index {
+        if position < rotation.factions.len() {
+            rotation.current_removed = true;
+        } else {
+            rotation.current_index = rotation.factions.len() - 1;
+            rotation.current_removed = false;
+        }
+    }
+}
+
+pub fn turn_rotation_upcoming(rotation: &TurnRotation, count: usize) -> Vec<Faction> {
+    let length = rotation.factions.len();
+    if length <= 1 {
+        return Vec::new();
+    }
+
+    (1..length)
+        .take(count)
+        .map(|offset| rotation.factions[(rotation.current_index + offset) % length])
+        .collect()
+}
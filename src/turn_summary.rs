@@ -0,0 +1,222 @@
+use crate::ecs::{
+    AttritionCause, Faction, GameEvents, GameWorld, HEX_POSITION, TILE, faction_name,
+};
+use crate::hex::HexCoord;
+use crate::hud_text::{HudText, hud_text_despawn, hud_text_spawn};
+use nightshade::prelude::*;
+use std::collections::HashSet;
+
+const SUMMARY_LINE_HEIGHT: f32 = 28.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct TurnSummary {
+    pub attacks_against_player: u32,
+    pub attacks_repelled: u32,
+    pub armies_lost: u32,
+    pub tiles_lost: usize,
+    pub tiles_gained: usize,
+    pub soldiers_reinforced: i32,
+    pub soldiers_lost_to_winter: i32,
+    pub soldiers_lost_to_swamp: i32,
+    pub eliminated: Vec<Faction>,
+}
+
+#[derive(Default)]
+pub struct TurnSummaryTracker {
+    pub recording: bool,
+    pub summary: TurnSummary,
+    pub tiles_at_handoff: HashSet<HexCoord>,
+    pub texts: Vec<HudText>,
+}
+
+fn player_tiles(game_world: &GameWorld, player_faction: Faction) -> HashSet<HexCoord> {
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .filter_map(|entity| {
+            let coord = game_world.get_hex_position(entity)?.0;
+            let tile = game_world.get_tile(entity)?;
+            (tile.faction == Some(player_faction)).then_some(coord)
+        })
+        .collect()
+}
+
+fn turn_summary_is_empty(summary: &TurnSummary) -> bool {
+    summary.attacks_against_player == 0
+        && summary.tiles_lost == 0
+        && summary.tiles_gained == 0
+        && summary.soldiers_reinforced == 0
+        && summary.soldiers_lost_to_winter == 0
+        && summary.soldiers_lost_to_swamp == 0
+        && summary.eliminated.is_empty()
+}
+
+pub fn turn_summary_lines(summary: &TurnSummary, turn_number: u32) -> Vec<String> {
+    let mut lines = vec![format!("While you waited (turn {})", turn_number)];
+    if summary.attacks_against_player > 0 {
+        lines.push(format!(
+            "Attacks against you: {} ({} repelled, {} armies lost)",
+            summary.attacks_against_player, summary.attacks_repelled, summary.armies_lost
+        ));
+    }
+    if summary.tiles_lost > 0 || summary.tiles_gained > 0 {
+        lines.push(format!(
+            "Tiles lost: {}  gained: {}",
+            summary.tiles_lost, summary.tiles_gained
+        ));
+    }
+    if summary.soldiers_reinforced > 0 {
+        lines.push(format!(
+            "Reinforcements received: +{} soldiers",
+            summary.soldiers_reinforced
+        ));
+    }
+    if summary.soldiers_lost_to_winter > 0 {
+        lines.push(format!(
+            "Winter attrition: -{} soldiers",
+            summary.soldiers_lost_to_winter
+        ));
+    }
+    if summary.soldiers_lost_to_swamp > 0 {
+        lines.push(format!(
+            "Swamp attrition: -{} soldiers",
+            summary.soldiers_lost_to_swamp
+        ));
+    }
+    if !summary.eliminated.is_empty() {
+        let names: Vec<_> = summary
+            .eliminated
+            .iter()
+            .map(|f| faction_name(*f))
+            .collect();
+        lines.push(format!("Eliminated: {}", names.join(", ")));
+    }
+    lines.push("[Enter] or click to continue".to_string());
+    lines
+}
+
+pub fn turn_summary_record_events(
+    tracker: &mut TurnSummaryTracker,
+    events: &GameEvents,
+    player_faction: Faction,
+) {
+    if !tracker.recording {
+        return;
+    }
+
+    let summary = &mut tracker.summary;
+    for event in &events.combat_events {
+        if event.defender_faction != player_faction {
+            continue;
+        }
+        summary.attacks_against_player += 1;
+        if event.defender_survived {
+            summary.attacks_repelled += 1;
+        } else {
+            summary.armies_lost += 1;
+        }
+    }
+    for event in &events.reinforcement_events {
+        if event.faction == player_faction {
+            summary.soldiers_reinforced += event.soldiers;
+        }
+    }
+    for event in &events.attrition_events {
+        if event.faction != player_faction {
+            continue;
+        }
+        match event.cause {
+            AttritionCause::Winter => summary.soldiers_lost_to_winter += event.soldiers,
+            AttritionCause::Swamp => summary.soldiers_lost_to_swamp += event.soldiers,
+        }
+    }
+    for event in &events.faction_eliminated_events {
+        if event.faction != player_faction {
+            summary.eliminated.push(event.faction);
+        }
+    }
+}
+
+pub fn turn_summary_is_open(tracker: &TurnSummaryTracker) -> bool {
+    !tracker.texts.is_empty()
+}
+
+fn spawn_turn_summary_ui(
+    tracker: &mut TurnSummaryTracker,
+    world: &mut World,
+    lines: &[String],
+    ui_scale: f32,
+) {
+    let title_props = TextProperties {
+        font_size: 30.0,
+        color: nalgebra_glm::vec4(1.0, 0.85, 0.4, 1.0),
+        alignment: TextAlignment::Center,
+        outline_width: 0.06,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+    let line_props = TextProperties {
+        font_size: 22.0,
+        color: nalgebra_glm::vec4(1.0, 1.0, 1.0, 1.0),
+        alignment: TextAlignment::Center,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    let top = -(lines.len() as f32 * SUMMARY_LINE_HEIGHT) / 2.0;
+    for (index, line) in lines.iter().enumerate() {
+        let props = if index == 0 {
+            title_props.clone()
+        } else {
+            line_props.clone()
+        };
+        tracker.texts.push(hud_text_spawn(
+            world,
+            line,
+            HudAnchor::Center,
+            nalgebra_glm::vec2(0.0, top + index as f32 * SUMMARY_LINE_HEIGHT),
+            props,
+            ui_scale,
+        ));
+    }
+}
+
+pub fn turn_summary_dismiss(tracker: &mut TurnSummaryTracker, world: &mut World) {
+    for mut hud_text in tracker.texts.drain(..) {
+        hud_text_despawn(world, &mut hud_text);
+    }
+}
+
+pub fn turn_summary_system(
+    tracker: &mut TurnSummaryTracker,
+    game_world: &GameWorld,
+    world: &mut World,
+    player_faction: Faction,
+    ui_scale: f32,
+) {
+    let is_player_turn = game_world.resources.current_faction == player_faction;
+
+    if !is_player_turn && !tracker.recording {
+        tracker.recording = true;
+        tracker.summary = TurnSummary::default();
+        tracker.tiles_at_handoff = player_tiles(game_world, player_faction);
+        return;
+    }
+
+    if !is_player_turn || !tracker.recording {
+        return;
+    }
+
+    tracker.recording = false;
+    let tiles_now = player_tiles(game_world, player_faction);
+    tracker.summary.tiles_lost = tracker.tiles_at_handoff.difference(&tiles_now).count();
+    tracker.summary.tiles_gained = tiles_now.difference(&tracker.tiles_at_handoff).count();
+
+    if turn_summary_is_empty(&tracker.summary) {
+        return;
+    }
+
+    let lines = turn_summary_lines(&tracker.summary, game_world.resources.turn_number);
+    turn_summary_dismiss(tracker, world);
+    spawn_turn_summary_ui(tracker, world, &lines, ui_scale);
+}
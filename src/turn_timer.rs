@@ -0,0 +1,61 @@
+use crate::ecs::{Faction, GameResources};
+
+pub const TURN_TIMER_OPTIONS: [u32; 4] = [0, 30, 60, 120];
+pub const TURN_TIMER_WARNING_SECONDS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TurnTimer {
+    pub seconds_per_turn: u32,
+    pub remaining: f32,
+    pub faction: Faction,
+    pub turn: u32,
+}
+
+pub fn turn_timer_label(seconds_per_turn: u32) -> String {
+    if seconds_per_turn == 0 {
+        "TURN TIMER: OFF".to_string()
+    } else {
+        format!("TURN TIMER: {}s", seconds_per_turn)
+    }
+}
+
+pub fn turn_timer_next_option(seconds_per_turn: u32) -> u32 {
+    let index = TURN_TIMER_OPTIONS
+        .iter()
+        .position(|option| *option == seconds_per_turn)
+        .unwrap_or(0);
+    TURN_TIMER_OPTIONS[(index + 1) % TURN_TIMER_OPTIONS.len()]
+}
+
+pub fn turn_timer_new(seconds_per_turn: u32) -> TurnTimer {
+    TurnTimer {
+        seconds_per_turn,
+        remaining: seconds_per_turn as f32,
+        faction: Faction::default(),
+        turn: 0,
+    }
+}
+
+pub fn turn_timer_tick(resources: &mut GameResources, delta_time: f32) {
+    let current_faction = resources.current_faction;
+    let turn_number = resources.turn_number;
+    let timer = &mut resources.turn_timer;
+    if timer.seconds_per_turn == 0 {
+        return;
+    }
+    if timer.faction != current_faction || timer.turn != turn_number {
+        timer.faction = current_faction;
+        timer.turn = turn_number;
+        timer.remaining = timer.seconds_per_turn as f32;
+        return;
+    }
+    timer.remaining = (timer.remaining - delta_time).max(0.0);
+}
+
+pub fn turn_timer_expired(timer: &TurnTimer) -> bool {
+    timer.seconds_per_turn > 0 && timer.remaining <= 0.0
+}
+
+pub fn turn_timer_seconds_left(timer: &TurnTimer) -> Option<u32> {
+    (timer.seconds_per_turn > 0).then(|| timer.remaining.ceil() as u32)
+}
@@ -0,0 +1,409 @@
+use crate::ecs::{
+    ALL_FACTIONS, Faction, GameEvents, GameWorld, HEX_POSITION, TILE, TileType, faction_index,
+    faction_name,
+};
+use crate::hex::{HexCoord, hex_to_world_position};
+use crate::hud_text::{HudText, hud_text_despawn, hud_text_set, hud_text_spawn};
+use crate::map::{GeneratedMap, MapGenParams, TileFeature, capital_coord};
+use crate::map_generation::spawn_hidden_lines_entity;
+use crate::rendering::generate_hex_outline_with_color;
+use crate::selection::{get_selected_unit, get_unit_at_tile};
+use crate::systems::{leader_ability, leader_ability_description, leader_ability_name, spawn_unit};
+use crate::turn_rotation::turn_rotation_remove;
+use nightshade::ecs::world::components::Line;
+use nightshade::prelude::*;
+
+pub const TUTORIAL_PLAYER: Faction = Faction::Redosia;
+pub const TUTORIAL_ENEMY: Faction = Faction::Violetnam;
+pub const TUTORIAL_SEED: u32 = 1;
+
+const TUTORIAL_MAP_WIDTH: i32 = 9;
+const TUTORIAL_MAP_HEIGHT: i32 = 7;
+const TUTORIAL_MOVE_TARGET: HexCoord = HexCoord { column: 4, row: 2 };
+const TUTORIAL_OUTPOST: HexCoord = HexCoord { column: 5, row: 2 };
+const TUTORIAL_RECRUIT_START: HexCoord = HexCoord { column: 1, row: 4 };
+const TUTORIAL_CITY: HexCoord = HexCoord { column: 3, row: 5 };
+const TUTORIAL_FORESTS: [HexCoord; 4] = [
+    HexCoord { column: 4, row: 4 },
+    HexCoord { column: 5, row: 5 },
+    HexCoord { column: 7, row: 1 },
+    HexCoord { column: 1, row: 1 },
+];
+const TUTORIAL_OUTPOST_SOLDIERS: i32 = 3;
+const TUTORIAL_RECRUIT_SOLDIERS: i32 = 6;
+const TUTORIAL_HIGHLIGHT_HEIGHT: f32 = 8.0;
+const TUTORIAL_HIGHLIGHT_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialStep {
+    SelectUnit,
+    MoveUnit,
+    Attack,
+    CaptureCity,
+    Speech,
+    EndTurn,
+    WatchEnemy,
+    Complete,
+}
+
+pub const TUTORIAL_SCRIPT: [TutorialStep; 8] = [
+    TutorialStep::SelectUnit,
+    TutorialStep::MoveUnit,
+    TutorialStep::Attack,
+    TutorialStep::CaptureCity,
+    TutorialStep::Speech,
+    TutorialStep::EndTurn,
+    TutorialStep::WatchEnemy,
+    TutorialStep::Complete,
+];
+
+pub struct Tutorial {
+    pub step_index: usize,
+    pub prompt: HudText,
+    pub detail: HudText,
+    pub highlight_entity: Option<Entity>,
+    pub highlighted_step: Option<usize>,
+    pub previous_map_params: MapGenParams,
+    pub previous_player_faction: Faction,
+}
+
+pub fn tutorial_map_params() -> MapGenParams {
+    MapGenParams {
+        map_width: TUTORIAL_MAP_WIDTH,
+        map_height: TUTORIAL_MAP_HEIGHT,
+        random_capitals: false,
+        wrap_columns: false,
+        ..Default::default()
+    }
+}
+
+pub fn tutorial_map() -> GeneratedMap {
+    let params = tutorial_map_params();
+    let mut generated = GeneratedMap {
+        width: params.map_width,
+        height: params.map_height,
+        ..Default::default()
+    };
+
+    for column in 0..params.map_width {
+        for row in 0..params.map_height {
+            let coord = HexCoord { column, row };
+            let tile_type = if TUTORIAL_FORESTS.contains(&coord) {
+                TileType::Forest
+            } else {
+                TileType::Land
+            };
+            generated.tiles.insert(coord, tile_type);
+        }
+    }
+
+    for faction in [TUTORIAL_PLAYER, TUTORIAL_ENEMY] {
+        generated.features.insert(
+            capital_coord(&params, faction),
+            TileFeature::Capital(faction),
+        );
+    }
+    generated.features.insert(TUTORIAL_CITY, TileFeature::City);
+
+    generated
+}
+
+pub fn tutorial_populate(game_world: &mut GameWorld) {
+    spawn_unit(
+        game_world,
+        TUTORIAL_OUTPOST,
+        TUTORIAL_ENEMY,
+        TUTORIAL_OUTPOST_SOLDIERS,
+    );
+    spawn_unit(
+        game_world,
+        TUTORIAL_RECRUIT_START,
+        TUTORIAL_PLAYER,
+        TUTORIAL_RECRUIT_SOLDIERS,
+    );
+}
+
+pub fn tutorial_begin(game_world: &mut GameWorld) {
+    for faction in ALL_FACTIONS {
+        if faction == TUTORIAL_PLAYER || faction == TUTORIAL_ENEMY {
+            continue;
+        }
+        game_world.resources.faction_eliminated[faction_index(faction)] = true;
+        turn_rotation_remove(&mut game_world.resources.turn_rotation, faction);
+    }
+}
+
+pub fn tutorial_new(
+    world: &mut World,
+    previous_map_params: MapGenParams,
+    previous_player_faction: Faction,
+    ui_scale: f32,
+) -> Tutorial {
+    let prompt_props = TextProperties {
+        font_size: 30.0,
+        color: nalgebra_glm::vec4(
+            TUTORIAL_HIGHLIGHT_COLOR[0],
+            TUTORIAL_HIGHLIGHT_COLOR[1],
+            TUTORIAL_HIGHLIGHT_COLOR[2],
+            TUTORIAL_HIGHLIGHT_COLOR[3],
+        ),
+        alignment: TextAlignment::Center,
+        outline_width: 0.06,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    let detail_props = TextProperties {
+        font_size: 20.0,
+        color: nalgebra_glm::vec4(1.0, 1.0, 1.0, 1.0),
+        alignment: TextAlignment::Center,
+        outline_width: 0.05,
+        outline_color: nalgebra_glm::vec4(0.0, 0.0, 0.0, 1.0),
+        ..Default::default()
+    };
+
+    Tutorial {
+        step_index: 0,
+        prompt: hud_text_spawn(
+            world,
+            "",
+            HudAnchor::TopCenter,
+            nalgebra_glm::vec2(0.0, 20.0),
+            prompt_props,
+            ui_scale,
+        ),
+        detail: hud_text_spawn(
+            world,
+            "",
+            HudAnchor::TopCenter,
+            nalgebra_glm::vec2(0.0, 60.0),
+            detail_props,
+            ui_scale,
+        ),
+        highlight_entity: Some(spawn_hidden_lines_entity(world)),
+        highlighted_step: None,
+        previous_map_params,
+        previous_player_faction,
+    }
+}
+
+pub fn despawn_tutorial(tutorial: &mut Tutorial, world: &mut World) {
+    hud_text_despawn(world, &mut tutorial.prompt);
+    hud_text_despawn(world, &mut tutorial.detail);
+    if let Some(entity) = tutorial.highlight_entity.take() {
+        world.queue_command(WorldCommand::DespawnRecursive { entity });
+    }
+}
+
+pub fn tutorial_current_step(tutorial: &Tutorial) -> TutorialStep {
+    TUTORIAL_SCRIPT[tutorial.step_index.min(TUTORIAL_SCRIPT.len() - 1)]
+}
+
+pub fn tutorial_step_prompt(step: TutorialStep) -> (String, String) {
+    let enemy = faction_name(TUTORIAL_ENEMY);
+    match step {
+        TutorialStep::SelectUnit => (
+            "Step 1/6: Select an army".to_string(),
+            "Left-click your army standing on the highlighted capital.".to_string(),
+        ),
+        TutorialStep::MoveUnit => (
+            "Step 2/6: Move".to_string(),
+            "Click the highlighted tile to march there. Every order costs one action.".to_string(),
+        ),
+        TutorialStep::Attack => (
+            "Step 3/6: Attack".to_string(),
+            format!(
+                "Select your army again, then click the adjacent {} outpost to attack it.",
+                enemy
+            ),
+        ),
+        TutorialStep::CaptureCity => (
+            "Step 4/6: Capture a city".to_string(),
+            "Select your second army and move it onto the highlighted city. Cities send reinforcements."
+                .to_string(),
+        ),
+        TutorialStep::Speech => (
+            "Step 5/6: Rally your troops".to_string(),
+            format!(
+                "Press [S] to use your leader ability, {}: {}.",
+                leader_ability_name(leader_ability(TUTORIAL_PLAYER)),
+                leader_ability_description(leader_ability(TUTORIAL_PLAYER))
+            ),
+        ),
+        TutorialStep::EndTurn => (
+            "Step 6/6: End your turn".to_string(),
+            format!("Press [SPACE] to hand the turn to {}.", enemy),
+        ),
+        TutorialStep::WatchEnemy => (
+            "Enemy turn".to_string(),
+            format!(
+                "{} is giving orders. Your turn resumes when they are done.",
+                enemy
+            ),
+        ),
+        TutorialStep::Complete => (
+            "Tutorial complete!".to_string(),
+            format!(
+                "Take {}'s capital to win, or press [Esc] to return to the main menu.",
+                enemy
+            ),
+        ),
+    }
+}
+
+pub fn tutorial_step_focus(step: TutorialStep) -> Vec<HexCoord> {
+    let params = tutorial_map_params();
+    match step {
+        TutorialStep::SelectUnit => vec![capital_coord(&params, TUTORIAL_PLAYER)],
+        TutorialStep::MoveUnit => vec![
+            capital_coord(&params, TUTORIAL_PLAYER),
+            TUTORIAL_MOVE_TARGET,
+        ],
+        TutorialStep::Attack => vec![TUTORIAL_MOVE_TARGET, TUTORIAL_OUTPOST],
+        TutorialStep::CaptureCity => vec![TUTORIAL_RECRUIT_START, TUTORIAL_CITY],
+        TutorialStep::Speech
+        | TutorialStep::EndTurn
+        | TutorialStep::WatchEnemy
+        | TutorialStep::Complete => Vec::new(),
+    }
+}
+
+fn tile_owner_at(game_world: &GameWorld, coord: HexCoord) -> Option<Faction> {
+    game_world
+        .query_entities(HEX_POSITION | TILE)
+        .find(|&entity| {
+            game_world
+                .get_hex_position(entity)
+                .map(|hex| hex.0 == coord)
+                .unwrap_or(false)
+        })
+        .and_then(|entity| game_world.get_tile(entity))
+        .and_then(|tile| tile.faction)
+}
+
+fn player_unit_arrived_at(game_world: &GameWorld, coord: HexCoord) -> bool {
+    get_unit_at_tile(game_world, coord)
+        .filter(|&entity| game_world.get_movement(entity).is_none())
+        .and_then(|entity| game_world.get_unit(entity))
+        .map(|unit| unit.faction == TUTORIAL_PLAYER)
+        .unwrap_or(false)
+}
+
+pub fn tutorial_step_complete(
+    step: TutorialStep,
+    game_world: &GameWorld,
+    events: &GameEvents,
+) -> bool {
+    let is_player_turn = game_world.resources.current_faction == TUTORIAL_PLAYER;
+    match step {
+        TutorialStep::SelectUnit => get_selected_unit(game_world)
+            .and_then(|entity| game_world.get_unit(entity))
+            .map(|unit| unit.faction == TUTORIAL_PLAYER)
+            .unwrap_or(false),
+        TutorialStep::MoveUnit => player_unit_arrived_at(game_world, TUTORIAL_MOVE_TARGET),
+        TutorialStep::Attack => events
+            .combat_events
+            .iter()
+            .any(|event| event.attacker_faction == TUTORIAL_PLAYER),
+        TutorialStep::CaptureCity => {
+            tile_owner_at(game_world, TUTORIAL_CITY) == Some(TUTORIAL_PLAYER)
+        }
+        TutorialStep::Speech => is_player_turn && game_world.resources.speech_used,
+        TutorialStep::EndTurn => !is_player_turn,
+        TutorialStep::WatchEnemy => is_player_turn,
+        TutorialStep::Complete => false,
+    }
+}
+
+pub fn tutorial_allows_click(tutorial: &Tutorial, coord: Option<HexCoord>) -> bool {
+    let step = tutorial_current_step(tutorial);
+    if step == TutorialStep::Complete {
+        return true;
+    }
+    coord
+        .map(|coord| tutorial_step_focus(step).contains(&coord))
+        .unwrap_or(false)
+}
+
+pub fn tutorial_allows_speech(tutorial: &Tutorial) -> bool {
+    matches!(
+        tutorial_current_step(tutorial),
+        TutorialStep::Speech | TutorialStep::Complete
+    )
+}
+
+pub fn tutorial_allows_end_turn(tutorial: &Tutorial) -> bool {
+    matches!(
+        tutorial_current_step(tutorial),
+        TutorialStep::EndTurn | TutorialStep::Complete
+    )
+}
+
+pub fn tutorial_is_complete(tutorial: &Tutorial) -> bool {
+    tutorial_current_step(tutorial) == TutorialStep::Complete
+}
+
+fn update_tutorial_highlight(tutorial: &Tutorial, game_world: &GameWorld, world: &mut World) {
+    let Some(entity) = tutorial.highlight_entity else {
+        return;
+    };
+
+    let hex_width = game_world.resources.hex_width;
+    let hex_depth = game_world.resources.hex_depth;
+    let orientation = game_world.resources.hex_orientation;
+    let highlight = nalgebra_glm::vec4(
+        TUTORIAL_HIGHLIGHT_COLOR[0],
+        TUTORIAL_HIGHLIGHT_COLOR[1],
+        TUTORIAL_HIGHLIGHT_COLOR[2],
+        TUTORIAL_HIGHLIGHT_COLOR[3],
+    );
+
+    let lines: Vec<Line> = tutorial_step_focus(tutorial_current_step(tutorial))
+        .iter()
+        .flat_map(|coord| {
+            let center =
+                hex_to_world_position(coord.column, coord.row, hex_width, hex_depth, orientation);
+            generate_hex_outline_with_color(
+                center,
+                hex_width,
+                hex_depth,
+                orientation,
+                TUTORIAL_HIGHLIGHT_HEIGHT,
+                highlight,
+            )
+        })
+        .collect();
+
+    let has_lines = !lines.is_empty();
+    if let Some(lines_component) = world.get_lines_mut(entity) {
+        lines_component.lines = lines;
+        lines_component.mark_dirty();
+    }
+    if let Some(visibility) = world.get_visibility_mut(entity) {
+        visibility.visible = has_lines;
+    }
+}
+
+pub fn tutorial_system(
+    tutorial: &mut Tutorial,
+    game_world: &GameWorld,
+    events: &GameEvents,
+    world: &mut World,
+) {
+    while tutorial.step_index + 1 < TUTORIAL_SCRIPT.len()
+        && tutorial_step_complete(tutorial_current_step(tutorial), game_world, events)
+    {
+        tutorial.step_index += 1;
+        log::info!("Tutorial step: {:?}", tutorial_current_step(tutorial));
+    }
+
+    if tutorial.highlighted_step == Some(tutorial.step_index) {
+        return;
+    }
+
+    let (prompt, detail) = tutorial_step_prompt(tutorial_current_step(tutorial));
+    hud_text_set(world, &mut tutorial.prompt, &prompt);
+    hud_text_set(world, &mut tutorial.detail, &detail);
+    update_tutorial_highlight(tutorial, game_world, world);
+    tutorial.highlighted_step = Some(tutorial.step_index);
+}
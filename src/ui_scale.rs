@@ -0,0 +1,28 @@
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 2.0;
+pub const UI_SCALE_STEPS: [f32; 5] = [1.0, 1.25, 1.5, 2.0, 0.75];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiScale {
+    pub scale: f32,
+}
+
+impl Default for UiScale {
+    fn default() -> Self {
+        Self { scale: 1.0 }
+    }
+}
+
+pub fn ui_scale_set(ui_scale: &mut UiScale, scale: f32) {
+    ui_scale.scale = scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+}
+
+pub fn ui_scale_cycle(ui_scale: &mut UiScale) -> f32 {
+    let next = UI_SCALE_STEPS
+        .iter()
+        .position(|step| (*step - ui_scale.scale).abs() < f32::EPSILON)
+        .map(|index| UI_SCALE_STEPS[(index + 1) % UI_SCALE_STEPS.len()])
+        .unwrap_or(1.0);
+    ui_scale_set(ui_scale, next);
+    ui_scale.scale
+}
@@ -0,0 +1,79 @@
+use crate::ecs::{Faction, TileType};
+use crate::quality::QualitySettings;
+use crate::systems::{FireworkShell, spawn_capture_firework};
+use nightshade::prelude::*;
+
+const VICTORY_DURATION: f32 = 6.0;
+const VOLLEY_INTERVAL: f32 = 0.6;
+const ORBIT_SPEED: f32 = 0.5;
+const ORBIT_RADIUS: f32 = 2500.0;
+const ORBIT_PITCH: f32 = 0.6;
+const VOLLEY_SPREAD: f32 = 400.0;
+
+pub struct VictoryCinematic {
+    pub winner: Faction,
+    pub center: Vec3,
+    pub high_score_rank: Option<usize>,
+    pub elapsed: f32,
+    pub next_volley: f32,
+}
+
+pub fn victory_cinematic_new(
+    winner: Faction,
+    center: Vec3,
+    high_score_rank: Option<usize>,
+) -> VictoryCinematic {
+    VictoryCinematic {
+        winner,
+        center,
+        high_score_rank,
+        elapsed: 0.0,
+        next_volley: 0.0,
+    }
+}
+
+pub fn victory_cinematic_system(
+    cinematic: &mut VictoryCinematic,
+    shells: &mut Vec<FireworkShell>,
+    world: &mut World,
+    quality: &QualitySettings,
+    delta_time: f32,
+) -> bool {
+    cinematic.elapsed += delta_time;
+
+    if let Some(camera_entity) = world.resources.active_camera
+        && let Some(pan_orbit) = world.get_pan_orbit_camera_mut(camera_entity)
+    {
+        pan_orbit.target_focus = nalgebra_glm::vec3(cinematic.center.x, 0.0, cinematic.center.z);
+        pan_orbit.target_radius = ORBIT_RADIUS;
+        pan_orbit.target_pitch = ORBIT_PITCH;
+        pan_orbit.target_yaw += ORBIT_SPEED * delta_time;
+    }
+
+    while cinematic.elapsed >= cinematic.next_volley
+        && cinematic.next_volley < VICTORY_DURATION - VOLLEY_INTERVAL
+    {
+        let mut rng = rand::rng();
+        let position = nalgebra_glm::vec3(
+            cinematic.center.x + rng.random_range(-VOLLEY_SPREAD..VOLLEY_SPREAD),
+            cinematic.center.y,
+            cinematic.center.z + rng.random_range(-VOLLEY_SPREAD..VOLLEY_SPREAD),
+        );
+        let tile_type = if cinematic.next_volley == 0.0 {
+            TileType::Capital
+        } else {
+            TileType::City
+        };
+        spawn_capture_firework(
+            shells,
+            world,
+            position,
+            tile_type,
+            cinematic.winner,
+            quality,
+        );
+        cinematic.next_volley += VOLLEY_INTERVAL;
+    }
+
+    cinematic.elapsed >= VICTORY_DURATION
+}
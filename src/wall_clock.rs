@@ -0,0 +1,16 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub fn unix_millis_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn unix_millis_now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+pub fn unix_seconds_now() -> u64 {
+    unix_millis_now() / 1000
+}